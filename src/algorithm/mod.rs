@@ -1,2 +1,3 @@
 pub mod neighbour;
+pub mod sample_weight;
 pub(crate) mod sort;
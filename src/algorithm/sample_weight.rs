@@ -0,0 +1,86 @@
+//! # Sample and Class Weights
+//!
+//! Weights of observations and classes shared by the estimators that accept them.
+//! The weight of every observation scales its contribution to the loss during fitting,
+//! for classifiers it is further multiplied by the weight of its class.
+use std::collections::HashMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::error::Failed;
+use crate::numbers::realnum::RealNumber;
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+/// Weights associated with classes, the weight of every sample is multiplied by the weight of its class during fitting.
+pub enum ClassWeight {
+    /// Weights inversely proportional to class frequencies, \\(\frac{n}{k n_c}\\)
+    /// for \\(n\\) samples, \\(k\\) classes and \\(n_c\\) samples of class \\(c\\).
+    Balanced,
+    /// Weight of every class, keyed by the index of the class in the sorted class labels,
+    /// e.g. with labels `-1` and `1` the key of `-1` is 0 and the key of `1` is 1.
+    /// Classes missing from the map have weight 1, fitting fails when a key is not the index of a class.
+    Custom(HashMap<usize, f64>),
+}
+
+/// Checks that there is one finite, non-negative weight per sample and that not all weights are zero.
+pub(crate) fn check_sample_weight<T: RealNumber>(
+    sample_weight: &[T],
+    n: usize,
+) -> Result<(), Failed> {
+    if sample_weight.len() != n {
+        return Err(Failed::fit(&format!(
+            "Number of sample weights {} doesn't match number of rows {n}",
+            sample_weight.len()
+        )));
+    }
+    if sample_weight
+        .iter()
+        .any(|w| !w.is_finite() || *w < T::zero())
+    {
+        return Err(Failed::fit("Sample weights should be finite and >= 0"));
+    }
+    if sample_weight.iter().all(|w| *w == T::zero()) {
+        return Err(Failed::fit("At least one sample weight should be > 0"));
+    }
+    Ok(())
+}
+
+/// Weight of every one of `k` classes, `y_idx` is the index of the class of every sample.
+pub(crate) fn resolve_class_weight(
+    class_weight: &ClassWeight,
+    k: usize,
+    y_idx: &[usize],
+) -> Result<Vec<f64>, Failed> {
+    match class_weight {
+        ClassWeight::Balanced => {
+            let mut counts = vec![0; k];
+            for &y_idx_i in y_idx.iter() {
+                counts[y_idx_i] += 1;
+            }
+            Ok(counts
+                .iter()
+                .map(|&count| y_idx.len() as f64 / (k * count) as f64)
+                .collect())
+        }
+        ClassWeight::Custom(weights) => {
+            if let Some(c) = weights.keys().find(|&&c| c >= k) {
+                return Err(Failed::fit(&format!(
+                    "Class weights are keyed by class index, got {c} for {k} classes"
+                )));
+            }
+            (0..k)
+                .map(|c| {
+                    let weight = weights.get(&c).copied().unwrap_or(1.0);
+                    if !weight.is_finite() || weight < 0.0 {
+                        return Err(Failed::fit(&format!(
+                            "Class weights should be finite and >= 0, got {weight}"
+                        )));
+                    }
+                    Ok(weight)
+                })
+                .collect()
+        }
+    }
+}
@@ -0,0 +1,468 @@
+//! # AdaBoost Classifier
+//! AdaBoost classifier fits a sequence of decision stumps to reweighted versions of the training set and combines them into a weighted vote.
+//! See [AdaBoost](../index.html) for more details.
+//!
+//! Two algorithms are available:
+//!
+//! * `SAMME` (the default) combines the class predicted by every stump, weighted by the accuracy of the stump on the weighted training set.
+//! * `SAMME.R` combines the weighted class frequencies in the leaves of the stumps, the contribution of stump \\(m\\) to class \\(k\\) is
+//!   \\((K - 1)\left(\log p_{mk}(x) - \frac{1}{K}\sum_{k'} \log p_{mk'}(x)\right)\\). It usually converges faster than `SAMME`.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::basic::matrix::DenseMatrix;
+//! use smartcore::ensemble::adaboost::adaboost_classifier::*;
+//!
+//! // Iris dataset
+//! let x = DenseMatrix::from_2d_array(&[
+//!              &[5.1, 3.5, 1.4, 0.2],
+//!              &[4.9, 3.0, 1.4, 0.2],
+//!              &[4.7, 3.2, 1.3, 0.2],
+//!              &[4.6, 3.1, 1.5, 0.2],
+//!              &[5.0, 3.6, 1.4, 0.2],
+//!              &[5.4, 3.9, 1.7, 0.4],
+//!              &[4.6, 3.4, 1.4, 0.3],
+//!              &[5.0, 3.4, 1.5, 0.2],
+//!              &[4.4, 2.9, 1.4, 0.2],
+//!              &[4.9, 3.1, 1.5, 0.1],
+//!              &[7.0, 3.2, 4.7, 1.4],
+//!              &[6.4, 3.2, 4.5, 1.5],
+//!              &[6.9, 3.1, 4.9, 1.5],
+//!              &[5.5, 2.3, 4.0, 1.3],
+//!              &[6.5, 2.8, 4.6, 1.5],
+//!              &[5.7, 2.8, 4.5, 1.3],
+//!              &[6.3, 3.3, 4.7, 1.6],
+//!              &[4.9, 2.4, 3.3, 1.0],
+//!              &[6.6, 2.9, 4.6, 1.3],
+//!              &[5.2, 2.7, 3.9, 1.4],
+//!         ]).unwrap();
+//! let y = vec![
+//!              0, 0, 0, 0, 0, 0, 0, 0,
+//!              1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+//!         ];
+//!
+//! let classifier = AdaBoostClassifier::fit(
+//!     &x,
+//!     &y,
+//!     AdaBoostClassifierParameters::default().with_algorithm(AdaBoostAlgorithm::SammeR),
+//! ).unwrap();
+//!
+//! let y_hat = classifier.predict(&x).unwrap(); // use the same data for prediction
+//! let y_proba = classifier.predict_proba(&x).unwrap(); // class probabilities
+//! ```
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use std::default::Default;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::api::{Predictor, SupervisedEstimator};
+use crate::ensemble::adaboost::stump::WeightedStump;
+use crate::ensemble::adaboost::{normalize, validate};
+use crate::ensemble::gradient_boosting::loss::softmax;
+use crate::error::Failed;
+use crate::linalg::basic::arrays::{Array1, Array2};
+use crate::numbers::basenum::Number;
+use crate::numbers::floatnum::FloatNumber;
+use crate::tree::sort_columns;
+
+/// Multiclass AdaBoost algorithm.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AdaBoostAlgorithm {
+    /// Stagewise Additive Modeling using a Multi-class Exponential loss, combines the classes predicted by the stumps.
+    #[default]
+    Samme,
+    /// Real version of SAMME, combines the class probabilities estimated by the stumps.
+    SammeR,
+}
+
+/// Parameters of the AdaBoost classifier.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct AdaBoostClassifierParameters {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Multiclass AdaBoost algorithm.
+    pub algorithm: AdaBoostAlgorithm,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The maximum number of boosted stumps.
+    pub n_trees: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Shrinks the weight of every stump.
+    pub learning_rate: f64,
+}
+
+/// AdaBoost Classifier
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+pub struct AdaBoostClassifier<
+    TX: Number + FloatNumber + PartialOrd,
+    TY: Number + Ord,
+    X: Array2<TX>,
+    Y: Array1<TY>,
+> {
+    classes: Vec<TY>,
+    algorithm: AdaBoostAlgorithm,
+    stumps: Vec<WeightedStump>,
+    weights: Vec<f64>,
+    _phantom_tx: PhantomData<TX>,
+    _phantom_x: PhantomData<X>,
+    _phantom_y: PhantomData<Y>,
+}
+
+impl AdaBoostClassifierParameters {
+    /// Multiclass AdaBoost algorithm.
+    pub fn with_algorithm(mut self, algorithm: AdaBoostAlgorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+    /// The maximum number of boosted stumps.
+    pub fn with_n_trees(mut self, n_trees: usize) -> Self {
+        self.n_trees = n_trees;
+        self
+    }
+    /// Shrinks the weight of every stump.
+    pub fn with_learning_rate(mut self, learning_rate: f64) -> Self {
+        self.learning_rate = learning_rate;
+        self
+    }
+}
+
+impl Default for AdaBoostClassifierParameters {
+    fn default() -> Self {
+        AdaBoostClassifierParameters {
+            algorithm: AdaBoostAlgorithm::default(),
+            n_trees: 50,
+            learning_rate: 1f64,
+        }
+    }
+}
+
+impl<TX: Number + FloatNumber + PartialOrd, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>>
+    PartialEq for AdaBoostClassifier<TX, TY, X, Y>
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.classes == other.classes
+            && self.algorithm == other.algorithm
+            && self.stumps == other.stumps
+            && self.weights.len() == other.weights.len()
+            && self
+                .weights
+                .iter()
+                .zip(other.weights.iter())
+                .all(|(a, b)| (a - b).abs() < f64::EPSILON)
+    }
+}
+
+impl<TX: Number + FloatNumber + PartialOrd, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>>
+    SupervisedEstimator<X, Y, AdaBoostClassifierParameters> for AdaBoostClassifier<TX, TY, X, Y>
+{
+    fn new() -> Self {
+        Self {
+            classes: Vec::new(),
+            algorithm: AdaBoostAlgorithm::default(),
+            stumps: Vec::new(),
+            weights: Vec::new(),
+            _phantom_tx: PhantomData,
+            _phantom_x: PhantomData,
+            _phantom_y: PhantomData,
+        }
+    }
+
+    fn fit(x: &X, y: &Y, parameters: AdaBoostClassifierParameters) -> Result<Self, Failed> {
+        AdaBoostClassifier::fit(x, y, parameters)
+    }
+}
+
+impl<TX: Number + FloatNumber + PartialOrd, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>>
+    Predictor<X, Y> for AdaBoostClassifier<TX, TY, X, Y>
+{
+    fn predict(&self, x: &X) -> Result<Y, Failed> {
+        self.predict(x)
+    }
+}
+
+impl<TX: Number + FloatNumber + PartialOrd, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>>
+    AdaBoostClassifier<TX, TY, X, Y>
+{
+    /// Build an AdaBoost classifier from the training data.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - the target class values
+    pub fn fit(
+        x: &X,
+        y: &Y,
+        parameters: AdaBoostClassifierParameters,
+    ) -> Result<AdaBoostClassifier<TX, TY, X, Y>, Failed> {
+        let (n, _) = x.shape();
+        if n != y.shape() {
+            return Err(Failed::fit("Size of x should equal size of y"));
+        }
+        validate(parameters.n_trees, parameters.learning_rate)?;
+
+        let classes = y.unique();
+        let k = classes.len();
+        if k < 2 {
+            return Err(Failed::fit(&format!(
+                "Incorrect number of classes: {k}. Should be >= 2."
+            )));
+        }
+        let yi: Vec<usize> = y
+            .iterator(0)
+            .map(|y_i| classes.iter().position(|c| y_i == c).unwrap())
+            .collect();
+        let targets: Vec<Vec<f64>> = yi
+            .iter()
+            .map(|&c| {
+                let mut target = vec![0f64; k];
+                target[c] = 1f64;
+                target
+            })
+            .collect();
+
+        let (order, missing) = sort_columns(x);
+        let learning_rate = parameters.learning_rate;
+        let k_f64 = k as f64;
+        let mut sample_weights = vec![1f64 / n as f64; n];
+        let mut stumps = Vec::new();
+        let mut weights = Vec::new();
+
+        for _ in 0..parameters.n_trees {
+            let stump = WeightedStump::fit(x, &order, &missing, &targets, &sample_weights);
+            let incorrect: Vec<bool> = (0..n)
+                .map(|i| argmax(stump.predict_row(x, i)) != yi[i])
+                .collect();
+            let error: f64 = sample_weights
+                .iter()
+                .zip(incorrect.iter())
+                .filter(|(_, &incorrect_i)| incorrect_i)
+                .map(|(w, _)| w)
+                .sum();
+
+            match parameters.algorithm {
+                AdaBoostAlgorithm::Samme => {
+                    if error <= 0f64 {
+                        stumps.push(stump);
+                        weights.push(1f64);
+                        break;
+                    }
+                    if error >= 1f64 - 1f64 / k_f64 {
+                        if stumps.is_empty() {
+                            return Err(Failed::fit(
+                                "The first stump is no better than random guessing",
+                            ));
+                        }
+                        break;
+                    }
+                    let alpha =
+                        learning_rate * (((1f64 - error) / error).ln() + (k_f64 - 1f64).ln());
+                    for (w, &incorrect_i) in sample_weights.iter_mut().zip(incorrect.iter()) {
+                        if incorrect_i {
+                            *w *= alpha.exp();
+                        }
+                    }
+                    stumps.push(stump);
+                    weights.push(alpha);
+                }
+                AdaBoostAlgorithm::SammeR => {
+                    // coded targets are 1 for the class of the sample and -1 / (K - 1) for all other classes
+                    for (i, w) in sample_weights.iter_mut().enumerate() {
+                        let margin: f64 = stump
+                            .predict_row(x, i)
+                            .iter()
+                            .enumerate()
+                            .map(|(c, p)| {
+                                let coded = if c == yi[i] {
+                                    1f64
+                                } else {
+                                    -1f64 / (k_f64 - 1f64)
+                                };
+                                coded * p.max(f64::EPSILON).ln()
+                            })
+                            .sum();
+                        *w *= (-learning_rate * (k_f64 - 1f64) / k_f64 * margin).exp();
+                    }
+                    stumps.push(stump);
+                    weights.push(1f64);
+                    if error <= 0f64 {
+                        break;
+                    }
+                }
+            }
+
+            if !normalize(&mut sample_weights) {
+                break;
+            }
+        }
+
+        Ok(AdaBoostClassifier {
+            classes,
+            algorithm: parameters.algorithm,
+            stumps,
+            weights,
+            _phantom_tx: PhantomData,
+            _phantom_x: PhantomData,
+            _phantom_y: PhantomData,
+        })
+    }
+
+    /// Predict class for `x`.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict(&self, x: &X) -> Result<Y, Failed> {
+        let (n, _) = x.shape();
+        Ok(Y::from_iterator(
+            (0..n).map(|i| self.classes[argmax(&self.decision_row(x, i))]),
+            n,
+        ))
+    }
+
+    /// Predict class probabilities for `x`, the softmax of the weighted votes of the stumps divided by \\(K - 1\\).
+    /// Returns a _KxC_ matrix, columns are ordered like the sorted class labels.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict_proba(&self, x: &X) -> Result<X, Failed> {
+        let (n, _) = x.shape();
+        let k = self.classes.len();
+        let mut proba = X::zeros(n, k);
+        for i in 0..n {
+            let decision: Vec<f64> = self
+                .decision_row(x, i)
+                .iter()
+                .map(|d| d / (k as f64 - 1f64))
+                .collect();
+            for (c, p) in softmax(&decision).into_iter().enumerate() {
+                proba.set((i, c), TX::from_f64(p).unwrap());
+            }
+        }
+        Ok(proba)
+    }
+
+    /// The number of boosted stumps, smaller than `n_trees` when boosting stopped early.
+    pub fn n_trees(&self) -> usize {
+        self.stumps.len()
+    }
+
+    /// Sorted class labels, in the order of the columns returned by `predict_proba`.
+    pub fn classes(&self) -> &Vec<TY> {
+        &self.classes
+    }
+
+    /// Weighted votes of the stumps for every class, normalized by the total weight of the stumps.
+    fn decision_row(&self, x: &X, row: usize) -> Vec<f64> {
+        let k = self.classes.len();
+        let mut decision = vec![0f64; k];
+        for (stump, weight) in self.stumps.iter().zip(self.weights.iter()) {
+            let proba = stump.predict_row(x, row);
+            match self.algorithm {
+                AdaBoostAlgorithm::Samme => decision[argmax(proba)] += weight,
+                AdaBoostAlgorithm::SammeR => {
+                    let log_proba: Vec<f64> =
+                        proba.iter().map(|p| p.max(f64::EPSILON).ln()).collect();
+                    let mean = log_proba.iter().sum::<f64>() / k as f64;
+                    for (d, log_p) in decision.iter_mut().zip(log_proba.iter()) {
+                        *d += (k as f64 - 1f64) * (log_p - mean);
+                    }
+                }
+            }
+        }
+        let total_weight: f64 = self.weights.iter().sum();
+        decision.iter().map(|d| d / total_weight).collect()
+    }
+}
+
+fn argmax(x: &[f64]) -> usize {
+    (1..x.len()).fold(0, |best, c| if x[c] > x[best] { c } else { best })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ensemble::testing::{assert_fits_classes, assert_probabilities};
+    use crate::linalg::basic::arrays::Array;
+    use crate::linalg::basic::matrix::DenseMatrix;
+    use crate::metrics::*;
+    use crate::test_datasets::iris;
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn fit_predict_samme() {
+        let (x, y) = iris();
+
+        assert_fits_classes(
+            &x,
+            &y,
+            |y| AdaBoostClassifier::fit(&x, y, AdaBoostClassifierParameters::default()).unwrap(),
+            |classifier| classifier.predict_proba(&x).unwrap(),
+        );
+
+        // a single stump separates two classes perfectly, boosting stops
+        let y: Vec<u32> = y.iter().map(|&y_i| y_i.min(1)).collect();
+        let classifier =
+            AdaBoostClassifier::fit(&x, &y, AdaBoostClassifierParameters::default()).unwrap();
+        assert_eq!(classifier.n_trees(), 1);
+        assert_eq!(classifier.predict(&x).unwrap(), y);
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn fit_predict_samme_r() {
+        let (x, y) = iris();
+
+        let classifier = AdaBoostClassifier::fit(
+            &x,
+            &y,
+            AdaBoostClassifierParameters::default()
+                .with_algorithm(AdaBoostAlgorithm::SammeR)
+                .with_n_trees(20)
+                .with_learning_rate(0.5),
+        )
+        .unwrap();
+        assert!(accuracy(&y, &classifier.predict(&x).unwrap()) >= 0.95);
+
+        let proba: DenseMatrix<f64> = classifier.predict_proba(&x).unwrap();
+        assert_probabilities(&proba, 3);
+        for (i, y_i) in y.iter().enumerate() {
+            assert!(*proba.get((i, *y_i as usize)) > 1. / 3.);
+        }
+
+        assert!(AdaBoostClassifier::fit(
+            &x,
+            &y,
+            AdaBoostClassifierParameters::default().with_learning_rate(0.)
+        )
+        .is_err());
+        assert!(AdaBoostClassifier::fit(
+            &x,
+            &y,
+            AdaBoostClassifierParameters::default().with_learning_rate(f64::NAN)
+        )
+        .is_err());
+        assert!(AdaBoostClassifier::fit(&x, &vec![1; 18], Default::default()).is_err());
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde() {
+        let (x, y) = iris();
+
+        let classifier = AdaBoostClassifier::fit(&x, &y, Default::default()).unwrap();
+
+        let deserialized_classifier: AdaBoostClassifier<f64, u32, DenseMatrix<f64>, Vec<u32>> =
+            bincode::deserialize(&bincode::serialize(&classifier).unwrap()).unwrap();
+
+        assert_eq!(classifier, deserialized_classifier);
+    }
+}
@@ -0,0 +1,433 @@
+//! # AdaBoost Regressor
+//! AdaBoost regressor implements AdaBoost.R2: it fits a sequence of decision stumps to reweighted versions of the training set,
+//! and predicts the weighted median of the predictions of the stumps. See [AdaBoost](../index.html) for more details.
+//!
+//! At every stage, the loss \\(L_i\\) of every sample is computed from its absolute error \\(e_i\\) relative to the largest absolute error \\(D\\) of the stump:
+//!
+//! * Linear loss, \\(e_i / D\\), the default.
+//! * Square loss, \\((e_i / D)^2\\).
+//! * Exponential loss, \\(1 - e^{-e_i / D}\\).
+//!
+//! With the weighted average loss \\(\bar{L}\\) and \\(\beta = \bar{L} / (1 - \bar{L})\\), the weight of the stump is \\(\nu\log(1 / \beta)\\)
+//! and the weights of the samples are multiplied by \\(\beta^{\nu(1 - L_i)}\\), where \\(\nu\\) is the learning rate.
+//! Boosting stops when the average loss reaches one half.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::basic::matrix::DenseMatrix;
+//! use smartcore::ensemble::adaboost::adaboost_regressor::*;
+//!
+//! // Longley dataset (https://www.statsmodels.org/stable/datasets/generated/longley.html)
+//! let x = DenseMatrix::from_2d_array(&[
+//!             &[234.289, 235.6, 159., 107.608, 1947., 60.323],
+//!             &[259.426, 232.5, 145.6, 108.632, 1948., 61.122],
+//!             &[258.054, 368.2, 161.6, 109.773, 1949., 60.171],
+//!             &[284.599, 335.1, 165., 110.929, 1950., 61.187],
+//!             &[328.975, 209.9, 309.9, 112.075, 1951., 63.221],
+//!             &[346.999, 193.2, 359.4, 113.27, 1952., 63.639],
+//!             &[365.385, 187., 354.7, 115.094, 1953., 64.989],
+//!             &[363.112, 357.8, 335., 116.219, 1954., 63.761],
+//!             &[397.469, 290.4, 304.8, 117.388, 1955., 66.019],
+//!             &[419.18, 282.2, 285.7, 118.734, 1956., 67.857],
+//!             &[442.769, 293.6, 279.8, 120.445, 1957., 68.169],
+//!             &[444.546, 468.1, 263.7, 121.95, 1958., 66.513],
+//!             &[482.704, 381.3, 255.2, 123.366, 1959., 68.655],
+//!             &[502.601, 393.1, 251.4, 125.368, 1960., 69.564],
+//!             &[518.173, 480.6, 257.2, 127.852, 1961., 69.331],
+//!             &[554.894, 400.7, 282.7, 130.081, 1962., 70.551],
+//!         ]).unwrap();
+//! let y = vec![
+//!             83.0, 88.5, 88.2, 89.5, 96.2, 98.1, 99.0, 100.0, 101.2,
+//!             104.6, 108.4, 110.8, 112.6, 114.2, 115.7, 116.9,
+//!         ];
+//!
+//! let regressor = AdaBoostRegressor::fit(
+//!     &x,
+//!     &y,
+//!     AdaBoostRegressorParameters::default().with_loss(AdaBoostRegressorLoss::Square),
+//! ).unwrap();
+//!
+//! let y_hat = regressor.predict(&x).unwrap(); // use the same data for prediction
+//! ```
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use std::default::Default;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::api::{Predictor, SupervisedEstimator};
+use crate::ensemble::adaboost::stump::WeightedStump;
+use crate::ensemble::adaboost::{normalize, validate};
+use crate::error::Failed;
+use crate::linalg::basic::arrays::{Array1, Array2};
+use crate::numbers::basenum::Number;
+use crate::tree::sort_columns;
+
+/// Loss of a sample, computed from its absolute error relative to the largest absolute error of a stump.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AdaBoostRegressorLoss {
+    /// Relative absolute error
+    #[default]
+    Linear,
+    /// Squared relative absolute error
+    Square,
+    /// One minus the exponential of the negative relative absolute error
+    Exponential,
+}
+
+impl AdaBoostRegressorLoss {
+    fn loss(self, relative_error: f64) -> f64 {
+        match self {
+            AdaBoostRegressorLoss::Linear => relative_error,
+            AdaBoostRegressorLoss::Square => relative_error * relative_error,
+            AdaBoostRegressorLoss::Exponential => 1f64 - (-relative_error).exp(),
+        }
+    }
+}
+
+/// Parameters of the AdaBoost regressor.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct AdaBoostRegressorParameters {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Loss used to update the weights of the samples.
+    pub loss: AdaBoostRegressorLoss,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The maximum number of boosted stumps.
+    pub n_trees: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Shrinks the weight of every stump.
+    pub learning_rate: f64,
+}
+
+/// AdaBoost Regressor
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+pub struct AdaBoostRegressor<TX: Number + PartialOrd, TY: Number, X: Array2<TX>, Y: Array1<TY>> {
+    stumps: Vec<WeightedStump>,
+    weights: Vec<f64>,
+    _phantom_tx: PhantomData<TX>,
+    _phantom_ty: PhantomData<TY>,
+    _phantom_x: PhantomData<X>,
+    _phantom_y: PhantomData<Y>,
+}
+
+impl AdaBoostRegressorParameters {
+    /// Loss used to update the weights of the samples.
+    pub fn with_loss(mut self, loss: AdaBoostRegressorLoss) -> Self {
+        self.loss = loss;
+        self
+    }
+    /// The maximum number of boosted stumps.
+    pub fn with_n_trees(mut self, n_trees: usize) -> Self {
+        self.n_trees = n_trees;
+        self
+    }
+    /// Shrinks the weight of every stump.
+    pub fn with_learning_rate(mut self, learning_rate: f64) -> Self {
+        self.learning_rate = learning_rate;
+        self
+    }
+}
+
+impl Default for AdaBoostRegressorParameters {
+    fn default() -> Self {
+        AdaBoostRegressorParameters {
+            loss: AdaBoostRegressorLoss::default(),
+            n_trees: 50,
+            learning_rate: 1f64,
+        }
+    }
+}
+
+impl<TX: Number + PartialOrd, TY: Number, X: Array2<TX>, Y: Array1<TY>> PartialEq
+    for AdaBoostRegressor<TX, TY, X, Y>
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.stumps == other.stumps
+            && self.weights.len() == other.weights.len()
+            && self
+                .weights
+                .iter()
+                .zip(other.weights.iter())
+                .all(|(a, b)| (a - b).abs() < f64::EPSILON)
+    }
+}
+
+impl<TX: Number + PartialOrd, TY: Number, X: Array2<TX>, Y: Array1<TY>>
+    SupervisedEstimator<X, Y, AdaBoostRegressorParameters> for AdaBoostRegressor<TX, TY, X, Y>
+{
+    fn new() -> Self {
+        Self {
+            stumps: Vec::new(),
+            weights: Vec::new(),
+            _phantom_tx: PhantomData,
+            _phantom_ty: PhantomData,
+            _phantom_x: PhantomData,
+            _phantom_y: PhantomData,
+        }
+    }
+
+    fn fit(x: &X, y: &Y, parameters: AdaBoostRegressorParameters) -> Result<Self, Failed> {
+        AdaBoostRegressor::fit(x, y, parameters)
+    }
+}
+
+impl<TX: Number + PartialOrd, TY: Number, X: Array2<TX>, Y: Array1<TY>> Predictor<X, Y>
+    for AdaBoostRegressor<TX, TY, X, Y>
+{
+    fn predict(&self, x: &X) -> Result<Y, Failed> {
+        self.predict(x)
+    }
+}
+
+impl<TX: Number + PartialOrd, TY: Number, X: Array2<TX>, Y: Array1<TY>>
+    AdaBoostRegressor<TX, TY, X, Y>
+{
+    /// Build an AdaBoost regressor from the training data.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - the target values
+    pub fn fit(
+        x: &X,
+        y: &Y,
+        parameters: AdaBoostRegressorParameters,
+    ) -> Result<AdaBoostRegressor<TX, TY, X, Y>, Failed> {
+        let (n, _) = x.shape();
+        if n != y.shape() {
+            return Err(Failed::fit("Size of x should equal size of y"));
+        }
+        if n == 0 {
+            return Err(Failed::fit("x should not be empty"));
+        }
+        validate(parameters.n_trees, parameters.learning_rate)?;
+
+        let targets: Vec<Vec<f64>> = y
+            .iterator(0)
+            .map(|y_i| vec![y_i.to_f64().unwrap()])
+            .collect();
+        let (order, missing) = sort_columns(x);
+        let learning_rate = parameters.learning_rate;
+        let mut sample_weights = vec![1f64 / n as f64; n];
+        let mut stumps = Vec::new();
+        let mut weights = Vec::new();
+
+        for _ in 0..parameters.n_trees {
+            let stump = WeightedStump::fit(x, &order, &missing, &targets, &sample_weights);
+            let errors: Vec<f64> = (0..n)
+                .map(|i| (stump.predict_row(x, i)[0] - targets[i][0]).abs())
+                .collect();
+            let max_error = errors.iter().cloned().fold(0f64, f64::max);
+            if max_error <= 0f64 {
+                stumps.push(stump);
+                weights.push(1f64);
+                break;
+            }
+
+            let losses: Vec<f64> = errors
+                .iter()
+                .map(|e| parameters.loss.loss(e / max_error))
+                .collect();
+            let average_loss: f64 = sample_weights
+                .iter()
+                .zip(losses.iter())
+                .map(|(w, l)| w * l)
+                .sum();
+            // the stump fits every sample with a positive weight
+            if average_loss <= 0f64 {
+                stumps.push(stump);
+                weights.push(1f64);
+                break;
+            }
+            if average_loss >= 0.5 {
+                // keep the first stump, so that the model can predict
+                if stumps.is_empty() {
+                    stumps.push(stump);
+                    weights.push(1f64);
+                }
+                break;
+            }
+
+            let beta = average_loss / (1f64 - average_loss);
+            for (w, l) in sample_weights.iter_mut().zip(losses.iter()) {
+                *w *= beta.powf(learning_rate * (1f64 - l));
+            }
+            stumps.push(stump);
+            weights.push(learning_rate * (1f64 / beta).ln());
+
+            if !normalize(&mut sample_weights) {
+                break;
+            }
+        }
+
+        Ok(AdaBoostRegressor {
+            stumps,
+            weights,
+            _phantom_tx: PhantomData,
+            _phantom_ty: PhantomData,
+            _phantom_x: PhantomData,
+            _phantom_y: PhantomData,
+        })
+    }
+
+    /// Predict regression value for `x`, the weighted median of the predictions of the stumps.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict(&self, x: &X) -> Result<Y, Failed> {
+        let (n, _) = x.shape();
+        Ok(Y::from_iterator(
+            (0..n).map(|i| {
+                let predictions: Vec<f64> = self
+                    .stumps
+                    .iter()
+                    .map(|stump| stump.predict_row(x, i)[0])
+                    .collect();
+                TY::from_f64(weighted_median(&predictions, &self.weights)).unwrap()
+            }),
+            n,
+        ))
+    }
+
+    /// The number of boosted stumps, smaller than `n_trees` when boosting stopped early.
+    pub fn n_trees(&self) -> usize {
+        self.stumps.len()
+    }
+}
+
+/// The smallest value such that values up to it hold at least half of the total weight.
+fn weighted_median(values: &[f64], weights: &[f64]) -> f64 {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|&a, &b| values[a].partial_cmp(&values[b]).unwrap());
+    let half_weight = weights.iter().sum::<f64>() / 2f64;
+    let mut cumulative_weight = 0f64;
+    for &i in order.iter() {
+        cumulative_weight += weights[i];
+        if cumulative_weight >= half_weight {
+            return values[i];
+        }
+    }
+    values[order[order.len() - 1]]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::basic::matrix::DenseMatrix;
+    use crate::metrics::mean_absolute_error;
+    use crate::test_datasets::longley;
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn fit_longley() {
+        let (x, y) = longley();
+
+        for loss in [
+            AdaBoostRegressorLoss::Linear,
+            AdaBoostRegressorLoss::Square,
+            AdaBoostRegressorLoss::Exponential,
+        ] {
+            let y_hat = AdaBoostRegressor::fit(
+                &x,
+                &y,
+                AdaBoostRegressorParameters::default().with_loss(loss),
+            )
+            .and_then(|regressor| regressor.predict(&x))
+            .unwrap();
+
+            assert!(mean_absolute_error(&y, &y_hat) < 5.0);
+        }
+
+        assert!(AdaBoostRegressor::fit(
+            &x,
+            &y,
+            AdaBoostRegressorParameters::default().with_n_trees(0)
+        )
+        .is_err());
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn fit_steps() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[0.],
+            &[1.],
+            &[2.],
+            &[3.],
+            &[4.],
+            &[5.],
+            &[6.],
+            &[7.],
+            &[8.],
+            &[9.],
+        ])
+        .unwrap();
+        let y: Vec<f64> = vec![0., 0., 0., 5., 5., 5., 5., 10., 10., 10.];
+
+        let stump = AdaBoostRegressor::fit(
+            &x,
+            &y,
+            AdaBoostRegressorParameters::default()
+                .with_loss(AdaBoostRegressorLoss::Exponential)
+                .with_n_trees(1),
+        )
+        .unwrap();
+        let regressor = AdaBoostRegressor::fit(
+            &x,
+            &y,
+            AdaBoostRegressorParameters::default().with_loss(AdaBoostRegressorLoss::Exponential),
+        )
+        .unwrap();
+
+        assert_eq!(stump.n_trees(), 1);
+        assert!(regressor.n_trees() > 1);
+        assert!(
+            mean_absolute_error(&y, &regressor.predict(&x).unwrap())
+                < mean_absolute_error(&y, &stump.predict(&x).unwrap())
+        );
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn weighted_median_of_predictions() {
+        assert_eq!(weighted_median(&[3., 1., 2.], &[1., 1., 1.]), 2.);
+        assert_eq!(weighted_median(&[3., 1., 2.], &[3., 1., 1.]), 3.);
+        assert_eq!(weighted_median(&[3., 1., 2.], &[1., 1., 2.]), 2.);
+
+        // a perfect stump ends boosting
+        let x = DenseMatrix::from_2d_array(&[&[1.], &[2.], &[3.], &[4.]]).unwrap();
+        let y: Vec<f64> = vec![1., 1., 5., 5.];
+        let regressor = AdaBoostRegressor::fit(&x, &y, Default::default()).unwrap();
+        assert_eq!(regressor.n_trees(), 1);
+        assert_eq!(regressor.predict(&x).unwrap(), y);
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde() {
+        let (x, y) = longley();
+
+        let regressor = AdaBoostRegressor::fit(&x, &y, Default::default()).unwrap();
+
+        let deserialized_regressor: AdaBoostRegressor<f64, f64, DenseMatrix<f64>, Vec<f64>> =
+            bincode::deserialize(&bincode::serialize(&regressor).unwrap()).unwrap();
+
+        assert_eq!(regressor, deserialized_regressor);
+    }
+}
@@ -0,0 +1,53 @@
+//! # AdaBoost
+//!
+//! AdaBoost fits a sequence of weak learners, here decision stumps (trees with a single split), to reweighted versions of the training set.
+//! All samples start with equal weights. After every stage the weights of the samples the new stump fits poorly are increased,
+//! so that the next stump focuses on them, and the stump itself gets a weight that grows with its accuracy.
+//!
+//! * [AdaBoost classifier](adaboost_classifier/index.html) implements SAMME, the multiclass extension of discrete AdaBoost that combines
+//!   the weighted votes of the stumps, and SAMME.R, which combines the class probabilities estimated by the stumps instead.
+//!   The weight of a stump with weighted error rate \\(err_m\\) in SAMME is \\(\alpha_m = \nu\left(\log\frac{1 - err_m}{err_m} + \log(K - 1)\right)\\),
+//!   where \\(K\\) is the number of classes and \\(\nu\\) is the learning rate.
+//! * [AdaBoost regressor](adaboost_regressor/index.html) implements AdaBoost.R2: the loss of every sample is its absolute error relative to the largest error of the stump,
+//!   optionally squared or exponentiated, and the prediction of the ensemble is the weighted median of the predictions of the stumps.
+//!
+//! Boosting stops early when a stump fits the training set perfectly, or when a stump is no better than random guessing.
+//!
+//! ## References:
+//!
+//! * ["A Decision-Theoretic Generalization of On-Line Learning and an Application to Boosting", Freund Y., Schapire R. E., Journal of Computer and System Sciences, 1997](https://doi.org/10.1006/jcss.1997.1504)
+//! * ["Multi-class AdaBoost", Zhu J., Zou H., Rosset S., Hastie T., Statistics and Its Interface, 2009](https://dx.doi.org/10.4310/SII.2009.v2.n3.a8)
+//! * ["Improving Regressors using Boosting Techniques", Drucker H., Proceedings of the 14th International Conference on Machine Learning, 1997](https://dl.acm.org/doi/10.5555/645526.657132)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+
+/// AdaBoost classifier
+pub mod adaboost_classifier;
+/// AdaBoost regressor
+pub mod adaboost_regressor;
+mod stump;
+
+use crate::error::Failed;
+
+fn validate(n_trees: usize, learning_rate: f64) -> Result<(), Failed> {
+    if n_trees == 0 {
+        return Err(Failed::fit("n_trees should be positive"));
+    }
+    if !(learning_rate.is_finite() && learning_rate > 0f64) {
+        return Err(Failed::fit("learning_rate should be positive and finite"));
+    }
+    Ok(())
+}
+
+/// Rescale `weights` to sum to one, returns false when they can not be rescaled.
+fn normalize(weights: &mut [f64]) -> bool {
+    let sum: f64 = weights.iter().sum();
+    if !(sum.is_finite() && sum > 0f64) {
+        return false;
+    }
+    for w in weights.iter_mut() {
+        *w /= sum;
+    }
+    true
+}
@@ -0,0 +1,195 @@
+//! Decision stumps fitted to weighted samples.
+//!
+//! Every sample has a vector of targets, one-hot encoded classes for classification or the response for regression.
+//! The stump minimizes the weighted squared error of the targets around the weighted mean of every child,
+//! which is the weighted Gini impurity for one-hot encoded classes.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::linalg::basic::arrays::Array2;
+use crate::numbers::basenum::Number;
+
+/// A single split into two leaves holding the weighted mean of the targets of their samples.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct WeightedStump {
+    feature: usize,
+    threshold: f64,
+    missing_to_left: bool,
+    left: Vec<f64>,
+    right: Vec<f64>,
+}
+
+impl WeightedStump {
+    /// Fit a stump to `targets` with sample `weights`, where `order` and `missing` are the rows of every column of `x`
+    /// sorted by value and the rows with a missing value of every column.
+    pub(crate) fn fit<TX: Number, X: Array2<TX>>(
+        x: &X,
+        order: &[Vec<usize>],
+        missing: &[Vec<usize>],
+        targets: &[Vec<f64>],
+        weights: &[f64],
+    ) -> WeightedStump {
+        let k = targets[0].len();
+        let (total_weight, total_sum) = weighted_sum(0..targets.len(), targets, weights, k);
+
+        let mean = |sum: &[f64], weight: f64| sum.iter().map(|s| s / weight).collect::<Vec<_>>();
+        let score = |sum: &[f64], weight: f64| sum.iter().map(|s| s * s).sum::<f64>() / weight;
+
+        // a stump without a split sends every sample to the left leaf
+        let mut best = WeightedStump {
+            feature: 0,
+            threshold: f64::INFINITY,
+            missing_to_left: true,
+            left: mean(&total_sum, total_weight),
+            right: mean(&total_sum, total_weight),
+        };
+        let mut best_score = f64::NEG_INFINITY;
+
+        for (j, (order_j, missing_j)) in order.iter().zip(missing.iter()).enumerate() {
+            let (missing_weight, missing_sum) =
+                weighted_sum(missing_j.iter().cloned(), targets, weights, k);
+
+            let mut left_weight = 0f64;
+            let mut left_sum = vec![0f64; k];
+            for (position, &i) in order_j.iter().enumerate() {
+                left_weight += weights[i];
+                for (s, t) in left_sum.iter_mut().zip(targets[i].iter()) {
+                    *s += weights[i] * t;
+                }
+
+                let x_ij = x.get((i, j)).to_f64().unwrap();
+                let next = match order_j.get(position + 1) {
+                    Some(&next) => x.get((next, j)).to_f64().unwrap(),
+                    None => break,
+                };
+                if next <= x_ij {
+                    continue;
+                }
+
+                // without missing values, missing values at prediction time follow the heavier child
+                let directions: &[bool] = if missing_weight > 0f64 {
+                    &[false, true]
+                } else if 2f64 * left_weight >= total_weight {
+                    &[true]
+                } else {
+                    &[false]
+                };
+                for &missing_to_left in directions {
+                    let (lw, ls) = if missing_to_left {
+                        let ls: Vec<f64> = left_sum
+                            .iter()
+                            .zip(missing_sum.iter())
+                            .map(|(l, m)| l + m)
+                            .collect();
+                        (left_weight + missing_weight, ls)
+                    } else {
+                        (left_weight, left_sum.clone())
+                    };
+                    let rw = total_weight - lw;
+                    if lw <= 0f64 || rw <= 0f64 {
+                        continue;
+                    }
+                    let rs: Vec<f64> = total_sum
+                        .iter()
+                        .zip(ls.iter())
+                        .map(|(t, l)| t - l)
+                        .collect();
+
+                    let split_score = score(&ls, lw) + score(&rs, rw);
+                    if split_score > best_score {
+                        best_score = split_score;
+                        best = WeightedStump {
+                            feature: j,
+                            threshold: (x_ij + next) / 2f64,
+                            missing_to_left,
+                            left: mean(&ls, lw),
+                            right: mean(&rs, rw),
+                        };
+                    }
+                }
+            }
+        }
+
+        best
+    }
+
+    /// Weighted mean of the targets in the leaf of `row` of `x`.
+    pub(crate) fn predict_row<TX: Number, X: Array2<TX>>(&self, x: &X, row: usize) -> &[f64] {
+        let value = x.get((row, self.feature)).to_f64().unwrap();
+        let goes_left = if value.is_nan() {
+            self.missing_to_left
+        } else {
+            value <= self.threshold
+        };
+        if goes_left {
+            &self.left
+        } else {
+            &self.right
+        }
+    }
+}
+
+fn weighted_sum(
+    rows: impl Iterator<Item = usize>,
+    targets: &[Vec<f64>],
+    weights: &[f64],
+    k: usize,
+) -> (f64, Vec<f64>) {
+    let mut weight = 0f64;
+    let mut sum = vec![0f64; k];
+    for i in rows {
+        weight += weights[i];
+        for (s, t) in sum.iter_mut().zip(targets[i].iter()) {
+            *s += weights[i] * t;
+        }
+    }
+    (weight, sum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::basic::matrix::DenseMatrix;
+    use crate::tree::sort_columns;
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn weighted_split() {
+        let nan = f64::NAN;
+        let x =
+            DenseMatrix::from_2d_array(&[&[1., 5.], &[2., 5.], &[3., 5.], &[4., nan], &[nan, 6.]])
+                .unwrap();
+        let targets = vec![vec![1.], vec![1.], vec![3.], vec![3.], vec![3.]];
+        let (order, missing) = sort_columns(&x);
+
+        let stump = WeightedStump::fit(&x, &order, &missing, &targets, &[1.; 5]);
+        assert_eq!(stump.feature, 0);
+        assert_eq!(stump.threshold, 2.5);
+        assert!(!stump.missing_to_left);
+        assert_eq!(stump.left, vec![1.]);
+        assert_eq!(stump.right, vec![3.]);
+
+        // a heavy sample moves the split, leaves hold weighted means
+        let x = DenseMatrix::from_2d_array(&[&[1.], &[2.], &[3.], &[4.], &[nan]]).unwrap();
+        let targets = vec![vec![1.], vec![3.], vec![1.], vec![3.], vec![3.]];
+        let (order, missing) = sort_columns(&x);
+        let stump = WeightedStump::fit(&x, &order, &missing, &targets, &[9., 1., 1., 1., 0.]);
+        assert_eq!(stump.threshold, 1.5);
+        let stump = WeightedStump::fit(&x, &order, &missing, &targets, &[1., 1., 1., 9., 0.]);
+        assert_eq!(stump.threshold, 3.5);
+        assert_eq!(stump.left, vec![5. / 3.]);
+        assert_eq!(stump.right, vec![3.]);
+        assert_eq!(stump.predict_row(&x, 4), &[3.]);
+
+        // constant features can not be split
+        let x = DenseMatrix::from_2d_array(&[&[1.], &[1.]]).unwrap();
+        let (order, missing) = sort_columns(&x);
+        let stump = WeightedStump::fit(&x, &order, &missing, &[vec![1.], vec![3.]], &[1., 3.]);
+        assert_eq!(stump.predict_row(&x, 0), &[2.5]);
+    }
+}
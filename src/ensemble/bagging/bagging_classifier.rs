@@ -0,0 +1,400 @@
+//! # Bagging Classifier
+//! Bagging classifier fits any classifier to random subsets of the training samples and features, and predicts the majority vote of the fitted classifiers.
+//! See [bagging](../index.html) for more details.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::basic::matrix::DenseMatrix;
+//! use smartcore::ensemble::bagging::bagging_classifier::*;
+//! use smartcore::tree::decision_tree_classifier::*;
+//!
+//! // Iris dataset
+//! let x = DenseMatrix::from_2d_array(&[
+//!              &[5.1, 3.5, 1.4, 0.2],
+//!              &[4.9, 3.0, 1.4, 0.2],
+//!              &[4.7, 3.2, 1.3, 0.2],
+//!              &[4.6, 3.1, 1.5, 0.2],
+//!              &[5.0, 3.6, 1.4, 0.2],
+//!              &[5.4, 3.9, 1.7, 0.4],
+//!              &[4.6, 3.4, 1.4, 0.3],
+//!              &[5.0, 3.4, 1.5, 0.2],
+//!              &[4.4, 2.9, 1.4, 0.2],
+//!              &[4.9, 3.1, 1.5, 0.1],
+//!              &[7.0, 3.2, 4.7, 1.4],
+//!              &[6.4, 3.2, 4.5, 1.5],
+//!              &[6.9, 3.1, 4.9, 1.5],
+//!              &[5.5, 2.3, 4.0, 1.3],
+//!              &[6.5, 2.8, 4.6, 1.5],
+//!              &[5.7, 2.8, 4.5, 1.3],
+//!              &[6.3, 3.3, 4.7, 1.6],
+//!              &[4.9, 2.4, 3.3, 1.0],
+//!              &[6.6, 2.9, 4.6, 1.3],
+//!              &[5.2, 2.7, 3.9, 1.4],
+//!         ]).unwrap();
+//! let y = vec![
+//!              0, 0, 0, 0, 0, 0, 0, 0,
+//!              1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+//!         ];
+//!
+//! // bag 20 decision trees, every tree sees three of the four features
+//! let classifier: BaggingClassifier<_, _, _, _, DecisionTreeClassifier<_, _, _, _>> =
+//!     BaggingClassifier::fit(
+//!         &x,
+//!         &y,
+//!         BaggingClassifierParameters::default()
+//!             .with_estimator_parameters(DecisionTreeClassifierParameters::default())
+//!             .with_n_estimators(20)
+//!             .with_max_features(0.75),
+//!     ).unwrap();
+//!
+//! let y_hat = classifier.predict(&x).unwrap(); // use the same data for prediction
+//! ```
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use std::default::Default;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::api::{Predictor, SupervisedEstimator};
+use crate::ensemble::bagging::Subsampling;
+use crate::error::Failed;
+use crate::linalg::basic::arrays::{Array1, Array2};
+use crate::numbers::basenum::Number;
+use crate::rand_custom::get_rng_impl;
+use crate::tree::decision_tree_classifier::which_max;
+
+/// Parameters of the bagging classifier, `P` are the parameters of the base classifier.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct BaggingClassifierParameters<P> {
+    /// Parameters of every base classifier.
+    pub estimator_parameters: P,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The number of base classifiers.
+    pub n_estimators: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Fraction of the training samples used to fit every base classifier.
+    pub max_samples: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Fraction of the features used to fit every base classifier.
+    pub max_features: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Whether samples are drawn with replacement.
+    pub bootstrap: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Whether features are drawn with replacement.
+    pub bootstrap_features: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Seed used to draw the samples and features of every base classifier.
+    pub seed: u64,
+}
+
+/// Bagging Classifier
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+pub struct BaggingClassifier<TX: Number, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>, E> {
+    estimators: Vec<E>,
+    features: Vec<Vec<usize>>,
+    classes: Vec<TY>,
+    _phantom_tx: PhantomData<TX>,
+    _phantom_x: PhantomData<X>,
+    _phantom_y: PhantomData<Y>,
+}
+
+impl<P> BaggingClassifierParameters<P> {
+    /// Parameters of every base classifier.
+    pub fn with_estimator_parameters(mut self, estimator_parameters: P) -> Self {
+        self.estimator_parameters = estimator_parameters;
+        self
+    }
+    /// The number of base classifiers.
+    pub fn with_n_estimators(mut self, n_estimators: usize) -> Self {
+        self.n_estimators = n_estimators;
+        self
+    }
+    /// Fraction of the training samples used to fit every base classifier.
+    pub fn with_max_samples(mut self, max_samples: f64) -> Self {
+        self.max_samples = max_samples;
+        self
+    }
+    /// Fraction of the features used to fit every base classifier.
+    pub fn with_max_features(mut self, max_features: f64) -> Self {
+        self.max_features = max_features;
+        self
+    }
+    /// Whether samples are drawn with replacement.
+    pub fn with_bootstrap(mut self, bootstrap: bool) -> Self {
+        self.bootstrap = bootstrap;
+        self
+    }
+    /// Whether features are drawn with replacement.
+    pub fn with_bootstrap_features(mut self, bootstrap_features: bool) -> Self {
+        self.bootstrap_features = bootstrap_features;
+        self
+    }
+    /// Seed used to draw the samples and features of every base classifier.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    fn subsampling(&self) -> Subsampling {
+        Subsampling {
+            n_estimators: self.n_estimators,
+            max_samples: self.max_samples,
+            max_features: self.max_features,
+            bootstrap: self.bootstrap,
+            bootstrap_features: self.bootstrap_features,
+        }
+    }
+}
+
+impl<P: Default> Default for BaggingClassifierParameters<P> {
+    fn default() -> Self {
+        BaggingClassifierParameters {
+            estimator_parameters: P::default(),
+            n_estimators: 10,
+            max_samples: 1f64,
+            max_features: 1f64,
+            bootstrap: true,
+            bootstrap_features: false,
+            seed: 0,
+        }
+    }
+}
+
+impl<TX: Number, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>, E: PartialEq> PartialEq
+    for BaggingClassifier<TX, TY, X, Y, E>
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.classes == other.classes
+            && self.features == other.features
+            && self.estimators == other.estimators
+    }
+}
+
+impl<TX: Number, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>, P: Clone, E>
+    SupervisedEstimator<X, Y, BaggingClassifierParameters<P>> for BaggingClassifier<TX, TY, X, Y, E>
+where
+    E: SupervisedEstimator<X, Y, P>,
+{
+    fn new() -> Self {
+        Self {
+            estimators: Vec::new(),
+            features: Vec::new(),
+            classes: Vec::new(),
+            _phantom_tx: PhantomData,
+            _phantom_x: PhantomData,
+            _phantom_y: PhantomData,
+        }
+    }
+
+    fn fit(x: &X, y: &Y, parameters: BaggingClassifierParameters<P>) -> Result<Self, Failed> {
+        BaggingClassifier::fit(x, y, parameters)
+    }
+}
+
+impl<TX: Number, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>, E: Predictor<X, Y>> Predictor<X, Y>
+    for BaggingClassifier<TX, TY, X, Y, E>
+{
+    fn predict(&self, x: &X) -> Result<Y, Failed> {
+        self.predict(x)
+    }
+}
+
+impl<TX: Number, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>, E: Predictor<X, Y>>
+    BaggingClassifier<TX, TY, X, Y, E>
+{
+    /// Fit base classifiers of type `E` to random subsets of the training data.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - the target class values
+    /// * `parameters` - bagging parameters and the parameters of the base classifier
+    pub fn fit<P: Clone>(
+        x: &X,
+        y: &Y,
+        parameters: BaggingClassifierParameters<P>,
+    ) -> Result<BaggingClassifier<TX, TY, X, Y, E>, Failed>
+    where
+        E: SupervisedEstimator<X, Y, P>,
+    {
+        let (n, m) = x.shape();
+        if n != y.shape() {
+            return Err(Failed::fit("Size of x should equal size of y"));
+        }
+        if n == 0 {
+            return Err(Failed::fit("x should not be empty"));
+        }
+        let subsampling = parameters.subsampling();
+        subsampling.validate()?;
+
+        let mut rng = get_rng_impl(Some(parameters.seed));
+        let mut estimators = Vec::with_capacity(parameters.n_estimators);
+        let mut features = Vec::with_capacity(parameters.n_estimators);
+        for _ in 0..parameters.n_estimators {
+            let (rows, columns) = subsampling.draw(n, m, &mut rng);
+            let estimator = <E as SupervisedEstimator<X, Y, P>>::fit(
+                &x.take(&rows, 0).take(&columns, 1),
+                &y.take(&rows),
+                parameters.estimator_parameters.clone(),
+            )?;
+            estimators.push(estimator);
+            features.push(columns);
+        }
+
+        Ok(BaggingClassifier {
+            estimators,
+            features,
+            classes: y.unique(),
+            _phantom_tx: PhantomData,
+            _phantom_x: PhantomData,
+            _phantom_y: PhantomData,
+        })
+    }
+
+    /// Predict class for `x`, the majority vote of the base classifiers.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict(&self, x: &X) -> Result<Y, Failed> {
+        let (n, _) = x.shape();
+        let mut votes = vec![vec![0; self.classes.len()]; n];
+        for (estimator, columns) in self.estimators.iter().zip(self.features.iter()) {
+            let y_hat = estimator.predict(&x.take(columns, 1))?;
+            for (i, votes_i) in votes.iter_mut().enumerate() {
+                if let Some(c) = self.classes.iter().position(|c| c == y_hat.get(i)) {
+                    votes_i[c] += 1;
+                }
+            }
+        }
+        Ok(Y::from_iterator(
+            votes.iter().map(|votes_i| self.classes[which_max(votes_i)]),
+            n,
+        ))
+    }
+
+    /// The fitted base classifiers.
+    pub fn estimators(&self) -> &Vec<E> {
+        &self.estimators
+    }
+
+    /// Features of the training data used by every base classifier.
+    pub fn features(&self) -> &Vec<Vec<usize>> {
+        &self.features
+    }
+
+    /// Sorted class labels.
+    pub fn classes(&self) -> &Vec<TY> {
+        &self.classes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::basic::matrix::DenseMatrix;
+    use crate::linear::logistic_regression::{LogisticRegression, LogisticRegressionParameters};
+    use crate::metrics::*;
+    use crate::test_datasets::iris;
+    use crate::tree::decision_tree_classifier::{
+        DecisionTreeClassifier, DecisionTreeClassifierParameters,
+    };
+
+    type Tree = DecisionTreeClassifier<f64, u32, DenseMatrix<f64>, Vec<u32>>;
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn fit_predict_trees() {
+        let (x, y) = iris();
+
+        let classifier: BaggingClassifier<_, _, _, _, Tree> = BaggingClassifier::fit(
+            &x,
+            &y,
+            BaggingClassifierParameters::default()
+                .with_estimator_parameters(DecisionTreeClassifierParameters::default())
+                .with_n_estimators(20)
+                .with_max_features(0.5)
+                .with_seed(11),
+        )
+        .unwrap();
+
+        assert_eq!(classifier.estimators().len(), 20);
+        assert!(classifier.features().iter().all(|f| f.len() == 2));
+        assert!(accuracy(&y, &classifier.predict(&x).unwrap()) >= 0.95);
+
+        // without any subsampling every tree is the tree fitted to the whole training set
+        let classifier: BaggingClassifier<_, _, _, _, Tree> = BaggingClassifier::fit(
+            &x,
+            &y,
+            BaggingClassifierParameters::default()
+                .with_estimator_parameters(DecisionTreeClassifierParameters::default())
+                .with_n_estimators(3)
+                .with_bootstrap(false),
+        )
+        .unwrap();
+        let tree = Tree::fit(&x, &y, Default::default()).unwrap();
+        assert!(classifier.estimators().iter().all(|t| *t == tree));
+
+        assert!(BaggingClassifier::<_, _, _, _, Tree>::fit(
+            &x,
+            &y,
+            BaggingClassifierParameters::<DecisionTreeClassifierParameters>::default()
+                .with_max_samples(1.5)
+        )
+        .is_err());
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn fit_predict_any_classifier() {
+        let (x, y) = iris();
+
+        let classifier: BaggingClassifier<
+            _,
+            _,
+            _,
+            _,
+            LogisticRegression<f64, u32, DenseMatrix<f64>, Vec<u32>>,
+        > = BaggingClassifier::fit(
+            &x,
+            &y,
+            BaggingClassifierParameters::default()
+                .with_estimator_parameters(LogisticRegressionParameters::default())
+                .with_n_estimators(5),
+        )
+        .unwrap();
+
+        assert!(accuracy(&y, &classifier.predict(&x).unwrap()) >= 0.9);
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde() {
+        let (x, y) = iris();
+
+        let classifier: BaggingClassifier<_, _, _, _, Tree> = BaggingClassifier::fit(
+            &x,
+            &y,
+            BaggingClassifierParameters::default()
+                .with_estimator_parameters(DecisionTreeClassifierParameters::default()),
+        )
+        .unwrap();
+
+        let deserialized_classifier: BaggingClassifier<f64, u32, DenseMatrix<f64>, Vec<u32>, Tree> =
+            bincode::deserialize(&bincode::serialize(&classifier).unwrap()).unwrap();
+
+        assert_eq!(classifier, deserialized_classifier);
+    }
+}
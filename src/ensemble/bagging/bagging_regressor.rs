@@ -0,0 +1,384 @@
+//! # Bagging Regressor
+//! Bagging regressor fits any regressor to random subsets of the training samples and features, and predicts the average prediction of the fitted regressors.
+//! See [bagging](../index.html) for more details.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::basic::matrix::DenseMatrix;
+//! use smartcore::ensemble::bagging::bagging_regressor::*;
+//! use smartcore::tree::decision_tree_regressor::*;
+//!
+//! // Longley dataset (https://www.statsmodels.org/stable/datasets/generated/longley.html)
+//! let x = DenseMatrix::from_2d_array(&[
+//!             &[234.289, 235.6, 159., 107.608, 1947., 60.323],
+//!             &[259.426, 232.5, 145.6, 108.632, 1948., 61.122],
+//!             &[258.054, 368.2, 161.6, 109.773, 1949., 60.171],
+//!             &[284.599, 335.1, 165., 110.929, 1950., 61.187],
+//!             &[328.975, 209.9, 309.9, 112.075, 1951., 63.221],
+//!             &[346.999, 193.2, 359.4, 113.27, 1952., 63.639],
+//!             &[365.385, 187., 354.7, 115.094, 1953., 64.989],
+//!             &[363.112, 357.8, 335., 116.219, 1954., 63.761],
+//!             &[397.469, 290.4, 304.8, 117.388, 1955., 66.019],
+//!             &[419.18, 282.2, 285.7, 118.734, 1956., 67.857],
+//!             &[442.769, 293.6, 279.8, 120.445, 1957., 68.169],
+//!             &[444.546, 468.1, 263.7, 121.95, 1958., 66.513],
+//!             &[482.704, 381.3, 255.2, 123.366, 1959., 68.655],
+//!             &[502.601, 393.1, 251.4, 125.368, 1960., 69.564],
+//!             &[518.173, 480.6, 257.2, 127.852, 1961., 69.331],
+//!             &[554.894, 400.7, 282.7, 130.081, 1962., 70.551],
+//!         ]).unwrap();
+//! let y = vec![
+//!             83.0, 88.5, 88.2, 89.5, 96.2, 98.1, 99.0, 100.0, 101.2,
+//!             104.6, 108.4, 110.8, 112.6, 114.2, 115.7, 116.9,
+//!         ];
+//!
+//! // bag 20 regression trees, every tree is fitted to half of the samples drawn without replacement
+//! let regressor: BaggingRegressor<_, _, _, _, DecisionTreeRegressor<_, _, _, _>> =
+//!     BaggingRegressor::fit(
+//!         &x,
+//!         &y,
+//!         BaggingRegressorParameters::default()
+//!             .with_estimator_parameters(DecisionTreeRegressorParameters::default())
+//!             .with_n_estimators(20)
+//!             .with_max_samples(0.5)
+//!             .with_bootstrap(false),
+//!     ).unwrap();
+//!
+//! let y_hat = regressor.predict(&x).unwrap(); // use the same data for prediction
+//! ```
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use std::default::Default;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::api::{Predictor, SupervisedEstimator};
+use crate::ensemble::bagging::Subsampling;
+use crate::error::Failed;
+use crate::linalg::basic::arrays::{Array1, Array2};
+use crate::numbers::basenum::Number;
+use crate::rand_custom::get_rng_impl;
+
+/// Parameters of the bagging regressor, `P` are the parameters of the base regressor.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct BaggingRegressorParameters<P> {
+    /// Parameters of every base regressor.
+    pub estimator_parameters: P,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The number of base regressors.
+    pub n_estimators: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Fraction of the training samples used to fit every base regressor.
+    pub max_samples: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Fraction of the features used to fit every base regressor.
+    pub max_features: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Whether samples are drawn with replacement.
+    pub bootstrap: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Whether features are drawn with replacement.
+    pub bootstrap_features: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Seed used to draw the samples and features of every base regressor.
+    pub seed: u64,
+}
+
+/// Bagging Regressor
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+pub struct BaggingRegressor<TX: Number, TY: Number, X: Array2<TX>, Y: Array1<TY>, E> {
+    estimators: Vec<E>,
+    features: Vec<Vec<usize>>,
+    _phantom_tx: PhantomData<TX>,
+    _phantom_ty: PhantomData<TY>,
+    _phantom_x: PhantomData<X>,
+    _phantom_y: PhantomData<Y>,
+}
+
+impl<P> BaggingRegressorParameters<P> {
+    /// Parameters of every base regressor.
+    pub fn with_estimator_parameters(mut self, estimator_parameters: P) -> Self {
+        self.estimator_parameters = estimator_parameters;
+        self
+    }
+    /// The number of base regressors.
+    pub fn with_n_estimators(mut self, n_estimators: usize) -> Self {
+        self.n_estimators = n_estimators;
+        self
+    }
+    /// Fraction of the training samples used to fit every base regressor.
+    pub fn with_max_samples(mut self, max_samples: f64) -> Self {
+        self.max_samples = max_samples;
+        self
+    }
+    /// Fraction of the features used to fit every base regressor.
+    pub fn with_max_features(mut self, max_features: f64) -> Self {
+        self.max_features = max_features;
+        self
+    }
+    /// Whether samples are drawn with replacement.
+    pub fn with_bootstrap(mut self, bootstrap: bool) -> Self {
+        self.bootstrap = bootstrap;
+        self
+    }
+    /// Whether features are drawn with replacement.
+    pub fn with_bootstrap_features(mut self, bootstrap_features: bool) -> Self {
+        self.bootstrap_features = bootstrap_features;
+        self
+    }
+    /// Seed used to draw the samples and features of every base regressor.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    fn subsampling(&self) -> Subsampling {
+        Subsampling {
+            n_estimators: self.n_estimators,
+            max_samples: self.max_samples,
+            max_features: self.max_features,
+            bootstrap: self.bootstrap,
+            bootstrap_features: self.bootstrap_features,
+        }
+    }
+}
+
+impl<P: Default> Default for BaggingRegressorParameters<P> {
+    fn default() -> Self {
+        BaggingRegressorParameters {
+            estimator_parameters: P::default(),
+            n_estimators: 10,
+            max_samples: 1f64,
+            max_features: 1f64,
+            bootstrap: true,
+            bootstrap_features: false,
+            seed: 0,
+        }
+    }
+}
+
+impl<TX: Number, TY: Number, X: Array2<TX>, Y: Array1<TY>, E: PartialEq> PartialEq
+    for BaggingRegressor<TX, TY, X, Y, E>
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.features == other.features && self.estimators == other.estimators
+    }
+}
+
+impl<TX: Number, TY: Number, X: Array2<TX>, Y: Array1<TY>, P: Clone, E>
+    SupervisedEstimator<X, Y, BaggingRegressorParameters<P>> for BaggingRegressor<TX, TY, X, Y, E>
+where
+    E: SupervisedEstimator<X, Y, P>,
+{
+    fn new() -> Self {
+        Self {
+            estimators: Vec::new(),
+            features: Vec::new(),
+            _phantom_tx: PhantomData,
+            _phantom_ty: PhantomData,
+            _phantom_x: PhantomData,
+            _phantom_y: PhantomData,
+        }
+    }
+
+    fn fit(x: &X, y: &Y, parameters: BaggingRegressorParameters<P>) -> Result<Self, Failed> {
+        BaggingRegressor::fit(x, y, parameters)
+    }
+}
+
+impl<TX: Number, TY: Number, X: Array2<TX>, Y: Array1<TY>, E: Predictor<X, Y>> Predictor<X, Y>
+    for BaggingRegressor<TX, TY, X, Y, E>
+{
+    fn predict(&self, x: &X) -> Result<Y, Failed> {
+        self.predict(x)
+    }
+}
+
+impl<TX: Number, TY: Number, X: Array2<TX>, Y: Array1<TY>, E: Predictor<X, Y>>
+    BaggingRegressor<TX, TY, X, Y, E>
+{
+    /// Fit base regressors of type `E` to random subsets of the training data.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - the target values
+    /// * `parameters` - bagging parameters and the parameters of the base regressor
+    pub fn fit<P: Clone>(
+        x: &X,
+        y: &Y,
+        parameters: BaggingRegressorParameters<P>,
+    ) -> Result<BaggingRegressor<TX, TY, X, Y, E>, Failed>
+    where
+        E: SupervisedEstimator<X, Y, P>,
+    {
+        let (n, m) = x.shape();
+        if n != y.shape() {
+            return Err(Failed::fit("Size of x should equal size of y"));
+        }
+        if n == 0 {
+            return Err(Failed::fit("x should not be empty"));
+        }
+        let subsampling = parameters.subsampling();
+        subsampling.validate()?;
+
+        let mut rng = get_rng_impl(Some(parameters.seed));
+        let mut estimators = Vec::with_capacity(parameters.n_estimators);
+        let mut features = Vec::with_capacity(parameters.n_estimators);
+        for _ in 0..parameters.n_estimators {
+            let (rows, columns) = subsampling.draw(n, m, &mut rng);
+            let estimator = <E as SupervisedEstimator<X, Y, P>>::fit(
+                &x.take(&rows, 0).take(&columns, 1),
+                &y.take(&rows),
+                parameters.estimator_parameters.clone(),
+            )?;
+            estimators.push(estimator);
+            features.push(columns);
+        }
+
+        Ok(BaggingRegressor {
+            estimators,
+            features,
+            _phantom_tx: PhantomData,
+            _phantom_ty: PhantomData,
+            _phantom_x: PhantomData,
+            _phantom_y: PhantomData,
+        })
+    }
+
+    /// Predict regression value for `x`, the average prediction of the base regressors.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict(&self, x: &X) -> Result<Y, Failed> {
+        let (n, _) = x.shape();
+        let mut sum = vec![0f64; n];
+        for (estimator, columns) in self.estimators.iter().zip(self.features.iter()) {
+            let y_hat = estimator.predict(&x.take(columns, 1))?;
+            for (i, sum_i) in sum.iter_mut().enumerate() {
+                *sum_i += y_hat.get(i).to_f64().unwrap();
+            }
+        }
+        let n_estimators = self.estimators.len() as f64;
+        Ok(Y::from_iterator(
+            sum.iter()
+                .map(|sum_i| TY::from_f64(sum_i / n_estimators).unwrap()),
+            n,
+        ))
+    }
+
+    /// The fitted base regressors.
+    pub fn estimators(&self) -> &Vec<E> {
+        &self.estimators
+    }
+
+    /// Features of the training data used by every base regressor.
+    pub fn features(&self) -> &Vec<Vec<usize>> {
+        &self.features
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::basic::matrix::DenseMatrix;
+    use crate::linear::linear_regression::{LinearRegression, LinearRegressionParameters};
+    use crate::metrics::mean_absolute_error;
+    use crate::test_datasets::longley;
+    use crate::tree::decision_tree_regressor::{
+        DecisionTreeRegressor, DecisionTreeRegressorParameters,
+    };
+
+    type Tree = DecisionTreeRegressor<f64, f64, DenseMatrix<f64>, Vec<f64>>;
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn fit_predict_trees() {
+        let (x, y) = longley();
+
+        let regressor: BaggingRegressor<_, _, _, _, Tree> = BaggingRegressor::fit(
+            &x,
+            &y,
+            BaggingRegressorParameters::default()
+                .with_estimator_parameters(DecisionTreeRegressorParameters::default())
+                .with_n_estimators(20)
+                .with_max_features(0.5)
+                .with_bootstrap_features(true)
+                .with_seed(3),
+        )
+        .unwrap();
+
+        assert_eq!(regressor.estimators().len(), 20);
+        assert!(regressor.features().iter().all(|f| f.len() == 3));
+        assert!(mean_absolute_error(&y, &regressor.predict(&x).unwrap()) < 2.0);
+
+        assert!(BaggingRegressor::<_, _, _, _, Tree>::fit(
+            &x,
+            &y,
+            BaggingRegressorParameters::<DecisionTreeRegressorParameters>::default()
+                .with_n_estimators(0)
+        )
+        .is_err());
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn fit_predict_any_regressor() {
+        let (x, y) = longley();
+
+        // without any subsampling every model is the model fitted to the whole training set
+        let regressor: BaggingRegressor<
+            _,
+            _,
+            _,
+            _,
+            LinearRegression<f64, f64, DenseMatrix<f64>, Vec<f64>>,
+        > = BaggingRegressor::fit(
+            &x,
+            &y,
+            BaggingRegressorParameters::default()
+                .with_estimator_parameters(LinearRegressionParameters::default())
+                .with_n_estimators(3)
+                .with_bootstrap(false),
+        )
+        .unwrap();
+        let y_hat = LinearRegression::fit(&x, &y, Default::default())
+            .and_then(|lr| lr.predict(&x))
+            .unwrap();
+
+        for (a, b) in regressor.predict(&x).unwrap().iter().zip(y_hat.iter()) {
+            assert!((a - b).abs() < 1e-8);
+        }
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde() {
+        let (x, y) = longley();
+
+        let regressor: BaggingRegressor<_, _, _, _, Tree> = BaggingRegressor::fit(
+            &x,
+            &y,
+            BaggingRegressorParameters::default()
+                .with_estimator_parameters(DecisionTreeRegressorParameters::default()),
+        )
+        .unwrap();
+
+        let deserialized_regressor: BaggingRegressor<f64, f64, DenseMatrix<f64>, Vec<f64>, Tree> =
+            bincode::deserialize(&bincode::serialize(&regressor).unwrap()).unwrap();
+
+        assert_eq!(regressor, deserialized_regressor);
+    }
+}
@@ -0,0 +1,114 @@
+//! # Bagging
+//!
+//! Bagging (bootstrap aggregation) fits the same base estimator to many random subsets of the training set and aggregates their predictions,
+//! the majority vote for classification and the average for regression. It reduces the variance of unstable estimators such as deep decision trees.
+//!
+//! Unlike [random forests](../random_forest_classifier/index.html), which are built from decision trees, bagging wraps any estimator that implements
+//! [`SupervisedEstimator`](../../api/trait.SupervisedEstimator.html). Every estimator of the ensemble is fitted to
+//!
+//! * a fraction `max_samples` of the training samples, drawn with replacement when `bootstrap` is set (the default) and without replacement otherwise,
+//! * a fraction `max_features` of the features, drawn without replacement unless `bootstrap_features` is set. Drawing samples without replacement
+//!   and a subset of the features is known as the random subspace method.
+//!
+//! The features of every estimator are kept, and selected again from the data passed to `predict`.
+//!
+//! ## References:
+//!
+//! * ["Bagging Predictors", Breiman L., Machine Learning 24, 1996](https://doi.org/10.1007/BF00058655)
+//! * ["The random subspace method for constructing decision forests", Ho T., IEEE Transactions on Pattern Analysis and Machine Intelligence, 1998](https://doi.org/10.1109/34.709601)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+
+/// Bagging classifier
+pub mod bagging_classifier;
+/// Bagging regressor
+pub mod bagging_regressor;
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::error::Failed;
+
+/// Settings of the random subsets shared by the classifier and the regressor.
+pub(crate) struct Subsampling {
+    pub(crate) n_estimators: usize,
+    pub(crate) max_samples: f64,
+    pub(crate) max_features: f64,
+    pub(crate) bootstrap: bool,
+    pub(crate) bootstrap_features: bool,
+}
+
+impl Subsampling {
+    pub(crate) fn validate(&self) -> Result<(), Failed> {
+        if self.n_estimators == 0 {
+            return Err(Failed::fit("n_estimators should be positive"));
+        }
+        if !(self.max_samples > 0f64 && self.max_samples <= 1f64) {
+            return Err(Failed::fit("max_samples should be in (0, 1]"));
+        }
+        if !(self.max_features > 0f64 && self.max_features <= 1f64) {
+            return Err(Failed::fit("max_features should be in (0, 1]"));
+        }
+        Ok(())
+    }
+
+    /// Rows and columns of the subset of a _NxM_ training set for the next estimator.
+    pub(crate) fn draw(&self, n: usize, m: usize, rng: &mut impl Rng) -> (Vec<usize>, Vec<usize>) {
+        (
+            draw(n, self.max_samples, self.bootstrap, rng),
+            draw(m, self.max_features, self.bootstrap_features, rng),
+        )
+    }
+}
+
+/// Draw a `fraction` of indices below `n`, at least one, with or without replacement.
+fn draw(n: usize, fraction: f64, replace: bool, rng: &mut impl Rng) -> Vec<usize> {
+    let size = ((fraction * n as f64).round() as usize).max(1);
+    if replace {
+        (0..size).map(|_| rng.gen_range(0..n)).collect()
+    } else {
+        let mut indices: Vec<usize> = (0..n).collect();
+        indices.shuffle(rng);
+        indices.truncate(size);
+        indices.sort_unstable();
+        indices
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rand_custom::get_rng_impl;
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn draw_subsets() {
+        let mut rng = get_rng_impl(Some(7));
+        let subsampling = Subsampling {
+            n_estimators: 1,
+            max_samples: 1.,
+            max_features: 0.5,
+            bootstrap: true,
+            bootstrap_features: false,
+        };
+        let (rows, features) = subsampling.draw(10, 4, &mut rng);
+        assert_eq!(rows.len(), 10);
+        assert!(rows.iter().all(|&i| i < 10));
+        assert_eq!(features.len(), 2);
+        assert!(features[0] < features[1] && features[1] < 4);
+
+        assert_eq!(draw(10, 1., false, &mut rng), (0..10).collect::<Vec<_>>());
+        assert_eq!(draw(10, 0.01, false, &mut rng).len(), 1);
+
+        assert!(Subsampling {
+            max_samples: 0.,
+            ..subsampling
+        }
+        .validate()
+        .is_err());
+    }
+}
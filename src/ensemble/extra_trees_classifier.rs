@@ -0,0 +1,391 @@
+//! # Extra Trees Classifier
+//! Extremely randomized trees are a variant of a [random forest](../random_forest_classifier/index.html) that injects more randomness into the trees:
+//! instead of searching for the best threshold of every candidate feature, every candidate feature is split at a threshold drawn
+//! at random between its smallest and largest value at the node, and the best of these random splits is kept.
+//! See [ensemble models](../index.html) for more details.
+//!
+//! By default every tree is grown from the whole training set; set `bootstrap` to grow every tree from a bootstrap sample instead.
+//! Random thresholds make extra trees faster to fit than a random forest and further reduce the variance of the ensemble,
+//! at the price of a slightly higher bias.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::basic::matrix::DenseMatrix;
+//! use smartcore::ensemble::extra_trees_classifier::ExtraTreesClassifier;
+//!
+//! // Iris dataset
+//! let x = DenseMatrix::from_2d_array(&[
+//!              &[5.1, 3.5, 1.4, 0.2],
+//!              &[4.9, 3.0, 1.4, 0.2],
+//!              &[4.7, 3.2, 1.3, 0.2],
+//!              &[4.6, 3.1, 1.5, 0.2],
+//!              &[5.0, 3.6, 1.4, 0.2],
+//!              &[5.4, 3.9, 1.7, 0.4],
+//!              &[4.6, 3.4, 1.4, 0.3],
+//!              &[5.0, 3.4, 1.5, 0.2],
+//!              &[4.4, 2.9, 1.4, 0.2],
+//!              &[4.9, 3.1, 1.5, 0.1],
+//!              &[7.0, 3.2, 4.7, 1.4],
+//!              &[6.4, 3.2, 4.5, 1.5],
+//!              &[6.9, 3.1, 4.9, 1.5],
+//!              &[5.5, 2.3, 4.0, 1.3],
+//!              &[6.5, 2.8, 4.6, 1.5],
+//!              &[5.7, 2.8, 4.5, 1.3],
+//!              &[6.3, 3.3, 4.7, 1.6],
+//!              &[4.9, 2.4, 3.3, 1.0],
+//!              &[6.6, 2.9, 4.6, 1.3],
+//!              &[5.2, 2.7, 3.9, 1.4],
+//!         ]).unwrap();
+//! let y = vec![
+//!              0, 0, 0, 0, 0, 0, 0, 0,
+//!              1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+//!         ];
+//!
+//! let classifier = ExtraTreesClassifier::fit(&x, &y, Default::default()).unwrap();
+//! let y_hat = classifier.predict(&x).unwrap(); // use the same data for prediction
+//! let y_proba = classifier.predict_proba(&x).unwrap(); // class probabilities averaged over the trees
+//! ```
+//!
+//! ## References:
+//!
+//! * ["Extremely randomized trees", Geurts P., Ernst D., Wehenkel L., Machine Learning 63, 2006](https://doi.org/10.1007/s10994-006-6226-1)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use rand::Rng;
+use std::default::Default;
+use std::fmt::Debug;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::api::{Predictor, SupervisedEstimator};
+use crate::error::Failed;
+use crate::linalg::basic::arrays::{Array1, Array2};
+use crate::numbers::basenum::Number;
+use crate::numbers::floatnum::FloatNumber;
+
+use crate::rand_custom::get_rng_impl;
+use crate::tree::decision_tree_classifier::{
+    which_max, DecisionTreeClassifier, DecisionTreeClassifierParameters, SplitCriterion,
+};
+use crate::tree::Splitter;
+
+/// Parameters of the Extra Trees algorithm.
+/// Some parameters here are passed directly into base estimator.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct ExtraTreesClassifierParameters {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Split criteria to use when building a tree. See [Decision Tree Classifier](../../tree/decision_tree_classifier/index.html)
+    pub criterion: SplitCriterion,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Tree max depth. See [Decision Tree Classifier](../../tree/decision_tree_classifier/index.html)
+    pub max_depth: Option<u16>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The minimum number of samples required to be at a leaf node. See [Decision Tree Classifier](../../tree/decision_tree_classifier/index.html)
+    pub min_samples_leaf: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The minimum number of samples required to split an internal node. See [Decision Tree Classifier](../../tree/decision_tree_classifier/index.html)
+    pub min_samples_split: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The number of trees in the ensemble.
+    pub n_trees: u16,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Number of random sample of predictors to use as split candidates.
+    pub m: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Whether to grow every tree from a bootstrap sample instead of the whole training set.
+    pub bootstrap: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Seed used for bootstrap sampling, feature selection and thresholds of each tree.
+    pub seed: u64,
+}
+
+/// Extra Trees Classifier
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+pub struct ExtraTreesClassifier<
+    TX: Number + FloatNumber + PartialOrd,
+    TY: Number + Ord,
+    X: Array2<TX>,
+    Y: Array1<TY>,
+> {
+    trees: Vec<DecisionTreeClassifier<TX, TY, X, Y>>,
+    classes: Vec<TY>,
+}
+
+impl ExtraTreesClassifierParameters {
+    /// Split criteria to use when building a tree. See [Decision Tree Classifier](../../tree/decision_tree_classifier/index.html)
+    pub fn with_criterion(mut self, criterion: SplitCriterion) -> Self {
+        self.criterion = criterion;
+        self
+    }
+    /// Tree max depth. See [Decision Tree Classifier](../../tree/decision_tree_classifier/index.html)
+    pub fn with_max_depth(mut self, max_depth: u16) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+    /// The minimum number of samples required to be at a leaf node. See [Decision Tree Classifier](../../tree/decision_tree_classifier/index.html)
+    pub fn with_min_samples_leaf(mut self, min_samples_leaf: usize) -> Self {
+        self.min_samples_leaf = min_samples_leaf;
+        self
+    }
+    /// The minimum number of samples required to split an internal node. See [Decision Tree Classifier](../../tree/decision_tree_classifier/index.html)
+    pub fn with_min_samples_split(mut self, min_samples_split: usize) -> Self {
+        self.min_samples_split = min_samples_split;
+        self
+    }
+    /// The number of trees in the ensemble.
+    pub fn with_n_trees(mut self, n_trees: u16) -> Self {
+        self.n_trees = n_trees;
+        self
+    }
+    /// Number of random sample of predictors to use as split candidates.
+    pub fn with_m(mut self, m: usize) -> Self {
+        self.m = Some(m);
+        self
+    }
+    /// Whether to grow every tree from a bootstrap sample instead of the whole training set.
+    pub fn with_bootstrap(mut self, bootstrap: bool) -> Self {
+        self.bootstrap = bootstrap;
+        self
+    }
+    /// Seed used for bootstrap sampling, feature selection and thresholds of each tree.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+}
+
+impl Default for ExtraTreesClassifierParameters {
+    fn default() -> Self {
+        ExtraTreesClassifierParameters {
+            criterion: SplitCriterion::Gini,
+            max_depth: Option::None,
+            min_samples_leaf: 1,
+            min_samples_split: 2,
+            n_trees: 100,
+            m: Option::None,
+            bootstrap: false,
+            seed: 0,
+        }
+    }
+}
+
+impl<TX: Number + FloatNumber + PartialOrd, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>>
+    PartialEq for ExtraTreesClassifier<TX, TY, X, Y>
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.classes == other.classes && self.trees == other.trees
+    }
+}
+
+impl<TX: Number + FloatNumber + PartialOrd, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>>
+    SupervisedEstimator<X, Y, ExtraTreesClassifierParameters>
+    for ExtraTreesClassifier<TX, TY, X, Y>
+{
+    fn new() -> Self {
+        Self {
+            trees: Vec::new(),
+            classes: Vec::new(),
+        }
+    }
+    fn fit(x: &X, y: &Y, parameters: ExtraTreesClassifierParameters) -> Result<Self, Failed> {
+        ExtraTreesClassifier::fit(x, y, parameters)
+    }
+}
+
+impl<TX: Number + FloatNumber + PartialOrd, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>>
+    Predictor<X, Y> for ExtraTreesClassifier<TX, TY, X, Y>
+{
+    fn predict(&self, x: &X) -> Result<Y, Failed> {
+        self.predict(x)
+    }
+}
+
+impl<TX: FloatNumber + PartialOrd, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>>
+    ExtraTreesClassifier<TX, TY, X, Y>
+{
+    /// Build an ensemble of extremely randomized trees from the training set.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - the target class values
+    pub fn fit(
+        x: &X,
+        y: &Y,
+        parameters: ExtraTreesClassifierParameters,
+    ) -> Result<ExtraTreesClassifier<TX, TY, X, Y>, Failed> {
+        let (x_nrows, num_attributes) = x.shape();
+        if x_nrows != y.shape() {
+            return Err(Failed::fit("Number of rows in X should = len(y)"));
+        }
+        if parameters.n_trees == 0 {
+            return Err(Failed::fit("n_trees should be positive"));
+        }
+
+        let mtry = parameters
+            .m
+            .unwrap_or_else(|| ((num_attributes as f64).sqrt().floor()) as usize);
+
+        let mut rng = get_rng_impl(Some(parameters.seed));
+        let mut trees = Vec::with_capacity(parameters.n_trees as usize);
+
+        for _ in 0..parameters.n_trees {
+            let samples = if parameters.bootstrap {
+                let mut samples = vec![0; x_nrows];
+                for _ in 0..x_nrows {
+                    samples[rng.gen_range(0..x_nrows)] += 1;
+                }
+                samples
+            } else {
+                vec![1; x_nrows]
+            };
+
+            // every tree draws its own thresholds, even when all trees see the same samples
+            let params = DecisionTreeClassifierParameters {
+                criterion: parameters.criterion.clone(),
+                max_depth: parameters.max_depth,
+                min_samples_leaf: parameters.min_samples_leaf,
+                min_samples_split: parameters.min_samples_split,
+                ccp_alpha: 0f64,
+                splitter: Splitter::Random,
+                seed: Some(rng.gen()),
+            };
+            trees.push(DecisionTreeClassifier::fit_weak_learner(
+                x, y, samples, mtry, params,
+            )?);
+        }
+
+        Ok(ExtraTreesClassifier {
+            trees,
+            classes: y.unique(),
+        })
+    }
+
+    /// Predict class for `x`, the majority vote of the trees.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict(&self, x: &X) -> Result<Y, Failed> {
+        let (n, _) = x.shape();
+        let mut result = Y::zeros(n);
+
+        for i in 0..n {
+            let mut votes = vec![0; self.classes.len()];
+            for tree in self.trees.iter() {
+                votes[tree.predict_for_row(x, i)] += 1;
+            }
+            result.set(i, self.classes[which_max(&votes)]);
+        }
+
+        Ok(result)
+    }
+
+    /// Predict class probabilities for `x`, the average of the class probabilities predicted by the trees.
+    /// Returns a _KxC_ matrix, columns are ordered like the sorted class labels.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict_proba(&self, x: &X) -> Result<X, Failed> {
+        let (n, _) = x.shape();
+        let k = self.classes.len();
+        let mut proba = X::zeros(n, k);
+
+        for i in 0..n {
+            let mut row = vec![0f64; k];
+            for tree in self.trees.iter() {
+                for (c, p) in tree.predict_proba_for_row(x, i).into_iter().enumerate() {
+                    row[c] += p;
+                }
+            }
+            for (c, p) in row.into_iter().enumerate() {
+                proba.set((i, c), TX::from_f64(p / self.trees.len() as f64).unwrap());
+            }
+        }
+
+        Ok(proba)
+    }
+
+    /// Sorted class labels.
+    pub fn classes(&self) -> &Vec<TY> {
+        &self.classes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ensemble::testing::assert_fits_classes;
+    use crate::metrics::*;
+    use crate::test_datasets::iris;
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn fit_predict() {
+        let (x, y) = iris();
+
+        assert_fits_classes(
+            &x,
+            &y,
+            |y| {
+                ExtraTreesClassifier::fit(
+                    &x,
+                    y,
+                    ExtraTreesClassifierParameters::default()
+                        .with_n_trees(50)
+                        .with_seed(87),
+                )
+                .unwrap()
+            },
+            |classifier| classifier.predict_proba(&x).unwrap(),
+        );
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn bootstrap() {
+        let (x, y) = iris();
+        let parameters = ExtraTreesClassifierParameters::default()
+            .with_n_trees(20)
+            .with_bootstrap(true)
+            .with_seed(3);
+
+        let classifier = ExtraTreesClassifier::fit(&x, &y, parameters.clone()).unwrap();
+        assert!(accuracy(&y, &classifier.predict(&x).unwrap()) >= 0.9);
+
+        let refitted = ExtraTreesClassifier::fit(&x, &y, parameters).unwrap();
+        assert_eq!(classifier, refitted);
+
+        assert!(ExtraTreesClassifier::fit(
+            &x,
+            &y,
+            ExtraTreesClassifierParameters::default().with_n_trees(0)
+        )
+        .is_err());
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde() {
+        let (x, y) = iris();
+
+        let classifier = ExtraTreesClassifier::fit(
+            &x,
+            &y,
+            ExtraTreesClassifierParameters::default().with_n_trees(10),
+        )
+        .unwrap();
+
+        let deserialized_classifier: ExtraTreesClassifier<f64, u32, _, Vec<u32>> =
+            bincode::deserialize(&bincode::serialize(&classifier).unwrap()).unwrap();
+
+        assert_eq!(classifier, deserialized_classifier);
+    }
+}
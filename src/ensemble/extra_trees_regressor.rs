@@ -0,0 +1,338 @@
+//! # Extra Trees Regressor
+//! Extremely randomized trees are a variant of a [random forest](../random_forest_regressor/index.html) that injects more randomness into the trees:
+//! instead of searching for the best threshold of every candidate feature, every candidate feature is split at a threshold drawn
+//! at random between its smallest and largest value at the node, and the best of these random splits is kept.
+//! See [ensemble models](../index.html) for more details.
+//!
+//! By default every tree is grown from the whole training set; set `bootstrap` to grow every tree from a bootstrap sample instead.
+//! The prediction of the ensemble is the average of the predictions of its trees.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::basic::matrix::DenseMatrix;
+//! use smartcore::ensemble::extra_trees_regressor::ExtraTreesRegressor;
+//!
+//! // Longley dataset (https://www.statsmodels.org/stable/datasets/generated/longley.html)
+//! let x = DenseMatrix::from_2d_array(&[
+//!             &[234.289, 235.6, 159., 107.608, 1947., 60.323],
+//!             &[259.426, 232.5, 145.6, 108.632, 1948., 61.122],
+//!             &[258.054, 368.2, 161.6, 109.773, 1949., 60.171],
+//!             &[284.599, 335.1, 165., 110.929, 1950., 61.187],
+//!             &[328.975, 209.9, 309.9, 112.075, 1951., 63.221],
+//!             &[346.999, 193.2, 359.4, 113.27, 1952., 63.639],
+//!             &[365.385, 187., 354.7, 115.094, 1953., 64.989],
+//!             &[363.112, 357.8, 335., 116.219, 1954., 63.761],
+//!             &[397.469, 290.4, 304.8, 117.388, 1955., 66.019],
+//!             &[419.18, 282.2, 285.7, 118.734, 1956., 67.857],
+//!             &[442.769, 293.6, 279.8, 120.445, 1957., 68.169],
+//!             &[444.546, 468.1, 263.7, 121.95, 1958., 66.513],
+//!             &[482.704, 381.3, 255.2, 123.366, 1959., 68.655],
+//!             &[502.601, 393.1, 251.4, 125.368, 1960., 69.564],
+//!             &[518.173, 480.6, 257.2, 127.852, 1961., 69.331],
+//!             &[554.894, 400.7, 282.7, 130.081, 1962., 70.551],
+//!         ]).unwrap();
+//! let y = vec![
+//!             83.0, 88.5, 88.2, 89.5, 96.2, 98.1, 99.0, 100.0, 101.2,
+//!             104.6, 108.4, 110.8, 112.6, 114.2, 115.7, 116.9
+//!         ];
+//!
+//! let regressor = ExtraTreesRegressor::fit(&x, &y, Default::default()).unwrap();
+//! let y_hat = regressor.predict(&x).unwrap(); // use the same data for prediction
+//! ```
+//!
+//! ## References:
+//!
+//! * ["Extremely randomized trees", Geurts P., Ernst D., Wehenkel L., Machine Learning 63, 2006](https://doi.org/10.1007/s10994-006-6226-1)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use rand::Rng;
+use std::default::Default;
+use std::fmt::Debug;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::api::{Predictor, SupervisedEstimator};
+use crate::error::Failed;
+use crate::linalg::basic::arrays::{Array1, Array2};
+use crate::numbers::basenum::Number;
+use crate::numbers::floatnum::FloatNumber;
+
+use crate::rand_custom::get_rng_impl;
+use crate::tree::decision_tree_regressor::{
+    DecisionTreeRegressor, DecisionTreeRegressorParameters,
+};
+use crate::tree::Splitter;
+
+/// Parameters of the Extra Trees Regressor
+/// Some parameters here are passed directly into base estimator.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct ExtraTreesRegressorParameters {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Tree max depth. See [Decision Tree Regressor](../../tree/decision_tree_regressor/index.html)
+    pub max_depth: Option<u16>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The minimum number of samples required to be at a leaf node. See [Decision Tree Regressor](../../tree/decision_tree_regressor/index.html)
+    pub min_samples_leaf: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The minimum number of samples required to split an internal node. See [Decision Tree Regressor](../../tree/decision_tree_regressor/index.html)
+    pub min_samples_split: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The number of trees in the ensemble.
+    pub n_trees: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Number of random sample of predictors to use as split candidates.
+    pub m: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Whether to grow every tree from a bootstrap sample instead of the whole training set.
+    pub bootstrap: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Seed used for bootstrap sampling, feature selection and thresholds of each tree.
+    pub seed: u64,
+}
+
+/// Extra Trees Regressor
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+pub struct ExtraTreesRegressor<
+    TX: Number + FloatNumber + PartialOrd,
+    TY: Number,
+    X: Array2<TX>,
+    Y: Array1<TY>,
+> {
+    trees: Vec<DecisionTreeRegressor<TX, TY, X, Y>>,
+}
+
+impl ExtraTreesRegressorParameters {
+    /// Tree max depth. See [Decision Tree Regressor](../../tree/decision_tree_regressor/index.html)
+    pub fn with_max_depth(mut self, max_depth: u16) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+    /// The minimum number of samples required to be at a leaf node. See [Decision Tree Regressor](../../tree/decision_tree_regressor/index.html)
+    pub fn with_min_samples_leaf(mut self, min_samples_leaf: usize) -> Self {
+        self.min_samples_leaf = min_samples_leaf;
+        self
+    }
+    /// The minimum number of samples required to split an internal node. See [Decision Tree Regressor](../../tree/decision_tree_regressor/index.html)
+    pub fn with_min_samples_split(mut self, min_samples_split: usize) -> Self {
+        self.min_samples_split = min_samples_split;
+        self
+    }
+    /// The number of trees in the ensemble.
+    pub fn with_n_trees(mut self, n_trees: usize) -> Self {
+        self.n_trees = n_trees;
+        self
+    }
+    /// Number of random sample of predictors to use as split candidates.
+    pub fn with_m(mut self, m: usize) -> Self {
+        self.m = Some(m);
+        self
+    }
+    /// Whether to grow every tree from a bootstrap sample instead of the whole training set.
+    pub fn with_bootstrap(mut self, bootstrap: bool) -> Self {
+        self.bootstrap = bootstrap;
+        self
+    }
+    /// Seed used for bootstrap sampling, feature selection and thresholds of each tree.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+}
+
+impl Default for ExtraTreesRegressorParameters {
+    fn default() -> Self {
+        ExtraTreesRegressorParameters {
+            max_depth: Option::None,
+            min_samples_leaf: 1,
+            min_samples_split: 2,
+            n_trees: 100,
+            m: Option::None,
+            bootstrap: false,
+            seed: 0,
+        }
+    }
+}
+
+impl<TX: Number + FloatNumber + PartialOrd, TY: Number, X: Array2<TX>, Y: Array1<TY>> PartialEq
+    for ExtraTreesRegressor<TX, TY, X, Y>
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.trees == other.trees
+    }
+}
+
+impl<TX: Number + FloatNumber + PartialOrd, TY: Number, X: Array2<TX>, Y: Array1<TY>>
+    SupervisedEstimator<X, Y, ExtraTreesRegressorParameters> for ExtraTreesRegressor<TX, TY, X, Y>
+{
+    fn new() -> Self {
+        Self { trees: Vec::new() }
+    }
+
+    fn fit(x: &X, y: &Y, parameters: ExtraTreesRegressorParameters) -> Result<Self, Failed> {
+        ExtraTreesRegressor::fit(x, y, parameters)
+    }
+}
+
+impl<TX: Number + FloatNumber + PartialOrd, TY: Number, X: Array2<TX>, Y: Array1<TY>>
+    Predictor<X, Y> for ExtraTreesRegressor<TX, TY, X, Y>
+{
+    fn predict(&self, x: &X) -> Result<Y, Failed> {
+        self.predict(x)
+    }
+}
+
+impl<TX: Number + FloatNumber + PartialOrd, TY: Number, X: Array2<TX>, Y: Array1<TY>>
+    ExtraTreesRegressor<TX, TY, X, Y>
+{
+    /// Build an ensemble of extremely randomized trees from the training set.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - the target values
+    pub fn fit(
+        x: &X,
+        y: &Y,
+        parameters: ExtraTreesRegressorParameters,
+    ) -> Result<ExtraTreesRegressor<TX, TY, X, Y>, Failed> {
+        let (n_rows, num_attributes) = x.shape();
+        if n_rows != y.shape() {
+            return Err(Failed::fit("Number of rows in X should = len(y)"));
+        }
+        if parameters.n_trees == 0 {
+            return Err(Failed::fit("n_trees should be positive"));
+        }
+
+        let mtry = parameters
+            .m
+            .unwrap_or((num_attributes as f64).sqrt().floor() as usize);
+
+        let mut rng = get_rng_impl(Some(parameters.seed));
+        let mut trees = Vec::with_capacity(parameters.n_trees);
+
+        for _ in 0..parameters.n_trees {
+            let samples = if parameters.bootstrap {
+                let mut samples = vec![0; n_rows];
+                for _ in 0..n_rows {
+                    samples[rng.gen_range(0..n_rows)] += 1;
+                }
+                samples
+            } else {
+                vec![1; n_rows]
+            };
+
+            // every tree draws its own thresholds, even when all trees see the same samples
+            let params = DecisionTreeRegressorParameters {
+                max_depth: parameters.max_depth,
+                min_samples_leaf: parameters.min_samples_leaf,
+                min_samples_split: parameters.min_samples_split,
+                ccp_alpha: 0f64,
+                splitter: Splitter::Random,
+                seed: Some(rng.gen()),
+            };
+            trees.push(DecisionTreeRegressor::fit_weak_learner(
+                x, y, samples, mtry, params,
+            )?);
+        }
+
+        Ok(ExtraTreesRegressor { trees })
+    }
+
+    /// Predict regression value for `x`, the average of the predictions of the trees.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict(&self, x: &X) -> Result<Y, Failed> {
+        let (n, _) = x.shape();
+        let mut result = Y::zeros(n);
+
+        for i in 0..n {
+            let mut sum = TY::zero();
+            for tree in self.trees.iter() {
+                sum += tree.predict_for_row(x, i);
+            }
+            result.set(i, sum / TY::from_usize(self.trees.len()).unwrap());
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::mean_absolute_error;
+    use crate::test_datasets::longley;
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn fit_longley() {
+        let (x, y) = longley();
+
+        let y_hat = ExtraTreesRegressor::fit(
+            &x,
+            &y,
+            ExtraTreesRegressorParameters::default()
+                .with_n_trees(50)
+                .with_min_samples_leaf(2),
+        )
+        .and_then(|regressor| regressor.predict(&x))
+        .unwrap();
+
+        assert!(mean_absolute_error(&y, &y_hat) < 2.0);
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn bootstrap() {
+        let (x, y) = longley();
+        let parameters = ExtraTreesRegressorParameters::default()
+            .with_n_trees(20)
+            .with_bootstrap(true)
+            .with_seed(5);
+
+        let regressor = ExtraTreesRegressor::fit(&x, &y, parameters.clone()).unwrap();
+        let y_hat = regressor.predict(&x).unwrap();
+        assert!(mean_absolute_error(&y, &y_hat) < 4.0);
+
+        let refitted = ExtraTreesRegressor::fit(&x, &y, parameters).unwrap();
+        assert_eq!(regressor, refitted);
+
+        // without bootstrap, the trees still differ through their random thresholds
+        let regressor = ExtraTreesRegressor::fit(
+            &x,
+            &y,
+            ExtraTreesRegressorParameters::default().with_n_trees(2),
+        )
+        .unwrap();
+        assert!(regressor.trees[0] != regressor.trees[1]);
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde() {
+        let (x, y) = longley();
+
+        let regressor = ExtraTreesRegressor::fit(
+            &x,
+            &y,
+            ExtraTreesRegressorParameters::default().with_n_trees(10),
+        )
+        .unwrap();
+
+        let deserialized_regressor: ExtraTreesRegressor<f64, f64, _, Vec<f64>> =
+            bincode::deserialize(&bincode::serialize(&regressor).unwrap()).unwrap();
+
+        assert_eq!(regressor, deserialized_regressor);
+    }
+}
@@ -0,0 +1,507 @@
+//! # Gradient Boosting Regressor
+//! Gradient boosting regressor fits a sequence of shallow [regression trees](../../../tree/decision_tree_regressor/index.html), every tree corrects
+//! the errors of the trees before it. See [gradient boosting](../index.html) for more details.
+//!
+//! The loss minimized by the model is one of
+//!
+//! * Squared error, \\(\frac{1}{2}(y - F(x))^2\\), the default.
+//! * Absolute error, \\(|y - F(x)|\\), which is robust to outliers in the target.
+//! * Huber loss, squared error for residuals up to the `alpha`-quantile of absolute residuals \\(\delta\\) and \\(\delta(|y - F(x)| - \delta / 2)\\) beyond it.
+//! * Quantile loss, \\(\alpha(y - F(x))\\) when \\(y > F(x)\\) and \\((1 - \alpha)(F(x) - y)\\) otherwise, which estimates the conditional `alpha`-quantile of the target,
+//!   e.g. to build prediction intervals.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::basic::matrix::DenseMatrix;
+//! use smartcore::ensemble::gradient_boosting::gradient_boosting_regressor::*;
+//!
+//! // Longley dataset (https://www.statsmodels.org/stable/datasets/generated/longley.html)
+//! let x = DenseMatrix::from_2d_array(&[
+//!             &[234.289, 235.6, 159., 107.608, 1947., 60.323],
+//!             &[259.426, 232.5, 145.6, 108.632, 1948., 61.122],
+//!             &[258.054, 368.2, 161.6, 109.773, 1949., 60.171],
+//!             &[284.599, 335.1, 165., 110.929, 1950., 61.187],
+//!             &[328.975, 209.9, 309.9, 112.075, 1951., 63.221],
+//!             &[346.999, 193.2, 359.4, 113.27, 1952., 63.639],
+//!             &[365.385, 187., 354.7, 115.094, 1953., 64.989],
+//!             &[363.112, 357.8, 335., 116.219, 1954., 63.761],
+//!             &[397.469, 290.4, 304.8, 117.388, 1955., 66.019],
+//!             &[419.18, 282.2, 285.7, 118.734, 1956., 67.857],
+//!             &[442.769, 293.6, 279.8, 120.445, 1957., 68.169],
+//!             &[444.546, 468.1, 263.7, 121.95, 1958., 66.513],
+//!             &[482.704, 381.3, 255.2, 123.366, 1959., 68.655],
+//!             &[502.601, 393.1, 251.4, 125.368, 1960., 69.564],
+//!             &[518.173, 480.6, 257.2, 127.852, 1961., 69.331],
+//!             &[554.894, 400.7, 282.7, 130.081, 1962., 70.551],
+//!         ]).unwrap();
+//! let y = vec![
+//!             83.0, 88.5, 88.2, 89.5, 96.2, 98.1, 99.0, 100.0, 101.2,
+//!             104.6, 108.4, 110.8, 112.6, 114.2, 115.7, 116.9,
+//!         ];
+//!
+//! let regressor = GradientBoostingRegressor::fit(
+//!     &x,
+//!     &y,
+//!     GradientBoostingRegressorParameters::default()
+//!         .with_loss(GradientBoostingRegressorLoss::Huber)
+//!         .with_n_trees(50),
+//! ).unwrap();
+//!
+//! let y_hat = regressor.predict(&x).unwrap(); // use the same data for prediction
+//! let y_hat_by_stage: Vec<Vec<f64>> = regressor.staged_predict(&x).collect();
+//! assert_eq!(y_hat_by_stage.len(), 50);
+//! ```
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use std::default::Default;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::api::{Predictor, SupervisedEstimator};
+use crate::ensemble::gradient_boosting::loss::Loss;
+use crate::ensemble::gradient_boosting::{BoostedTree, Boosting, StagedRaw};
+use crate::error::Failed;
+use crate::linalg::basic::arrays::{Array1, Array2};
+use crate::numbers::basenum::Number;
+use crate::tree::decision_tree_regressor::DecisionTreeRegressorParameters;
+use crate::tree::Splitter;
+
+/// Loss function minimized by the gradient boosting regressor.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GradientBoostingRegressorLoss {
+    /// Squared error
+    #[default]
+    SquaredError,
+    /// Absolute error
+    AbsoluteError,
+    /// Huber loss, the threshold between squared and absolute error is the `alpha`-quantile of absolute residuals
+    Huber,
+    /// Quantile loss of the `alpha`-quantile
+    Quantile,
+}
+
+impl GradientBoostingRegressorLoss {
+    pub(crate) fn to_loss(self, alpha: f64) -> Loss {
+        match self {
+            GradientBoostingRegressorLoss::SquaredError => Loss::SquaredError,
+            GradientBoostingRegressorLoss::AbsoluteError => Loss::AbsoluteError,
+            GradientBoostingRegressorLoss::Huber => Loss::Huber(alpha),
+            GradientBoostingRegressorLoss::Quantile => Loss::Quantile(alpha),
+        }
+    }
+}
+
+/// Parameters of the gradient boosting regressor.
+/// Some parameters here are passed directly into base estimator.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct GradientBoostingRegressorParameters {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Loss function to minimize.
+    pub loss: GradientBoostingRegressorLoss,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Quantile of the Huber and quantile losses.
+    pub alpha: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Shrinks the contribution of every tree.
+    pub learning_rate: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The number of boosting stages.
+    pub n_trees: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Fraction of the training samples used to fit every tree.
+    pub subsample: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Tree max depth. See [Decision Tree Regressor](../../../tree/decision_tree_regressor/index.html)
+    pub max_depth: Option<u16>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The minimum number of samples required to be at a leaf node. See [Decision Tree Regressor](../../../tree/decision_tree_regressor/index.html)
+    pub min_samples_leaf: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The minimum number of samples required to split an internal node. See [Decision Tree Regressor](../../../tree/decision_tree_regressor/index.html)
+    pub min_samples_split: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Fraction of the training samples held out for early stopping.
+    pub validation_fraction: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Stop when the validation loss has not improved for this number of stages. No early stopping by default.
+    pub n_iter_no_change: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The minimum improvement of the validation loss for early stopping.
+    pub tol: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Seed used for subsampling and the validation split.
+    pub seed: Option<u64>,
+}
+
+/// Gradient Boosting Regressor
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+pub struct GradientBoostingRegressor<
+    TX: Number + PartialOrd,
+    TY: Number,
+    X: Array2<TX>,
+    Y: Array1<TY>,
+> {
+    init: f64,
+    learning_rate: f64,
+    trees: Vec<BoostedTree<TX, X>>,
+    _phantom_ty: PhantomData<TY>,
+    _phantom_y: PhantomData<Y>,
+}
+
+impl GradientBoostingRegressorParameters {
+    /// Loss function to minimize.
+    pub fn with_loss(mut self, loss: GradientBoostingRegressorLoss) -> Self {
+        self.loss = loss;
+        self
+    }
+    /// Quantile of the Huber and quantile losses.
+    pub fn with_alpha(mut self, alpha: f64) -> Self {
+        self.alpha = alpha;
+        self
+    }
+    /// Shrinks the contribution of every tree.
+    pub fn with_learning_rate(mut self, learning_rate: f64) -> Self {
+        self.learning_rate = learning_rate;
+        self
+    }
+    /// The number of boosting stages.
+    pub fn with_n_trees(mut self, n_trees: usize) -> Self {
+        self.n_trees = n_trees;
+        self
+    }
+    /// Fraction of the training samples used to fit every tree.
+    pub fn with_subsample(mut self, subsample: f64) -> Self {
+        self.subsample = subsample;
+        self
+    }
+    /// Tree max depth. See [Decision Tree Regressor](../../../tree/decision_tree_regressor/index.html)
+    pub fn with_max_depth(mut self, max_depth: u16) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+    /// The minimum number of samples required to be at a leaf node. See [Decision Tree Regressor](../../../tree/decision_tree_regressor/index.html)
+    pub fn with_min_samples_leaf(mut self, min_samples_leaf: usize) -> Self {
+        self.min_samples_leaf = min_samples_leaf;
+        self
+    }
+    /// The minimum number of samples required to split an internal node. See [Decision Tree Regressor](../../../tree/decision_tree_regressor/index.html)
+    pub fn with_min_samples_split(mut self, min_samples_split: usize) -> Self {
+        self.min_samples_split = min_samples_split;
+        self
+    }
+    /// Fraction of the training samples held out for early stopping.
+    pub fn with_validation_fraction(mut self, validation_fraction: f64) -> Self {
+        self.validation_fraction = validation_fraction;
+        self
+    }
+    /// Stop when the validation loss has not improved for this number of stages.
+    pub fn with_n_iter_no_change(mut self, n_iter_no_change: usize) -> Self {
+        self.n_iter_no_change = Some(n_iter_no_change);
+        self
+    }
+    /// The minimum improvement of the validation loss for early stopping.
+    pub fn with_tol(mut self, tol: f64) -> Self {
+        self.tol = tol;
+        self
+    }
+    /// Seed used for subsampling and the validation split.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+}
+
+impl Default for GradientBoostingRegressorParameters {
+    fn default() -> Self {
+        GradientBoostingRegressorParameters {
+            loss: GradientBoostingRegressorLoss::default(),
+            alpha: 0.9,
+            learning_rate: 0.1,
+            n_trees: 100,
+            subsample: 1.0,
+            max_depth: Some(3),
+            min_samples_leaf: 1,
+            min_samples_split: 2,
+            validation_fraction: 0.1,
+            n_iter_no_change: Option::None,
+            tol: 1e-4,
+            seed: Option::None,
+        }
+    }
+}
+
+impl<TX: Number + PartialOrd, TY: Number, X: Array2<TX>, Y: Array1<TY>> PartialEq
+    for GradientBoostingRegressor<TX, TY, X, Y>
+{
+    fn eq(&self, other: &Self) -> bool {
+        (self.init - other.init).abs() < f64::EPSILON
+            && (self.learning_rate - other.learning_rate).abs() < f64::EPSILON
+            && self.trees == other.trees
+    }
+}
+
+impl<TX: Number + PartialOrd, TY: Number, X: Array2<TX>, Y: Array1<TY>>
+    SupervisedEstimator<X, Y, GradientBoostingRegressorParameters>
+    for GradientBoostingRegressor<TX, TY, X, Y>
+{
+    fn new() -> Self {
+        Self {
+            init: 0f64,
+            learning_rate: 0f64,
+            trees: Vec::new(),
+            _phantom_ty: PhantomData,
+            _phantom_y: PhantomData,
+        }
+    }
+
+    fn fit(x: &X, y: &Y, parameters: GradientBoostingRegressorParameters) -> Result<Self, Failed> {
+        GradientBoostingRegressor::fit(x, y, parameters)
+    }
+}
+
+impl<TX: Number + PartialOrd, TY: Number, X: Array2<TX>, Y: Array1<TY>> Predictor<X, Y>
+    for GradientBoostingRegressor<TX, TY, X, Y>
+{
+    fn predict(&self, x: &X) -> Result<Y, Failed> {
+        self.predict(x)
+    }
+}
+
+impl<TX: Number + PartialOrd, TY: Number, X: Array2<TX>, Y: Array1<TY>>
+    GradientBoostingRegressor<TX, TY, X, Y>
+{
+    /// Build a gradient boosting regressor from the training data.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - the target values
+    pub fn fit(
+        x: &X,
+        y: &Y,
+        parameters: GradientBoostingRegressorParameters,
+    ) -> Result<GradientBoostingRegressor<TX, TY, X, Y>, Failed> {
+        let (x_nrows, _) = x.shape();
+        if x_nrows != y.shape() {
+            return Err(Failed::fit("Size of x should equal size of y"));
+        }
+
+        let boosting = Boosting {
+            loss: parameters.loss.to_loss(parameters.alpha),
+            learning_rate: parameters.learning_rate,
+            n_trees: parameters.n_trees,
+            subsample: parameters.subsample,
+            tree_parameters: DecisionTreeRegressorParameters {
+                max_depth: parameters.max_depth,
+                min_samples_leaf: parameters.min_samples_leaf,
+                min_samples_split: parameters.min_samples_split,
+                ccp_alpha: 0f64,
+                splitter: Splitter::Best,
+                seed: parameters.seed,
+            },
+            validation_fraction: parameters.validation_fraction,
+            n_iter_no_change: parameters.n_iter_no_change,
+            tol: parameters.tol,
+            seed: parameters.seed,
+        };
+
+        let y: Vec<f64> = y.iterator(0).map(|y_i| y_i.to_f64().unwrap()).collect();
+        let (init, trees) = boosting.fit(x, &y)?;
+
+        Ok(GradientBoostingRegressor {
+            init: init[0],
+            learning_rate: parameters.learning_rate,
+            trees,
+            _phantom_ty: PhantomData,
+            _phantom_y: PhantomData,
+        })
+    }
+
+    /// Predict regression value for `x`.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict(&self, x: &X) -> Result<Y, Failed> {
+        Ok(self.to_y(&self.staged_raw(x).finish()[0]))
+    }
+
+    /// Predictions for `x` of the model after every boosting stage, i.e. with the first 1, 2, ... trees.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn staged_predict<'a>(&'a self, x: &'a X) -> impl Iterator<Item = Y> + 'a {
+        self.staged_raw(x).map(move |raw| self.to_y(&raw[0]))
+    }
+
+    /// The number of fitted trees, smaller than `n_trees` when boosting stopped early.
+    pub fn n_trees(&self) -> usize {
+        self.trees.len()
+    }
+
+    fn staged_raw<'a>(&'a self, x: &'a X) -> StagedRaw<'a, TX, X, BoostedTree<TX, X>> {
+        StagedRaw::new(x, &[self.init], self.learning_rate, &self.trees)
+    }
+
+    fn to_y(&self, raw: &[f64]) -> Y {
+        Y::from_iterator(
+            raw.iter().map(|&raw_i| TY::from_f64(raw_i).unwrap()),
+            raw.len(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::mean_absolute_error;
+    use crate::test_datasets::longley;
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn fit_longley() {
+        let (x, y) = longley();
+
+        for loss in [
+            GradientBoostingRegressorLoss::SquaredError,
+            GradientBoostingRegressorLoss::AbsoluteError,
+            GradientBoostingRegressorLoss::Huber,
+        ] {
+            let y_hat = GradientBoostingRegressor::fit(
+                &x,
+                &y,
+                GradientBoostingRegressorParameters::default().with_loss(loss),
+            )
+            .and_then(|gb| gb.predict(&x))
+            .unwrap();
+
+            assert!(mean_absolute_error(&y, &y_hat) < 1.0);
+        }
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn quantile_loss() {
+        let (x, y) = longley();
+
+        let parameters = GradientBoostingRegressorParameters::default()
+            .with_loss(GradientBoostingRegressorLoss::Quantile)
+            .with_max_depth(1)
+            .with_n_trees(20);
+        let upper = GradientBoostingRegressor::fit(&x, &y, parameters.clone().with_alpha(0.9))
+            .and_then(|gb| gb.predict(&x))
+            .unwrap();
+        let lower = GradientBoostingRegressor::fit(&x, &y, parameters.with_alpha(0.1))
+            .and_then(|gb| gb.predict(&x))
+            .unwrap();
+
+        for (lower_i, upper_i) in lower.iter().zip(upper.iter()) {
+            assert!(lower_i <= upper_i);
+        }
+        let above = y
+            .iter()
+            .zip(upper.iter())
+            .filter(|(y_i, u)| y_i > u)
+            .count();
+        let below = y
+            .iter()
+            .zip(lower.iter())
+            .filter(|(y_i, l)| y_i < l)
+            .count();
+        assert!(above <= 4);
+        assert!(below <= 4);
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn staged_predict() {
+        let (x, y) = longley();
+
+        let regressor = GradientBoostingRegressor::fit(
+            &x,
+            &y,
+            GradientBoostingRegressorParameters::default()
+                .with_n_trees(30)
+                .with_subsample(0.8)
+                .with_seed(42),
+        )
+        .unwrap();
+
+        let staged: Vec<Vec<f64>> = regressor.staged_predict(&x).collect();
+        assert_eq!(staged.len(), 30);
+        assert_eq!(staged[29], regressor.predict(&x).unwrap());
+
+        let errors: Vec<f64> = staged
+            .iter()
+            .map(|y_hat| mean_absolute_error(&y, y_hat))
+            .collect();
+        assert!(errors[29] < errors[0]);
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn early_stopping() {
+        let (x, y) = longley();
+
+        let regressor = GradientBoostingRegressor::fit(
+            &x,
+            &y,
+            GradientBoostingRegressorParameters::default()
+                .with_n_trees(500)
+                .with_learning_rate(0.5)
+                .with_validation_fraction(0.25)
+                .with_n_iter_no_change(3)
+                .with_seed(42),
+        )
+        .unwrap();
+
+        assert!(regressor.n_trees() < 500);
+        assert_eq!(regressor.staged_predict(&x).count(), regressor.n_trees());
+
+        assert!(GradientBoostingRegressor::fit(
+            &x,
+            &y,
+            GradientBoostingRegressorParameters::default().with_learning_rate(0.)
+        )
+        .is_err());
+        assert!(GradientBoostingRegressor::fit(
+            &x,
+            &y,
+            GradientBoostingRegressorParameters::default().with_learning_rate(f64::NAN)
+        )
+        .is_err());
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde() {
+        let (x, y) = longley();
+
+        let regressor = GradientBoostingRegressor::fit(
+            &x,
+            &y,
+            GradientBoostingRegressorParameters::default().with_n_trees(10),
+        )
+        .unwrap();
+
+        crate::ensemble::testing::assert_json_round_trip(&regressor, |regressor| {
+            regressor.predict(&x).unwrap()
+        });
+    }
+}
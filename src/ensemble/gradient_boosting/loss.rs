@@ -0,0 +1,359 @@
+//! Loss functions minimized by gradient boosting.
+//!
+//! Raw predictions are stored per output column: a single column for regression and binary classification,
+//! one column per class for multiclass classification. Targets of classification losses are class indices.
+
+/// Loss function of a gradient boosting model.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Loss {
+    /// \\(\frac{1}{2}(y - F)^2\\)
+    SquaredError,
+    /// \\(|y - F|\\)
+    AbsoluteError,
+    /// Squared error for residuals within the `alpha`-quantile of absolute residuals, absolute error beyond it.
+    Huber(f64),
+    /// Pinball loss of the `alpha`-quantile.
+    Quantile(f64),
+    /// Negative log-likelihood of the logistic model of two classes.
+    BinomialDeviance,
+    /// Negative log-likelihood of the softmax model of the given number of classes.
+    MultinomialDeviance(usize),
+}
+
+impl Loss {
+    /// Number of raw prediction columns, and of trees fitted at every boosting iteration.
+    pub(crate) fn n_outputs(&self) -> usize {
+        match self {
+            Loss::MultinomialDeviance(k) => *k,
+            _ => 1,
+        }
+    }
+
+    /// Whether leaf values of every tree are replaced by a line search of the loss.
+    pub(crate) fn updates_leaves(&self) -> bool {
+        !matches!(self, Loss::SquaredError)
+    }
+
+    /// Constant raw prediction minimizing the loss over `rows`.
+    pub(crate) fn init_estimate(&self, y: &[f64], rows: &[usize]) -> Vec<f64> {
+        let n = rows.len() as f64;
+        match self {
+            Loss::SquaredError => vec![rows.iter().map(|&i| y[i]).sum::<f64>() / n],
+            Loss::AbsoluteError | Loss::Huber(_) => vec![quantile(rows.iter().map(|&i| y[i]), 0.5)],
+            Loss::Quantile(alpha) => vec![quantile(rows.iter().map(|&i| y[i]), *alpha)],
+            Loss::BinomialDeviance => {
+                let p = clip_probability(rows.iter().map(|&i| y[i]).sum::<f64>() / n);
+                vec![(p / (1f64 - p)).ln()]
+            }
+            Loss::MultinomialDeviance(k) => {
+                let mut prior = vec![0f64; *k];
+                for &i in rows {
+                    prior[y[i] as usize] += 1f64;
+                }
+                prior
+                    .iter()
+                    .map(|count| clip_probability(count / n).ln())
+                    .collect()
+            }
+        }
+    }
+
+    /// Huber threshold: the `alpha`-quantile of absolute residuals over `rows`, zero for other losses.
+    pub(crate) fn delta(&self, y: &[f64], raw: &[Vec<f64>], rows: &[usize]) -> f64 {
+        match self {
+            Loss::Huber(alpha) => quantile(rows.iter().map(|&i| (y[i] - raw[0][i]).abs()), *alpha),
+            _ => 0f64,
+        }
+    }
+
+    /// Negative gradient of the loss with respect to the raw predictions of column `k`, for every row.
+    pub(crate) fn negative_gradient(
+        &self,
+        y: &[f64],
+        raw: &[Vec<f64>],
+        k: usize,
+        delta: f64,
+    ) -> Vec<f64> {
+        (0..y.len())
+            .map(|i| match self {
+                Loss::SquaredError => y[i] - raw[0][i],
+                Loss::AbsoluteError => sign(y[i] - raw[0][i]),
+                Loss::Huber(_) => {
+                    let diff = y[i] - raw[0][i];
+                    if diff.abs() <= delta {
+                        diff
+                    } else {
+                        delta * sign(diff)
+                    }
+                }
+                Loss::Quantile(alpha) => {
+                    if y[i] > raw[0][i] {
+                        *alpha
+                    } else {
+                        alpha - 1f64
+                    }
+                }
+                Loss::BinomialDeviance => y[i] - sigmoid(raw[0][i]),
+                Loss::MultinomialDeviance(_) => {
+                    let indicator = if y[i] as usize == k { 1f64 } else { 0f64 };
+                    indicator - softmax(&raw_row(raw, i))[k]
+                }
+            })
+            .collect()
+    }
+
+    /// Whether `hessian` is the second derivative of the loss, so that leaf values can be found by a Newton step.
+    pub(crate) fn has_hessian(&self) -> bool {
+        matches!(
+            self,
+            Loss::SquaredError | Loss::BinomialDeviance | Loss::MultinomialDeviance(_)
+        )
+    }
+
+    /// Second derivative of the loss with respect to the raw predictions of column `k`, for every row.
+    /// Unit hessian for the absolute error, Huber and quantile losses, see `has_hessian`.
+    pub(crate) fn hessian(&self, raw: &[Vec<f64>], k: usize) -> Vec<f64> {
+        (0..raw[0].len())
+            .map(|i| match self {
+                Loss::BinomialDeviance => {
+                    let p = sigmoid(raw[0][i]);
+                    p * (1f64 - p)
+                }
+                Loss::MultinomialDeviance(_) => {
+                    let p = softmax(&raw_row(raw, i))[k];
+                    p * (1f64 - p)
+                }
+                _ => 1f64,
+            })
+            .collect()
+    }
+
+    /// Value of a leaf of the tree fitted to the `residual` of column `k`, computed from the training rows in the leaf.
+    pub(crate) fn leaf_value(
+        &self,
+        y: &[f64],
+        raw: &[Vec<f64>],
+        residual: &[f64],
+        rows: &[usize],
+        delta: f64,
+    ) -> f64 {
+        match self {
+            Loss::SquaredError => {
+                rows.iter().map(|&i| residual[i]).sum::<f64>() / rows.len() as f64
+            }
+            Loss::AbsoluteError => quantile(rows.iter().map(|&i| y[i] - raw[0][i]), 0.5),
+            Loss::Quantile(alpha) => quantile(rows.iter().map(|&i| y[i] - raw[0][i]), *alpha),
+            Loss::Huber(_) => {
+                let median = quantile(rows.iter().map(|&i| y[i] - raw[0][i]), 0.5);
+                median
+                    + rows
+                        .iter()
+                        .map(|&i| {
+                            let diff = y[i] - raw[0][i] - median;
+                            sign(diff) * diff.abs().min(delta)
+                        })
+                        .sum::<f64>()
+                        / rows.len() as f64
+            }
+            Loss::BinomialDeviance => {
+                let numerator: f64 = rows.iter().map(|&i| residual[i]).sum();
+                let denominator: f64 = rows
+                    .iter()
+                    .map(|&i| (y[i] - residual[i]) * (1f64 - y[i] + residual[i]))
+                    .sum();
+                newton_step(numerator, denominator)
+            }
+            Loss::MultinomialDeviance(k) => {
+                let numerator: f64 =
+                    rows.iter().map(|&i| residual[i]).sum::<f64>() * (*k as f64 - 1f64) / *k as f64;
+                let denominator: f64 = rows
+                    .iter()
+                    .map(|&i| residual[i].abs() * (1f64 - residual[i].abs()))
+                    .sum();
+                newton_step(numerator, denominator)
+            }
+        }
+    }
+
+    /// Mean loss over `rows`.
+    pub(crate) fn loss(&self, y: &[f64], raw: &[Vec<f64>], rows: &[usize]) -> f64 {
+        let delta = self.delta(y, raw, rows);
+        rows.iter()
+            .map(|&i| match self {
+                Loss::SquaredError => 0.5 * (y[i] - raw[0][i]).powi(2),
+                Loss::AbsoluteError => (y[i] - raw[0][i]).abs(),
+                Loss::Huber(_) => {
+                    let diff = (y[i] - raw[0][i]).abs();
+                    if diff <= delta {
+                        0.5 * diff * diff
+                    } else {
+                        delta * (diff - 0.5 * delta)
+                    }
+                }
+                Loss::Quantile(alpha) => {
+                    let diff = y[i] - raw[0][i];
+                    if diff > 0f64 {
+                        alpha * diff
+                    } else {
+                        (alpha - 1f64) * diff
+                    }
+                }
+                Loss::BinomialDeviance => ln_1pe(raw[0][i]) - y[i] * raw[0][i],
+                Loss::MultinomialDeviance(_) => {
+                    let row = raw_row(raw, i);
+                    log_sum_exp(&row) - row[y[i] as usize]
+                }
+            })
+            .sum::<f64>()
+            / rows.len() as f64
+    }
+}
+
+/// Raw predictions of all columns for row `i`.
+pub(crate) fn raw_row(raw: &[Vec<f64>], i: usize) -> Vec<f64> {
+    raw.iter().map(|column| column[i]).collect()
+}
+
+pub(crate) fn sigmoid(x: f64) -> f64 {
+    if x >= 0f64 {
+        1f64 / (1f64 + (-x).exp())
+    } else {
+        let e = x.exp();
+        e / (1f64 + e)
+    }
+}
+
+pub(crate) fn softmax(x: &[f64]) -> Vec<f64> {
+    let log_sum = log_sum_exp(x);
+    x.iter().map(|x_i| (x_i - log_sum).exp()).collect()
+}
+
+fn log_sum_exp(x: &[f64]) -> f64 {
+    let max = x.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    max + x.iter().map(|x_i| (x_i - max).exp()).sum::<f64>().ln()
+}
+
+/// \\(\log(1 + e^x)\\) without overflow.
+fn ln_1pe(x: f64) -> f64 {
+    if x > 0f64 {
+        x + (-x).exp().ln_1p()
+    } else {
+        x.exp().ln_1p()
+    }
+}
+
+fn sign(x: f64) -> f64 {
+    if x > 0f64 {
+        1f64
+    } else if x < 0f64 {
+        -1f64
+    } else {
+        0f64
+    }
+}
+
+fn clip_probability(p: f64) -> f64 {
+    p.clamp(f64::EPSILON, 1f64 - f64::EPSILON)
+}
+
+fn newton_step(numerator: f64, denominator: f64) -> f64 {
+    if denominator.abs() < 1e-150 {
+        0f64
+    } else {
+        numerator / denominator
+    }
+}
+
+/// The `alpha`-quantile of `values`, linearly interpolated between the closest ranks.
+fn quantile(values: impl Iterator<Item = f64>, alpha: f64) -> f64 {
+    let mut values: Vec<f64> = values.collect();
+    if values.is_empty() {
+        return 0f64;
+    }
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let position = alpha * (values.len() - 1) as f64;
+    let lower = position.floor() as usize;
+    let upper = position.ceil() as usize;
+    values[lower] + (position - lower as f64) * (values[upper] - values[lower])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn quantiles() {
+        assert_eq!(quantile([3., 1., 2.].into_iter(), 0.5), 2.);
+        assert_eq!(quantile([4., 1., 2., 3.].into_iter(), 0.5), 2.5);
+        assert_eq!(quantile([4., 1., 2., 3.].into_iter(), 1.), 4.);
+        assert_eq!(quantile([4., 1., 2., 3.].into_iter(), 0.), 1.);
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn negative_gradients_match_finite_differences() {
+        let y = [0f64, 1., 2., 1., 0.];
+        let raw = [
+            vec![0.3, -0.2, 1.5, 0.1, -1.],
+            vec![-0.5, 0.4, 0.2, 0.9, 0.3],
+            vec![0.1, 0.1, -0.7, 0.2, 0.6],
+        ];
+        let losses = [
+            (Loss::SquaredError, 1),
+            (Loss::BinomialDeviance, 1),
+            (Loss::MultinomialDeviance(3), 3),
+        ];
+        let h = 1e-6;
+        for (loss, n_outputs) in losses.iter() {
+            let y: Vec<f64> = match loss {
+                Loss::BinomialDeviance => y.iter().map(|y_i| y_i.min(1.)).collect(),
+                _ => y.to_vec(),
+            };
+            let raw = &raw[..*n_outputs];
+            for k in 0..*n_outputs {
+                let gradient = loss.negative_gradient(&y, raw, k, 0.);
+                for i in 0..y.len() {
+                    let mut shifted = raw.to_vec();
+                    shifted[k][i] += h;
+                    let derivative = (loss.loss(&y, &shifted, &[i]) - loss.loss(&y, raw, &[i])) / h;
+                    assert!((gradient[i] + derivative).abs() < 1e-4);
+                }
+            }
+        }
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn hessians_match_finite_differences() {
+        let y = [0f64, 1., 2., 1., 0.];
+        let raw = [
+            vec![0.3, -0.2, 1.5, 0.1, -1.],
+            vec![-0.5, 0.4, 0.2, 0.9, 0.3],
+            vec![0.1, 0.1, -0.7, 0.2, 0.6],
+        ];
+        let h = 1e-6;
+        for k in 0..3 {
+            let loss = Loss::MultinomialDeviance(3);
+            let hessian = loss.hessian(&raw, k);
+            let gradient = loss.negative_gradient(&y, &raw, k, 0.);
+            let mut shifted = raw.to_vec();
+            for raw_ki in shifted[k].iter_mut() {
+                *raw_ki += h;
+            }
+            let shifted_gradient = loss.negative_gradient(&y, &shifted, k, 0.);
+            for i in 0..y.len() {
+                assert!((hessian[i] + (shifted_gradient[i] - gradient[i]) / h).abs() < 1e-4);
+            }
+        }
+    }
+}
@@ -0,0 +1,306 @@
+//! # Gradient Boosting
+//!
+//! Gradient boosting builds an additive model \\(F_M(x) = F_0(x) + \nu\sum_{m=1}^M h_m(x)\\) in a forward stage-wise fashion.
+//! \\(F_0\\) is the constant that minimizes the loss \\(L\\) over the training set. At every stage \\(m\\) a [regression tree](../../tree/decision_tree_regressor/index.html)
+//! \\(h_m\\) is fitted to the negative gradient of the loss evaluated at the current model,
+//!
+//! \\[r_{im} = -\left[\frac{\partial L(y_i, F(x_i))}{\partial F(x_i)}\right]_{F = F_{m-1}}\\]
+//!
+//! and the value of every leaf of the tree is then replaced by the constant that minimizes the loss over the training samples in the leaf.
+//!
+//! * The learning rate \\(0 < \nu \leq 1\\) shrinks the contribution of every tree. Smaller learning rates need more trees, but generalize better.
+//! * With `subsample` smaller than one, every tree is fitted to a random fraction of the training samples drawn without replacement (stochastic gradient boosting),
+//!   which reduces variance and speeds up training.
+//! * With `n_iter_no_change` set, a `validation_fraction` of the training samples is held out and boosting stops when the loss on the held out samples
+//!   has not improved by at least `tol` for `n_iter_no_change` consecutive iterations.
+//! * `staged_predict` returns the predictions of the model after every stage, e.g. to select the number of trees on a test set.
+//!
+//! [Gradient boosting regressor](gradient_boosting_regressor/index.html) supports squared error, absolute error, Huber and quantile losses.
+//! [Gradient boosting classifier](gradient_boosting_classifier/index.html) minimizes the binomial deviance for two classes and the multinomial deviance,
+//! with one tree per class at every stage, for more classes.
+//!
+//! ## References:
+//!
+//! * ["Greedy Function Approximation: A Gradient Boosting Machine", Friedman J. H., The Annals of Statistics, 2001](https://projecteuclid.org/journals/annals-of-statistics/volume-29/issue-5/Greedy-function-approximation-A-gradient-boostingmachine/10.1214/aos/1013203451.full)
+//! * ["Stochastic Gradient Boosting", Friedman J. H., Computational Statistics & Data Analysis, 2002](https://doi.org/10.1016/S0167-9473(01)00065-2)
+//! * ["The Elements of Statistical Learning", Hastie T., Tibshirani R., Friedman J., Chapter 10](https://hastie.su.domains/ElemStatLearn/)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+
+/// Gradient boosting classifier
+pub mod gradient_boosting_classifier;
+/// Gradient boosting regressor
+pub mod gradient_boosting_regressor;
+pub(crate) mod loss;
+
+use std::collections::BTreeMap;
+use std::marker::PhantomData;
+
+use rand::seq::SliceRandom;
+
+use crate::error::Failed;
+use crate::linalg::basic::arrays::Array2;
+use crate::numbers::basenum::Number;
+use crate::rand_custom::get_rng_impl;
+use crate::tree::decision_tree_regressor::{
+    DecisionTreeRegressor, DecisionTreeRegressorParameters,
+};
+
+use loss::Loss;
+
+/// Regression tree fitted at every stage of boosting.
+pub(crate) type BoostedTree<TX, X> = DecisionTreeRegressor<TX, f64, X, Vec<f64>>;
+
+/// Settings of the boosting procedure shared by the classifier and the regressor.
+pub(crate) struct Boosting {
+    pub(crate) loss: Loss,
+    pub(crate) learning_rate: f64,
+    pub(crate) n_trees: usize,
+    pub(crate) subsample: f64,
+    pub(crate) tree_parameters: DecisionTreeRegressorParameters,
+    pub(crate) validation_fraction: f64,
+    pub(crate) n_iter_no_change: Option<usize>,
+    pub(crate) tol: f64,
+    pub(crate) seed: Option<u64>,
+}
+
+/// Initial raw predictions and the fitted trees, stage by stage with one tree per raw prediction column.
+pub(crate) type BoostedModel<TX, X> = (Vec<f64>, Vec<BoostedTree<TX, X>>);
+
+impl Boosting {
+    pub(crate) fn validate(&self) -> Result<(), Failed> {
+        if !(self.learning_rate.is_finite() && self.learning_rate > 0f64) {
+            return Err(Failed::fit("learning_rate should be positive and finite"));
+        }
+        if self.n_trees == 0 {
+            return Err(Failed::fit("n_trees should be positive"));
+        }
+        if !(self.subsample > 0f64 && self.subsample <= 1f64) {
+            return Err(Failed::fit("subsample should be in (0, 1]"));
+        }
+        if self.n_iter_no_change.is_some()
+            && !(self.validation_fraction > 0f64 && self.validation_fraction < 1f64)
+        {
+            return Err(Failed::fit("validation_fraction should be in (0, 1)"));
+        }
+        if let Loss::Huber(alpha) | Loss::Quantile(alpha) = self.loss {
+            if !(alpha > 0f64 && alpha < 1f64) {
+                return Err(Failed::fit("alpha should be in (0, 1)"));
+            }
+        }
+        Ok(())
+    }
+
+    /// Fit the boosted trees to targets `y`, class indices for classification losses.
+    pub(crate) fn fit<TX: Number + PartialOrd, X: Array2<TX>>(
+        &self,
+        x: &X,
+        y: &[f64],
+    ) -> Result<BoostedModel<TX, X>, Failed> {
+        self.validate()?;
+
+        let (n, num_attributes) = x.shape();
+        let mut rng = get_rng_impl(self.seed);
+
+        let (rows, validation_rows) = if self.n_iter_no_change.is_some() {
+            validation_split(n, self.validation_fraction, &mut rng)?
+        } else {
+            ((0..n).collect(), Vec::new())
+        };
+        let n_subsample = ((self.subsample * rows.len() as f64) as usize).max(1);
+
+        let init = self.loss.init_estimate(y, &rows);
+        let mut raw: Vec<Vec<f64>> = init.iter().map(|&init_k| vec![init_k; n]).collect();
+        let mut stages = Vec::new();
+        let mut early_stopping = EarlyStopping::new(self.n_iter_no_change, self.tol);
+
+        for _ in 0..self.n_trees {
+            let in_bag = if n_subsample < rows.len() {
+                let mut in_bag = rows.clone();
+                in_bag.shuffle(&mut rng);
+                in_bag.truncate(n_subsample);
+                in_bag.sort_unstable();
+                in_bag
+            } else {
+                rows.clone()
+            };
+            let mut samples = vec![0; n];
+            for &i in in_bag.iter() {
+                samples[i] = 1;
+            }
+
+            let delta = self.loss.delta(y, &raw, &in_bag);
+            let residuals: Vec<Vec<f64>> = (0..self.loss.n_outputs())
+                .map(|k| self.loss.negative_gradient(y, &raw, k, delta))
+                .collect();
+
+            let mut trees = Vec::with_capacity(residuals.len());
+            let mut updates = Vec::with_capacity(residuals.len());
+            for residual in residuals.iter() {
+                let mut tree = BoostedTree::fit_weak_learner(
+                    x,
+                    residual,
+                    samples.clone(),
+                    num_attributes,
+                    self.tree_parameters.clone(),
+                )?;
+                let leaves: Vec<usize> = (0..n).map(|i| tree.leaf_for_row(x, i)).collect();
+
+                if self.loss.updates_leaves() {
+                    let mut leaf_rows: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+                    for &i in in_bag.iter() {
+                        leaf_rows.entry(leaves[i]).or_default().push(i);
+                    }
+                    for (leaf, leaf_rows) in leaf_rows.iter() {
+                        let value = self.loss.leaf_value(y, &raw, residual, leaf_rows, delta);
+                        tree.set_node_output(*leaf, value);
+                    }
+                }
+
+                updates.push(
+                    leaves
+                        .iter()
+                        .map(|&leaf| self.learning_rate * tree.node_output(leaf))
+                        .collect::<Vec<f64>>(),
+                );
+                trees.push(tree);
+            }
+
+            for (raw_k, update_k) in raw.iter_mut().zip(updates.iter()) {
+                for (raw_ki, update_ki) in raw_k.iter_mut().zip(update_k.iter()) {
+                    *raw_ki += update_ki;
+                }
+            }
+            stages.extend(trees);
+
+            if early_stopping.is_enabled()
+                && early_stopping.should_stop(self.loss.loss(y, &raw, &validation_rows))
+            {
+                break;
+            }
+        }
+
+        Ok((init, stages))
+    }
+}
+
+/// Shuffles rows `0..n` and holds out `validation_fraction` of them, returns sorted training and validation rows.
+pub(crate) fn validation_split(
+    n: usize,
+    validation_fraction: f64,
+    rng: &mut impl rand::Rng,
+) -> Result<(Vec<usize>, Vec<usize>), Failed> {
+    let mut rows: Vec<usize> = (0..n).collect();
+    rows.shuffle(rng);
+    let n_validation = ((validation_fraction * n as f64).ceil() as usize).max(1);
+    if n_validation >= n {
+        return Err(Failed::fit(
+            "Not enough samples to hold out a validation set",
+        ));
+    }
+    let mut validation_rows = rows.split_off(n - n_validation);
+    validation_rows.sort_unstable();
+    rows.sort_unstable();
+    Ok((rows, validation_rows))
+}
+
+/// Stops boosting when none of the last `n_iter_no_change` validation losses is improved by at least `tol`.
+pub(crate) struct EarlyStopping {
+    history: Vec<f64>,
+    tol: f64,
+    iteration: usize,
+}
+
+impl EarlyStopping {
+    pub(crate) fn new(n_iter_no_change: Option<usize>, tol: f64) -> Self {
+        EarlyStopping {
+            history: vec![f64::INFINITY; n_iter_no_change.unwrap_or(0)],
+            tol,
+            iteration: 0,
+        }
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        !self.history.is_empty()
+    }
+
+    /// Records the validation loss of the current iteration.
+    pub(crate) fn should_stop(&mut self, validation_loss: f64) -> bool {
+        if self.history.iter().any(|&h| validation_loss + self.tol < h) {
+            let n_history = self.history.len();
+            self.history[self.iteration % n_history] = validation_loss;
+            self.iteration += 1;
+            false
+        } else {
+            true
+        }
+    }
+}
+
+/// A tree fitted at one stage of boosting to one raw prediction column.
+pub(crate) trait StageTree<TX: Number, X: Array2<TX>> {
+    /// Unshrunk contribution of the tree to the raw prediction of row `row` of `x`.
+    fn predict_row(&self, x: &X, row: usize) -> f64;
+}
+
+impl<TX: Number + PartialOrd, X: Array2<TX>> StageTree<TX, X> for BoostedTree<TX, X> {
+    fn predict_row(&self, x: &X, row: usize) -> f64 {
+        self.node_output(self.leaf_for_row(x, row))
+    }
+}
+
+/// Raw predictions of the model after every stage, one vector per raw prediction column.
+pub(crate) struct StagedRaw<'a, TX: Number, X: Array2<TX>, T: StageTree<TX, X>> {
+    x: &'a X,
+    learning_rate: f64,
+    stages: std::slice::Chunks<'a, T>,
+    raw: Vec<Vec<f64>>,
+    _phantom_tx: PhantomData<TX>,
+}
+
+impl<'a, TX: Number, X: Array2<TX>, T: StageTree<TX, X>> StagedRaw<'a, TX, X, T> {
+    pub(crate) fn new(x: &'a X, init: &[f64], learning_rate: f64, trees: &'a [T]) -> Self {
+        let (n, _) = x.shape();
+        StagedRaw {
+            x,
+            learning_rate,
+            stages: trees.chunks(init.len()),
+            raw: init.iter().map(|&init_k| vec![init_k; n]).collect(),
+            _phantom_tx: PhantomData,
+        }
+    }
+
+    /// Raw predictions of the full model.
+    pub(crate) fn finish(mut self) -> Vec<Vec<f64>> {
+        while self.advance() {}
+        self.raw
+    }
+
+    /// Add the next stage to the raw predictions, returns false when all stages were added.
+    fn advance(&mut self) -> bool {
+        match self.stages.next() {
+            Some(trees) => {
+                for (raw_k, tree) in self.raw.iter_mut().zip(trees.iter()) {
+                    for (i, raw_ki) in raw_k.iter_mut().enumerate() {
+                        *raw_ki += self.learning_rate * tree.predict_row(self.x, i);
+                    }
+                }
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl<TX: Number, X: Array2<TX>, T: StageTree<TX, X>> Iterator for StagedRaw<'_, TX, X, T> {
+    type Item = Vec<Vec<f64>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.advance() {
+            Some(self.raw.clone())
+        } else {
+            None
+        }
+    }
+}
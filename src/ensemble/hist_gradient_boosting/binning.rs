@@ -0,0 +1,203 @@
+//! Discretization of features into at most 255 bins, plus one bin for missing values.
+//!
+//! Numerical features are split at quantiles of their training values, or between consecutive distinct values when there are few of them.
+//! Every category of a categorical feature gets a bin of its own.
+
+use crate::error::Failed;
+use crate::linalg::basic::arrays::Array2;
+use crate::numbers::basenum::Number;
+
+/// Bin of missing values of every feature.
+pub(crate) const MISSING_BIN: usize = 255;
+/// Number of bins of a histogram, including the bin of missing values.
+pub(crate) const N_BINS: usize = MISSING_BIN + 1;
+
+/// Maps feature values to bins, learned from the training data.
+#[derive(Debug, Clone)]
+pub(crate) struct BinMapper {
+    /// Upper bounds of the bins of every numerical feature: bin `b` holds values in `(thresholds[b - 1], thresholds[b]]`,
+    /// the last bin holds all values above the last threshold.
+    thresholds: Vec<Vec<f64>>,
+    /// Sorted distinct values of every categorical feature, empty for numerical features.
+    categories: Vec<Vec<f64>>,
+    is_categorical: Vec<bool>,
+    n_bins: Vec<usize>,
+}
+
+impl BinMapper {
+    /// Learn the bins of every feature from `rows` of `x`.
+    pub(crate) fn fit<TX: Number, X: Array2<TX>>(
+        x: &X,
+        rows: &[usize],
+        max_bins: usize,
+        categorical_features: &[usize],
+    ) -> Result<BinMapper, Failed> {
+        let (_, num_attributes) = x.shape();
+        if !(2..=MISSING_BIN).contains(&max_bins) {
+            return Err(Failed::fit(&format!(
+                "max_bins should be in [2, {MISSING_BIN}]"
+            )));
+        }
+        let mut is_categorical = vec![false; num_attributes];
+        for &j in categorical_features {
+            if j >= num_attributes {
+                return Err(Failed::fit(&format!(
+                    "Categorical feature {j} is out of bounds, x has {num_attributes} features"
+                )));
+            }
+            is_categorical[j] = true;
+        }
+
+        let mut thresholds = Vec::with_capacity(num_attributes);
+        let mut categories = Vec::with_capacity(num_attributes);
+        let mut n_bins = Vec::with_capacity(num_attributes);
+        for (j, &is_categorical_j) in is_categorical.iter().enumerate() {
+            let mut values: Vec<f64> = rows
+                .iter()
+                .map(|&i| x.get((i, j)).to_f64().unwrap())
+                .filter(|v| !v.is_nan())
+                .collect();
+            values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            let mut distinct = values.clone();
+            distinct.dedup();
+
+            if is_categorical_j {
+                if distinct.len() > max_bins {
+                    return Err(Failed::fit(&format!(
+                        "Categorical feature {j} has {} categories, more than max_bins = {max_bins}",
+                        distinct.len()
+                    )));
+                }
+                n_bins.push(distinct.len());
+                thresholds.push(Vec::new());
+                categories.push(distinct);
+            } else {
+                let thresholds_j = if distinct.len() <= max_bins {
+                    distinct.windows(2).map(|w| (w[0] + w[1]) / 2f64).collect()
+                } else {
+                    let mut thresholds_j: Vec<f64> = (1..max_bins)
+                        .map(|b| {
+                            let position = b * (values.len() - 1) / max_bins;
+                            (values[position] + values[position + 1]) / 2f64
+                        })
+                        .collect();
+                    thresholds_j.dedup();
+                    thresholds_j
+                };
+                n_bins.push(thresholds_j.len() + 1);
+                thresholds.push(thresholds_j);
+                categories.push(Vec::new());
+            }
+        }
+
+        Ok(BinMapper {
+            thresholds,
+            categories,
+            is_categorical,
+            n_bins,
+        })
+    }
+
+    /// Number of bins of non-missing values of feature `j`.
+    pub(crate) fn n_bins(&self, j: usize) -> usize {
+        self.n_bins[j]
+    }
+
+    pub(crate) fn is_categorical(&self, j: usize) -> bool {
+        self.is_categorical[j]
+    }
+
+    /// Threshold of numerical feature `j` between bins up to `bin` and the following bins, infinity after the last bin.
+    pub(crate) fn threshold(&self, j: usize, bin: usize) -> f64 {
+        self.thresholds[j]
+            .get(bin)
+            .cloned()
+            .unwrap_or(f64::INFINITY)
+    }
+
+    /// Category of categorical feature `j` in `bin`.
+    pub(crate) fn category(&self, j: usize, bin: usize) -> f64 {
+        self.categories[j][bin]
+    }
+
+    /// Bins of every row of `x`, one vector per feature.
+    pub(crate) fn transform<TX: Number, X: Array2<TX>>(&self, x: &X) -> Vec<Vec<u8>> {
+        let (n, num_attributes) = x.shape();
+        (0..num_attributes)
+            .map(|j| {
+                (0..n)
+                    .map(|i| self.bin(j, x.get((i, j)).to_f64().unwrap()) as u8)
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn bin(&self, j: usize, value: f64) -> usize {
+        if value.is_nan() {
+            MISSING_BIN
+        } else if self.is_categorical[j] {
+            self.categories[j]
+                .binary_search_by(|c| c.partial_cmp(&value).unwrap())
+                .unwrap_or(MISSING_BIN)
+        } else {
+            self.thresholds[j].partition_point(|&t| t < value)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::basic::matrix::DenseMatrix;
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn bins() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[1., 3.],
+            &[2., 1.],
+            &[f64::NAN, 3.],
+            &[4., f64::NAN],
+            &[2., 7.],
+        ])
+        .unwrap();
+        let mapper = BinMapper::fit(&x, &[0, 1, 2, 3, 4], 255, &[1]).unwrap();
+
+        assert_eq!(mapper.n_bins(0), 3);
+        assert_eq!(mapper.threshold(0, 0), 1.5);
+        assert_eq!(mapper.threshold(0, 1), 3.);
+        assert_eq!(mapper.threshold(0, 2), f64::INFINITY);
+        assert_eq!(mapper.n_bins(1), 3);
+        assert_eq!(mapper.category(1, 2), 7.);
+
+        let binned = mapper.transform(&x);
+        assert_eq!(binned[0], vec![0, 1, 255, 2, 1]);
+        assert_eq!(binned[1], vec![1, 0, 1, 255, 2]);
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn quantile_bins() {
+        let values: Vec<Vec<f64>> = (0..1000).map(|i| vec![(i % 500) as f64]).collect();
+        let x = DenseMatrix::from_2d_vec(&values).unwrap();
+        let rows: Vec<usize> = (0..1000).collect();
+        let mapper = BinMapper::fit(&x, &rows, 10, &[]).unwrap();
+
+        assert_eq!(mapper.n_bins(0), 10);
+        let binned = mapper.transform(&x);
+        let mut counts = [0; 10];
+        for &b in binned[0].iter() {
+            counts[b as usize] += 1;
+        }
+        assert!(counts.iter().all(|&count| count == 100));
+
+        assert!(BinMapper::fit(&x, &rows, 3, &[0]).is_err());
+        assert!(BinMapper::fit(&x, &rows, 256, &[]).is_err());
+    }
+}
@@ -0,0 +1,486 @@
+//! Leaf-wise growth of regression trees from histograms of gradients and hessians.
+//!
+//! The histogram of a node sums the gradients and hessians of its samples by bin of every feature, and the best split of a feature
+//! is found by a single scan over its bins. Only the histogram of the smaller child of a split is built from its samples,
+//! the histogram of the larger child is the difference between the histograms of the parent and the smaller child.
+
+use std::iter;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::ensemble::gradient_boosting::StageTree;
+use crate::ensemble::hist_gradient_boosting::binning::{BinMapper, MISSING_BIN, N_BINS};
+use crate::linalg::basic::arrays::Array2;
+use crate::numbers::basenum::Number;
+
+/// Smoothing of the ratio of gradients to hessians that orders the categories of a categorical feature.
+const CATEGORY_SMOOTHING: f64 = 10f64;
+/// Minimum sum of hessians of a child of a split.
+const MIN_HESSIAN: f64 = 1e-3;
+
+#[derive(Debug, Clone, Copy, Default)]
+struct HistogramBin {
+    sum_gradients: f64,
+    sum_hessians: f64,
+    count: usize,
+}
+
+impl HistogramBin {
+    fn add(&mut self, other: &HistogramBin) {
+        self.sum_gradients += other.sum_gradients;
+        self.sum_hessians += other.sum_hessians;
+        self.count += other.count;
+    }
+
+    fn subtract(&self, other: &HistogramBin) -> HistogramBin {
+        HistogramBin {
+            sum_gradients: self.sum_gradients - other.sum_gradients,
+            sum_hessians: self.sum_hessians - other.sum_hessians,
+            count: self.count - other.count,
+        }
+    }
+}
+
+/// Histogram of every feature.
+type Histogram = Vec<[HistogramBin; N_BINS]>;
+
+/// Node of a histogram tree.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub(crate) struct HistNode {
+    value: f64,
+    n_node_samples: usize,
+    /// split feature, `None` for leaves
+    feature: Option<usize>,
+    /// observations with a value of a numerical split feature up to the threshold go to the true child
+    threshold: f64,
+    /// sorted categories of a categorical split feature that go to the true child
+    true_categories: Option<Vec<f64>>,
+    missing_to_true_child: bool,
+    true_child: usize,
+    false_child: usize,
+}
+
+impl HistNode {
+    fn new(value: f64, n_node_samples: usize) -> Self {
+        HistNode {
+            value,
+            n_node_samples,
+            feature: Option::None,
+            threshold: 0f64,
+            true_categories: Option::None,
+            missing_to_true_child: false,
+            true_child: 0,
+            false_child: 0,
+        }
+    }
+
+    fn goes_to_true_child(&self, value: f64) -> bool {
+        if value.is_nan() {
+            self.missing_to_true_child
+        } else {
+            match &self.true_categories {
+                Some(categories) => categories
+                    .binary_search_by(|c| c.partial_cmp(&value).unwrap())
+                    .is_ok(),
+                None => value <= self.threshold,
+            }
+        }
+    }
+}
+
+impl PartialEq for HistNode {
+    fn eq(&self, other: &Self) -> bool {
+        (self.value - other.value).abs() < f64::EPSILON
+            && self.n_node_samples == other.n_node_samples
+            && self.feature == other.feature
+            && (self.threshold - other.threshold).abs() < f64::EPSILON
+            && self.true_categories == other.true_categories
+            && self.missing_to_true_child == other.missing_to_true_child
+            && self.true_child == other.true_child
+            && self.false_child == other.false_child
+    }
+}
+
+/// Regression tree grown from histograms. Predictions are made from feature values, not from bins.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct HistTree {
+    nodes: Vec<HistNode>,
+}
+
+impl HistTree {
+    pub(crate) fn value(&self, node_id: usize) -> f64 {
+        self.nodes[node_id].value
+    }
+
+    pub(crate) fn set_value(&mut self, node_id: usize, value: f64) {
+        self.nodes[node_id].value = value;
+    }
+
+    fn leaf_for_row<TX: Number, X: Array2<TX>>(&self, x: &X, row: usize) -> usize {
+        let mut node_id = 0;
+        while let Some(feature) = self.nodes[node_id].feature {
+            let node = &self.nodes[node_id];
+            node_id = if node.goes_to_true_child(x.get((row, feature)).to_f64().unwrap()) {
+                node.true_child
+            } else {
+                node.false_child
+            };
+        }
+        node_id
+    }
+}
+
+impl<TX: Number, X: Array2<TX>> StageTree<TX, X> for HistTree {
+    fn predict_row(&self, x: &X, row: usize) -> f64 {
+        self.value(self.leaf_for_row(x, row))
+    }
+}
+
+/// Best split of a node.
+struct Split {
+    gain: f64,
+    feature: usize,
+    /// whether samples in every bin of the feature, including the bin of missing values, go to the true child
+    goes_to_true_child: [bool; N_BINS],
+}
+
+/// Leaf of a tree that is still growing.
+struct Leaf {
+    node_id: usize,
+    rows: Vec<usize>,
+    histogram: Histogram,
+    depth: u16,
+    split: Option<Split>,
+}
+
+/// Grows a tree fitted to the gradients and hessians of the loss with respect to one raw prediction column.
+pub(crate) struct TreeGrower<'a> {
+    pub(crate) binned: &'a [Vec<u8>],
+    pub(crate) bin_mapper: &'a BinMapper,
+    pub(crate) gradients: &'a [f64],
+    pub(crate) hessians: &'a [f64],
+    pub(crate) max_leaf_nodes: Option<usize>,
+    pub(crate) max_depth: Option<u16>,
+    pub(crate) min_samples_leaf: usize,
+    pub(crate) l2_regularization: f64,
+}
+
+impl TreeGrower<'_> {
+    /// Grows a tree on `rows`, best split first, with leaf values \\(-G / (H + \lambda)\\).
+    /// Returns the tree and the rows of every leaf.
+    pub(crate) fn grow(&self, rows: Vec<usize>) -> (HistTree, Vec<(usize, Vec<usize>)>) {
+        let histogram = self.histogram(&rows);
+        let mut nodes = vec![self.node(&rows)];
+        let mut leaves = vec![self.leaf(0, rows, histogram, 0)];
+
+        while self
+            .max_leaf_nodes
+            .is_none_or(|max_leaf_nodes| leaves.len() < max_leaf_nodes)
+        {
+            let best = leaves
+                .iter()
+                .enumerate()
+                .filter_map(|(i, leaf)| leaf.split.as_ref().map(|split| (i, split.gain)))
+                .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+            let leaf = match best {
+                Some((i, _)) => leaves.swap_remove(i),
+                None => break,
+            };
+            let split = leaf.split.unwrap();
+
+            let bins = &self.binned[split.feature];
+            let (true_rows, false_rows): (Vec<usize>, Vec<usize>) = leaf
+                .rows
+                .iter()
+                .partition(|&&i| split.goes_to_true_child[bins[i] as usize]);
+            let (true_histogram, false_histogram) = if true_rows.len() <= false_rows.len() {
+                let true_histogram = self.histogram(&true_rows);
+                let false_histogram = subtract(&leaf.histogram, &true_histogram);
+                (true_histogram, false_histogram)
+            } else {
+                let false_histogram = self.histogram(&false_rows);
+                let true_histogram = subtract(&leaf.histogram, &false_histogram);
+                (true_histogram, false_histogram)
+            };
+
+            let true_child = nodes.len();
+            let false_child = true_child + 1;
+            nodes.push(self.node(&true_rows));
+            nodes.push(self.node(&false_rows));
+
+            let node = &mut nodes[leaf.node_id];
+            node.feature = Some(split.feature);
+            node.missing_to_true_child = split.goes_to_true_child[MISSING_BIN];
+            node.true_child = true_child;
+            node.false_child = false_child;
+            let mut true_bins =
+                (0..self.bin_mapper.n_bins(split.feature)).filter(|&b| split.goes_to_true_child[b]);
+            if self.bin_mapper.is_categorical(split.feature) {
+                node.true_categories = Some(
+                    true_bins
+                        .map(|b| self.bin_mapper.category(split.feature, b))
+                        .collect(),
+                );
+            } else {
+                let last_true_bin = true_bins.next_back().unwrap();
+                node.threshold = self.bin_mapper.threshold(split.feature, last_true_bin);
+            }
+
+            leaves.push(self.leaf(true_child, true_rows, true_histogram, leaf.depth + 1));
+            leaves.push(self.leaf(false_child, false_rows, false_histogram, leaf.depth + 1));
+        }
+
+        let leaf_rows = leaves
+            .into_iter()
+            .map(|leaf| (leaf.node_id, leaf.rows))
+            .collect();
+        (HistTree { nodes }, leaf_rows)
+    }
+
+    fn node(&self, rows: &[usize]) -> HistNode {
+        let sum_gradients: f64 = rows.iter().map(|&i| self.gradients[i]).sum();
+        let sum_hessians: f64 = rows.iter().map(|&i| self.hessians[i]).sum();
+        HistNode::new(
+            -sum_gradients / (sum_hessians + self.l2_regularization),
+            rows.len(),
+        )
+    }
+
+    fn leaf(&self, node_id: usize, rows: Vec<usize>, histogram: Histogram, depth: u16) -> Leaf {
+        let can_split = self.max_depth.is_none_or(|max_depth| depth < max_depth)
+            && rows.len() >= 2 * self.min_samples_leaf;
+        let split = if can_split {
+            self.find_split(&histogram)
+        } else {
+            Option::None
+        };
+        Leaf {
+            node_id,
+            rows,
+            // the histogram is only needed to split the leaf
+            histogram: if split.is_some() {
+                histogram
+            } else {
+                Vec::new()
+            },
+            depth,
+            split,
+        }
+    }
+
+    fn histogram(&self, rows: &[usize]) -> Histogram {
+        self.binned
+            .iter()
+            .map(|bins| {
+                let mut histogram = [HistogramBin::default(); N_BINS];
+                for &i in rows {
+                    let bin = &mut histogram[bins[i] as usize];
+                    bin.sum_gradients += self.gradients[i];
+                    bin.sum_hessians += self.hessians[i];
+                    bin.count += 1;
+                }
+                histogram
+            })
+            .collect()
+    }
+
+    fn score(&self, bin: &HistogramBin) -> f64 {
+        bin.sum_gradients * bin.sum_gradients / (bin.sum_hessians + self.l2_regularization)
+    }
+
+    /// Finds the split with the largest gain. A split sends a prefix of an ordering of the bins of a feature to the true child:
+    /// bins in increasing order for numerical features, with the bin of missing values first or last,
+    /// and bins ordered by their ratio of gradients to hessians for categorical features.
+    fn find_split(&self, histogram: &Histogram) -> Option<Split> {
+        let mut total = HistogramBin::default();
+        for bin in histogram[0].iter() {
+            total.add(bin);
+        }
+        let parent_score = self.score(&total);
+
+        let mut best: Option<Split> = Option::None;
+        for (j, histogram_j) in histogram.iter().enumerate() {
+            let bins: Vec<usize> = (0..self.bin_mapper.n_bins(j))
+                .filter(|&b| histogram_j[b].count > 0)
+                .collect();
+            let has_missing = histogram_j[MISSING_BIN].count > 0;
+
+            // orderings of the bins, with the length of the shortest prefix to consider
+            let orderings: Vec<(Vec<usize>, usize)> = if self.bin_mapper.is_categorical(j) {
+                let mut ordering: Vec<usize> = bins
+                    .into_iter()
+                    .chain(iter::once(MISSING_BIN).filter(|_| has_missing))
+                    .collect();
+                let ratio = |b: &usize| {
+                    histogram_j[*b].sum_gradients
+                        / (histogram_j[*b].sum_hessians + CATEGORY_SMOOTHING)
+                };
+                ordering.sort_by(|a, b| ratio(a).partial_cmp(&ratio(b)).unwrap());
+                vec![(ordering, 1)]
+            } else if has_missing {
+                vec![
+                    (
+                        bins.iter()
+                            .cloned()
+                            .chain(iter::once(MISSING_BIN))
+                            .collect(),
+                        1,
+                    ),
+                    // the bin of missing values alone is the complement of all other bins, found above
+                    (iter::once(MISSING_BIN).chain(bins).collect(), 2),
+                ]
+            } else {
+                vec![(bins, 1)]
+            };
+
+            for (ordering, min_prefix) in orderings {
+                let mut true_side = HistogramBin::default();
+                for (p, &b) in ordering
+                    .iter()
+                    .enumerate()
+                    .take(ordering.len().saturating_sub(1))
+                {
+                    true_side.add(&histogram_j[b]);
+                    if p + 1 < min_prefix {
+                        continue;
+                    }
+                    let false_side = total.subtract(&true_side);
+                    if true_side.count < self.min_samples_leaf
+                        || false_side.count < self.min_samples_leaf
+                        || true_side.sum_hessians < MIN_HESSIAN
+                        || false_side.sum_hessians < MIN_HESSIAN
+                    {
+                        continue;
+                    }
+                    let gain = self.score(&true_side) + self.score(&false_side) - parent_score;
+                    if gain > best.as_ref().map_or(0f64, |split| split.gain) {
+                        let mut goes_to_true_child = [false; N_BINS];
+                        for &b in ordering[..=p].iter() {
+                            goes_to_true_child[b] = true;
+                        }
+                        if !has_missing {
+                            // missing values at prediction time follow the child with more training samples
+                            goes_to_true_child[MISSING_BIN] = true_side.count >= false_side.count;
+                        }
+                        best = Some(Split {
+                            gain,
+                            feature: j,
+                            goes_to_true_child,
+                        });
+                    }
+                }
+            }
+        }
+        best
+    }
+}
+
+fn subtract(parent: &Histogram, child: &Histogram) -> Histogram {
+    parent
+        .iter()
+        .zip(child.iter())
+        .map(|(parent_j, child_j)| {
+            let mut histogram = [HistogramBin::default(); N_BINS];
+            for (b, bin) in histogram.iter_mut().enumerate() {
+                *bin = parent_j[b].subtract(&child_j[b]);
+            }
+            histogram
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::basic::matrix::DenseMatrix;
+
+    fn grow(
+        x: &DenseMatrix<f64>,
+        y: &[f64],
+        categorical_features: &[usize],
+        max_leaf_nodes: Option<usize>,
+    ) -> HistTree {
+        let rows: Vec<usize> = (0..y.len()).collect();
+        let bin_mapper = BinMapper::fit(x, &rows, 255, categorical_features).unwrap();
+        let binned = bin_mapper.transform(x);
+        let gradients: Vec<f64> = y.iter().map(|y_i| -y_i).collect();
+        let hessians = vec![1f64; y.len()];
+        let grower = TreeGrower {
+            binned: &binned,
+            bin_mapper: &bin_mapper,
+            gradients: &gradients,
+            hessians: &hessians,
+            max_leaf_nodes,
+            max_depth: Option::None,
+            min_samples_leaf: 1,
+            l2_regularization: 0f64,
+        };
+        let (tree, leaves) = grower.grow(rows);
+        assert_eq!(
+            leaves.iter().map(|(_, rows)| rows.len()).sum::<usize>(),
+            y.len()
+        );
+        tree
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn numerical_splits_with_missing_values() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[1.],
+            &[2.],
+            &[3.],
+            &[f64::NAN],
+            &[4.],
+            &[5.],
+            &[f64::NAN],
+        ])
+        .unwrap();
+        let y = [1., 1., 1., 10., 3., 3., 10.];
+
+        let tree = grow(&x, &y, &[], Option::None);
+        for (i, y_i) in y.iter().enumerate() {
+            assert!((tree.predict_row(&x, i) - y_i).abs() < 1e-8);
+        }
+
+        // the first split separates missing values from all other values
+        let tree = grow(&x, &y, &[], Some(2));
+        assert_eq!(tree.nodes[0].threshold, f64::INFINITY);
+        assert!(!tree.nodes[0].missing_to_true_child);
+        assert!((tree.predict_row(&x, 3) - 10.).abs() < 1e-8);
+        assert!((tree.predict_row(&x, 0) - 1.8).abs() < 1e-8);
+        // infinite values are present, not missing
+        let x_test = DenseMatrix::from_2d_array(&[&[f64::INFINITY]]).unwrap();
+        assert!((tree.predict_row(&x_test, 0) - 1.8).abs() < 1e-8);
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn categorical_split() {
+        let x =
+            DenseMatrix::from_2d_array(&[&[0.], &[1.], &[2.], &[3.], &[0.], &[1.], &[2.], &[3.]])
+                .unwrap();
+        let y = [1., -1., 1., -1., 1., -1., 1., -1.];
+
+        // a single categorical split separates categories 0 and 2 from 1 and 3
+        let tree = grow(&x, &y, &[0], Some(2));
+        assert_eq!(tree.nodes.len(), 3);
+        for (i, y_i) in y.iter().enumerate() {
+            assert!((tree.predict_row(&x, i) - y_i).abs() < 1e-8);
+        }
+        assert_eq!(tree.nodes[0].true_categories, Some(vec![0., 2.]));
+
+        // unknown categories go to the false child
+        let unknown = DenseMatrix::from_2d_array(&[&[7.]]).unwrap();
+        assert!((tree.predict_row(&unknown, 0) + 1.).abs() < 1e-8);
+    }
+}
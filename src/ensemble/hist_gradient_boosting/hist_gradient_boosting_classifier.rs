@@ -0,0 +1,494 @@
+//! # Histogram-Based Gradient Boosting Classifier
+//! Histogram-based gradient boosting classifier fits a sequence of regression trees grown leaf-wise from binned features to the gradient
+//! of the deviance, like the [gradient boosting classifier](../../gradient_boosting/gradient_boosting_classifier/index.html).
+//! It handles missing values natively and splits categorical features into sets of categories.
+//! See [histogram-based gradient boosting](../index.html) for more details.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::basic::matrix::DenseMatrix;
+//! use smartcore::ensemble::hist_gradient_boosting::hist_gradient_boosting_classifier::*;
+//!
+//! // Iris dataset
+//! let x = DenseMatrix::from_2d_array(&[
+//!              &[5.1, 3.5, 1.4, 0.2],
+//!              &[4.9, 3.0, 1.4, 0.2],
+//!              &[4.7, 3.2, 1.3, 0.2],
+//!              &[4.6, 3.1, 1.5, 0.2],
+//!              &[5.0, 3.6, 1.4, 0.2],
+//!              &[5.4, 3.9, 1.7, 0.4],
+//!              &[4.6, 3.4, 1.4, 0.3],
+//!              &[5.0, 3.4, 1.5, 0.2],
+//!              &[4.4, 2.9, 1.4, 0.2],
+//!              &[4.9, 3.1, 1.5, 0.1],
+//!              &[7.0, 3.2, 4.7, 1.4],
+//!              &[6.4, 3.2, 4.5, 1.5],
+//!              &[6.9, 3.1, 4.9, 1.5],
+//!              &[5.5, 2.3, 4.0, 1.3],
+//!              &[6.5, 2.8, 4.6, 1.5],
+//!              &[5.7, 2.8, 4.5, 1.3],
+//!              &[6.3, 3.3, 4.7, 1.6],
+//!              &[4.9, 2.4, 3.3, 1.0],
+//!              &[6.6, 2.9, 4.6, 1.3],
+//!              &[5.2, 2.7, 3.9, 1.4],
+//!         ]).unwrap();
+//! let y = vec![
+//!              0, 0, 0, 0, 0, 0, 0, 0,
+//!              1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+//!         ];
+//!
+//! let classifier = HistGradientBoostingClassifier::fit(
+//!     &x,
+//!     &y,
+//!     HistGradientBoostingClassifierParameters::default().with_min_samples_leaf(2),
+//! ).unwrap();
+//!
+//! let y_hat = classifier.predict(&x).unwrap(); // use the same data for prediction
+//! let y_proba = classifier.predict_proba(&x).unwrap(); // class probabilities
+//! ```
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use std::default::Default;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::api::{Predictor, SupervisedEstimator};
+use crate::ensemble::gradient_boosting::loss::{raw_row, sigmoid, softmax, Loss};
+use crate::ensemble::gradient_boosting::StagedRaw;
+use crate::ensemble::hist_gradient_boosting::grower::HistTree;
+use crate::ensemble::hist_gradient_boosting::HistBoosting;
+use crate::error::Failed;
+use crate::linalg::basic::arrays::{Array1, Array2};
+use crate::numbers::basenum::Number;
+use crate::numbers::floatnum::FloatNumber;
+
+/// Parameters of the histogram-based gradient boosting classifier.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct HistGradientBoostingClassifierParameters {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Shrinks the contribution of every tree.
+    pub learning_rate: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The number of boosting stages.
+    pub n_trees: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The maximum number of leaves of every tree.
+    pub max_leaf_nodes: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The maximum number of splits from the root of every tree to a leaf. No limit by default.
+    pub max_depth: Option<u16>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The minimum number of samples required to be at a leaf node.
+    pub min_samples_leaf: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// L2 regularization of leaf values.
+    pub l2_regularization: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The maximum number of bins of non-missing values of every feature, at most 255.
+    pub max_bins: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Indices of categorical features. Categories can be any values, a feature can have at most `max_bins` categories.
+    pub categorical_features: Vec<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Fraction of the training samples held out for early stopping.
+    pub validation_fraction: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Stop when the validation loss has not improved for this number of stages. No early stopping by default.
+    pub n_iter_no_change: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The minimum improvement of the validation loss for early stopping.
+    pub tol: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Seed used for the validation split.
+    pub seed: Option<u64>,
+}
+
+/// Histogram-Based Gradient Boosting Classifier
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+pub struct HistGradientBoostingClassifier<
+    TX: Number + FloatNumber + PartialOrd,
+    TY: Number + Ord,
+    X: Array2<TX>,
+    Y: Array1<TY>,
+> {
+    classes: Vec<TY>,
+    init: Vec<f64>,
+    learning_rate: f64,
+    trees: Vec<HistTree>,
+    _phantom_tx: PhantomData<TX>,
+    _phantom_x: PhantomData<X>,
+    _phantom_y: PhantomData<Y>,
+}
+
+impl HistGradientBoostingClassifierParameters {
+    /// Shrinks the contribution of every tree.
+    pub fn with_learning_rate(mut self, learning_rate: f64) -> Self {
+        self.learning_rate = learning_rate;
+        self
+    }
+    /// The number of boosting stages.
+    pub fn with_n_trees(mut self, n_trees: usize) -> Self {
+        self.n_trees = n_trees;
+        self
+    }
+    /// The maximum number of leaves of every tree.
+    pub fn with_max_leaf_nodes(mut self, max_leaf_nodes: usize) -> Self {
+        self.max_leaf_nodes = Some(max_leaf_nodes);
+        self
+    }
+    /// The maximum number of splits from the root of every tree to a leaf.
+    pub fn with_max_depth(mut self, max_depth: u16) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+    /// The minimum number of samples required to be at a leaf node.
+    pub fn with_min_samples_leaf(mut self, min_samples_leaf: usize) -> Self {
+        self.min_samples_leaf = min_samples_leaf;
+        self
+    }
+    /// L2 regularization of leaf values.
+    pub fn with_l2_regularization(mut self, l2_regularization: f64) -> Self {
+        self.l2_regularization = l2_regularization;
+        self
+    }
+    /// The maximum number of bins of non-missing values of every feature, at most 255.
+    pub fn with_max_bins(mut self, max_bins: usize) -> Self {
+        self.max_bins = max_bins;
+        self
+    }
+    /// Indices of categorical features.
+    pub fn with_categorical_features(mut self, categorical_features: Vec<usize>) -> Self {
+        self.categorical_features = categorical_features;
+        self
+    }
+    /// Fraction of the training samples held out for early stopping.
+    pub fn with_validation_fraction(mut self, validation_fraction: f64) -> Self {
+        self.validation_fraction = validation_fraction;
+        self
+    }
+    /// Stop when the validation loss has not improved for this number of stages.
+    pub fn with_n_iter_no_change(mut self, n_iter_no_change: usize) -> Self {
+        self.n_iter_no_change = Some(n_iter_no_change);
+        self
+    }
+    /// The minimum improvement of the validation loss for early stopping.
+    pub fn with_tol(mut self, tol: f64) -> Self {
+        self.tol = tol;
+        self
+    }
+    /// Seed used for the validation split.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+}
+
+impl Default for HistGradientBoostingClassifierParameters {
+    fn default() -> Self {
+        HistGradientBoostingClassifierParameters {
+            learning_rate: 0.1,
+            n_trees: 100,
+            max_leaf_nodes: Some(31),
+            max_depth: Option::None,
+            min_samples_leaf: 20,
+            l2_regularization: 0f64,
+            max_bins: 255,
+            categorical_features: Vec::new(),
+            validation_fraction: 0.1,
+            n_iter_no_change: Option::None,
+            tol: 1e-7,
+            seed: Option::None,
+        }
+    }
+}
+
+impl<TX: Number + FloatNumber + PartialOrd, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>>
+    PartialEq for HistGradientBoostingClassifier<TX, TY, X, Y>
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.classes == other.classes
+            && self.init.len() == other.init.len()
+            && self
+                .init
+                .iter()
+                .zip(other.init.iter())
+                .all(|(a, b)| (a - b).abs() < f64::EPSILON)
+            && (self.learning_rate - other.learning_rate).abs() < f64::EPSILON
+            && self.trees == other.trees
+    }
+}
+
+impl<TX: Number + FloatNumber + PartialOrd, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>>
+    SupervisedEstimator<X, Y, HistGradientBoostingClassifierParameters>
+    for HistGradientBoostingClassifier<TX, TY, X, Y>
+{
+    fn new() -> Self {
+        Self {
+            classes: Vec::new(),
+            init: Vec::new(),
+            learning_rate: 0f64,
+            trees: Vec::new(),
+            _phantom_tx: PhantomData,
+            _phantom_x: PhantomData,
+            _phantom_y: PhantomData,
+        }
+    }
+
+    fn fit(
+        x: &X,
+        y: &Y,
+        parameters: HistGradientBoostingClassifierParameters,
+    ) -> Result<Self, Failed> {
+        HistGradientBoostingClassifier::fit(x, y, parameters)
+    }
+}
+
+impl<TX: Number + FloatNumber + PartialOrd, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>>
+    Predictor<X, Y> for HistGradientBoostingClassifier<TX, TY, X, Y>
+{
+    fn predict(&self, x: &X) -> Result<Y, Failed> {
+        self.predict(x)
+    }
+}
+
+impl<TX: Number + FloatNumber + PartialOrd, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>>
+    HistGradientBoostingClassifier<TX, TY, X, Y>
+{
+    /// Build a histogram-based gradient boosting classifier from the training data.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - the target class values
+    pub fn fit(
+        x: &X,
+        y: &Y,
+        parameters: HistGradientBoostingClassifierParameters,
+    ) -> Result<HistGradientBoostingClassifier<TX, TY, X, Y>, Failed> {
+        let (x_nrows, _) = x.shape();
+        if x_nrows != y.shape() {
+            return Err(Failed::fit("Size of x should equal size of y"));
+        }
+
+        let classes = y.unique();
+        let k = classes.len();
+        if k < 2 {
+            return Err(Failed::fit(&format!(
+                "Incorrect number of classes: {k}. Should be >= 2."
+            )));
+        }
+        let yi: Vec<f64> = y
+            .iterator(0)
+            .map(|y_i| classes.iter().position(|c| y_i == c).unwrap() as f64)
+            .collect();
+
+        let boosting = HistBoosting {
+            loss: if k == 2 {
+                Loss::BinomialDeviance
+            } else {
+                Loss::MultinomialDeviance(k)
+            },
+            learning_rate: parameters.learning_rate,
+            n_trees: parameters.n_trees,
+            max_leaf_nodes: parameters.max_leaf_nodes,
+            max_depth: parameters.max_depth,
+            min_samples_leaf: parameters.min_samples_leaf,
+            l2_regularization: parameters.l2_regularization,
+            max_bins: parameters.max_bins,
+            categorical_features: parameters.categorical_features,
+            validation_fraction: parameters.validation_fraction,
+            n_iter_no_change: parameters.n_iter_no_change,
+            tol: parameters.tol,
+            seed: parameters.seed,
+        };
+        let (init, trees) = boosting.fit(x, &yi)?;
+
+        Ok(HistGradientBoostingClassifier {
+            classes,
+            init,
+            learning_rate: parameters.learning_rate,
+            trees,
+            _phantom_tx: PhantomData,
+            _phantom_x: PhantomData,
+            _phantom_y: PhantomData,
+        })
+    }
+
+    /// Predict class value for `x`.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict(&self, x: &X) -> Result<Y, Failed> {
+        Ok(self.to_y(&self.staged_raw(x).finish()))
+    }
+
+    /// Predict class probabilities for `x`.
+    /// Returns a _KxC_ matrix, columns are ordered like the sorted class labels.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict_proba(&self, x: &X) -> Result<X, Failed> {
+        Ok(self.to_proba(&self.staged_raw(x).finish()))
+    }
+
+    /// Predicted classes for `x` of the model after every boosting stage.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn staged_predict<'a>(&'a self, x: &'a X) -> impl Iterator<Item = Y> + 'a {
+        self.staged_raw(x).map(move |raw| self.to_y(&raw))
+    }
+
+    /// Predicted class probabilities for `x` of the model after every boosting stage.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn staged_predict_proba<'a>(&'a self, x: &'a X) -> impl Iterator<Item = X> + 'a {
+        self.staged_raw(x).map(move |raw| self.to_proba(&raw))
+    }
+
+    /// The number of boosting stages, smaller than `n_trees` when boosting stopped early.
+    /// With more than two classes every stage fits one tree per class.
+    pub fn n_trees(&self) -> usize {
+        self.trees.len() / self.init.len()
+    }
+
+    /// Sorted class labels, in the order of the columns returned by `predict_proba`.
+    pub fn classes(&self) -> &Vec<TY> {
+        &self.classes
+    }
+
+    fn staged_raw<'a>(&'a self, x: &'a X) -> StagedRaw<'a, TX, X, HistTree> {
+        StagedRaw::new(x, &self.init, self.learning_rate, &self.trees)
+    }
+
+    fn row_proba(raw: &[Vec<f64>], i: usize) -> Vec<f64> {
+        if raw.len() == 1 {
+            let p = sigmoid(raw[0][i]);
+            vec![1f64 - p, p]
+        } else {
+            softmax(&raw_row(raw, i))
+        }
+    }
+
+    fn to_proba(&self, raw: &[Vec<f64>]) -> X {
+        let n = raw[0].len();
+        let mut proba = X::zeros(n, self.classes.len());
+        for i in 0..n {
+            for (c, p) in Self::row_proba(raw, i).into_iter().enumerate() {
+                proba.set((i, c), TX::from_f64(p).unwrap());
+            }
+        }
+        proba
+    }
+
+    fn to_y(&self, raw: &[Vec<f64>]) -> Y {
+        let n = raw[0].len();
+        Y::from_iterator(
+            (0..n).map(|i| {
+                let proba = Self::row_proba(raw, i);
+                let class = (1..proba.len()).fold(
+                    0,
+                    |best, c| {
+                        if proba[c] > proba[best] {
+                            c
+                        } else {
+                            best
+                        }
+                    },
+                );
+                self.classes[class]
+            }),
+            n,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ensemble::testing::assert_fits_classes;
+    use crate::linalg::basic::arrays::MutArray;
+    use crate::linalg::basic::matrix::DenseMatrix;
+    use crate::test_datasets::iris;
+
+    /// The shared iris data with a few missing values.
+    fn iris_with_missing_values() -> (DenseMatrix<f64>, Vec<u32>) {
+        let (mut x, y) = iris();
+        for (i, j) in [(2, 2), (4, 3), (8, 2), (15, 3)] {
+            x.set((i, j), f64::NAN);
+        }
+        (x, y)
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn fit_predict_with_missing_values() {
+        let (x, y) = iris_with_missing_values();
+
+        assert_fits_classes(
+            &x,
+            &y,
+            |y| {
+                HistGradientBoostingClassifier::fit(
+                    &x,
+                    y,
+                    HistGradientBoostingClassifierParameters::default()
+                        .with_min_samples_leaf(2)
+                        .with_n_trees(30),
+                )
+                .unwrap()
+            },
+            |classifier| classifier.predict_proba(&x).unwrap(),
+        );
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn staged_predict() {
+        let (x, y) = iris_with_missing_values();
+
+        let classifier = HistGradientBoostingClassifier::fit(
+            &x,
+            &y,
+            HistGradientBoostingClassifierParameters::default()
+                .with_min_samples_leaf(2)
+                .with_n_trees(30),
+        )
+        .unwrap();
+
+        assert_eq!(classifier.n_trees(), 30);
+        assert_eq!(classifier.classes(), &vec![0, 1, 2]);
+
+        let staged: Vec<Vec<u32>> = classifier.staged_predict(&x).collect();
+        assert_eq!(staged.len(), 30);
+        assert_eq!(staged[29], classifier.predict(&x).unwrap());
+        let staged_proba: Vec<DenseMatrix<f64>> = classifier.staged_predict_proba(&x).collect();
+        assert_eq!(staged_proba[29], classifier.predict_proba(&x).unwrap());
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde() {
+        let (x, y) = iris_with_missing_values();
+
+        let classifier = HistGradientBoostingClassifier::fit(
+            &x,
+            &y,
+            HistGradientBoostingClassifierParameters::default()
+                .with_min_samples_leaf(2)
+                .with_n_trees(5),
+        )
+        .unwrap();
+
+        crate::ensemble::testing::assert_json_round_trip(&classifier, |classifier| {
+            classifier.predict_proba(&x).unwrap()
+        });
+    }
+}
@@ -0,0 +1,561 @@
+//! # Histogram-Based Gradient Boosting Regressor
+//! Histogram-based gradient boosting regressor fits a sequence of regression trees grown leaf-wise from binned features.
+//! It supports the same losses as the [gradient boosting regressor](../../gradient_boosting/gradient_boosting_regressor/index.html),
+//! handles missing values natively and splits categorical features into sets of categories.
+//! See [histogram-based gradient boosting](../index.html) for more details.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::basic::matrix::DenseMatrix;
+//! use smartcore::ensemble::hist_gradient_boosting::hist_gradient_boosting_regressor::*;
+//!
+//! // Longley dataset (https://www.statsmodels.org/stable/datasets/generated/longley.html)
+//! let x = DenseMatrix::from_2d_array(&[
+//!             &[234.289, 235.6, 159., 107.608, 1947., 60.323],
+//!             &[259.426, 232.5, 145.6, 108.632, 1948., 61.122],
+//!             &[258.054, 368.2, 161.6, 109.773, 1949., 60.171],
+//!             &[284.599, 335.1, 165., 110.929, 1950., 61.187],
+//!             &[328.975, 209.9, 309.9, 112.075, 1951., 63.221],
+//!             &[346.999, 193.2, 359.4, 113.27, 1952., 63.639],
+//!             &[365.385, 187., 354.7, 115.094, 1953., 64.989],
+//!             &[363.112, 357.8, 335., 116.219, 1954., 63.761],
+//!             &[397.469, 290.4, 304.8, 117.388, 1955., 66.019],
+//!             &[419.18, 282.2, 285.7, 118.734, 1956., 67.857],
+//!             &[442.769, 293.6, 279.8, 120.445, 1957., 68.169],
+//!             &[444.546, 468.1, 263.7, 121.95, 1958., 66.513],
+//!             &[482.704, 381.3, 255.2, 123.366, 1959., 68.655],
+//!             &[502.601, 393.1, 251.4, 125.368, 1960., 69.564],
+//!             &[518.173, 480.6, 257.2, 127.852, 1961., 69.331],
+//!             &[554.894, 400.7, 282.7, 130.081, 1962., 70.551],
+//!         ]).unwrap();
+//! let y = vec![
+//!             83.0, 88.5, 88.2, 89.5, 96.2, 98.1, 99.0, 100.0, 101.2,
+//!             104.6, 108.4, 110.8, 112.6, 114.2, 115.7, 116.9,
+//!         ];
+//!
+//! let regressor = HistGradientBoostingRegressor::fit(
+//!     &x,
+//!     &y,
+//!     HistGradientBoostingRegressorParameters::default()
+//!         .with_min_samples_leaf(2)
+//!         .with_n_trees(50),
+//! ).unwrap();
+//!
+//! let y_hat = regressor.predict(&x).unwrap(); // use the same data for prediction
+//! ```
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use std::default::Default;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::api::{Predictor, SupervisedEstimator};
+use crate::ensemble::gradient_boosting::gradient_boosting_regressor::GradientBoostingRegressorLoss;
+use crate::ensemble::gradient_boosting::StagedRaw;
+use crate::ensemble::hist_gradient_boosting::grower::HistTree;
+use crate::ensemble::hist_gradient_boosting::HistBoosting;
+use crate::error::Failed;
+use crate::linalg::basic::arrays::{Array1, Array2};
+use crate::numbers::basenum::Number;
+
+/// Parameters of the histogram-based gradient boosting regressor.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct HistGradientBoostingRegressorParameters {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Loss function to minimize.
+    pub loss: GradientBoostingRegressorLoss,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Quantile of the Huber and quantile losses.
+    pub alpha: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Shrinks the contribution of every tree.
+    pub learning_rate: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The number of boosting stages.
+    pub n_trees: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The maximum number of leaves of every tree.
+    pub max_leaf_nodes: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The maximum number of splits from the root of every tree to a leaf. No limit by default.
+    pub max_depth: Option<u16>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The minimum number of samples required to be at a leaf node.
+    pub min_samples_leaf: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// L2 regularization of leaf values.
+    pub l2_regularization: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The maximum number of bins of non-missing values of every feature, at most 255.
+    pub max_bins: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Indices of categorical features. Categories can be any values, a feature can have at most `max_bins` categories.
+    pub categorical_features: Vec<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Fraction of the training samples held out for early stopping.
+    pub validation_fraction: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Stop when the validation loss has not improved for this number of stages. No early stopping by default.
+    pub n_iter_no_change: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The minimum improvement of the validation loss for early stopping.
+    pub tol: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Seed used for the validation split.
+    pub seed: Option<u64>,
+}
+
+/// Histogram-Based Gradient Boosting Regressor
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+pub struct HistGradientBoostingRegressor<TX: Number, TY: Number, X: Array2<TX>, Y: Array1<TY>> {
+    init: f64,
+    learning_rate: f64,
+    trees: Vec<HistTree>,
+    _phantom_tx: PhantomData<TX>,
+    _phantom_ty: PhantomData<TY>,
+    _phantom_x: PhantomData<X>,
+    _phantom_y: PhantomData<Y>,
+}
+
+impl HistGradientBoostingRegressorParameters {
+    /// Loss function to minimize.
+    pub fn with_loss(mut self, loss: GradientBoostingRegressorLoss) -> Self {
+        self.loss = loss;
+        self
+    }
+    /// Quantile of the Huber and quantile losses.
+    pub fn with_alpha(mut self, alpha: f64) -> Self {
+        self.alpha = alpha;
+        self
+    }
+    /// Shrinks the contribution of every tree.
+    pub fn with_learning_rate(mut self, learning_rate: f64) -> Self {
+        self.learning_rate = learning_rate;
+        self
+    }
+    /// The number of boosting stages.
+    pub fn with_n_trees(mut self, n_trees: usize) -> Self {
+        self.n_trees = n_trees;
+        self
+    }
+    /// The maximum number of leaves of every tree.
+    pub fn with_max_leaf_nodes(mut self, max_leaf_nodes: usize) -> Self {
+        self.max_leaf_nodes = Some(max_leaf_nodes);
+        self
+    }
+    /// The maximum number of splits from the root of every tree to a leaf.
+    pub fn with_max_depth(mut self, max_depth: u16) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+    /// The minimum number of samples required to be at a leaf node.
+    pub fn with_min_samples_leaf(mut self, min_samples_leaf: usize) -> Self {
+        self.min_samples_leaf = min_samples_leaf;
+        self
+    }
+    /// L2 regularization of leaf values.
+    pub fn with_l2_regularization(mut self, l2_regularization: f64) -> Self {
+        self.l2_regularization = l2_regularization;
+        self
+    }
+    /// The maximum number of bins of non-missing values of every feature, at most 255.
+    pub fn with_max_bins(mut self, max_bins: usize) -> Self {
+        self.max_bins = max_bins;
+        self
+    }
+    /// Indices of categorical features.
+    pub fn with_categorical_features(mut self, categorical_features: Vec<usize>) -> Self {
+        self.categorical_features = categorical_features;
+        self
+    }
+    /// Fraction of the training samples held out for early stopping.
+    pub fn with_validation_fraction(mut self, validation_fraction: f64) -> Self {
+        self.validation_fraction = validation_fraction;
+        self
+    }
+    /// Stop when the validation loss has not improved for this number of stages.
+    pub fn with_n_iter_no_change(mut self, n_iter_no_change: usize) -> Self {
+        self.n_iter_no_change = Some(n_iter_no_change);
+        self
+    }
+    /// The minimum improvement of the validation loss for early stopping.
+    pub fn with_tol(mut self, tol: f64) -> Self {
+        self.tol = tol;
+        self
+    }
+    /// Seed used for the validation split.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+}
+
+impl Default for HistGradientBoostingRegressorParameters {
+    fn default() -> Self {
+        HistGradientBoostingRegressorParameters {
+            loss: GradientBoostingRegressorLoss::default(),
+            alpha: 0.9,
+            learning_rate: 0.1,
+            n_trees: 100,
+            max_leaf_nodes: Some(31),
+            max_depth: Option::None,
+            min_samples_leaf: 20,
+            l2_regularization: 0f64,
+            max_bins: 255,
+            categorical_features: Vec::new(),
+            validation_fraction: 0.1,
+            n_iter_no_change: Option::None,
+            tol: 1e-7,
+            seed: Option::None,
+        }
+    }
+}
+
+impl<TX: Number, TY: Number, X: Array2<TX>, Y: Array1<TY>> PartialEq
+    for HistGradientBoostingRegressor<TX, TY, X, Y>
+{
+    fn eq(&self, other: &Self) -> bool {
+        (self.init - other.init).abs() < f64::EPSILON
+            && (self.learning_rate - other.learning_rate).abs() < f64::EPSILON
+            && self.trees == other.trees
+    }
+}
+
+impl<TX: Number, TY: Number, X: Array2<TX>, Y: Array1<TY>>
+    SupervisedEstimator<X, Y, HistGradientBoostingRegressorParameters>
+    for HistGradientBoostingRegressor<TX, TY, X, Y>
+{
+    fn new() -> Self {
+        Self {
+            init: 0f64,
+            learning_rate: 0f64,
+            trees: Vec::new(),
+            _phantom_tx: PhantomData,
+            _phantom_ty: PhantomData,
+            _phantom_x: PhantomData,
+            _phantom_y: PhantomData,
+        }
+    }
+
+    fn fit(
+        x: &X,
+        y: &Y,
+        parameters: HistGradientBoostingRegressorParameters,
+    ) -> Result<Self, Failed> {
+        HistGradientBoostingRegressor::fit(x, y, parameters)
+    }
+}
+
+impl<TX: Number, TY: Number, X: Array2<TX>, Y: Array1<TY>> Predictor<X, Y>
+    for HistGradientBoostingRegressor<TX, TY, X, Y>
+{
+    fn predict(&self, x: &X) -> Result<Y, Failed> {
+        self.predict(x)
+    }
+}
+
+impl<TX: Number, TY: Number, X: Array2<TX>, Y: Array1<TY>>
+    HistGradientBoostingRegressor<TX, TY, X, Y>
+{
+    /// Build a histogram-based gradient boosting regressor from the training data.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation, missing values are `NaN`.
+    /// * `y` - the target values
+    pub fn fit(
+        x: &X,
+        y: &Y,
+        parameters: HistGradientBoostingRegressorParameters,
+    ) -> Result<HistGradientBoostingRegressor<TX, TY, X, Y>, Failed> {
+        let (x_nrows, _) = x.shape();
+        if x_nrows != y.shape() {
+            return Err(Failed::fit("Size of x should equal size of y"));
+        }
+
+        let boosting = HistBoosting {
+            loss: parameters.loss.to_loss(parameters.alpha),
+            learning_rate: parameters.learning_rate,
+            n_trees: parameters.n_trees,
+            max_leaf_nodes: parameters.max_leaf_nodes,
+            max_depth: parameters.max_depth,
+            min_samples_leaf: parameters.min_samples_leaf,
+            l2_regularization: parameters.l2_regularization,
+            max_bins: parameters.max_bins,
+            categorical_features: parameters.categorical_features,
+            validation_fraction: parameters.validation_fraction,
+            n_iter_no_change: parameters.n_iter_no_change,
+            tol: parameters.tol,
+            seed: parameters.seed,
+        };
+
+        let y: Vec<f64> = y.iterator(0).map(|y_i| y_i.to_f64().unwrap()).collect();
+        let (init, trees) = boosting.fit(x, &y)?;
+
+        Ok(HistGradientBoostingRegressor {
+            init: init[0],
+            learning_rate: parameters.learning_rate,
+            trees,
+            _phantom_tx: PhantomData,
+            _phantom_ty: PhantomData,
+            _phantom_x: PhantomData,
+            _phantom_y: PhantomData,
+        })
+    }
+
+    /// Predict regression value for `x`.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict(&self, x: &X) -> Result<Y, Failed> {
+        Ok(self.to_y(&self.staged_raw(x).finish()[0]))
+    }
+
+    /// Predictions for `x` of the model after every boosting stage, i.e. with the first 1, 2, ... trees.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn staged_predict<'a>(&'a self, x: &'a X) -> impl Iterator<Item = Y> + 'a {
+        self.staged_raw(x).map(move |raw| self.to_y(&raw[0]))
+    }
+
+    /// The number of fitted trees, smaller than `n_trees` when boosting stopped early.
+    pub fn n_trees(&self) -> usize {
+        self.trees.len()
+    }
+
+    fn staged_raw<'a>(&'a self, x: &'a X) -> StagedRaw<'a, TX, X, HistTree> {
+        StagedRaw::new(x, &[self.init], self.learning_rate, &self.trees)
+    }
+
+    fn to_y(&self, raw: &[f64]) -> Y {
+        Y::from_iterator(
+            raw.iter().map(|&raw_i| TY::from_f64(raw_i).unwrap()),
+            raw.len(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::basic::matrix::DenseMatrix;
+    use crate::metrics::{mean_absolute_error, mean_squared_error};
+    use crate::test_datasets::longley;
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn fit_longley() {
+        let (x, y) = longley();
+
+        for loss in [
+            GradientBoostingRegressorLoss::SquaredError,
+            GradientBoostingRegressorLoss::AbsoluteError,
+            GradientBoostingRegressorLoss::Huber,
+        ] {
+            let y_hat = HistGradientBoostingRegressor::fit(
+                &x,
+                &y,
+                HistGradientBoostingRegressorParameters::default()
+                    .with_loss(loss)
+                    .with_min_samples_leaf(1),
+            )
+            .and_then(|gb| gb.predict(&x))
+            .unwrap();
+
+            assert!(mean_absolute_error(&y, &y_hat) < 1.0);
+        }
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn fit_binned_features() {
+        // more distinct values than bins
+        let n = 2000;
+        let x: Vec<Vec<f64>> = (0..n)
+            .map(|i| {
+                vec![
+                    ((i * 7919) % 1000) as f64 / 100.,
+                    ((i * 104729) % 997) as f64 / 997.,
+                ]
+            })
+            .collect();
+        let y: Vec<f64> = x.iter().map(|x_i| x_i[0].sin() + 2. * x_i[1]).collect();
+        let x = DenseMatrix::from_2d_vec(&x).unwrap();
+
+        let regressor = HistGradientBoostingRegressor::fit(
+            &x,
+            &y,
+            HistGradientBoostingRegressorParameters::default().with_max_bins(32),
+        )
+        .unwrap();
+
+        assert!(mean_absolute_error(&y, &regressor.predict(&x).unwrap()) < 0.1);
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn missing_values() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[1., 0.],
+            &[f64::NAN, 1.],
+            &[2., 0.],
+            &[3., f64::NAN],
+            &[f64::NAN, 0.],
+            &[4., 1.],
+            &[5., 0.],
+            &[f64::NAN, f64::NAN],
+        ])
+        .unwrap();
+        let y: Vec<f64> = vec![1., 10., 2., 3., 10., 4., 5., 10.];
+
+        let regressor = HistGradientBoostingRegressor::fit(
+            &x,
+            &y,
+            HistGradientBoostingRegressorParameters::default()
+                .with_min_samples_leaf(1)
+                .with_learning_rate(1.)
+                .with_n_trees(10),
+        )
+        .unwrap();
+
+        let y_hat = regressor.predict(&x).unwrap();
+        assert!(mean_absolute_error(&y, &y_hat) < 1e-6);
+
+        let x_test = DenseMatrix::from_2d_array(&[&[f64::NAN, 0.5], &[f64::NAN, 3.]]).unwrap();
+        for y_hat_i in regressor.predict(&x_test).unwrap() {
+            assert!((y_hat_i - 10.).abs() < 1e-6);
+        }
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn categorical_features() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[0.],
+            &[1.],
+            &[2.],
+            &[3.],
+            &[4.],
+            &[5.],
+            &[0.],
+            &[1.],
+            &[2.],
+            &[3.],
+            &[4.],
+            &[5.],
+        ])
+        .unwrap();
+        let y = vec![1., 5., 1., 5., 1., 5., 1., 5., 1., 5., 1., 5.];
+
+        // a single split of a tree with two leaves
+        let parameters = HistGradientBoostingRegressorParameters::default()
+            .with_min_samples_leaf(1)
+            .with_learning_rate(1.)
+            .with_n_trees(1)
+            .with_max_leaf_nodes(2);
+
+        let numerical = HistGradientBoostingRegressor::fit(&x, &y, parameters.clone())
+            .and_then(|gb| gb.predict(&x))
+            .unwrap();
+        let categorical = HistGradientBoostingRegressor::fit(
+            &x,
+            &y,
+            parameters.with_categorical_features(vec![0]),
+        )
+        .and_then(|gb| gb.predict(&x))
+        .unwrap();
+
+        assert!(mean_squared_error(&y, &numerical) > 1.);
+        assert!(mean_squared_error(&y, &categorical) < 1e-8);
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn staged_predict_and_early_stopping() {
+        let (x, y) = longley();
+
+        let regressor = HistGradientBoostingRegressor::fit(
+            &x,
+            &y,
+            HistGradientBoostingRegressorParameters::default()
+                .with_min_samples_leaf(1)
+                .with_n_trees(30),
+        )
+        .unwrap();
+        let staged: Vec<Vec<f64>> = regressor.staged_predict(&x).collect();
+        assert_eq!(staged.len(), 30);
+        assert_eq!(staged[29], regressor.predict(&x).unwrap());
+
+        let regressor = HistGradientBoostingRegressor::fit(
+            &x,
+            &y,
+            HistGradientBoostingRegressorParameters::default()
+                .with_min_samples_leaf(1)
+                .with_n_trees(500)
+                .with_learning_rate(0.5)
+                .with_validation_fraction(0.25)
+                .with_n_iter_no_change(3)
+                .with_seed(42),
+        )
+        .unwrap();
+        assert!(regressor.n_trees() < 500);
+
+        assert!(HistGradientBoostingRegressor::fit(
+            &x,
+            &y,
+            HistGradientBoostingRegressorParameters::default().with_max_bins(256)
+        )
+        .is_err());
+        assert!(HistGradientBoostingRegressor::fit(
+            &x,
+            &y,
+            HistGradientBoostingRegressorParameters::default().with_learning_rate(f64::NAN)
+        )
+        .is_err());
+        assert!(HistGradientBoostingRegressor::fit(
+            &x,
+            &y,
+            HistGradientBoostingRegressorParameters::default().with_l2_regularization(f64::NAN)
+        )
+        .is_err());
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde() {
+        let (x, y) = longley();
+
+        let regressor = HistGradientBoostingRegressor::fit(
+            &x,
+            &y,
+            HistGradientBoostingRegressorParameters::default()
+                .with_min_samples_leaf(1)
+                .with_n_trees(10),
+        )
+        .unwrap();
+
+        crate::ensemble::testing::assert_json_round_trip(&regressor, |regressor| {
+            regressor.predict(&x).unwrap()
+        });
+    }
+}
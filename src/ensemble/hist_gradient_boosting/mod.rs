@@ -0,0 +1,183 @@
+//! # Histogram-Based Gradient Boosting
+//!
+//! Histogram-based [gradient boosting](../gradient_boosting/index.html) fits the same additive model \\(F_M(x) = F_0(x) + \nu\sum_{m=1}^M h_m(x)\\),
+//! but grows its trees from binned features, which makes it much faster than exact boosting on large datasets.
+//!
+//! * Before training, every numerical feature is discretized into at most `max_bins` (255) bins at quantiles of its values,
+//!   every category of a categorical feature is put into a bin of its own. Splits are only searched at bin boundaries.
+//! * Every tree is fitted to the gradients \\(g_i\\) and hessians \\(h_i\\) of the loss. The histogram of a node sums gradients and hessians by bin
+//!   of every feature, the best split of a feature is found with a single pass over its bins, and only the histogram of the smaller child
+//!   of a split is computed from its samples: the histogram of its sibling is the difference between the histograms of the parent and the smaller child.
+//! * Trees are grown leaf-wise: the leaf whose best split reduces the loss the most is split next, until the tree has `max_leaf_nodes` leaves.
+//!   The gain of a split into children \\(L\\) and \\(R\\) of a node \\(P\\) is
+//!   \\[\frac{G_L^2}{H_L + \lambda} + \frac{G_R^2}{H_R + \lambda} - \frac{G_P^2}{H_P + \lambda}\\]
+//!   where \\(G\\) and \\(H\\) are the sums of gradients and hessians of the samples in a node and \\(\lambda\\) is the `l2_regularization`.
+//!   The value of a leaf is \\(-G / (H + \lambda)\\).
+//! * Missing values, encoded as `NaN`, are put into a bin of their own. Every split learns whether missing values go to the left or to the right child,
+//!   and a split can separate missing values from all other values. When no training sample at a node has a missing value,
+//!   missing values at prediction time follow the child with more training samples.
+//! * Features listed in `categorical_features` are split into two sets of categories. The categories at a node are sorted by
+//!   the ratio of their gradients to their hessians, and splits between consecutive categories of this order are considered.
+//!   Categories not seen in training go to the right child.
+//!
+//! Early stopping and `staged_predict` work like in [gradient boosting](../gradient_boosting/index.html).
+//!
+//! ## References:
+//!
+//! * ["LightGBM: A Highly Efficient Gradient Boosting Decision Tree", Ke G. et al., Advances in Neural Information Processing Systems 30, 2017](https://papers.nips.cc/paper/2017/hash/6449f44a102fde848669bdd9eb6b76fa-Abstract.html)
+//! * ["XGBoost: A Scalable Tree Boosting System", Chen T., Guestrin C., Proceedings of the 22nd ACM SIGKDD, 2016](https://doi.org/10.1145/2939672.2939785)
+//! * ["On Grouping for Maximum Homogeneity", Fisher W. D., Journal of the American Statistical Association, 1958](https://doi.org/10.1080/01621459.1958.10501479)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+
+mod binning;
+mod grower;
+/// Histogram-based gradient boosting classifier
+pub mod hist_gradient_boosting_classifier;
+/// Histogram-based gradient boosting regressor
+pub mod hist_gradient_boosting_regressor;
+
+use crate::ensemble::gradient_boosting::loss::Loss;
+use crate::ensemble::gradient_boosting::{validation_split, EarlyStopping, StageTree};
+use crate::error::Failed;
+use crate::linalg::basic::arrays::Array2;
+use crate::numbers::basenum::Number;
+use crate::rand_custom::get_rng_impl;
+
+use binning::BinMapper;
+use grower::{HistTree, TreeGrower};
+
+/// Settings of histogram-based boosting shared by the classifier and the regressor.
+pub(crate) struct HistBoosting {
+    pub(crate) loss: Loss,
+    pub(crate) learning_rate: f64,
+    pub(crate) n_trees: usize,
+    pub(crate) max_leaf_nodes: Option<usize>,
+    pub(crate) max_depth: Option<u16>,
+    pub(crate) min_samples_leaf: usize,
+    pub(crate) l2_regularization: f64,
+    pub(crate) max_bins: usize,
+    pub(crate) categorical_features: Vec<usize>,
+    pub(crate) validation_fraction: f64,
+    pub(crate) n_iter_no_change: Option<usize>,
+    pub(crate) tol: f64,
+    pub(crate) seed: Option<u64>,
+}
+
+/// Initial raw predictions and the fitted trees, stage by stage with one tree per raw prediction column.
+pub(crate) type HistBoostedModel = (Vec<f64>, Vec<HistTree>);
+
+impl HistBoosting {
+    fn validate(&self) -> Result<(), Failed> {
+        if !(self.learning_rate.is_finite() && self.learning_rate > 0f64) {
+            return Err(Failed::fit("learning_rate should be positive and finite"));
+        }
+        if self.n_trees == 0 {
+            return Err(Failed::fit("n_trees should be positive"));
+        }
+        if self
+            .max_leaf_nodes
+            .is_some_and(|max_leaf_nodes| max_leaf_nodes < 2)
+        {
+            return Err(Failed::fit("max_leaf_nodes should be at least 2"));
+        }
+        if self.min_samples_leaf == 0 {
+            return Err(Failed::fit("min_samples_leaf should be positive"));
+        }
+        if !(self.l2_regularization.is_finite() && self.l2_regularization >= 0f64) {
+            return Err(Failed::fit(
+                "l2_regularization should be non-negative and finite",
+            ));
+        }
+        if self.n_iter_no_change.is_some()
+            && !(self.validation_fraction > 0f64 && self.validation_fraction < 1f64)
+        {
+            return Err(Failed::fit("validation_fraction should be in (0, 1)"));
+        }
+        if let Loss::Huber(alpha) | Loss::Quantile(alpha) = self.loss {
+            if !(alpha > 0f64 && alpha < 1f64) {
+                return Err(Failed::fit("alpha should be in (0, 1)"));
+            }
+        }
+        Ok(())
+    }
+
+    /// Fit the boosted trees to targets `y`, class indices for classification losses.
+    pub(crate) fn fit<TX: Number, X: Array2<TX>>(
+        &self,
+        x: &X,
+        y: &[f64],
+    ) -> Result<HistBoostedModel, Failed> {
+        self.validate()?;
+
+        let (n, _) = x.shape();
+        let mut rng = get_rng_impl(self.seed);
+        let (rows, validation_rows) = if self.n_iter_no_change.is_some() {
+            validation_split(n, self.validation_fraction, &mut rng)?
+        } else {
+            ((0..n).collect(), Vec::new())
+        };
+
+        let bin_mapper = BinMapper::fit(x, &rows, self.max_bins, &self.categorical_features)?;
+        let binned = bin_mapper.transform(x);
+
+        let init = self.loss.init_estimate(y, &rows);
+        let mut raw: Vec<Vec<f64>> = init.iter().map(|&init_k| vec![init_k; n]).collect();
+        let mut stages = Vec::new();
+        let mut early_stopping = EarlyStopping::new(self.n_iter_no_change, self.tol);
+
+        for _ in 0..self.n_trees {
+            let delta = self.loss.delta(y, &raw, &rows);
+            let mut trees = Vec::with_capacity(raw.len());
+            let mut updates = Vec::with_capacity(raw.len());
+            for k in 0..self.loss.n_outputs() {
+                let residual = self.loss.negative_gradient(y, &raw, k, delta);
+                let gradients: Vec<f64> = residual.iter().map(|r| -r).collect();
+                let hessians = self.loss.hessian(&raw, k);
+                let grower = TreeGrower {
+                    binned: &binned,
+                    bin_mapper: &bin_mapper,
+                    gradients: &gradients,
+                    hessians: &hessians,
+                    max_leaf_nodes: self.max_leaf_nodes,
+                    max_depth: self.max_depth,
+                    min_samples_leaf: self.min_samples_leaf,
+                    l2_regularization: self.l2_regularization,
+                };
+                let (mut tree, leaves) = grower.grow(rows.clone());
+
+                let mut update = vec![0f64; n];
+                for (leaf, leaf_rows) in leaves.iter() {
+                    if !self.loss.has_hessian() {
+                        let value = self.loss.leaf_value(y, &raw, &residual, leaf_rows, delta);
+                        tree.set_value(*leaf, value);
+                    }
+                    for &i in leaf_rows.iter() {
+                        update[i] = self.learning_rate * tree.value(*leaf);
+                    }
+                }
+                for &i in validation_rows.iter() {
+                    update[i] = self.learning_rate * tree.predict_row(x, i);
+                }
+                updates.push(update);
+                trees.push(tree);
+            }
+
+            for (raw_k, update_k) in raw.iter_mut().zip(updates.iter()) {
+                for (raw_ki, update_ki) in raw_k.iter_mut().zip(update_k.iter()) {
+                    *raw_ki += update_ki;
+                }
+            }
+            stages.extend(trees);
+
+            if early_stopping.is_enabled()
+                && early_stopping.should_stop(self.loss.loss(y, &raw, &validation_rows))
+            {
+                break;
+            }
+        }
+
+        Ok((init, stages))
+    }
+}
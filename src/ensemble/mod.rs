@@ -11,12 +11,35 @@
 //! Random forests provide an improvement over bagged trees by way of a small tweak that decorrelates the trees. As in bagging, we build a number of
 //! decision trees on bootstrapped training samples. But when building these decision trees, each time a split in a tree is considered,
 //! a random sample of _m_ predictors is chosen as split candidates from the full set of _p_ predictors.
+//! [Extra trees](extra_trees_classifier/index.html) go one step further and split every candidate predictor at a random threshold,
+//! growing every tree from the whole training set unless bootstrap sampling is requested.
+//!
+//! [Gradient boosting](gradient_boosting/index.html) takes a different approach: shallow trees are grown sequentially, every tree fitted to
+//! the errors of the ensemble built so far, and their shrunken predictions are added up. [Histogram-based gradient boosting](hist_gradient_boosting/index.html)
+//! grows the trees from binned features, which scales to much larger datasets.
 //!
 //! ## References:
 //!
 //! * ["An Introduction to Statistical Learning", James G., Witten D., Hastie T., Tibshirani R., 8.2 Bagging, Random Forests, Boosting](http://faculty.marshall.usc.edu/gareth-james/ISL/)
+//! * ["Extremely randomized trees", Geurts P., Ernst D., Wehenkel L., Machine Learning 63, 2006](https://doi.org/10.1007/s10994-006-6226-1)
 
+/// AdaBoost
+pub mod adaboost;
+/// Bagging of any estimator
+pub mod bagging;
+/// Extra trees classifier
+pub mod extra_trees_classifier;
+/// Extra trees regressor
+pub mod extra_trees_regressor;
+/// Gradient boosting
+pub mod gradient_boosting;
+/// Histogram-based gradient boosting
+pub mod hist_gradient_boosting;
 /// Random forest classifier
 pub mod random_forest_classifier;
 /// Random forest regressor
 pub mod random_forest_regressor;
+/// Checks shared by the unit tests of the ensembles.
+/// Only meant for internal usage.
+#[cfg(test)]
+pub(crate) mod testing;
@@ -41,6 +41,7 @@
 //!
 //! let classifier = RandomForestClassifier::fit(&x, &y, Default::default()).unwrap();
 //! let y_hat = classifier.predict(&x).unwrap(); // use the same data for prediction
+//! let y_proba = classifier.predict_proba(&x).unwrap(); // class probabilities averaged over the trees
 //! ```
 //!
 //! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
@@ -63,6 +64,7 @@ use crate::rand_custom::get_rng_impl;
 use crate::tree::decision_tree_classifier::{
     which_max, DecisionTreeClassifier, DecisionTreeClassifierParameters, SplitCriterion,
 };
+use crate::tree::Splitter;
 
 /// Parameters of the Random Forest algorithm.
 /// Some parameters here are passed directly into base estimator.
@@ -496,6 +498,8 @@ impl<TX: FloatNumber + PartialOrd, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY
                 max_depth: parameters.max_depth,
                 min_samples_leaf: parameters.min_samples_leaf,
                 min_samples_split: parameters.min_samples_split,
+                ccp_alpha: 0f64,
+                splitter: Splitter::Best,
                 seed: Some(parameters.seed),
             };
             let tree = DecisionTreeClassifier::fit_weak_learner(x, y, samples, mtry, params)?;
@@ -536,6 +540,31 @@ impl<TX: FloatNumber + PartialOrd, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY
         which_max(&result)
     }
 
+    /// Predict class probabilities for `x`, the average of the class probabilities predicted by the trees of the forest.
+    /// Returns a _KxC_ matrix, columns are ordered like the sorted class labels.
+    /// Note that [`predict`](#method.predict) takes a majority vote of the trees instead, so the most probable class may differ from the predicted class when the vote is close.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict_proba(&self, x: &X) -> Result<X, Failed> {
+        let (n, _) = x.shape();
+        let k = self.classes.as_ref().unwrap().len();
+        let trees = self.trees.as_ref().unwrap();
+        let mut proba = X::zeros(n, k);
+
+        for i in 0..n {
+            let mut row = vec![0f64; k];
+            for tree in trees.iter() {
+                for (c, p) in tree.predict_proba_for_row(x, i).into_iter().enumerate() {
+                    row[c] += p;
+                }
+            }
+            for (c, p) in row.into_iter().enumerate() {
+                proba.set((i, c), TX::from_f64(p / trees.len() as f64).unwrap());
+            }
+        }
+
+        Ok(proba)
+    }
+
     /// Predict OOB classes for `x`. `x` is expected to be equal to the dataset used in training.
     pub fn predict_oob(&self, x: &X) -> Result<Y, Failed> {
         let (n, _) = x.shape();
@@ -607,8 +636,10 @@ impl<TX: FloatNumber + PartialOrd, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::linalg::basic::arrays::Array;
     use crate::linalg::basic::matrix::DenseMatrix;
     use crate::metrics::*;
+    use crate::test_datasets::iris;
 
     #[test]
     fn search_parameters() {
@@ -683,6 +714,49 @@ mod tests {
         assert!(accuracy(&y, &classifier.predict(&x).unwrap()) >= 0.95);
     }
 
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn predict_proba() {
+        let (x, y) = iris();
+
+        let classifier = RandomForestClassifier::fit(
+            &x,
+            &y,
+            RandomForestClassifierParameters::default()
+                .with_n_trees(10)
+                .with_seed(87),
+        )
+        .unwrap();
+
+        let proba: DenseMatrix<f64> = classifier.predict_proba(&x).unwrap();
+        assert_eq!(proba.shape(), (18, 3));
+
+        let trees = classifier.trees.as_ref().unwrap();
+        for i in 0..18 {
+            assert!(((0..3).map(|c| proba.get((i, c))).sum::<f64>() - 1.).abs() < 1e-8);
+            for c in 0..3 {
+                let mean = trees
+                    .iter()
+                    .map(|tree| tree.predict_proba_for_row(&x, i)[c])
+                    .sum::<f64>()
+                    / trees.len() as f64;
+                assert!((proba.get((i, c)) - mean).abs() < 1e-8);
+            }
+        }
+
+        let y_hat: Vec<u32> = (0..18)
+            .map(|i| {
+                (0..3)
+                    .max_by(|&a, &b| proba.get((i, a)).total_cmp(proba.get((i, b))))
+                    .unwrap() as u32
+            })
+            .collect();
+        assert!(accuracy(&y, &y_hat) >= 0.9);
+    }
+
     #[test]
     fn test_random_matrix_with_wrong_rownum() {
         let x_rand: DenseMatrix<f64> = DenseMatrix::<f64>::rand(21, 200);
@@ -0,0 +1,55 @@
+//! Checks shared by the unit tests of the ensembles.
+use crate::api::Predictor;
+use crate::linalg::basic::arrays::Array;
+use crate::linalg::basic::matrix::DenseMatrix;
+use crate::metrics::accuracy;
+
+/// Checks that every row of `proba` is a probability distribution over `n_classes` classes.
+pub(crate) fn assert_probabilities(proba: &DenseMatrix<f64>, n_classes: usize) {
+    let (n, k) = proba.shape();
+    assert_eq!(k, n_classes);
+    for i in 0..n {
+        let row: Vec<f64> = (0..k).map(|c| *proba.get((i, c))).collect();
+        assert!(row.iter().all(|p| (0f64..=1f64).contains(p)));
+        assert!((row.iter().sum::<f64>() - 1f64).abs() < 1e-8);
+    }
+}
+
+/// Fits `fit` to class 0 against all other classes of `y` and to all classes of `y`,
+/// and checks the training accuracy and the class probabilities of both classifiers.
+pub(crate) fn assert_fits_classes<C: Predictor<DenseMatrix<f64>, Vec<u32>>>(
+    x: &DenseMatrix<f64>,
+    y: &[u32],
+    fit: impl Fn(&Vec<u32>) -> C,
+    predict_proba: impl Fn(&C) -> DenseMatrix<f64>,
+) {
+    let binary: Vec<u32> = y.iter().map(|&y_i| y_i.min(1)).collect();
+    for y in [binary, y.to_vec()] {
+        let n_classes = *y.iter().max().unwrap() as usize + 1;
+        let classifier = fit(&y);
+        assert!(accuracy(&y, &classifier.predict(x).unwrap()) >= 0.95);
+
+        let proba = predict_proba(&classifier);
+        assert_eq!(proba.shape(), (y.len(), n_classes));
+        assert_probabilities(&proba, n_classes);
+    }
+}
+
+/// Serializes `model` to JSON and back and checks that the copy computes the same `outputs`.
+/// Leaf values are not always exact after a JSON round trip, so outputs are compared with a tolerance.
+#[cfg(feature = "serde")]
+pub(crate) fn assert_json_round_trip<
+    M: serde::Serialize + serde::de::DeserializeOwned,
+    S: PartialEq + std::fmt::Debug,
+    O: Array<f64, S>,
+>(
+    model: &M,
+    outputs: impl Fn(&M) -> O,
+) {
+    let deserialized: M = serde_json::from_str(&serde_json::to_string(model).unwrap()).unwrap();
+    let (expected, actual) = (outputs(model), outputs(&deserialized));
+    assert_eq!(expected.shape(), actual.shape());
+    for (a, b) in expected.iterator(0).zip(actual.iterator(0)) {
+        assert!((a - b).abs() < 1e-8);
+    }
+}
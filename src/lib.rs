@@ -133,3 +133,7 @@ pub mod svm;
 pub mod tree;
 
 pub(crate) mod rand_custom;
+/// Small datasets shared by the unit tests.
+/// Only meant for internal usage.
+#[cfg(test)]
+pub(crate) mod test_datasets;
@@ -97,7 +97,7 @@ impl<T: Number + RealNumber, M: Array2<T>> QR<T, M> {
         Q
     }
 
-    fn solve(&self, mut b: M) -> Result<M, Failed> {
+    pub(crate) fn solve(&self, mut b: M) -> Result<M, Failed> {
         let (m, n) = self.QR.shape();
         let (b_nrows, b_ncols) = b.shape();
 
@@ -53,7 +53,7 @@ pub struct SVD<T: Number + RealNumber, M: SVDDecomposable<T>> {
     ///
     n: usize,
     ///
-    tol: T,
+    pub(crate) tol: T,
 }
 
 impl<T: Number + RealNumber, M: SVDDecomposable<T>> SVD<T, M> {
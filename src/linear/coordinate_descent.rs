@@ -0,0 +1,688 @@
+//! # Coordinate Descent
+//!
+//! Cyclic coordinate descent solver for the elastic net problem and the regularization paths built on top of it.
+//!
+//! The solver minimizes the same objective as [`ElasticNet`](../elastic_net/struct.ElasticNet.html) and [`Lasso`](../lasso/struct.Lasso.html):
+//!
+//! \\[\frac{1}{n} \vert \boldsymbol{y} - \boldsymbol{X}\beta\vert^2 + \alpha l_{1r} \vert \beta \vert_1 + \alpha (1 - l_{1r}) \vert \beta \vert^2\\]
+//!
+//! updating one coefficient at a time with a closed-form soft-thresholding step while keeping the residual up to date.
+//! Because every update is cheap and the solution changes smoothly with \\(\alpha\\), coordinate descent is the method of choice
+//! for fitting a whole grid of penalties: [`enet_path`](fn.enet_path.html) and [`lasso_path`](fn.lasso_path.html) walk a decreasing grid of
+//! \\(\alpha\\) values and warm-start every fit from the solution of the previous one.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::basic::arrays::Array;
+//! use smartcore::linalg::basic::matrix::DenseMatrix;
+//! use smartcore::linear::coordinate_descent::*;
+//!
+//! let x = DenseMatrix::from_2d_array(&[
+//!               &[0.0, 1931.0, 1.2232755825400514],
+//!               &[1.0, 1933.0, 1.1379726120972395],
+//!               &[2.0, 1920.0, 1.4366265120543429],
+//!               &[3.0, 1918.0, 1.206005737827858],
+//!               &[4.0, 1934.0, 1.436613542400669],
+//!               &[5.0, 1918.0, 1.1594588621640636],
+//!               &[6.0, 1933.0, 1.19809994745985],
+//!               &[7.0, 1918.0, 1.3396363871645678],
+//!               &[8.0, 1931.0, 1.2535342096493207],
+//!               &[9.0, 1933.0, 1.3101281563456293],
+//!          ]).unwrap();
+//! let y: Vec<f64> = vec![1.48, 2.72, 4.52, 5.72, 5.25, 4.07, 3.75, 4.75, 6.77, 4.72];
+//!
+//! let path = lasso_path(&x, &y, RegularizationPathParameters::default().with_n_alphas(20)).unwrap();
+//!
+//! assert_eq!(path.alphas().len(), 20);
+//! assert_eq!(path.coefficients().shape(), (3, 20));
+//! ```
+//!
+//! ## References:
+//!
+//! * ["Regularization Paths for Generalized Linear Models via Coordinate Descent", Friedman J., Hastie T., Tibshirani R.](https://www.jstatsoft.org/article/view/v033i01)
+//! * ["The Elements of Statistical Learning", Hastie T., Tibshirani R., Friedman J., 3.8.6 Pathwise Coordinate Optimization](https://hastie.su.domains/ElemStatLearn/)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use std::fmt::Debug;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::error::Failed;
+use crate::linalg::basic::arrays::{Array1, Array2};
+use crate::numbers::basenum::Number;
+use crate::numbers::floatnum::FloatNumber;
+use crate::numbers::realnum::RealNumber;
+
+/// Cyclic coordinate descent solver for the elastic net objective.
+pub struct CoordinateDescentOptimizer<T: FloatNumber> {
+    columns: Vec<Vec<T>>,
+    col_sq_norms: Vec<T>,
+}
+
+impl<T: FloatNumber> CoordinateDescentOptimizer<T> {
+    /// Creates a new solver for the design matrix `x`.
+    /// Columns of `x` and their squared norms are cached so that they can be reused across many fits.
+    pub fn new<X: Array2<T>>(x: &X) -> CoordinateDescentOptimizer<T> {
+        let (n, p) = x.shape();
+        let mut columns = Vec::with_capacity(p);
+        let mut col_sq_norms = Vec::with_capacity(p);
+        for j in 0..p {
+            let mut col = vec![T::zero(); n];
+            x.copy_col_as_vec(j, &mut col);
+            col_sq_norms.push(col.iter().fold(T::zero(), |acc, &v| acc + v * v));
+            columns.push(col);
+        }
+        CoordinateDescentOptimizer {
+            columns,
+            col_sq_norms,
+        }
+    }
+
+    /// Minimizes \\(\frac{1}{n} \vert y - Xw\vert^2 + \lambda_1 \vert w \vert_1 + \lambda_2 \vert w \vert^2\\).
+    /// * `y` - target values
+    /// * `w` - initial coefficients, overwritten with the solution. Pass the solution of a previous fit to warm-start the solver.
+    /// * `l1_reg` - strength of the L1 penalty, \\(\lambda_1\\)
+    /// * `l2_reg` - strength of the L2 penalty, \\(\lambda_2\\)
+    /// * `max_iter` - maximum number of passes over all coefficients
+    /// * `tol` - the solver stops when the largest coefficient update is smaller than `tol` times the largest coefficient
+    ///
+    /// Returns the number of passes that were made.
+    pub fn optimize(
+        &self,
+        y: &[T],
+        w: &mut [T],
+        l1_reg: T,
+        l2_reg: T,
+        max_iter: usize,
+        tol: T,
+    ) -> Result<usize, Failed> {
+        let p = self.columns.len();
+        let n = y.len();
+
+        if w.len() != p {
+            return Err(Failed::fit(
+                "Number of coefficients should = number of columns in X",
+            ));
+        }
+        if self.columns.iter().any(|col| col.len() != n) {
+            return Err(Failed::fit("Number of rows in X should = len(y)"));
+        }
+        if max_iter == 0 {
+            return Err(Failed::fit("max_iter should be > 0"));
+        }
+        if tol <= T::zero() {
+            return Err(Failed::fit("tol should be > 0"));
+        }
+
+        let n_t = T::from_usize(n).unwrap();
+        // the smooth part is (1/n)|r|^2, hence the 1/2 in front of the L1 threshold
+        let threshold = T::half() * l1_reg;
+
+        let mut r = y.to_vec();
+        for (j, &w_j) in w.iter().enumerate() {
+            if w_j != T::zero() {
+                for (r_i, &x_ij) in r.iter_mut().zip(self.columns[j].iter()) {
+                    *r_i -= x_ij * w_j;
+                }
+            }
+        }
+
+        for iter in 1..=max_iter {
+            let mut w_max = T::zero();
+            let mut d_w_max = T::zero();
+
+            for (j, w_j) in w.iter_mut().enumerate() {
+                let z = self.col_sq_norms[j] / n_t;
+                if z == T::zero() {
+                    continue;
+                }
+                let col = &self.columns[j];
+                let old_w_j = *w_j;
+
+                let rho = col
+                    .iter()
+                    .zip(r.iter())
+                    .fold(T::zero(), |acc, (&x_ij, &r_i)| acc + x_ij * r_i)
+                    / n_t
+                    + z * old_w_j;
+
+                let new_w_j = Self::soft_threshold(rho, threshold) / (z + l2_reg);
+
+                if new_w_j != old_w_j {
+                    let delta = new_w_j - old_w_j;
+                    for (r_i, &x_ij) in r.iter_mut().zip(col.iter()) {
+                        *r_i -= x_ij * delta;
+                    }
+                    *w_j = new_w_j;
+                }
+
+                d_w_max = d_w_max.max((new_w_j - old_w_j).abs());
+                w_max = w_max.max(new_w_j.abs());
+            }
+
+            if w_max == T::zero() || d_w_max <= tol * w_max {
+                return Ok(iter);
+            }
+        }
+
+        Ok(max_iter)
+    }
+
+    pub(crate) fn soft_threshold(v: T, threshold: T) -> T {
+        if v > threshold {
+            v - threshold
+        } else if v < -threshold {
+            v + threshold
+        } else {
+            T::zero()
+        }
+    }
+}
+
+/// Regularization path parameters
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct RegularizationPathParameters {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Regularization parameters to fit, sorted in decreasing order before fitting.
+    /// When not set, a grid of `n_alphas` values is generated from the data.
+    pub alphas: Option<Vec<f64>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Number of regularization parameters in the generated grid.
+    pub n_alphas: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Length of the generated grid, `alpha_min / alpha_max = eps`.
+    pub eps: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The elastic net mixing parameter, with 0 <= l1_ratio <= 1. Ignored by `lasso_path`.
+    /// For l1_ratio = 0 the penalty is an L2 penalty.
+    /// For l1_ratio = 1 it is an L1 penalty. For 0 < l1_ratio < 1, the penalty is a combination of L1 and L2.
+    pub l1_ratio: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// If True, the regressors X will be normalized before regression by subtracting the mean and dividing by the standard deviation.
+    pub normalize: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The tolerance for the optimization
+    pub tol: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The maximum number of iterations for every value of alpha
+    pub max_iter: usize,
+}
+
+impl RegularizationPathParameters {
+    /// Regularization parameters to fit.
+    pub fn with_alphas(mut self, alphas: Vec<f64>) -> Self {
+        self.alphas = Some(alphas);
+        self
+    }
+    /// Number of regularization parameters in the generated grid.
+    pub fn with_n_alphas(mut self, n_alphas: usize) -> Self {
+        self.n_alphas = n_alphas;
+        self
+    }
+    /// Length of the generated grid, `alpha_min / alpha_max = eps`.
+    pub fn with_eps(mut self, eps: f64) -> Self {
+        self.eps = eps;
+        self
+    }
+    /// The elastic net mixing parameter, with 0 <= l1_ratio <= 1.
+    pub fn with_l1_ratio(mut self, l1_ratio: f64) -> Self {
+        self.l1_ratio = l1_ratio;
+        self
+    }
+    /// If True, the regressors X will be normalized before regression by subtracting the mean and dividing by the standard deviation.
+    pub fn with_normalize(mut self, normalize: bool) -> Self {
+        self.normalize = normalize;
+        self
+    }
+    /// The tolerance for the optimization
+    pub fn with_tol(mut self, tol: f64) -> Self {
+        self.tol = tol;
+        self
+    }
+    /// The maximum number of iterations for every value of alpha
+    pub fn with_max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+}
+
+impl Default for RegularizationPathParameters {
+    fn default() -> Self {
+        RegularizationPathParameters {
+            alphas: None,
+            n_alphas: 100,
+            eps: 1e-3,
+            l1_ratio: 0.5,
+            normalize: true,
+            tol: 1e-4,
+            max_iter: 1000,
+        }
+    }
+}
+
+/// Coefficients fitted along a grid of regularization parameters.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct RegularizationPath<T: FloatNumber, X: Array2<T>> {
+    alphas: Vec<f64>,
+    coefficients: X,
+    intercepts: Vec<T>,
+    n_iter: Vec<usize>,
+}
+
+impl<T: FloatNumber, X: Array2<T>> RegularizationPath<T, X> {
+    /// Regularization parameters, in decreasing order
+    pub fn alphas(&self) -> &Vec<f64> {
+        &self.alphas
+    }
+
+    /// _MxK_ matrix of coefficients, one column per value of alpha
+    pub fn coefficients(&self) -> &X {
+        &self.coefficients
+    }
+
+    /// Intercepts, one per value of alpha
+    pub fn intercepts(&self) -> &Vec<T> {
+        &self.intercepts
+    }
+
+    /// Number of coordinate descent passes made for each value of alpha
+    pub fn n_iter(&self) -> &Vec<usize> {
+        &self.n_iter
+    }
+}
+
+/// Computes the Lasso path with coordinate descent.
+/// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+/// * `y` - target values
+/// * `parameters` - path parameters, `l1_ratio` is ignored and set to 1.
+pub fn lasso_path<TX: FloatNumber + RealNumber, TY: Number, X: Array2<TX>, Y: Array1<TY>>(
+    x: &X,
+    y: &Y,
+    parameters: RegularizationPathParameters,
+) -> Result<RegularizationPath<TX, X>, Failed> {
+    enet_path(x, y, parameters.with_l1_ratio(1.0))
+}
+
+/// Computes the elastic net path with coordinate descent.
+/// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+/// * `y` - target values
+/// * `parameters` - path parameters, use `Default::default()` to set parameters to default values.
+pub fn enet_path<TX: FloatNumber + RealNumber, TY: Number, X: Array2<TX>, Y: Array1<TY>>(
+    x: &X,
+    y: &Y,
+    parameters: RegularizationPathParameters,
+) -> Result<RegularizationPath<TX, X>, Failed> {
+    let (n, p) = x.shape();
+
+    if y.shape() != n {
+        return Err(Failed::fit("Number of rows in X should = len(y)"));
+    }
+
+    if !(0.0..=1.0).contains(&parameters.l1_ratio) {
+        return Err(Failed::fit("l1_ratio should be between 0 and 1"));
+    }
+
+    let (x, col_mean, col_std) = center_x(x, parameters.normalize)?;
+
+    let y: Vec<TX> = y.iterator(0).map(|&v| TX::from(v).unwrap()).collect();
+    let y_mean = y.iter().fold(TX::zero(), |acc, &v| acc + v) / TX::from_usize(n).unwrap();
+    let y: Vec<TX> = y.iter().map(|&v| v - y_mean).collect();
+
+    let optimizer = CoordinateDescentOptimizer::new(&x);
+
+    let mut alphas = match parameters.alphas {
+        Some(alphas) => alphas,
+        None => alpha_grid(&optimizer, &y, &parameters)?,
+    };
+
+    check_alphas(&alphas)?;
+    alphas.sort_by(|a, b| b.partial_cmp(a).unwrap());
+
+    let tol = TX::from_f64(parameters.tol).unwrap();
+    let mut coefficients = X::zeros(p, alphas.len());
+    let mut intercepts = Vec::with_capacity(alphas.len());
+    let mut n_iter = Vec::with_capacity(alphas.len());
+
+    let mut w = vec![TX::zero(); p];
+
+    for (k, &alpha) in alphas.iter().enumerate() {
+        let l1_reg = TX::from_f64(alpha * parameters.l1_ratio).unwrap();
+        let l2_reg = TX::from_f64(alpha * (1.0 - parameters.l1_ratio)).unwrap();
+
+        n_iter.push(optimizer.optimize(&y, &mut w, l1_reg, l2_reg, parameters.max_iter, tol)?);
+
+        let mut b = y_mean;
+        for (j, &w_j) in w.iter().enumerate() {
+            let w_j = w_j / col_std[j];
+            coefficients.set((j, k), w_j);
+            b -= w_j * col_mean[j];
+        }
+        intercepts.push(b);
+    }
+
+    Ok(RegularizationPath {
+        alphas,
+        coefficients,
+        intercepts,
+        n_iter,
+    })
+}
+
+/// Generates the grid of alphas that [`enet_path`](fn.enet_path.html) fits when `alphas` are not set.
+pub(crate) fn enet_alpha_grid<
+    TX: FloatNumber + RealNumber,
+    TY: Number,
+    X: Array2<TX>,
+    Y: Array1<TY>,
+>(
+    x: &X,
+    y: &Y,
+    parameters: &RegularizationPathParameters,
+) -> Result<Vec<f64>, Failed> {
+    let n = x.shape().0;
+
+    if y.shape() != n {
+        return Err(Failed::fit("Number of rows in X should = len(y)"));
+    }
+
+    let (x, _, _) = center_x(x, parameters.normalize)?;
+
+    let y: Vec<TX> = y.iterator(0).map(|&v| TX::from(v).unwrap()).collect();
+    let y_mean = y.iter().fold(TX::zero(), |acc, &v| acc + v) / TX::from_usize(n).unwrap();
+    let y: Vec<TX> = y.iter().map(|&v| v - y_mean).collect();
+
+    alpha_grid(&CoordinateDescentOptimizer::new(&x), &y, parameters)
+}
+
+/// Checks that a user supplied grid of alphas is not empty and holds only finite, non-negative values.
+pub(crate) fn check_alphas(alphas: &[f64]) -> Result<(), Failed> {
+    if alphas.is_empty() {
+        return Err(Failed::fit("alphas should not be empty"));
+    }
+    if !alphas
+        .iter()
+        .all(|&alpha| alpha.is_finite() && alpha >= 0f64)
+    {
+        return Err(Failed::fit("alpha should be finite and >= 0"));
+    }
+    Ok(())
+}
+
+/// Centers the columns of `x` and, when `normalize` is set, scales them to unit standard deviation.
+fn center_x<TX: FloatNumber + RealNumber, X: Array2<TX>>(
+    x: &X,
+    normalize: bool,
+) -> Result<(X, Vec<TX>, Vec<TX>), Failed> {
+    let col_mean: Vec<TX> = x
+        .mean_by(0)
+        .iter()
+        .map(|&v| TX::from_f64(v).unwrap())
+        .collect();
+
+    let col_std: Vec<TX> = if normalize {
+        let col_std: Vec<TX> = x
+            .std_dev(0)
+            .iter()
+            .map(|&v| TX::from_f64(v).unwrap())
+            .collect();
+
+        for (i, col_std_i) in col_std.iter().enumerate() {
+            if (*col_std_i - TX::zero()).abs() < TX::epsilon() {
+                return Err(Failed::fit(&format!("Cannot rescale constant column {i}")));
+            }
+        }
+        col_std
+    } else {
+        vec![TX::one(); col_mean.len()]
+    };
+
+    let mut scaled_x = x.clone();
+    scaled_x.scale_mut(&col_mean, &col_std, 0);
+    Ok((scaled_x, col_mean, col_std))
+}
+
+/// Geometric grid that starts at the smallest alpha for which all coefficients are zero.
+fn alpha_grid<T: FloatNumber>(
+    optimizer: &CoordinateDescentOptimizer<T>,
+    y: &[T],
+    parameters: &RegularizationPathParameters,
+) -> Result<Vec<f64>, Failed> {
+    if parameters.l1_ratio <= 0.0 {
+        return Err(Failed::fit(
+            "Cannot generate a grid of alphas for l1_ratio = 0, set alphas explicitly",
+        ));
+    }
+    if parameters.n_alphas == 0 {
+        return Err(Failed::fit("n_alphas should be > 0"));
+    }
+    if parameters.eps <= 0.0 {
+        return Err(Failed::fit("eps should be > 0"));
+    }
+
+    let n = y.len() as f64;
+    let xy_max = optimizer
+        .columns
+        .iter()
+        .map(|col| {
+            col.iter()
+                .zip(y.iter())
+                .fold(T::zero(), |acc, (&x_ij, &y_i)| acc + x_ij * y_i)
+                .abs()
+                .to_f64()
+                .unwrap()
+        })
+        .fold(0f64, f64::max);
+
+    let alpha_max = (2.0 * xy_max / (n * parameters.l1_ratio)).max(f64::EPSILON);
+
+    if parameters.n_alphas == 1 {
+        return Ok(vec![alpha_max]);
+    }
+
+    let log_max = alpha_max.ln();
+    let log_min = (alpha_max * parameters.eps).ln();
+    let step = (log_max - log_min) / (parameters.n_alphas - 1) as f64;
+
+    Ok((0..parameters.n_alphas)
+        .map(|i| (log_max - step * i as f64).exp())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::basic::arrays::{Array, ArrayView1};
+    use crate::linear::lasso::{Lasso, LassoParameters};
+    use crate::linear::linear_regression::LinearRegression;
+    use crate::test_datasets::longley;
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn lasso_path_longley() {
+        let (x, y) = longley();
+
+        let path = lasso_path(
+            &x,
+            &y,
+            RegularizationPathParameters::default()
+                .with_n_alphas(50)
+                .with_eps(1e-6)
+                .with_tol(1e-8)
+                .with_max_iter(100000),
+        )
+        .unwrap();
+
+        assert_eq!(path.alphas().len(), 50);
+        assert_eq!(path.coefficients().shape(), (6, 50));
+        assert!(path.alphas().windows(2).all(|w| w[0] > w[1]));
+
+        // at alpha_max every coefficient is zero and the intercept is the mean of y
+        for j in 0..6 {
+            assert_eq!(*path.coefficients().get((j, 0)), 0.0);
+        }
+        assert!((path.intercepts()[0] - y.mean_by()).abs() < 1e-8);
+
+        // the number of active features grows along the path
+        let active = |k: usize| {
+            (0..6)
+                .filter(|&j| *path.coefficients().get((j, k)) != 0.0)
+                .count()
+        };
+        assert!(active(49) > active(10));
+
+        // at a tiny alpha the solution approaches ordinary least squares
+        let ols = LinearRegression::fit(&x, &y, Default::default()).unwrap();
+        let y_ols = ols.predict(&x).unwrap();
+        let y_path: Vec<f64> = (0..y.len())
+            .map(|i| {
+                (0..6).fold(path.intercepts()[49], |acc, j| {
+                    acc + x.get((i, j)) * path.coefficients().get((j, 49))
+                })
+            })
+            .collect();
+        assert!(y_ols.approximate_eq(&y_path, 0.5));
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn lasso_path_matches_lasso() {
+        let (x, y) = longley();
+
+        let alphas = vec![1.0, 0.5, 0.1];
+
+        let path = lasso_path(
+            &x,
+            &y,
+            RegularizationPathParameters::default()
+                .with_alphas(alphas.clone())
+                .with_tol(1e-10)
+                .with_max_iter(100000),
+        )
+        .unwrap();
+
+        for (k, &alpha) in alphas.iter().enumerate() {
+            let lasso = Lasso::fit(
+                &x,
+                &y,
+                LassoParameters::default().with_alpha(alpha).with_tol(1e-10),
+            )
+            .unwrap();
+
+            let y_lasso = lasso.predict(&x).unwrap();
+            let y_path: Vec<f64> = (0..y.len())
+                .map(|i| {
+                    (0..6).fold(path.intercepts()[k], |acc, j| {
+                        acc + x.get((i, j)) * path.coefficients().get((j, k))
+                    })
+                })
+                .collect();
+            assert!(y_lasso.approximate_eq(&y_path, 1e-4));
+        }
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn warm_start() {
+        let (x, y) = longley();
+        let (x, _, _) = center_x(&x, true).unwrap();
+        let y_mean = y.mean_by();
+        let y: Vec<f64> = y.iter().map(|v| v - y_mean).collect();
+
+        let optimizer = CoordinateDescentOptimizer::new(&x);
+
+        let mut w_cold = vec![0f64; 6];
+        let n_cold = optimizer
+            .optimize(&y, &mut w_cold, 0.1, 0.0, 10000, 1e-8)
+            .unwrap();
+
+        let mut w_warm = w_cold.clone();
+        let n_warm = optimizer
+            .optimize(&y, &mut w_warm, 0.1, 0.0, 10000, 1e-8)
+            .unwrap();
+
+        assert!(n_warm < n_cold);
+        assert!(w_cold.approximate_eq(&w_warm, 1e-6));
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn enet_optimality_conditions() {
+        let (x, y) = longley();
+        let (x, _, _) = center_x(&x, true).unwrap();
+        let y_mean = y.mean_by();
+        let y: Vec<f64> = y.iter().map(|v| v - y_mean).collect();
+        let n = y.len() as f64;
+
+        let (l1_reg, l2_reg) = (0.25, 0.25);
+
+        let optimizer = CoordinateDescentOptimizer::new(&x);
+        let mut w = vec![0f64; 6];
+        optimizer
+            .optimize(&y, &mut w, l1_reg, l2_reg, 100000, 1e-12)
+            .unwrap();
+
+        let y_hat = x.ax(false, &w);
+        let r: Vec<f64> = y.iter().zip(y_hat.iter()).map(|(a, b)| a - b).collect();
+
+        // subgradient of the objective should contain zero
+        for (j, w_j) in w.iter().enumerate() {
+            let grad = 2.0 * x.get_col(j).dot(&r) / n - 2.0 * l2_reg * w_j;
+            if *w_j != 0.0 {
+                assert!((grad - l1_reg * w_j.signum()).abs() < 1e-6);
+            } else {
+                assert!(grad.abs() <= l1_reg + 1e-6);
+            }
+        }
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn enet_path_invalid_parameters() {
+        let (x, y) = longley();
+
+        assert!(enet_path(
+            &x,
+            &y,
+            RegularizationPathParameters::default().with_l1_ratio(1.5)
+        )
+        .is_err());
+        assert!(enet_path(
+            &x,
+            &y,
+            RegularizationPathParameters::default().with_l1_ratio(0.0)
+        )
+        .is_err());
+        assert!(enet_path(
+            &x,
+            &y,
+            RegularizationPathParameters::default().with_alphas(vec![-1.0])
+        )
+        .is_err());
+        assert!(enet_path(
+            &x,
+            &y,
+            RegularizationPathParameters::default().with_alphas(vec![1.0, f64::NAN])
+        )
+        .is_err());
+    }
+}
@@ -13,6 +13,7 @@
 //! In essense, elastic net combines both the [L1](../lasso/index.html) and [L2](../ridge_regression/index.html) penalties during training,
 //! which can result in better performance than a model with either one or the other penalty on some problems.
 //! The elastic net is particularly useful when the number of predictors (p) is much bigger than the number of observations (n).
+//! To fit a whole grid of regularization parameters at once use [`enet_path`](../coordinate_descent/fn.enet_path.html).
 //!
 //! Example:
 //!
@@ -67,7 +68,9 @@ use crate::numbers::basenum::Number;
 use crate::numbers::floatnum::FloatNumber;
 use crate::numbers::realnum::RealNumber;
 
+use crate::algorithm::sample_weight::check_sample_weight;
 use crate::linear::lasso_optimizer::InteriorPointOptimizer;
+use crate::linear::{sqrt_weigh_rows, weighted_col_mean_std};
 
 /// Elastic net parameters
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -317,6 +320,21 @@ impl<TX: FloatNumber + RealNumber, TY: Number, X: Array2<TX>, Y: Array1<TY>>
         x: &X,
         y: &Y,
         parameters: ElasticNetParameters,
+    ) -> Result<ElasticNet<TX, TY, X, Y>, Failed> {
+        Self::fit_with_sample_weight(x, y, &vec![TX::one(); x.shape().0], parameters)
+    }
+
+    /// Fits elastic net regression to your data, each observation contributing to the loss in proportion to its weight.
+    /// Weights are rescaled to sum up to the number of observations, so that `alpha` keeps its meaning.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - target values
+    /// * `sample_weight` - non-negative weight of every observation
+    /// * `parameters` - other parameters, use `Default::default()` to set parameters to default values.
+    pub fn fit_with_sample_weight(
+        x: &X,
+        y: &Y,
+        sample_weight: &[TX],
+        parameters: ElasticNetParameters,
     ) -> Result<ElasticNet<TX, TY, X, Y>, Failed> {
         let (n, p) = x.shape();
 
@@ -324,22 +342,42 @@ impl<TX: FloatNumber + RealNumber, TY: Number, X: Array2<TX>, Y: Array1<TY>>
             return Err(Failed::fit("Number of rows in X should = len(y)"));
         }
 
+        check_sample_weight(sample_weight, n)?;
+
         let n_float = n as f64;
 
         let l1_reg = TX::from_f64(parameters.alpha * parameters.l1_ratio * n_float).unwrap();
         let l2_reg =
             TX::from_f64(parameters.alpha * (1.0 - parameters.l1_ratio) * n_float).unwrap();
 
-        let y_mean = TX::from_f64(y.mean_by()).unwrap();
+        let weight_sum: TX = sample_weight.iter().copied().sum();
+        let sample_weight: Vec<TX> = sample_weight
+            .iter()
+            .map(|&w| w * TX::from_usize(n).unwrap() / weight_sum)
+            .collect();
+
+        let y: Vec<TX> = y.iterator(0).map(|&v| TX::from(v).unwrap()).collect();
+        let y_mean = y
+            .iter()
+            .zip(sample_weight.iter())
+            .map(|(&y_i, &w_i)| y_i * w_i)
+            .sum::<TX>()
+            / TX::from_usize(n).unwrap();
+        let y_centered: Vec<TX> = y
+            .iter()
+            .zip(sample_weight.iter())
+            .map(|(&y_i, &w_i)| (y_i - y_mean) * w_i.sqrt())
+            .collect();
 
         let (w, b) = if parameters.normalize {
-            let (scaled_x, col_mean, col_std) = Self::rescale_x(x)?;
+            let (scaled_x, col_mean, col_std) = Self::rescale_x(x, &sample_weight)?;
+            let scaled_x = sqrt_weigh_rows(&scaled_x, &sample_weight);
 
-            let (x, y, gamma) = Self::augment_x_and_y(&scaled_x, y, l2_reg);
+            let (x, y, gamma) = Self::augment_x_and_y(&scaled_x, &y_centered, l2_reg);
 
             let mut optimizer = InteriorPointOptimizer::new(&x, p);
 
-            let mut w = optimizer.optimize(
+            let mut w = optimizer.optimize_centered(
                 &x,
                 &y,
                 l1_reg * gamma,
@@ -361,11 +399,13 @@ impl<TX: FloatNumber + RealNumber, TY: Number, X: Array2<TX>, Y: Array1<TY>>
 
             (X::from_column(&w), b)
         } else {
-            let (x, y, gamma) = Self::augment_x_and_y(x, y, l2_reg);
+            let x = sqrt_weigh_rows(x, &sample_weight);
+
+            let (x, y, gamma) = Self::augment_x_and_y(&x, &y_centered, l2_reg);
 
             let mut optimizer = InteriorPointOptimizer::new(&x, p);
 
-            let mut w = optimizer.optimize(
+            let mut w = optimizer.optimize_centered(
                 &x,
                 &y,
                 l1_reg * gamma,
@@ -411,17 +451,8 @@ impl<TX: FloatNumber + RealNumber, TY: Number, X: Array2<TX>, Y: Array1<TY>>
         self.intercept.as_ref().unwrap()
     }
 
-    fn rescale_x(x: &X) -> Result<(X, Vec<TX>, Vec<TX>), Failed> {
-        let col_mean: Vec<TX> = x
-            .mean_by(0)
-            .iter()
-            .map(|&v| TX::from_f64(v).unwrap())
-            .collect();
-        let col_std: Vec<TX> = x
-            .std_dev(0)
-            .iter()
-            .map(|&v| TX::from_f64(v).unwrap())
-            .collect();
+    fn rescale_x(x: &X, sample_weight: &[TX]) -> Result<(X, Vec<TX>, Vec<TX>), Failed> {
+        let (col_mean, col_std) = weighted_col_mean_std(x, sample_weight);
 
         for (i, col_std_i) in col_std.iter().enumerate() {
             if (*col_std_i - TX::zero()).abs() < TX::epsilon() {
@@ -434,15 +465,15 @@ impl<TX: FloatNumber + RealNumber, TY: Number, X: Array2<TX>, Y: Array1<TY>>
         Ok((scaled_x, col_mean, col_std))
     }
 
-    fn augment_x_and_y(x: &X, y: &Y, l2_reg: TX) -> (X, Vec<TX>, TX) {
+    fn augment_x_and_y(x: &X, y: &[TX], l2_reg: TX) -> (X, Vec<TX>, TX) {
         let (n, p) = x.shape();
 
         let gamma = TX::one() / (TX::one() + l2_reg).sqrt();
         let padding = gamma * l2_reg.sqrt();
 
         let mut y2 = Vec::<TX>::zeros(n + p);
-        for i in 0..y.shape() {
-            y2.set(i, TX::from(*y.get(i)).unwrap());
+        for (i, y_i) in y.iter().enumerate() {
+            y2.set(i, *y_i);
         }
 
         let mut x2 = X::zeros(n + p, p);
@@ -464,6 +495,7 @@ mod tests {
     use super::*;
     use crate::linalg::basic::matrix::DenseMatrix;
     use crate::metrics::mean_absolute_error;
+    use crate::test_datasets::{assert_weights_repeat_rows, longley};
 
     #[test]
     fn search_parameters() {
@@ -643,4 +675,48 @@ mod tests {
 
     //     assert_eq!(lr, deserialized_lr);
     // }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn elasticnet_fit_with_sample_weight() {
+        let (x, y) = longley();
+        let sample_weight = vec![
+            1., 2., 1., 3., 1., 1., 2., 1., 1., 1., 2., 1., 1., 1., 1., 2.,
+        ];
+
+        for parameters in [true, false].map(|normalize| {
+            ElasticNetParameters::default()
+                .with_alpha(0.1)
+                .with_normalize(normalize)
+                .with_tol(1e-8)
+        }) {
+            assert_weights_repeat_rows(
+                &x,
+                &y,
+                &sample_weight,
+                |x_w, y_w, w| {
+                    ElasticNet::fit_with_sample_weight(x_w, y_w, w, parameters.clone())
+                        .and_then(|lr| lr.predict(&x))
+                        .unwrap()
+                },
+                |x_r, y_r| {
+                    ElasticNet::fit(x_r, y_r, parameters.clone())
+                        .and_then(|lr| lr.predict(&x))
+                        .unwrap()
+                },
+                1e-4,
+            );
+        }
+
+        assert!(ElasticNet::fit_with_sample_weight(
+            &x,
+            &y,
+            &sample_weight[1..],
+            Default::default()
+        )
+        .is_err());
+    }
 }
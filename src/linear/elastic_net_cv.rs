@@ -0,0 +1,515 @@
+//! # Elastic Net with Cross-Validation
+//!
+//! [Elastic net](../elastic_net/index.html) with the regularization parameter \\(\alpha\\) and the mixing parameter \\(l_{1r}\\)
+//! chosen automatically by k-fold cross-validation.
+//!
+//! For every candidate \\(l_{1r}\\) a decreasing grid of \\(\alpha\\) values is generated from the data, or taken from the parameters.
+//! On every fold the whole [regularization path](../coordinate_descent/index.html) is fitted on the training part with warm starts,
+//! and the mean squared error of every \\((l_{1r}, \alpha)\\) pair is measured on the held-out part.
+//! The pair with the lowest error averaged over all folds is then used to fit the final model on the whole dataset.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::basic::matrix::DenseMatrix;
+//! use smartcore::linear::elastic_net_cv::*;
+//!
+//! // Longley dataset (https://www.statsmodels.org/stable/datasets/generated/longley.html)
+//! let x = DenseMatrix::from_2d_array(&[
+//!               &[234.289, 235.6, 159.0, 107.608, 1947., 60.323],
+//!               &[259.426, 232.5, 145.6, 108.632, 1948., 61.122],
+//!               &[258.054, 368.2, 161.6, 109.773, 1949., 60.171],
+//!               &[284.599, 335.1, 165.0, 110.929, 1950., 61.187],
+//!               &[328.975, 209.9, 309.9, 112.075, 1951., 63.221],
+//!               &[346.999, 193.2, 359.4, 113.270, 1952., 63.639],
+//!               &[365.385, 187.0, 354.7, 115.094, 1953., 64.989],
+//!               &[363.112, 357.8, 335.0, 116.219, 1954., 63.761],
+//!               &[397.469, 290.4, 304.8, 117.388, 1955., 66.019],
+//!               &[419.180, 282.2, 285.7, 118.734, 1956., 67.857],
+//!               &[442.769, 293.6, 279.8, 120.445, 1957., 68.169],
+//!               &[444.546, 468.1, 263.7, 121.950, 1958., 66.513],
+//!               &[482.704, 381.3, 255.2, 123.366, 1959., 68.655],
+//!               &[502.601, 393.1, 251.4, 125.368, 1960., 69.564],
+//!               &[518.173, 480.6, 257.2, 127.852, 1961., 69.331],
+//!               &[554.894, 400.7, 282.7, 130.081, 1962., 70.551],
+//!          ]).unwrap();
+//!
+//! let y: Vec<f64> = vec![83.0, 88.5, 88.2, 89.5, 96.2, 98.1, 99.0,
+//!           100.0, 101.2, 104.6, 108.4, 110.8, 112.6, 114.2, 115.7, 116.9];
+//!
+//! let enet = ElasticNetCV::fit(&x, &y,
+//!                 ElasticNetCVParameters::default().with_l1_ratio(vec![0.1, 0.5, 0.9])).unwrap();
+//!
+//! let alpha = enet.alpha();
+//! let l1_ratio = enet.l1_ratio();
+//! let y_hat = enet.predict(&x).unwrap();
+//! ```
+//!
+//! ## References:
+//!
+//! * ["An Introduction to Statistical Learning", James G., Witten D., Hastie T., Tibshirani R., 5.1 Cross-Validation](http://faculty.marshall.usc.edu/gareth-james/ISL/)
+//! * ["Regularization Paths for Generalized Linear Models via Coordinate Descent", Friedman J., Hastie T., Tibshirani R.](https://www.jstatsoft.org/article/view/v033i01)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::api::{Predictor, SupervisedEstimator};
+use crate::error::Failed;
+use crate::linalg::basic::arrays::{Array1, Array2};
+use crate::linear::coordinate_descent::{
+    check_alphas, enet_alpha_grid, enet_path, RegularizationPathParameters,
+};
+use crate::model_selection::{BaseKFold, KFold};
+use crate::numbers::basenum::Number;
+use crate::numbers::floatnum::FloatNumber;
+use crate::numbers::realnum::RealNumber;
+
+/// Elastic net cross-validation parameters
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct ElasticNetCVParameters {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Candidate values of the elastic net mixing parameter, with 0 < l1_ratio <= 1.
+    pub l1_ratio: Vec<f64>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Candidate values of the regularization parameter.
+    /// When not set, a grid of `n_alphas` values is generated for every value of `l1_ratio`.
+    pub alphas: Option<Vec<f64>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Number of regularization parameters in the generated grid.
+    pub n_alphas: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Length of the generated grid, `alpha_min / alpha_max = eps`.
+    pub eps: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The cross-validation splitting strategy
+    pub cv: KFold,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// If True, the regressors X will be normalized before regression by subtracting the mean and dividing by the standard deviation.
+    pub normalize: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The tolerance for the optimization
+    pub tol: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The maximum number of iterations
+    pub max_iter: usize,
+}
+
+impl ElasticNetCVParameters {
+    /// Candidate values of the elastic net mixing parameter, with 0 < l1_ratio <= 1.
+    pub fn with_l1_ratio(mut self, l1_ratio: Vec<f64>) -> Self {
+        self.l1_ratio = l1_ratio;
+        self
+    }
+    /// Candidate values of the regularization parameter.
+    pub fn with_alphas(mut self, alphas: Vec<f64>) -> Self {
+        self.alphas = Some(alphas);
+        self
+    }
+    /// Number of regularization parameters in the generated grid.
+    pub fn with_n_alphas(mut self, n_alphas: usize) -> Self {
+        self.n_alphas = n_alphas;
+        self
+    }
+    /// Length of the generated grid, `alpha_min / alpha_max = eps`.
+    pub fn with_eps(mut self, eps: f64) -> Self {
+        self.eps = eps;
+        self
+    }
+    /// The cross-validation splitting strategy
+    pub fn with_cv(mut self, cv: KFold) -> Self {
+        self.cv = cv;
+        self
+    }
+    /// If True, the regressors X will be normalized before regression by subtracting the mean and dividing by the standard deviation.
+    pub fn with_normalize(mut self, normalize: bool) -> Self {
+        self.normalize = normalize;
+        self
+    }
+    /// The tolerance for the optimization
+    pub fn with_tol(mut self, tol: f64) -> Self {
+        self.tol = tol;
+        self
+    }
+    /// The maximum number of iterations
+    pub fn with_max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+}
+
+impl Default for ElasticNetCVParameters {
+    fn default() -> Self {
+        let path_params = RegularizationPathParameters::default();
+
+        ElasticNetCVParameters {
+            l1_ratio: vec![path_params.l1_ratio],
+            alphas: None,
+            n_alphas: path_params.n_alphas,
+            eps: path_params.eps,
+            cv: KFold::default().with_n_splits(5).with_shuffle(false),
+            normalize: path_params.normalize,
+            tol: path_params.tol,
+            max_iter: path_params.max_iter,
+        }
+    }
+}
+
+/// Elastic net with built-in cross-validation
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+pub struct ElasticNetCV<TX: FloatNumber + RealNumber, TY: Number, X: Array2<TX>, Y: Array1<TY>> {
+    coefficients: Option<X>,
+    intercept: Option<TX>,
+    alpha: Option<f64>,
+    l1_ratio: Option<f64>,
+    alphas: Option<Vec<Vec<f64>>>,
+    mse_path: Option<Vec<Vec<Vec<f64>>>>,
+    _phantom_ty: PhantomData<TY>,
+    _phantom_y: PhantomData<Y>,
+}
+
+impl<TX: FloatNumber + RealNumber, TY: Number, X: Array2<TX>, Y: Array1<TY>> PartialEq
+    for ElasticNetCV<TX, TY, X, Y>
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.intercept == other.intercept
+            && self.alpha == other.alpha
+            && self.l1_ratio == other.l1_ratio
+            && self.coefficients().shape() == other.coefficients().shape()
+            && self
+                .coefficients()
+                .iterator(0)
+                .zip(other.coefficients().iterator(0))
+                .all(|(&a, &b)| (a - b).abs() <= TX::epsilon())
+    }
+}
+
+impl<TX: FloatNumber + RealNumber, TY: Number, X: Array2<TX>, Y: Array1<TY>>
+    SupervisedEstimator<X, Y, ElasticNetCVParameters> for ElasticNetCV<TX, TY, X, Y>
+{
+    fn new() -> Self {
+        Self {
+            coefficients: Option::None,
+            intercept: Option::None,
+            alpha: Option::None,
+            l1_ratio: Option::None,
+            alphas: Option::None,
+            mse_path: Option::None,
+            _phantom_ty: PhantomData,
+            _phantom_y: PhantomData,
+        }
+    }
+
+    fn fit(x: &X, y: &Y, parameters: ElasticNetCVParameters) -> Result<Self, Failed> {
+        ElasticNetCV::fit(x, y, parameters)
+    }
+}
+
+impl<TX: FloatNumber + RealNumber, TY: Number, X: Array2<TX>, Y: Array1<TY>> Predictor<X, Y>
+    for ElasticNetCV<TX, TY, X, Y>
+{
+    fn predict(&self, x: &X) -> Result<Y, Failed> {
+        self.predict(x)
+    }
+}
+
+impl<TX: FloatNumber + RealNumber, TY: Number, X: Array2<TX>, Y: Array1<TY>>
+    ElasticNetCV<TX, TY, X, Y>
+{
+    /// Selects regularization parameters by cross-validation and fits elastic net regression to your data.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - target values
+    /// * `parameters` - other parameters, use `Default::default()` to set parameters to default values.
+    pub fn fit(
+        x: &X,
+        y: &Y,
+        parameters: ElasticNetCVParameters,
+    ) -> Result<ElasticNetCV<TX, TY, X, Y>, Failed> {
+        let path_parameters = RegularizationPathParameters {
+            alphas: parameters.alphas,
+            n_alphas: parameters.n_alphas,
+            eps: parameters.eps,
+            l1_ratio: 1.0,
+            normalize: parameters.normalize,
+            tol: parameters.tol,
+            max_iter: parameters.max_iter,
+        };
+
+        let result = fit_path_cv(x, y, &parameters.l1_ratio, path_parameters, &parameters.cv)?;
+
+        Ok(ElasticNetCV {
+            coefficients: Some(result.coefficients),
+            intercept: Some(result.intercept),
+            alpha: Some(result.alpha),
+            l1_ratio: Some(result.l1_ratio),
+            alphas: Some(result.alphas),
+            mse_path: Some(result.mse_path),
+            _phantom_ty: PhantomData,
+            _phantom_y: PhantomData,
+        })
+    }
+
+    /// Predict target values from `x`
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict(&self, x: &X) -> Result<Y, Failed> {
+        let (nrows, _) = x.shape();
+        let mut y_hat = x.matmul(self.coefficients());
+        let bias = X::fill(nrows, 1, self.intercept.unwrap());
+        y_hat.add_mut(&bias);
+        Ok(Y::from_iterator(
+            y_hat.iterator(0).map(|&v| TY::from(v).unwrap()),
+            nrows,
+        ))
+    }
+
+    /// Get estimates regression coefficients
+    pub fn coefficients(&self) -> &X {
+        self.coefficients.as_ref().unwrap()
+    }
+
+    /// Get estimate of intercept
+    pub fn intercept(&self) -> &TX {
+        self.intercept.as_ref().unwrap()
+    }
+
+    /// Regularization parameter chosen by cross-validation
+    pub fn alpha(&self) -> f64 {
+        self.alpha.unwrap()
+    }
+
+    /// Mixing parameter chosen by cross-validation
+    pub fn l1_ratio(&self) -> f64 {
+        self.l1_ratio.unwrap()
+    }
+
+    /// Grid of regularization parameters for every value of `l1_ratio`, in decreasing order
+    pub fn alphas(&self) -> &Vec<Vec<f64>> {
+        self.alphas.as_ref().unwrap()
+    }
+
+    /// Mean squared error on every fold, indexed by `l1_ratio`, `alpha` and fold
+    pub fn mse_path(&self) -> &Vec<Vec<Vec<f64>>> {
+        self.mse_path.as_ref().unwrap()
+    }
+}
+
+/// Outcome of a cross-validated search over regularization paths.
+pub(crate) struct PathCVResult<TX: FloatNumber, X: Array2<TX>> {
+    pub(crate) coefficients: X,
+    pub(crate) intercept: TX,
+    pub(crate) alpha: f64,
+    pub(crate) l1_ratio: f64,
+    pub(crate) alphas: Vec<Vec<f64>>,
+    pub(crate) mse_path: Vec<Vec<Vec<f64>>>,
+}
+
+/// Fits a regularization path on every fold of `cv` for every value of `l1_ratios`
+/// and refits the best pair of parameters on the whole dataset.
+pub(crate) fn fit_path_cv<
+    TX: FloatNumber + RealNumber,
+    TY: Number,
+    X: Array2<TX>,
+    Y: Array1<TY>,
+>(
+    x: &X,
+    y: &Y,
+    l1_ratios: &[f64],
+    parameters: RegularizationPathParameters,
+    cv: &KFold,
+) -> Result<PathCVResult<TX, X>, Failed> {
+    let (n, _) = x.shape();
+
+    if y.shape() != n {
+        return Err(Failed::fit("Number of rows in X should = len(y)"));
+    }
+
+    if l1_ratios.is_empty() {
+        return Err(Failed::fit("l1_ratio should not be empty"));
+    }
+
+    if cv.n_splits() < 2 || cv.n_splits() > n {
+        return Err(Failed::fit(&format!(
+            "Number of splits should be between 2 and {n}"
+        )));
+    }
+
+    let mut alphas = Vec::with_capacity(l1_ratios.len());
+    for &l1_ratio in l1_ratios {
+        if l1_ratio <= 0.0 || l1_ratio > 1.0 {
+            return Err(Failed::fit("l1_ratio should be > 0 and <= 1"));
+        }
+        let mut alphas_r = match &parameters.alphas {
+            Some(alphas) => {
+                check_alphas(alphas)?;
+                alphas.clone()
+            }
+            None => enet_alpha_grid(x, y, &parameters.clone().with_l1_ratio(l1_ratio))?,
+        };
+        alphas_r.sort_by(|a, b| b.partial_cmp(a).unwrap());
+        alphas.push(alphas_r);
+    }
+
+    let mut mse_path: Vec<Vec<Vec<f64>>> = alphas
+        .iter()
+        .map(|alphas_r| vec![Vec::with_capacity(cv.n_splits()); alphas_r.len()])
+        .collect();
+
+    for (train_idx, test_idx) in cv.split(x) {
+        let x_train = x.take(&train_idx, 0);
+        let y_train = y.take(&train_idx);
+        let x_test = x.take(&test_idx, 0);
+        let y_test: Vec<TX> = test_idx
+            .iter()
+            .map(|&i| TX::from(*y.get(i)).unwrap())
+            .collect();
+
+        for (r, &l1_ratio) in l1_ratios.iter().enumerate() {
+            let path = enet_path(
+                &x_train,
+                &y_train,
+                parameters
+                    .clone()
+                    .with_alphas(alphas[r].clone())
+                    .with_l1_ratio(l1_ratio),
+            )?;
+
+            let y_hat = x_test.matmul(path.coefficients());
+
+            for (k, &intercept) in path.intercepts().iter().enumerate() {
+                let mse = y_test.iter().enumerate().fold(0f64, |acc, (i, &y_i)| {
+                    let e = (y_i - *y_hat.get((i, k)) - intercept).to_f64().unwrap();
+                    acc + e * e
+                }) / y_test.len() as f64;
+                mse_path[r][k].push(mse);
+            }
+        }
+    }
+
+    let mut best = (0, 0);
+    let mut best_mse = f64::INFINITY;
+    for (r, mse_r) in mse_path.iter().enumerate() {
+        for (k, mse_k) in mse_r.iter().enumerate() {
+            let mean_mse = mse_k.iter().sum::<f64>() / mse_k.len() as f64;
+            if mean_mse < best_mse {
+                best_mse = mean_mse;
+                best = (r, k);
+            }
+        }
+    }
+
+    let (alpha, l1_ratio) = (alphas[best.0][best.1], l1_ratios[best.0]);
+
+    let path = enet_path(
+        x,
+        y,
+        parameters.with_alphas(vec![alpha]).with_l1_ratio(l1_ratio),
+    )?;
+
+    Ok(PathCVResult {
+        coefficients: path.coefficients().clone(),
+        intercept: path.intercepts()[0],
+        alpha,
+        l1_ratio,
+        alphas,
+        mse_path,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::basic::matrix::DenseMatrix;
+    use crate::metrics::mean_absolute_error;
+    use crate::test_datasets::longley;
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn elasticnet_cv_fit_predict() {
+        let (x, y) = longley();
+
+        let enet = ElasticNetCV::fit(
+            &x,
+            &y,
+            ElasticNetCVParameters::default()
+                .with_l1_ratio(vec![0.2, 0.8])
+                .with_n_alphas(20)
+                .with_cv(KFold::default().with_n_splits(4).with_shuffle(false)),
+        )
+        .unwrap();
+
+        assert_eq!(enet.alphas().len(), 2);
+        assert_eq!(enet.mse_path().len(), 2);
+        assert_eq!(enet.mse_path()[0].len(), 20);
+        assert_eq!(enet.mse_path()[0][0].len(), 4);
+        assert!([0.2, 0.8].contains(&enet.l1_ratio()));
+
+        let r = if enet.l1_ratio() == 0.2 { 0 } else { 1 };
+        assert!(enet.alphas()[r].contains(&enet.alpha()));
+
+        // the selected pair has the lowest average error
+        let mean = |v: &Vec<f64>| v.iter().sum::<f64>() / v.len() as f64;
+        let k = enet.alphas()[r]
+            .iter()
+            .position(|&a| a == enet.alpha())
+            .unwrap();
+        let best = mean(&enet.mse_path()[r][k]);
+        assert!(enet
+            .mse_path()
+            .iter()
+            .all(|mse_r| mse_r.iter().all(|mse_k| mean(mse_k) >= best)));
+
+        let y_hat = enet.predict(&x).unwrap();
+        assert!(mean_absolute_error(&y_hat, &y) < 2.0);
+    }
+
+    #[test]
+    fn elasticnet_cv_invalid_l1_ratio() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[1.0, 2.0],
+            &[2.0, 1.0],
+            &[3.0, 5.0],
+            &[4.0, 3.0],
+            &[5.0, 4.0],
+        ])
+        .unwrap();
+        let y: Vec<f64> = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+
+        let params = ElasticNetCVParameters::default()
+            .with_cv(KFold::default().with_n_splits(2))
+            .with_l1_ratio(vec![0.0]);
+        assert!(ElasticNetCV::fit(&x, &y, params).is_err());
+
+        let params = ElasticNetCVParameters::default()
+            .with_cv(KFold::default().with_n_splits(2))
+            .with_l1_ratio(vec![]);
+        assert!(ElasticNetCV::fit(&x, &y, params).is_err());
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn elasticnet_cv_invalid_alphas() {
+        let (x, y) = longley();
+
+        let params = ElasticNetCVParameters::default()
+            .with_cv(KFold::default().with_n_splits(2))
+            .with_alphas(vec![0.1, f64::NAN]);
+        assert!(ElasticNetCV::fit(&x, &y, params).is_err());
+
+        let params = ElasticNetCVParameters::default()
+            .with_cv(KFold::default().with_n_splits(2))
+            .with_alphas(vec![]);
+        assert!(ElasticNetCV::fit(&x, &y, params).is_err());
+    }
+}
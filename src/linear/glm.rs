@@ -0,0 +1,845 @@
+//! # Generalized Linear Models
+//!
+//! [Linear regression](../linear_regression/index.html) assumes that the response is normally distributed with constant variance.
+//! Counts, claim amounts, durations and many other quantities are non-negative and their variance grows with their mean,
+//! so this assumption does not hold. Generalized linear models relate the expected value \\(\mu\\) of the response to a linear predictor through a link function \\(g\\)
+//!
+//! \\[g(\mu) = \beta_0 + \sum_{i=1}^n \beta_iX_i\\]
+//!
+//! and assume a response distribution from the exponential family with variance \\(Var(y) \propto V(\mu)\\).
+//! `smartcore` supports distributions from the Tweedie family, where \\(V(\mu) = \mu^p\\):
+//!
+//! * _Poisson_, \\(p = 1\\), for counts, e.g. number of claims.
+//! * _Gamma_, \\(p = 2\\), for positive continuous values, e.g. claim severity.
+//! * _Tweedie_ with any \\(p = 0\\) or \\(p \geq 1\\). \\(1 < p < 2\\) is the compound Poisson-Gamma distribution which has a point mass at zero, e.g. total claim amount.
+//!
+//! and two link functions, \\(g(\mu) = \ln \mu\\) (_log_, the default) and \\(g(\mu) = \mu\\) (_identity_).
+//!
+//! The coefficients minimize the mean deviance with an optional L2 penalty on all coefficients but the intercept
+//!
+//! \\[\frac{1}{2 \sum_i w_i} \sum_i w_i d(y_i, \mu_i) + \frac{\alpha}{2} \lVert \beta \rVert_2^2\\]
+//!
+//! where \\(w_i\\) are sample weights and \\(d\\) is the unit deviance, see [mean Tweedie deviance](../../metrics/mean_tweedie_deviance/index.html).
+//! The objective is minimized by iteratively reweighted least squares (IRLS). Every iteration solves a weighted least squares problem
+//! with [Cholesky](../../linalg/cholesky/index.html) or [QR](../../linalg/qr/index.html) decomposition; steps that increase the objective or
+//! produce a mean outside of the domain of the distribution are halved.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::basic::matrix::DenseMatrix;
+//! use smartcore::linear::glm::*;
+//! use smartcore::metrics::mean_poisson_deviance;
+//!
+//! // number of claims given age of the driver and power of the car
+//! let x = DenseMatrix::from_2d_array(&[
+//!             &[0.2, 0.5],
+//!             &[0.4, 0.1],
+//!             &[0.5, 0.9],
+//!             &[0.7, 0.3],
+//!             &[0.9, 0.6],
+//!             &[0.3, 0.2],
+//!             &[0.6, 0.7],
+//!             &[0.8, 0.8],
+//!         ]).unwrap();
+//! let y: Vec<f64> = vec![2., 0., 3., 1., 2., 0., 2., 4.];
+//!
+//! let glm = GLM::fit(&x, &y, GLMParameters::default().with_family(GLMFamily::Poisson)).unwrap();
+//!
+//! let y_hat = glm.predict(&x).unwrap();
+//! let deviance = mean_poisson_deviance(&y, &y_hat);
+//! ```
+//!
+//! ## References:
+//!
+//! * ["Generalized Linear Models", McCullagh P., Nelder J.A., 2nd ed., 2.5 Algorithms for fitting](https://doi.org/10.1007/978-1-4899-3242-6)
+//! * ["Statistical Theory and Modelling", Jørgensen B., The Theory of Dispersion Models, 4. Tweedie Models](https://www.routledge.com/The-Theory-of-Dispersion-Models/Jorgensen/p/book/9780412997112)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::algorithm::sample_weight::check_sample_weight;
+use crate::api::{Predictor, SupervisedEstimator};
+use crate::error::Failed;
+use crate::linalg::basic::arrays::{Array1, Array2};
+use crate::linalg::traits::cholesky::CholeskyDecomposable;
+use crate::linalg::traits::qr::QRDecomposable;
+use crate::linear::sqrt_weigh_rows;
+use crate::metrics::mean_tweedie_deviance::{check_tweedie_domain, tweedie_unit_deviance};
+use crate::numbers::basenum::Number;
+use crate::numbers::realnum::RealNumber;
+
+/// Maximum number of times a step of IRLS is halved before the iterations stop.
+const MAX_STEP_HALVINGS: usize = 30;
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Default)]
+/// Distribution of the response.
+pub enum GLMFamily {
+    /// Poisson distribution, \\(V(\mu) = \mu\\), for non-negative targets
+    #[default]
+    Poisson,
+    /// Gamma distribution, \\(V(\mu) = \mu^2\\), for positive targets
+    Gamma,
+    /// Tweedie distribution, \\(V(\mu) = \mu^p\\) with power \\(p = 0\\) or \\(p \geq 1\\)
+    Tweedie(f64),
+}
+
+impl GLMFamily {
+    /// Power of the variance function.
+    pub fn power(&self) -> f64 {
+        match self {
+            GLMFamily::Poisson => 1.,
+            GLMFamily::Gamma => 2.,
+            GLMFamily::Tweedie(power) => *power,
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+/// Link between the mean of the response and the linear predictor.
+pub enum GLMLink {
+    /// \\(g(\mu) = \ln \mu\\), predictions are always positive
+    #[default]
+    Log,
+    /// \\(g(\mu) = \mu\\)
+    Identity,
+}
+
+impl GLMLink {
+    fn link<T: RealNumber>(&self, mu: T) -> T {
+        match self {
+            GLMLink::Log => mu.ln(),
+            GLMLink::Identity => mu,
+        }
+    }
+
+    fn inverse<T: RealNumber>(&self, eta: T) -> T {
+        match self {
+            GLMLink::Log => eta.exp(),
+            GLMLink::Identity => eta,
+        }
+    }
+
+    fn derivative<T: RealNumber>(&self, mu: T) -> T {
+        match self {
+            GLMLink::Log => T::one() / mu,
+            GLMLink::Identity => T::one(),
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+/// Approach to use for the weighted least squares problem solved at every IRLS iteration. Cholesky is more efficient but QR is more stable.
+pub enum GLMSolverName {
+    /// Cholesky decomposition of the normal equations, see [Cholesky](../../linalg/cholesky/index.html)
+    #[default]
+    Cholesky,
+    /// QR decomposition, see [QR](../../linalg/qr/index.html)
+    QR,
+}
+
+/// Generalized linear model parameters
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct GLMParameters<T: Number + RealNumber> {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Distribution of the response.
+    pub family: GLMFamily,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Link function.
+    pub link: GLMLink,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Solver to use for the weighted least squares problems.
+    pub solver: GLMSolverName,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Strength of the L2 penalty, 0 for an unpenalized fit.
+    pub alpha: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Maximum number of IRLS iterations.
+    pub max_iter: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The iterations stop when the relative change of the objective is smaller than `tol`.
+    pub tol: T,
+}
+
+impl<T: Number + RealNumber> GLMParameters<T> {
+    /// Distribution of the response.
+    pub fn with_family(mut self, family: GLMFamily) -> Self {
+        self.family = family;
+        self
+    }
+    /// Link function.
+    pub fn with_link(mut self, link: GLMLink) -> Self {
+        self.link = link;
+        self
+    }
+    /// Solver to use for the weighted least squares problems.
+    pub fn with_solver(mut self, solver: GLMSolverName) -> Self {
+        self.solver = solver;
+        self
+    }
+    /// Strength of the L2 penalty.
+    pub fn with_alpha(mut self, alpha: T) -> Self {
+        self.alpha = alpha;
+        self
+    }
+    /// Maximum number of IRLS iterations.
+    pub fn with_max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+    /// Tolerance for the relative change of the objective.
+    pub fn with_tol(mut self, tol: T) -> Self {
+        self.tol = tol;
+        self
+    }
+}
+
+impl<T: Number + RealNumber> Default for GLMParameters<T> {
+    fn default() -> Self {
+        GLMParameters {
+            family: GLMFamily::default(),
+            link: GLMLink::default(),
+            solver: GLMSolverName::default(),
+            alpha: T::zero(),
+            max_iter: 100,
+            tol: T::from_f64(1e-8).unwrap(),
+        }
+    }
+}
+
+/// Generalized linear model grid search parameters
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct GLMSearchParameters<T: Number + RealNumber> {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Distribution of the response.
+    pub family: Vec<GLMFamily>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Link function.
+    pub link: Vec<GLMLink>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Strength of the L2 penalty.
+    pub alpha: Vec<T>,
+}
+
+/// Generalized linear model grid search iterator
+pub struct GLMSearchParametersIterator<T: Number + RealNumber> {
+    glm_search_parameters: GLMSearchParameters<T>,
+    current_family: usize,
+    current_link: usize,
+    current_alpha: usize,
+}
+
+impl<T: Number + RealNumber> IntoIterator for GLMSearchParameters<T> {
+    type Item = GLMParameters<T>;
+    type IntoIter = GLMSearchParametersIterator<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        GLMSearchParametersIterator {
+            glm_search_parameters: self,
+            current_family: 0,
+            current_link: 0,
+            current_alpha: 0,
+        }
+    }
+}
+
+impl<T: Number + RealNumber> Iterator for GLMSearchParametersIterator<T> {
+    type Item = GLMParameters<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.current_family == self.glm_search_parameters.family.len()
+            && self.current_link == self.glm_search_parameters.link.len()
+            && self.current_alpha == self.glm_search_parameters.alpha.len()
+        {
+            return None;
+        }
+
+        let next = GLMParameters {
+            family: self.glm_search_parameters.family[self.current_family].clone(),
+            link: self.glm_search_parameters.link[self.current_link].clone(),
+            alpha: self.glm_search_parameters.alpha[self.current_alpha],
+            ..Default::default()
+        };
+
+        if self.current_family + 1 < self.glm_search_parameters.family.len() {
+            self.current_family += 1;
+        } else if self.current_link + 1 < self.glm_search_parameters.link.len() {
+            self.current_family = 0;
+            self.current_link += 1;
+        } else if self.current_alpha + 1 < self.glm_search_parameters.alpha.len() {
+            self.current_family = 0;
+            self.current_link = 0;
+            self.current_alpha += 1;
+        } else {
+            self.current_family += 1;
+            self.current_link += 1;
+            self.current_alpha += 1;
+        }
+
+        Some(next)
+    }
+}
+
+impl<T: Number + RealNumber> Default for GLMSearchParameters<T> {
+    fn default() -> Self {
+        let default_params = GLMParameters::default();
+
+        GLMSearchParameters {
+            family: vec![default_params.family],
+            link: vec![default_params.link],
+            alpha: vec![default_params.alpha],
+        }
+    }
+}
+
+/// Generalized linear model
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+pub struct GLM<
+    TX: Number + RealNumber,
+    TY: Number,
+    X: Array2<TX> + CholeskyDecomposable<TX> + QRDecomposable<TX>,
+    Y: Array1<TY>,
+> {
+    coefficients: Option<X>,
+    intercept: Option<TX>,
+    link: GLMLink,
+    n_iter: usize,
+    _phantom_ty: PhantomData<TY>,
+    _phantom_y: PhantomData<Y>,
+}
+
+impl<
+        TX: Number + RealNumber,
+        TY: Number,
+        X: Array2<TX> + CholeskyDecomposable<TX> + QRDecomposable<TX>,
+        Y: Array1<TY>,
+    > PartialEq for GLM<TX, TY, X, Y>
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.link == other.link
+            && self.intercept() == other.intercept()
+            && self.coefficients().shape() == other.coefficients().shape()
+            && self
+                .coefficients()
+                .iterator(0)
+                .zip(other.coefficients().iterator(0))
+                .all(|(&a, &b)| (a - b).abs() <= TX::epsilon())
+    }
+}
+
+impl<
+        TX: Number + RealNumber,
+        TY: Number,
+        X: Array2<TX> + CholeskyDecomposable<TX> + QRDecomposable<TX>,
+        Y: Array1<TY>,
+    > SupervisedEstimator<X, Y, GLMParameters<TX>> for GLM<TX, TY, X, Y>
+{
+    fn new() -> Self {
+        Self {
+            coefficients: Option::None,
+            intercept: Option::None,
+            link: GLMLink::default(),
+            n_iter: 0,
+            _phantom_ty: PhantomData,
+            _phantom_y: PhantomData,
+        }
+    }
+
+    fn fit(x: &X, y: &Y, parameters: GLMParameters<TX>) -> Result<Self, Failed> {
+        GLM::fit(x, y, parameters)
+    }
+}
+
+impl<
+        TX: Number + RealNumber,
+        TY: Number,
+        X: Array2<TX> + CholeskyDecomposable<TX> + QRDecomposable<TX>,
+        Y: Array1<TY>,
+    > Predictor<X, Y> for GLM<TX, TY, X, Y>
+{
+    fn predict(&self, x: &X) -> Result<Y, Failed> {
+        self.predict(x)
+    }
+}
+
+impl<
+        TX: Number + RealNumber,
+        TY: Number,
+        X: Array2<TX> + CholeskyDecomposable<TX> + QRDecomposable<TX>,
+        Y: Array1<TY>,
+    > GLM<TX, TY, X, Y>
+{
+    /// Fits generalized linear model to your data.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - target values
+    /// * `parameters` - other parameters, use `Default::default()` to set parameters to default values.
+    pub fn fit(x: &X, y: &Y, parameters: GLMParameters<TX>) -> Result<GLM<TX, TY, X, Y>, Failed> {
+        Self::fit_with_sample_weight(x, y, &vec![TX::one(); x.shape().0], parameters)
+    }
+
+    /// Fits generalized linear model to your data, minimizing the weighted mean deviance.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - target values
+    /// * `sample_weight` - non-negative weight of every observation
+    /// * `parameters` - other parameters, use `Default::default()` to set parameters to default values.
+    pub fn fit_with_sample_weight(
+        x: &X,
+        y: &Y,
+        sample_weight: &[TX],
+        parameters: GLMParameters<TX>,
+    ) -> Result<GLM<TX, TY, X, Y>, Failed> {
+        let (n, p) = x.shape();
+
+        if y.shape() != n {
+            return Err(Failed::fit("Number of rows in X should = len(y)"));
+        }
+
+        check_sample_weight(sample_weight, n)?;
+
+        if parameters.alpha < TX::zero() {
+            return Err(Failed::fit(&format!(
+                "alpha should be >= 0, got {}",
+                parameters.alpha
+            )));
+        }
+
+        let power = parameters.family.power();
+        let y: Vec<TX> = y.iterator(0).map(|&v| TX::from(v).unwrap()).collect();
+        for y_i in y.iter() {
+            check_tweedie_domain(power, y_i.to_f64().unwrap(), false)
+                .map_err(|msg| Failed::fit(&msg))?;
+        }
+
+        let total_weight: TX = sample_weight.iter().copied().sum();
+        let y_mean = y
+            .iter()
+            .zip(sample_weight.iter())
+            .map(|(&y_i, &w_i)| y_i * w_i)
+            .sum::<TX>()
+            / total_weight;
+        if (power > 0. || parameters.link == GLMLink::Log) && y_mean <= TX::zero() {
+            return Err(Failed::fit(
+                "Weighted mean of y should be positive for this family and link",
+            ));
+        }
+
+        let irls = IRLS {
+            a: x.h_stack(&X::ones(n, 1)),
+            y,
+            sample_weight,
+            total_weight,
+            power,
+            link: &parameters.link,
+            alpha: parameters.alpha,
+        };
+
+        // start from the constant model that predicts the mean of y
+        let mut beta = vec![TX::zero(); p + 1];
+        beta[p] = parameters.link.link(y_mean);
+        let mut objective = irls
+            .objective(&beta)
+            .ok_or_else(|| Failed::fit("The mean of y is out of the domain of the distribution"))?;
+
+        let mut n_iter = 0;
+        while n_iter < parameters.max_iter {
+            n_iter += 1;
+            let mut step = irls.solve_weighted_least_squares(&beta, &parameters.solver)?;
+            let mut step_objective = irls.objective(&step);
+            let mut halvings = 0;
+            while step_objective.is_none_or(|f| f > objective) && halvings < MAX_STEP_HALVINGS {
+                for (s_j, &b_j) in step.iter_mut().zip(beta.iter()) {
+                    *s_j = (*s_j + b_j) * <TX as RealNumber>::half();
+                }
+                step_objective = irls.objective(&step);
+                halvings += 1;
+            }
+            let Some(step_objective) = step_objective.filter(|&f| f <= objective) else {
+                // no step decreases the objective, beta is optimal up to numerical precision
+                break;
+            };
+            let change = (objective - step_objective).abs();
+            beta = step;
+            objective = step_objective;
+            if change <= parameters.tol * (objective.abs() + TX::from_f64(0.1).unwrap()) {
+                break;
+            }
+        }
+
+        Ok(GLM {
+            coefficients: Some(X::from_iterator(beta[..p].iter().copied(), p, 1, 0)),
+            intercept: Some(beta[p]),
+            link: parameters.link,
+            n_iter,
+            _phantom_ty: PhantomData,
+            _phantom_y: PhantomData,
+        })
+    }
+
+    /// Predict the mean of the response for every observation in `x`
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict(&self, x: &X) -> Result<Y, Failed> {
+        let (nrows, ncols) = x.shape();
+        let (n_coefficients, _) = self.coefficients().shape();
+        if ncols != n_coefficients {
+            return Err(Failed::predict(&format!(
+                "Number of features of X {ncols} doesn't match number of coefficients {n_coefficients}"
+            )));
+        }
+        let eta = x.matmul(self.coefficients());
+        Ok(Y::from_iterator(
+            eta.iterator(0)
+                .map(|&eta_i| TY::from(self.link.inverse(eta_i + *self.intercept())).unwrap()),
+            nrows,
+        ))
+    }
+
+    /// Get estimates regression coefficients
+    pub fn coefficients(&self) -> &X {
+        self.coefficients.as_ref().unwrap()
+    }
+
+    /// Get estimate of intercept
+    pub fn intercept(&self) -> &TX {
+        self.intercept.as_ref().unwrap()
+    }
+
+    /// Number of IRLS iterations run by `fit`
+    pub fn n_iter(&self) -> usize {
+        self.n_iter
+    }
+}
+
+/// Training data and settings of an IRLS fit. The last element of every coefficient vector is the intercept.
+struct IRLS<'a, T: Number + RealNumber, X: Array2<T>> {
+    /// `x` with an appended column of ones
+    a: X,
+    y: Vec<T>,
+    sample_weight: &'a [T],
+    total_weight: T,
+    power: f64,
+    link: &'a GLMLink,
+    alpha: T,
+}
+
+impl<T: Number + RealNumber, X: Array2<T> + CholeskyDecomposable<T> + QRDecomposable<T>>
+    IRLS<'_, T, X>
+{
+    fn mean(&self, beta: &[T]) -> Vec<T> {
+        let (n, p) = self.a.shape();
+        (0..n)
+            .map(|i| {
+                let eta = (0..p).fold(T::zero(), |eta, j| eta + *self.a.get((i, j)) * beta[j]);
+                self.link.inverse(eta)
+            })
+            .collect()
+    }
+
+    /// Penalized mean deviance, `None` if the mean of some observation is out of the domain of the distribution.
+    fn objective(&self, beta: &[T]) -> Option<T> {
+        let mut deviance = 0f64;
+        for ((&y_i, mu_i), &w_i) in self
+            .y
+            .iter()
+            .zip(self.mean(beta))
+            .zip(self.sample_weight.iter())
+        {
+            let mu_i = mu_i.to_f64().unwrap();
+            check_tweedie_domain(self.power, mu_i, true).ok()?;
+            deviance += w_i.to_f64().unwrap()
+                * tweedie_unit_deviance(y_i.to_f64().unwrap(), mu_i, self.power);
+        }
+        let penalty = beta[..beta.len() - 1]
+            .iter()
+            .fold(T::zero(), |s, &b_j| s + b_j * b_j);
+        Some(
+            T::from_f64(deviance).unwrap() / (<T as RealNumber>::two() * self.total_weight)
+                + self.alpha * penalty * <T as RealNumber>::half(),
+        )
+    }
+
+    /// Solves the weighted least squares problem of the working response linearized at `beta`.
+    fn solve_weighted_least_squares(
+        &self,
+        beta: &[T],
+        solver: &GLMSolverName,
+    ) -> Result<Vec<T>, Failed> {
+        let (n, p) = self.a.shape();
+        let mu = self.mean(beta);
+        let power = T::from_f64(self.power).unwrap();
+
+        let mut working_weight = vec![T::zero(); n];
+        let mut z = X::zeros(n, 1);
+        for i in 0..n {
+            let d_i = self.link.derivative(mu[i]);
+            working_weight[i] = self.sample_weight[i] / (mu[i].powf(power) * d_i * d_i);
+            z.set((i, 0), self.link.link(mu[i]) + (self.y[i] - mu[i]) * d_i);
+        }
+
+        let a = sqrt_weigh_rows(&self.a, &working_weight);
+        let z = sqrt_weigh_rows(&z, &working_weight);
+        let penalty = self.alpha * self.total_weight;
+
+        let solution = match solver {
+            GLMSolverName::Cholesky => {
+                let a_t = a.transpose();
+                let mut a_t_a = a_t.matmul(&a);
+                for j in 0..p - 1 {
+                    a_t_a.add_element_mut((j, j), penalty);
+                }
+                a_t_a.cholesky_solve_mut(a_t.matmul(&z))?
+            }
+            GLMSolverName::QR => {
+                if penalty > T::zero() {
+                    let mut ridge = X::zeros(p - 1, p);
+                    for j in 0..p - 1 {
+                        ridge.set((j, j), penalty.sqrt());
+                    }
+                    a.v_stack(&ridge)
+                        .qr_solve_mut(z.v_stack(&X::zeros(p - 1, 1)))?
+                } else {
+                    a.qr_solve_mut(z)?
+                }
+            }
+        };
+
+        // QR leaves the residuals below the solution
+        Ok(solution.iterator(0).take(p).copied().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::basic::arrays::Array;
+    use crate::linalg::basic::matrix::DenseMatrix;
+    use crate::linear::linear_regression::LinearRegression;
+    use crate::metrics::mean_tweedie_deviance;
+
+    fn claims() -> (DenseMatrix<f64>, Vec<f64>, Vec<f64>) {
+        let x = DenseMatrix::from_2d_array(&[
+            &[0.2, 0.5],
+            &[0.4, 0.1],
+            &[0.5, 0.9],
+            &[0.7, 0.3],
+            &[0.9, 0.6],
+            &[0.3, 0.2],
+            &[0.6, 0.7],
+            &[0.8, 0.8],
+            &[0.1, 0.4],
+            &[0.5, 0.5],
+            &[0.4, 0.6],
+            &[0.9, 0.1],
+        ])
+        .unwrap();
+        let counts = vec![2., 0., 3., 1., 2., 0., 2., 4., 1., 1., 0., 1.];
+        let severity = vec![1.2, 0.8, 3.1, 1.5, 2.4, 0.7, 2.2, 3.9, 1.1, 1.6, 1.9, 0.9];
+        (x, counts, severity)
+    }
+
+    /// Gradient of the penalized mean deviance, zero at the optimum.
+    fn gradient(
+        x: &DenseMatrix<f64>,
+        y: &[f64],
+        glm: &GLM<f64, f64, DenseMatrix<f64>, Vec<f64>>,
+        power: f64,
+        alpha: f64,
+        link: GLMLink,
+    ) -> Vec<f64> {
+        let (n, p) = x.shape();
+        let mu = glm.predict(x).unwrap();
+        let mut grad = vec![0.; p + 1];
+        for i in 0..n {
+            let r = (y[i] - mu[i]) / (mu[i].powf(power) * link.derivative(mu[i])) / n as f64;
+            for (j, g_j) in grad.iter_mut().enumerate().take(p) {
+                *g_j -= r * x.get((i, j)) - alpha * glm.coefficients().get((j, 0)) / n as f64;
+            }
+            grad[p] -= r;
+        }
+        grad
+    }
+
+    #[test]
+    fn search_parameters() {
+        let parameters = GLMSearchParameters {
+            family: vec![GLMFamily::Poisson, GLMFamily::Gamma],
+            alpha: vec![0., 1.],
+            ..Default::default()
+        };
+        let mut iter = parameters.into_iter();
+        let next = iter.next().unwrap();
+        assert_eq!(next.family, GLMFamily::Poisson);
+        assert_eq!(next.alpha, 0.);
+        let next = iter.next().unwrap();
+        assert_eq!(next.family, GLMFamily::Gamma);
+        assert_eq!(next.alpha, 0.);
+        let next = iter.next().unwrap();
+        assert_eq!(next.family, GLMFamily::Poisson);
+        assert_eq!(next.alpha, 1.);
+        let next = iter.next().unwrap();
+        assert_eq!(next.family, GLMFamily::Gamma);
+        assert_eq!(next.alpha, 1.);
+        assert!(iter.next().is_none());
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn glm_fit_optimality() {
+        let (x, counts, severity) = claims();
+
+        let cases = [
+            (GLMFamily::Poisson, GLMLink::Log, 0., &counts),
+            (GLMFamily::Poisson, GLMLink::Identity, 0., &severity),
+            (GLMFamily::Poisson, GLMLink::Log, 0.1, &counts),
+            (GLMFamily::Gamma, GLMLink::Log, 0., &severity),
+            (GLMFamily::Gamma, GLMLink::Identity, 0.05, &severity),
+            (GLMFamily::Tweedie(1.5), GLMLink::Log, 0., &counts),
+            (GLMFamily::Tweedie(3.), GLMLink::Log, 0., &severity),
+        ];
+
+        for (family, link, alpha, y) in cases {
+            let power = family.power();
+            for solver in [GLMSolverName::Cholesky, GLMSolverName::QR] {
+                let glm = GLM::fit(
+                    &x,
+                    y,
+                    GLMParameters::default()
+                        .with_family(family.clone())
+                        .with_link(link.clone())
+                        .with_solver(solver)
+                        .with_alpha(alpha)
+                        .with_tol(1e-12),
+                )
+                .unwrap();
+                assert!(glm.n_iter() < 100);
+                let grad = gradient(&x, y, &glm, power, alpha, link.clone());
+                assert!(
+                    grad.iter().all(|g| g.abs() < 1e-6),
+                    "{family:?} {link:?} {alpha}: {grad:?}"
+                );
+            }
+        }
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn glm_normal_identity_is_ols() {
+        let (x, _, severity) = claims();
+
+        let glm = GLM::fit(
+            &x,
+            &severity,
+            GLMParameters::default()
+                .with_family(GLMFamily::Tweedie(0.))
+                .with_link(GLMLink::Identity),
+        )
+        .unwrap();
+        let ols = LinearRegression::fit(&x, &severity, Default::default()).unwrap();
+
+        assert!(glm.coefficients().approximate_eq(ols.coefficients(), 1e-8));
+        assert!((glm.intercept() - ols.intercept()).abs() < 1e-8);
+
+        let y_hat = glm.predict(&x).unwrap();
+        let deviance = mean_tweedie_deviance(&severity, &y_hat, 0.);
+        let baseline = mean_tweedie_deviance(&severity, &vec![1.775; 12], 0.);
+        assert!(deviance < baseline);
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn glm_fit_with_sample_weight() {
+        let (x, counts, _) = claims();
+
+        let sample_weight = vec![1., 2., 1., 1., 3., 1., 1., 2., 1., 1., 1., 2.];
+        let rows: Vec<usize> = sample_weight
+            .iter()
+            .enumerate()
+            .flat_map(|(i, &w)| vec![i; w as usize])
+            .collect();
+        let x_repeated = x.take(&rows, 0);
+        let y_repeated: Vec<f64> = rows.iter().map(|&i| counts[i]).collect();
+
+        let parameters = GLMParameters::default().with_alpha(0.1);
+        let weighted =
+            GLM::fit_with_sample_weight(&x, &counts, &sample_weight, parameters.clone()).unwrap();
+        let repeated = GLM::fit(&x_repeated, &y_repeated, parameters).unwrap();
+
+        assert!(weighted
+            .coefficients()
+            .approximate_eq(repeated.coefficients(), 1e-8));
+        assert!((weighted.intercept() - repeated.intercept()).abs() < 1e-8);
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn glm_invalid_input() {
+        let (x, counts, _) = claims();
+
+        // zero claims are out of the domain of the Gamma distribution
+        assert!(GLM::<f64, f64, DenseMatrix<f64>, Vec<f64>>::fit(
+            &x,
+            &counts,
+            GLMParameters::default().with_family(GLMFamily::Gamma)
+        )
+        .is_err());
+        // there is no Tweedie distribution with power between 0 and 1
+        assert!(GLM::<f64, f64, DenseMatrix<f64>, Vec<f64>>::fit(
+            &x,
+            &counts,
+            GLMParameters::default().with_family(GLMFamily::Tweedie(0.5))
+        )
+        .is_err());
+        assert!(GLM::<f64, f64, DenseMatrix<f64>, Vec<f64>>::fit(
+            &x,
+            &vec![0.; 12],
+            GLMParameters::default()
+        )
+        .is_err());
+
+        let glm: GLM<f64, f64, DenseMatrix<f64>, Vec<f64>> =
+            GLM::fit(&x, &counts, GLMParameters::default()).unwrap();
+        assert!(glm
+            .predict(&DenseMatrix::from_2d_array(&[&[1., 2., 3.]]).unwrap())
+            .is_err());
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde() {
+        let (x, counts, _) = claims();
+
+        let glm: GLM<f64, f64, DenseMatrix<f64>, Vec<f64>> =
+            GLM::fit(&x, &counts, GLMParameters::default()).unwrap();
+
+        let deserialized_glm: GLM<f64, f64, DenseMatrix<f64>, Vec<f64>> =
+            serde_json::from_str(&serde_json::to_string(&glm).unwrap()).unwrap();
+
+        assert_eq!(glm, deserialized_glm);
+    }
+}
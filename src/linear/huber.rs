@@ -0,0 +1,715 @@
+//! # Huber Regression
+//!
+//! [Linear regression](../linear_regression/index.html) minimizes the sum of squared residuals, so a handful of gross outliers can pull the fitted line arbitrarily far
+//! from the rest of the data. Huber regression replaces the square with a loss that is quadratic for small residuals and linear for large ones,
+//! which bounds the influence of any single observation. The scale \\(\sigma\\) of the residuals is estimated together with the coefficients by minimizing
+//!
+//! \\[\sum_{i=1}^n \left(\sigma + H_{\epsilon}\left(\frac{y_i - x_i^T\beta - \beta_0}{\sigma}\right)\sigma\right) + \alpha \lVert \beta \rVert_2^2, \quad H_{\epsilon}(z) = \begin{cases} z^2 & |z| \leq \epsilon \\\\ 2\epsilon|z| - \epsilon^2 & |z| > \epsilon \end{cases}\\]
+//!
+//! Observations with \\(|y_i - \hat{y}_i| > \epsilon \sigma\\) are treated as outliers. The default \\(\epsilon = 1.35\\) keeps 95% statistical efficiency
+//! when the errors are normally distributed. The objective is jointly convex in the coefficients and \\(\sigma\\), and can be minimized with
+//!
+//! * _LBFGS_, a quasi-Newton method, see [LBFGS](../../optimization/first_order/lbfgs/index.html), or
+//! * _IRLS_, iteratively reweighted least squares alternating a weighted ridge step for the coefficients with an exact update of \\(\sigma\\).
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::basic::matrix::DenseMatrix;
+//! use smartcore::linear::huber::*;
+//!
+//! let x = DenseMatrix::from_2d_array(&[
+//!             &[1.], &[2.], &[3.], &[4.], &[5.], &[6.], &[7.], &[8.], &[9.], &[10.],
+//!         ]).unwrap();
+//! // y = 2x + 1, the last observation is an outlier
+//! let y: Vec<f64> = vec![3.1, 4.9, 7.0, 9.1, 10.9, 13.0, 15.1, 16.9, 19.0, 60.0];
+//!
+//! let huber = HuberRegressor::fit(&x, &y, Default::default()).unwrap();
+//!
+//! let y_hat = huber.predict(&x).unwrap();
+//! let outliers = huber.outliers();
+//! ```
+//!
+//! ## References:
+//!
+//! * ["Robust Statistics", Huber P.J., Ronchetti E.M., 2nd ed., 7.7 Computation of Regression M-Estimates](https://onlinelibrary.wiley.com/doi/book/10.1002/9780470434697)
+//! * ["A robust hybrid of lasso and ridge regression", Owen A.B., 2007](https://statweb.stanford.edu/~owen/reports/hhu.pdf)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::api::{Predictor, SupervisedEstimator};
+use crate::error::Failed;
+use crate::linalg::basic::arrays::{Array1, Array2};
+use crate::linalg::traits::cholesky::CholeskyDecomposable;
+use crate::linear::{median, sqrt_weigh_rows};
+use crate::numbers::basenum::Number;
+use crate::numbers::floatnum::FloatNumber;
+use crate::numbers::realnum::RealNumber;
+use crate::optimization::first_order::lbfgs::LBFGS;
+use crate::optimization::first_order::FirstOrderOptimizer;
+use crate::optimization::line_search::Backtracking;
+use crate::optimization::FunctionOrder;
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+/// Solver to use for estimation of regression coefficients.
+pub enum HuberRegressorSolverName {
+    /// Limited-memory Broyden–Fletcher–Goldfarb–Shanno method, see [LBFGS](../../optimization/first_order/lbfgs/index.html)
+    #[default]
+    LBFGS,
+    /// Iteratively reweighted least squares
+    IRLS,
+}
+
+/// Huber regression parameters
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct HuberRegressorParameters<T: Number + FloatNumber> {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Solver to use for estimation of regression coefficients.
+    pub solver: HuberRegressorSolverName,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Residuals larger than `epsilon` times the scale are treated as outliers, should be >= 1.
+    pub epsilon: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Strength of the L2 penalty on the coefficients.
+    pub alpha: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Maximum number of iterations of the solver.
+    pub max_iter: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// LBFGS stops when the largest absolute component of the gradient is below `tol`,
+    /// IRLS when no coefficient changes by more than `tol` relative to the largest coefficient.
+    pub tol: T,
+}
+
+impl<T: Number + FloatNumber> HuberRegressorParameters<T> {
+    /// Solver to use for estimation of regression coefficients.
+    pub fn with_solver(mut self, solver: HuberRegressorSolverName) -> Self {
+        self.solver = solver;
+        self
+    }
+    /// Threshold, in units of the scale, above which residuals are treated as outliers.
+    pub fn with_epsilon(mut self, epsilon: T) -> Self {
+        self.epsilon = epsilon;
+        self
+    }
+    /// Strength of the L2 penalty on the coefficients.
+    pub fn with_alpha(mut self, alpha: T) -> Self {
+        self.alpha = alpha;
+        self
+    }
+    /// Maximum number of iterations of the solver.
+    pub fn with_max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+    /// Tolerance for the stopping criterion.
+    pub fn with_tol(mut self, tol: T) -> Self {
+        self.tol = tol;
+        self
+    }
+}
+
+impl<T: Number + FloatNumber> Default for HuberRegressorParameters<T> {
+    fn default() -> Self {
+        HuberRegressorParameters {
+            solver: HuberRegressorSolverName::default(),
+            epsilon: T::from_f64(1.35).unwrap(),
+            alpha: T::from_f64(1e-4).unwrap(),
+            max_iter: 100,
+            tol: T::from_f64(1e-5).unwrap(),
+        }
+    }
+}
+
+/// Huber regression grid search parameters
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct HuberRegressorSearchParameters<T: Number + FloatNumber> {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Threshold, in units of the scale, above which residuals are treated as outliers.
+    pub epsilon: Vec<T>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Strength of the L2 penalty on the coefficients.
+    pub alpha: Vec<T>,
+}
+
+/// Huber regression grid search iterator
+pub struct HuberRegressorSearchParametersIterator<T: Number + FloatNumber> {
+    huber_regressor_search_parameters: HuberRegressorSearchParameters<T>,
+    current_epsilon: usize,
+    current_alpha: usize,
+}
+
+impl<T: Number + FloatNumber> IntoIterator for HuberRegressorSearchParameters<T> {
+    type Item = HuberRegressorParameters<T>;
+    type IntoIter = HuberRegressorSearchParametersIterator<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        HuberRegressorSearchParametersIterator {
+            huber_regressor_search_parameters: self,
+            current_epsilon: 0,
+            current_alpha: 0,
+        }
+    }
+}
+
+impl<T: Number + FloatNumber> Iterator for HuberRegressorSearchParametersIterator<T> {
+    type Item = HuberRegressorParameters<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.current_epsilon == self.huber_regressor_search_parameters.epsilon.len()
+            && self.current_alpha == self.huber_regressor_search_parameters.alpha.len()
+        {
+            return None;
+        }
+
+        let next = HuberRegressorParameters {
+            epsilon: self.huber_regressor_search_parameters.epsilon[self.current_epsilon],
+            alpha: self.huber_regressor_search_parameters.alpha[self.current_alpha],
+            ..Default::default()
+        };
+
+        if self.current_epsilon + 1 < self.huber_regressor_search_parameters.epsilon.len() {
+            self.current_epsilon += 1;
+        } else if self.current_alpha + 1 < self.huber_regressor_search_parameters.alpha.len() {
+            self.current_epsilon = 0;
+            self.current_alpha += 1;
+        } else {
+            self.current_epsilon += 1;
+            self.current_alpha += 1;
+        }
+
+        Some(next)
+    }
+}
+
+impl<T: Number + FloatNumber> Default for HuberRegressorSearchParameters<T> {
+    fn default() -> Self {
+        let default_params = HuberRegressorParameters::default();
+
+        HuberRegressorSearchParameters {
+            epsilon: vec![default_params.epsilon],
+            alpha: vec![default_params.alpha],
+        }
+    }
+}
+
+/// Huber regression
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+pub struct HuberRegressor<
+    TX: Number + FloatNumber + RealNumber,
+    TY: Number,
+    X: Array2<TX> + CholeskyDecomposable<TX>,
+    Y: Array1<TY>,
+> {
+    coefficients: Option<X>,
+    intercept: Option<TX>,
+    scale: Option<TX>,
+    outliers: Option<Vec<bool>>,
+    n_iter: usize,
+    _phantom_ty: PhantomData<TY>,
+    _phantom_y: PhantomData<Y>,
+}
+
+impl<
+        TX: Number + FloatNumber + RealNumber,
+        TY: Number,
+        X: Array2<TX> + CholeskyDecomposable<TX>,
+        Y: Array1<TY>,
+    > PartialEq for HuberRegressor<TX, TY, X, Y>
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.intercept() == other.intercept()
+            && self.scale() == other.scale()
+            && self.coefficients().shape() == other.coefficients().shape()
+            && self
+                .coefficients()
+                .iterator(0)
+                .zip(other.coefficients().iterator(0))
+                .all(|(&a, &b)| (a - b).abs() <= TX::epsilon())
+    }
+}
+
+impl<
+        TX: Number + FloatNumber + RealNumber,
+        TY: Number,
+        X: Array2<TX> + CholeskyDecomposable<TX>,
+        Y: Array1<TY>,
+    > SupervisedEstimator<X, Y, HuberRegressorParameters<TX>> for HuberRegressor<TX, TY, X, Y>
+{
+    fn new() -> Self {
+        Self {
+            coefficients: Option::None,
+            intercept: Option::None,
+            scale: Option::None,
+            outliers: Option::None,
+            n_iter: 0,
+            _phantom_ty: PhantomData,
+            _phantom_y: PhantomData,
+        }
+    }
+
+    fn fit(x: &X, y: &Y, parameters: HuberRegressorParameters<TX>) -> Result<Self, Failed> {
+        HuberRegressor::fit(x, y, parameters)
+    }
+}
+
+impl<
+        TX: Number + FloatNumber + RealNumber,
+        TY: Number,
+        X: Array2<TX> + CholeskyDecomposable<TX>,
+        Y: Array1<TY>,
+    > Predictor<X, Y> for HuberRegressor<TX, TY, X, Y>
+{
+    fn predict(&self, x: &X) -> Result<Y, Failed> {
+        self.predict(x)
+    }
+}
+
+impl<
+        TX: Number + FloatNumber + RealNumber,
+        TY: Number,
+        X: Array2<TX> + CholeskyDecomposable<TX>,
+        Y: Array1<TY>,
+    > HuberRegressor<TX, TY, X, Y>
+{
+    /// Fits Huber regression to your data.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - target values
+    /// * `parameters` - other parameters, use `Default::default()` to set parameters to default values.
+    pub fn fit(
+        x: &X,
+        y: &Y,
+        parameters: HuberRegressorParameters<TX>,
+    ) -> Result<HuberRegressor<TX, TY, X, Y>, Failed> {
+        let (n, p) = x.shape();
+
+        if y.shape() != n {
+            return Err(Failed::fit("Number of rows in X should = len(y)"));
+        }
+        if n == 0 {
+            return Err(Failed::fit("X should have at least one row"));
+        }
+        if parameters.epsilon < TX::one() {
+            return Err(Failed::fit(&format!(
+                "epsilon should be >= 1, got {}",
+                parameters.epsilon
+            )));
+        }
+        if parameters.alpha < TX::zero() {
+            return Err(Failed::fit(&format!(
+                "alpha should be >= 0, got {}",
+                parameters.alpha
+            )));
+        }
+
+        let objective = HuberObjective {
+            a: x.h_stack(&X::ones(n, 1)),
+            y: y.iterator(0).map(|&v| TX::from(v).unwrap()).collect(),
+            epsilon: parameters.epsilon,
+            alpha: parameters.alpha,
+        };
+
+        // start from the constant model at the median with the normalized median absolute deviation as scale
+        let mut beta = vec![TX::zero(); p + 1];
+        let mut sorted_y = objective.y.clone();
+        beta[p] = median(&mut sorted_y);
+        let mut abs_deviation: Vec<TX> = objective.y.iter().map(|&v| (v - beta[p]).abs()).collect();
+        let mad = median(&mut abs_deviation) / TX::from_f64(0.6745).unwrap();
+        let sigma = if mad > TX::zero() { mad } else { TX::one() };
+
+        let (beta, sigma, n_iter) = match parameters.solver {
+            HuberRegressorSolverName::LBFGS => {
+                let mut theta = beta;
+                theta.push(sigma.ln());
+
+                let f = |theta: &Vec<TX>| -> TX {
+                    objective.value(&theta[..=p], theta[p + 1].exp(), None)
+                };
+                let df = |g: &mut Vec<TX>, theta: &Vec<TX>| {
+                    let sigma = theta[p + 1].exp();
+                    objective.value(&theta[..=p], sigma, Some(g));
+                    // chain rule for sigma = exp(theta[p + 1])
+                    g[p + 1] *= sigma;
+                };
+
+                let ls: Backtracking<TX> = Backtracking {
+                    order: FunctionOrder::THIRD,
+                    ..Default::default()
+                };
+                let optimizer = LBFGS {
+                    max_iter: parameters.max_iter,
+                    g_atol: parameters.tol.to_f64().unwrap(),
+                    ..Default::default()
+                };
+                let result = optimizer.optimize(&f, &df, &theta, &ls);
+                let sigma = result.x[p + 1].exp();
+                (result.x[..=p].to_vec(), sigma, result.iterations)
+            }
+            HuberRegressorSolverName::IRLS => objective.irls(beta, &parameters)?,
+        };
+
+        let residuals = objective.residuals(&beta);
+        let outliers = residuals
+            .iter()
+            .map(|r| r.abs() > parameters.epsilon * sigma)
+            .collect();
+
+        Ok(HuberRegressor {
+            coefficients: Some(X::from_iterator(beta[..p].iter().copied(), p, 1, 0)),
+            intercept: Some(beta[p]),
+            scale: Some(sigma),
+            outliers: Some(outliers),
+            n_iter,
+            _phantom_ty: PhantomData,
+            _phantom_y: PhantomData,
+        })
+    }
+
+    /// Predict target values from `x`
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict(&self, x: &X) -> Result<Y, Failed> {
+        let (nrows, ncols) = x.shape();
+        let (n_coefficients, _) = self.coefficients().shape();
+        if ncols != n_coefficients {
+            return Err(Failed::predict(&format!(
+                "Number of features of X {ncols} doesn't match number of coefficients {n_coefficients}"
+            )));
+        }
+        let y_hat = x.matmul(self.coefficients());
+        Ok(Y::from_iterator(
+            y_hat
+                .iterator(0)
+                .map(|&v| TY::from(v + *self.intercept()).unwrap()),
+            nrows,
+        ))
+    }
+
+    /// Get estimates regression coefficients
+    pub fn coefficients(&self) -> &X {
+        self.coefficients.as_ref().unwrap()
+    }
+
+    /// Get estimate of intercept
+    pub fn intercept(&self) -> &TX {
+        self.intercept.as_ref().unwrap()
+    }
+
+    /// Get estimate of the scale of the residuals
+    pub fn scale(&self) -> &TX {
+        self.scale.as_ref().unwrap()
+    }
+
+    /// Training observations with an absolute residual larger than `epsilon` times the scale
+    pub fn outliers(&self) -> &Vec<bool> {
+        self.outliers.as_ref().unwrap()
+    }
+
+    /// Number of iterations run by the solver
+    pub fn n_iter(&self) -> usize {
+        self.n_iter
+    }
+}
+
+/// Huber objective. Coefficient vectors hold the feature coefficients followed by the intercept.
+struct HuberObjective<T: Number + FloatNumber + RealNumber, X: Array2<T>> {
+    /// `x` with an appended column of ones
+    a: X,
+    y: Vec<T>,
+    epsilon: T,
+    alpha: T,
+}
+
+impl<T: Number + FloatNumber + RealNumber, X: Array2<T> + CholeskyDecomposable<T>>
+    HuberObjective<T, X>
+{
+    fn residuals(&self, beta: &[T]) -> Vec<T> {
+        let (n, p) = self.a.shape();
+        (0..n)
+            .map(|i| self.y[i] - (0..p).fold(T::zero(), |s, j| s + *self.a.get((i, j)) * beta[j]))
+            .collect()
+    }
+
+    /// Value of the objective, writes the gradient with respect to `beta` and `sigma` into `grad` when given.
+    fn value(&self, beta: &[T], sigma: T, grad: Option<&mut Vec<T>>) -> T {
+        let (n, p) = self.a.shape();
+        let two = <T as RealNumber>::two();
+        let residuals = self.residuals(beta);
+        let penalty = beta[..p - 1].iter().fold(T::zero(), |s, &b| s + b * b);
+        let mut f = self.alpha * penalty;
+        let mut dr = vec![T::zero(); n];
+        let mut d_sigma = T::zero();
+        for (r, dr_i) in residuals.iter().zip(dr.iter_mut()) {
+            let abs_r = r.abs();
+            if abs_r <= self.epsilon * sigma {
+                f += sigma + *r * *r / sigma;
+                *dr_i = -two * *r / sigma;
+                d_sigma += T::one() - *r * *r / (sigma * sigma);
+            } else {
+                f += sigma + two * self.epsilon * abs_r - self.epsilon * self.epsilon * sigma;
+                *dr_i = -two * self.epsilon * r.signum();
+                d_sigma += T::one() - self.epsilon * self.epsilon;
+            }
+        }
+        if let Some(g) = grad {
+            for j in 0..p {
+                g[j] = (0..n).fold(T::zero(), |s, i| s + dr[i] * *self.a.get((i, j)));
+                if j + 1 < p {
+                    g[j] += two * self.alpha * beta[j];
+                }
+            }
+            g[p] = d_sigma;
+        }
+        f
+    }
+
+    /// Scale minimizing the objective for fixed residuals. The objective is convex in `sigma`,
+    /// so its derivative is found with bisection.
+    fn optimal_scale(&self, residuals: &[T]) -> T {
+        let n = T::from_usize(residuals.len()).unwrap();
+        let eps2 = self.epsilon * self.epsilon;
+        let derivative = |sigma: T| {
+            residuals.iter().fold(T::zero(), |s, &r| {
+                if r.abs() <= self.epsilon * sigma {
+                    s + T::one() - r * r / (sigma * sigma)
+                } else {
+                    s + T::one() - eps2
+                }
+            })
+        };
+        let max_abs = residuals.iter().fold(T::zero(), |m, r| m.max(r.abs()));
+        let rms = (residuals.iter().fold(T::zero(), |s, &r| s + r * r) / n).sqrt();
+        let mut hi = <T as RealNumber>::two() * (max_abs / self.epsilon).max(rms);
+        if hi <= T::zero() {
+            // perfect fit, any positive scale is optimal
+            return T::one();
+        }
+        let mut lo = hi * T::epsilon();
+        if derivative(lo) >= T::zero() {
+            return lo;
+        }
+        for _ in 0..100 {
+            let mid = (lo + hi) * <T as RealNumber>::half();
+            if derivative(mid) < T::zero() {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        (lo + hi) * <T as RealNumber>::half()
+    }
+
+    /// Alternates an exact update of the scale with a reweighted ridge step for the coefficients.
+    /// With the scale fixed, the weighted least squares problem majorizes the Huber loss, so neither step increases the objective.
+    fn irls(
+        &self,
+        mut beta: Vec<T>,
+        parameters: &HuberRegressorParameters<T>,
+    ) -> Result<(Vec<T>, T, usize), Failed> {
+        let (n, p) = self.a.shape();
+        let mut n_iter = 0;
+        while n_iter < parameters.max_iter {
+            n_iter += 1;
+            let residuals = self.residuals(&beta);
+            let sigma = self.optimal_scale(&residuals);
+
+            let weights: Vec<T> = residuals
+                .iter()
+                .map(|r| {
+                    if r.abs() <= self.epsilon * sigma {
+                        T::one()
+                    } else {
+                        self.epsilon * sigma / r.abs()
+                    }
+                })
+                .collect();
+            let a = sqrt_weigh_rows(&self.a, &weights);
+            let z = sqrt_weigh_rows(&X::from_iterator(self.y.iter().copied(), n, 1, 0), &weights);
+            let a_t = a.transpose();
+            let mut a_t_a = a_t.matmul(&a);
+            for j in 0..p - 1 {
+                a_t_a.add_element_mut((j, j), self.alpha * sigma);
+            }
+            let solution = a_t_a.cholesky_solve_mut(a_t.matmul(&z))?;
+
+            let mut max_change = T::zero();
+            let mut max_beta = T::zero();
+            for (j, b_j) in beta.iter_mut().enumerate() {
+                let new_b_j = *solution.get((j, 0));
+                max_change = max_change.max((new_b_j - *b_j).abs());
+                max_beta = max_beta.max(new_b_j.abs());
+                *b_j = new_b_j;
+            }
+            if max_change <= parameters.tol * max_beta.max(T::one()) {
+                break;
+            }
+        }
+        let sigma = self.optimal_scale(&self.residuals(&beta));
+        Ok((beta, sigma, n_iter))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::basic::arrays::Array;
+    use crate::linalg::basic::matrix::DenseMatrix;
+    use crate::linear::linear_regression::LinearRegression;
+
+    fn data_with_outliers() -> (DenseMatrix<f64>, Vec<f64>) {
+        let x = DenseMatrix::from_2d_array(&[
+            &[1., 0.5],
+            &[2., 1.5],
+            &[3., 0.2],
+            &[4., 2.2],
+            &[5., 1.1],
+            &[6., 0.8],
+            &[7., 1.9],
+            &[8., 0.3],
+            &[9., 1.4],
+            &[10., 2.5],
+            &[11., 0.9],
+            &[12., 1.7],
+            &[13., 0.6],
+            &[14., 2.1],
+            &[15., 1.2],
+        ])
+        .unwrap();
+        // y = 2 x_1 - x_2 + 1 with small noise, the last three observations are outliers
+        let mut y: Vec<f64> = (0..15)
+            .map(|i| {
+                let noise = [
+                    0.1, -0.2, 0.05, 0.15, -0.1, 0.2, -0.05, 0.1, -0.15, 0.05, 0.1, -0.1,
+                ][i % 12];
+                2. * x.get((i, 0)) - x.get((i, 1)) + 1. + noise
+            })
+            .collect();
+        y[12] += 40.;
+        y[13] -= 35.;
+        y[14] += 50.;
+        (x, y)
+    }
+
+    #[test]
+    fn search_parameters() {
+        let parameters = HuberRegressorSearchParameters {
+            epsilon: vec![1.35, 2.],
+            alpha: vec![0.],
+        };
+        let mut iter = parameters.into_iter();
+        let next = iter.next().unwrap();
+        assert_eq!(next.epsilon, 1.35);
+        assert_eq!(next.alpha, 0.);
+        let next = iter.next().unwrap();
+        assert_eq!(next.epsilon, 2.);
+        assert_eq!(next.alpha, 0.);
+        assert!(iter.next().is_none());
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn huber_fit_predict() {
+        let (x, y) = data_with_outliers();
+
+        let ols = LinearRegression::fit(&x, &y, Default::default()).unwrap();
+        assert!((ols.coefficients().get((0, 0)) - 2.).abs() > 0.5);
+
+        for solver in [
+            HuberRegressorSolverName::LBFGS,
+            HuberRegressorSolverName::IRLS,
+        ] {
+            let huber = HuberRegressor::fit(
+                &x,
+                &y,
+                HuberRegressorParameters::default().with_solver(solver.clone()),
+            )
+            .unwrap();
+
+            assert!(
+                (huber.coefficients().get((0, 0)) - 2.).abs() < 0.1,
+                "{solver:?}"
+            );
+            assert!(
+                (huber.coefficients().get((1, 0)) + 1.).abs() < 0.3,
+                "{solver:?}"
+            );
+            assert!((huber.intercept() - 1.).abs() < 0.5, "{solver:?}");
+            assert_eq!(&huber.outliers()[12..], &[true, true, true]);
+
+            let y_hat = huber.predict(&x).unwrap();
+            assert!((0..12).all(|i| (y[i] - y_hat[i]).abs() < 0.5));
+        }
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn huber_solvers_agree() {
+        let (x, y) = data_with_outliers();
+
+        let lbfgs = HuberRegressor::fit(
+            &x,
+            &y,
+            HuberRegressorParameters::default()
+                .with_alpha(0.1)
+                .with_tol(1e-8)
+                .with_max_iter(1000),
+        )
+        .unwrap();
+        let irls = HuberRegressor::fit(
+            &x,
+            &y,
+            HuberRegressorParameters::default()
+                .with_solver(HuberRegressorSolverName::IRLS)
+                .with_alpha(0.1)
+                .with_tol(1e-10)
+                .with_max_iter(1000),
+        )
+        .unwrap();
+
+        assert!(lbfgs
+            .coefficients()
+            .approximate_eq(irls.coefficients(), 1e-4));
+        assert!((lbfgs.intercept() - irls.intercept()).abs() < 1e-4);
+        assert!((lbfgs.scale() - irls.scale()).abs() < 1e-4);
+
+        assert!(HuberRegressor::<f64, f64, DenseMatrix<f64>, Vec<f64>>::fit(
+            &x,
+            &y,
+            HuberRegressorParameters::default().with_epsilon(0.5)
+        )
+        .is_err());
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde() {
+        let (x, y) = data_with_outliers();
+
+        let huber: HuberRegressor<f64, f64, DenseMatrix<f64>, Vec<f64>> =
+            HuberRegressor::fit(&x, &y, Default::default()).unwrap();
+
+        let deserialized_huber: HuberRegressor<f64, f64, DenseMatrix<f64>, Vec<f64>> =
+            serde_json::from_str(&serde_json::to_string(&huber).unwrap()).unwrap();
+
+        assert_eq!(huber, deserialized_huber);
+    }
+}
@@ -13,6 +13,7 @@
 //!
 //! This problem is solved with an interior-point method that is comparable to coordinate descent in solving large problems with modest accuracy,
 //! but is able to solve them with high accuracy with relatively small additional computational cost.
+//! To fit a whole grid of regularization parameters at once use [`lasso_path`](../coordinate_descent/fn.lasso_path.html).
 //!
 //! ## References:
 //!
@@ -28,10 +29,12 @@ use std::marker::PhantomData;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+use crate::algorithm::sample_weight::check_sample_weight;
 use crate::api::{Predictor, SupervisedEstimator};
 use crate::error::Failed;
-use crate::linalg::basic::arrays::{Array1, Array2, ArrayView1};
+use crate::linalg::basic::arrays::{Array1, Array2};
 use crate::linear::lasso_optimizer::InteriorPointOptimizer;
+use crate::linear::{sqrt_weigh_rows, weighted_col_mean_std};
 use crate::numbers::basenum::Number;
 use crate::numbers::floatnum::FloatNumber;
 use crate::numbers::realnum::RealNumber;
@@ -244,6 +247,21 @@ impl<TX: FloatNumber + RealNumber, TY: Number, X: Array2<TX>, Y: Array1<TY>> Las
     /// * `y` - target values
     /// * `parameters` - other parameters, use `Default::default()` to set parameters to default values.
     pub fn fit(x: &X, y: &Y, parameters: LassoParameters) -> Result<Lasso<TX, TY, X, Y>, Failed> {
+        Self::fit_with_sample_weight(x, y, &vec![TX::one(); x.shape().0], parameters)
+    }
+
+    /// Fits Lasso regression to your data, each observation contributing to the loss in proportion to its weight.
+    /// Weights are rescaled to sum up to the number of observations, so that `alpha` keeps its meaning.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - target values
+    /// * `sample_weight` - non-negative weight of every observation
+    /// * `parameters` - other parameters, use `Default::default()` to set parameters to default values.
+    pub fn fit_with_sample_weight(
+        x: &X,
+        y: &Y,
+        sample_weight: &[TX],
+        parameters: LassoParameters,
+    ) -> Result<Lasso<TX, TY, X, Y>, Failed> {
         let (n, p) = x.shape();
 
         if n <= p {
@@ -268,18 +286,38 @@ impl<TX: FloatNumber + RealNumber, TY: Number, X: Array2<TX>, Y: Array1<TY>> Las
             return Err(Failed::fit("Number of rows in X should = len(y)"));
         }
 
+        check_sample_weight(sample_weight, n)?;
+
+        let weight_sum: TX = sample_weight.iter().copied().sum();
+        let sample_weight: Vec<TX> = sample_weight
+            .iter()
+            .map(|&w| w * TX::from_usize(n).unwrap() / weight_sum)
+            .collect();
+
         let y: Vec<TX> = y.iterator(0).map(|&v| TX::from(v).unwrap()).collect();
+        let y_mean = y
+            .iter()
+            .zip(sample_weight.iter())
+            .map(|(&y_i, &w_i)| y_i * w_i)
+            .sum::<TX>()
+            / TX::from_usize(n).unwrap();
+        let y_centered: Vec<TX> = y
+            .iter()
+            .zip(sample_weight.iter())
+            .map(|(&y_i, &w_i)| (y_i - y_mean) * w_i.sqrt())
+            .collect();
 
         let l1_reg = TX::from_f64(parameters.alpha * n as f64).unwrap();
 
         let (w, b) = if parameters.normalize {
-            let (scaled_x, col_mean, col_std) = Self::rescale_x(x)?;
+            let (scaled_x, col_mean, col_std) = Self::rescale_x(x, &sample_weight)?;
+            let scaled_x = sqrt_weigh_rows(&scaled_x, &sample_weight);
 
             let mut optimizer = InteriorPointOptimizer::new(&scaled_x, p);
 
-            let mut w = optimizer.optimize(
+            let mut w = optimizer.optimize_centered(
                 &scaled_x,
-                &y,
+                &y_centered,
                 l1_reg,
                 parameters.max_iter,
                 TX::from_f64(parameters.tol).unwrap(),
@@ -295,20 +333,22 @@ impl<TX: FloatNumber + RealNumber, TY: Number, X: Array2<TX>, Y: Array1<TY>> Las
                 b += w[i] * *col_mean_i;
             }
 
-            b = TX::from_f64(y.mean_by()).unwrap() - b;
+            b = y_mean - b;
             (X::from_column(&w), b)
         } else {
-            let mut optimizer = InteriorPointOptimizer::new(x, p);
+            let x = sqrt_weigh_rows(x, &sample_weight);
 
-            let w = optimizer.optimize(
-                x,
-                &y,
+            let mut optimizer = InteriorPointOptimizer::new(&x, p);
+
+            let w = optimizer.optimize_centered(
+                &x,
+                &y_centered,
                 l1_reg,
                 parameters.max_iter,
                 TX::from_f64(parameters.tol).unwrap(),
             )?;
 
-            (X::from_column(&w), TX::from_f64(y.mean_by()).unwrap())
+            (X::from_column(&w), y_mean)
         };
 
         Ok(Lasso {
@@ -342,17 +382,8 @@ impl<TX: FloatNumber + RealNumber, TY: Number, X: Array2<TX>, Y: Array1<TY>> Las
         self.intercept.as_ref().unwrap()
     }
 
-    fn rescale_x(x: &X) -> Result<(X, Vec<TX>, Vec<TX>), Failed> {
-        let col_mean: Vec<TX> = x
-            .mean_by(0)
-            .iter()
-            .map(|&v| TX::from_f64(v).unwrap())
-            .collect();
-        let col_std: Vec<TX> = x
-            .std_dev(0)
-            .iter()
-            .map(|&v| TX::from_f64(v).unwrap())
-            .collect();
+    fn rescale_x(x: &X, sample_weight: &[TX]) -> Result<(X, Vec<TX>, Vec<TX>), Failed> {
+        let (col_mean, col_std) = weighted_col_mean_std(x, sample_weight);
 
         for (i, col_std_i) in col_std.iter().enumerate() {
             if (*col_std_i - TX::zero()).abs() < TX::epsilon() {
@@ -371,6 +402,7 @@ mod tests {
     use super::*;
     use crate::linalg::basic::matrix::DenseMatrix;
     use crate::metrics::mean_absolute_error;
+    use crate::test_datasets::{assert_weights_repeat_rows, longley};
 
     #[test]
     fn search_parameters() {
@@ -484,4 +516,44 @@ mod tests {
 
     //     assert_eq!(lr, deserialized_lr);
     // }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn lasso_fit_with_sample_weight() {
+        let (x, y) = longley();
+        let sample_weight = vec![
+            1., 2., 1., 3., 1., 1., 2., 1., 1., 1., 2., 1., 1., 1., 1., 2.,
+        ];
+
+        for parameters in [true, false].map(|normalize| {
+            LassoParameters::default()
+                .with_alpha(0.1)
+                .with_normalize(normalize)
+                .with_tol(1e-8)
+        }) {
+            assert_weights_repeat_rows(
+                &x,
+                &y,
+                &sample_weight,
+                |x_w, y_w, w| {
+                    Lasso::fit_with_sample_weight(x_w, y_w, w, parameters.clone())
+                        .and_then(|lr| lr.predict(&x))
+                        .unwrap()
+                },
+                |x_r, y_r| {
+                    Lasso::fit(x_r, y_r, parameters.clone())
+                        .and_then(|lr| lr.predict(&x))
+                        .unwrap()
+                },
+                1e-4,
+            );
+        }
+
+        assert!(
+            Lasso::fit_with_sample_weight(&x, &y, &sample_weight[1..], Default::default()).is_err()
+        );
+    }
 }
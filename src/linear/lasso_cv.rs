@@ -0,0 +1,314 @@
+//! # Lasso with Cross-Validation
+//!
+//! [Lasso](../lasso/index.html) with the regularization parameter \\(\alpha\\) chosen automatically by k-fold cross-validation.
+//!
+//! A decreasing grid of \\(\alpha\\) values is generated from the data, or taken from the parameters.
+//! On every fold the whole [regularization path](../coordinate_descent/index.html) is fitted on the training part with warm starts,
+//! and the mean squared error of every \\(\alpha\\) is measured on the held-out part.
+//! The value with the lowest error averaged over all folds is then used to fit the final model on the whole dataset.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::basic::matrix::DenseMatrix;
+//! use smartcore::linear::lasso_cv::*;
+//!
+//! // Longley dataset (https://www.statsmodels.org/stable/datasets/generated/longley.html)
+//! let x = DenseMatrix::from_2d_array(&[
+//!               &[234.289, 235.6, 159.0, 107.608, 1947., 60.323],
+//!               &[259.426, 232.5, 145.6, 108.632, 1948., 61.122],
+//!               &[258.054, 368.2, 161.6, 109.773, 1949., 60.171],
+//!               &[284.599, 335.1, 165.0, 110.929, 1950., 61.187],
+//!               &[328.975, 209.9, 309.9, 112.075, 1951., 63.221],
+//!               &[346.999, 193.2, 359.4, 113.270, 1952., 63.639],
+//!               &[365.385, 187.0, 354.7, 115.094, 1953., 64.989],
+//!               &[363.112, 357.8, 335.0, 116.219, 1954., 63.761],
+//!               &[397.469, 290.4, 304.8, 117.388, 1955., 66.019],
+//!               &[419.180, 282.2, 285.7, 118.734, 1956., 67.857],
+//!               &[442.769, 293.6, 279.8, 120.445, 1957., 68.169],
+//!               &[444.546, 468.1, 263.7, 121.950, 1958., 66.513],
+//!               &[482.704, 381.3, 255.2, 123.366, 1959., 68.655],
+//!               &[502.601, 393.1, 251.4, 125.368, 1960., 69.564],
+//!               &[518.173, 480.6, 257.2, 127.852, 1961., 69.331],
+//!               &[554.894, 400.7, 282.7, 130.081, 1962., 70.551],
+//!          ]).unwrap();
+//!
+//! let y: Vec<f64> = vec![83.0, 88.5, 88.2, 89.5, 96.2, 98.1, 99.0,
+//!           100.0, 101.2, 104.6, 108.4, 110.8, 112.6, 114.2, 115.7, 116.9];
+//!
+//! let lasso = LassoCV::fit(&x, &y, Default::default()).unwrap();
+//!
+//! let alpha = lasso.alpha();
+//! let y_hat = lasso.predict(&x).unwrap();
+//! ```
+//!
+//! ## References:
+//!
+//! * ["An Introduction to Statistical Learning", James G., Witten D., Hastie T., Tibshirani R., 6.2. Shrinkage Methods](http://faculty.marshall.usc.edu/gareth-james/ISL/)
+//! * ["Regularization Paths for Generalized Linear Models via Coordinate Descent", Friedman J., Hastie T., Tibshirani R.](https://www.jstatsoft.org/article/view/v033i01)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::api::{Predictor, SupervisedEstimator};
+use crate::error::Failed;
+use crate::linalg::basic::arrays::{Array1, Array2};
+use crate::linear::coordinate_descent::RegularizationPathParameters;
+use crate::linear::elastic_net_cv::fit_path_cv;
+use crate::model_selection::KFold;
+use crate::numbers::basenum::Number;
+use crate::numbers::floatnum::FloatNumber;
+use crate::numbers::realnum::RealNumber;
+
+/// Lasso cross-validation parameters
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct LassoCVParameters {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Candidate values of the regularization parameter.
+    /// When not set, a grid of `n_alphas` values is generated from the data.
+    pub alphas: Option<Vec<f64>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Number of regularization parameters in the generated grid.
+    pub n_alphas: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Length of the generated grid, `alpha_min / alpha_max = eps`.
+    pub eps: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The cross-validation splitting strategy
+    pub cv: KFold,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// If true the regressors X will be normalized before regression
+    /// by subtracting the mean and dividing by the standard deviation.
+    pub normalize: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The tolerance for the optimization
+    pub tol: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The maximum number of iterations
+    pub max_iter: usize,
+}
+
+impl LassoCVParameters {
+    /// Candidate values of the regularization parameter.
+    pub fn with_alphas(mut self, alphas: Vec<f64>) -> Self {
+        self.alphas = Some(alphas);
+        self
+    }
+    /// Number of regularization parameters in the generated grid.
+    pub fn with_n_alphas(mut self, n_alphas: usize) -> Self {
+        self.n_alphas = n_alphas;
+        self
+    }
+    /// Length of the generated grid, `alpha_min / alpha_max = eps`.
+    pub fn with_eps(mut self, eps: f64) -> Self {
+        self.eps = eps;
+        self
+    }
+    /// The cross-validation splitting strategy
+    pub fn with_cv(mut self, cv: KFold) -> Self {
+        self.cv = cv;
+        self
+    }
+    /// If True, the regressors X will be normalized before regression by subtracting the mean and dividing by the standard deviation.
+    pub fn with_normalize(mut self, normalize: bool) -> Self {
+        self.normalize = normalize;
+        self
+    }
+    /// The tolerance for the optimization
+    pub fn with_tol(mut self, tol: f64) -> Self {
+        self.tol = tol;
+        self
+    }
+    /// The maximum number of iterations
+    pub fn with_max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+}
+
+impl Default for LassoCVParameters {
+    fn default() -> Self {
+        let path_params = RegularizationPathParameters::default();
+
+        LassoCVParameters {
+            alphas: None,
+            n_alphas: path_params.n_alphas,
+            eps: path_params.eps,
+            cv: KFold::default().with_n_splits(5).with_shuffle(false),
+            normalize: path_params.normalize,
+            tol: path_params.tol,
+            max_iter: path_params.max_iter,
+        }
+    }
+}
+
+/// Lasso regressor with built-in cross-validation
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+pub struct LassoCV<TX: FloatNumber + RealNumber, TY: Number, X: Array2<TX>, Y: Array1<TY>> {
+    coefficients: Option<X>,
+    intercept: Option<TX>,
+    alpha: Option<f64>,
+    alphas: Option<Vec<f64>>,
+    mse_path: Option<Vec<Vec<f64>>>,
+    _phantom_ty: PhantomData<TY>,
+    _phantom_y: PhantomData<Y>,
+}
+
+impl<TX: FloatNumber + RealNumber, TY: Number, X: Array2<TX>, Y: Array1<TY>> PartialEq
+    for LassoCV<TX, TY, X, Y>
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.intercept == other.intercept
+            && self.alpha == other.alpha
+            && self.coefficients().shape() == other.coefficients().shape()
+            && self
+                .coefficients()
+                .iterator(0)
+                .zip(other.coefficients().iterator(0))
+                .all(|(&a, &b)| (a - b).abs() <= TX::epsilon())
+    }
+}
+
+impl<TX: FloatNumber + RealNumber, TY: Number, X: Array2<TX>, Y: Array1<TY>>
+    SupervisedEstimator<X, Y, LassoCVParameters> for LassoCV<TX, TY, X, Y>
+{
+    fn new() -> Self {
+        Self {
+            coefficients: Option::None,
+            intercept: Option::None,
+            alpha: Option::None,
+            alphas: Option::None,
+            mse_path: Option::None,
+            _phantom_ty: PhantomData,
+            _phantom_y: PhantomData,
+        }
+    }
+
+    fn fit(x: &X, y: &Y, parameters: LassoCVParameters) -> Result<Self, Failed> {
+        LassoCV::fit(x, y, parameters)
+    }
+}
+
+impl<TX: FloatNumber + RealNumber, TY: Number, X: Array2<TX>, Y: Array1<TY>> Predictor<X, Y>
+    for LassoCV<TX, TY, X, Y>
+{
+    fn predict(&self, x: &X) -> Result<Y, Failed> {
+        self.predict(x)
+    }
+}
+
+impl<TX: FloatNumber + RealNumber, TY: Number, X: Array2<TX>, Y: Array1<TY>> LassoCV<TX, TY, X, Y> {
+    /// Selects the regularization parameter by cross-validation and fits Lasso regression to your data.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - target values
+    /// * `parameters` - other parameters, use `Default::default()` to set parameters to default values.
+    pub fn fit(
+        x: &X,
+        y: &Y,
+        parameters: LassoCVParameters,
+    ) -> Result<LassoCV<TX, TY, X, Y>, Failed> {
+        let path_parameters = RegularizationPathParameters {
+            alphas: parameters.alphas,
+            n_alphas: parameters.n_alphas,
+            eps: parameters.eps,
+            l1_ratio: 1.0,
+            normalize: parameters.normalize,
+            tol: parameters.tol,
+            max_iter: parameters.max_iter,
+        };
+
+        let mut result = fit_path_cv(x, y, &[1.0], path_parameters, &parameters.cv)?;
+
+        Ok(LassoCV {
+            coefficients: Some(result.coefficients),
+            intercept: Some(result.intercept),
+            alpha: Some(result.alpha),
+            alphas: result.alphas.pop(),
+            mse_path: result.mse_path.pop(),
+            _phantom_ty: PhantomData,
+            _phantom_y: PhantomData,
+        })
+    }
+
+    /// Predict target values from `x`
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict(&self, x: &X) -> Result<Y, Failed> {
+        let (nrows, _) = x.shape();
+        let mut y_hat = x.matmul(self.coefficients());
+        let bias = X::fill(nrows, 1, self.intercept.unwrap());
+        y_hat.add_mut(&bias);
+        Ok(Y::from_iterator(
+            y_hat.iterator(0).map(|&v| TY::from(v).unwrap()),
+            nrows,
+        ))
+    }
+
+    /// Get estimates regression coefficients
+    pub fn coefficients(&self) -> &X {
+        self.coefficients.as_ref().unwrap()
+    }
+
+    /// Get estimate of intercept
+    pub fn intercept(&self) -> &TX {
+        self.intercept.as_ref().unwrap()
+    }
+
+    /// Regularization parameter chosen by cross-validation
+    pub fn alpha(&self) -> f64 {
+        self.alpha.unwrap()
+    }
+
+    /// Grid of regularization parameters, in decreasing order
+    pub fn alphas(&self) -> &Vec<f64> {
+        self.alphas.as_ref().unwrap()
+    }
+
+    /// Mean squared error on every fold, indexed by `alpha` and fold
+    pub fn mse_path(&self) -> &Vec<Vec<f64>> {
+        self.mse_path.as_ref().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::mean_absolute_error;
+    use crate::test_datasets::longley;
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn lasso_cv_fit_predict() {
+        let (x, y) = longley();
+
+        let alphas = vec![100.0, 1.0, 0.1, 0.01];
+
+        let lasso = LassoCV::fit(
+            &x,
+            &y,
+            LassoCVParameters::default()
+                .with_alphas(alphas.clone())
+                .with_cv(KFold::default().with_n_splits(4).with_shuffle(false)),
+        )
+        .unwrap();
+
+        assert_eq!(lasso.alphas(), &alphas);
+        assert_eq!(lasso.mse_path().len(), 4);
+        assert!(lasso.mse_path().iter().all(|mse| mse.len() == 4));
+        assert!(alphas.contains(&lasso.alpha()));
+        // the heaviest penalty leaves an intercept-only model, which should not win
+        assert!(lasso.alpha() < 100.0);
+
+        let y_hat = lasso.predict(&x).unwrap();
+        assert!(mean_absolute_error(&y_hat, &y) < 2.0);
+    }
+}
@@ -38,7 +38,7 @@ impl<T: FloatNumber, X: Array2<T>> InteriorPointOptimizer<T, X> {
         }
     }
 
-    ///
+    /// Minimizes \\(\|y - Xw\|^2 + \lambda \|w\|_1\\) after subtracting the mean of `y`.
     pub fn optimize(
         &mut self,
         x: &X,
@@ -46,6 +46,21 @@ impl<T: FloatNumber, X: Array2<T>> InteriorPointOptimizer<T, X> {
         lambda: T,
         max_iter: usize,
         tol: T,
+    ) -> Result<Vec<T>, Failed> {
+        let y = y.sub_scalar(T::from_f64(y.mean_by()).unwrap());
+
+        self.optimize_centered(x, &y, lambda, max_iter, tol)
+    }
+
+    /// Same as [`optimize`](InteriorPointOptimizer::optimize), but `y` is used as is, callers
+    /// that center `y` themselves, e.g. with a weighted mean, should use this method.
+    pub(crate) fn optimize_centered(
+        &mut self,
+        x: &X,
+        y: &Vec<T>,
+        lambda: T,
+        max_iter: usize,
+        tol: T,
     ) -> Result<Vec<T>, Failed> {
         let (n, p) = x.shape();
         let p_f64 = T::from_usize(p).unwrap();
@@ -61,9 +76,6 @@ impl<T: FloatNumber, X: Array2<T>> InteriorPointOptimizer<T, X> {
         let gamma = T::from_f64(-0.25).unwrap();
         let mu = T::two();
 
-        // let y = M::from_row_vector(y.sub_scalar(y.mean_by())).transpose();
-        let y = y.sub_scalar(T::from_f64(y.mean_by()).unwrap());
-
         let mut max_ls_iter = 100;
         let mut pitr = 0;
         let mut w = Vec::zeros(p);
@@ -108,7 +120,7 @@ impl<T: FloatNumber, X: Array2<T>> InteriorPointOptimizer<T, X> {
             }
 
             let pobj = z.dot(&z) + lambda * T::from_f64(w.norm(1f64)).unwrap();
-            dobj = dobj.max(gamma * nu.dot(&nu) - nu.dot(&y));
+            dobj = dobj.max(gamma * nu.dot(&nu) - nu.dot(y));
 
             let gap = pobj - dobj;
 
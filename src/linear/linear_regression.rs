@@ -50,29 +50,49 @@
 //!             with_solver(LinearRegressionSolverName::QR)).unwrap();
 //!
 //! let y_hat = lr.predict(&x).unwrap();
+//!
+//! // standard errors, t-statistics, p-values and goodness of fit of the estimates
+//! let summary = lr.summary().unwrap();
+//! println!("{summary}");
 //! ```
 //!
+//! ## Statistical inference
+//!
+//! [`LinearRegression::summary`](struct.LinearRegression.html#method.summary) describes the fitted model under the classical assumptions
+//! of independent, normally distributed errors with constant variance. The covariance of the estimates is
+//!
+//! \\[\widehat{Var}(\hat{\beta}) = \hat{\sigma}^2 (X^TWX)^{-1}, \quad \hat{\sigma}^2 = \frac{\sum_i w_i (y_i - \hat{y}_i)^2}{n - p}\\]
+//!
+//! where \\(W\\) is the diagonal matrix of sample weights and \\(p\\) is the number of estimated coefficients, including the intercept.
+//! \\((X^TWX)^{-1}\\) is recovered from the decomposition computed in `fit`, as \\(R^{-1}R^{-T}\\) for QR and \\(VS^{-2}V^T\\) for SVD.
+//!
 //! ## References:
 //!
 //! * ["Pattern Recognition and Machine Learning", C.M. Bishop, Linear Models for Regression](https://www.microsoft.com/en-us/research/uploads/prod/2006/01/Bishop-Pattern-Recognition-and-Machine-Learning-2006.pdf)
 //! * ["An Introduction to Statistical Learning", James G., Witten D., Hastie T., Tibshirani R., 3. Linear Regression](http://faculty.marshall.usc.edu/gareth-james/ISL/)
 //! * ["Numerical Recipes: The Art of Scientific Computing",  Press W.H., Teukolsky S.A., Vetterling W.T, Flannery B.P, 3rd ed., Section 15.4 General Linear Least Squares](http://numerical.recipes/)
+//! * ["Applied Linear Statistical Models", Kutner M.H., Nachtsheim C.J., Neter J., Li W., 5th ed., Ch. 6, 7](https://www.mheducation.com/)
 //!
 //! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
 //! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use std::fmt;
 use std::fmt::Debug;
 use std::marker::PhantomData;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+use crate::algorithm::sample_weight::check_sample_weight;
 use crate::api::{Predictor, SupervisedEstimator};
 use crate::error::Failed;
+use crate::error::FailedError;
 use crate::linalg::basic::arrays::{Array1, Array2};
 use crate::linalg::traits::qr::QRDecomposable;
 use crate::linalg::traits::svd::SVDDecomposable;
+use crate::linear::sqrt_weigh_rows;
 use crate::numbers::basenum::Number;
 use crate::numbers::realnum::RealNumber;
+use crate::numbers::special::{f_sf, student_t_cdf, student_t_ppf};
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Default, Clone, Eq, PartialEq)]
@@ -113,10 +133,121 @@ pub struct LinearRegression<
 > {
     coefficients: Option<X>,
     intercept: Option<TX>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    fit_statistics: Option<FitStatistics<TX>>,
     _phantom_ty: PhantomData<TY>,
     _phantom_y: PhantomData<Y>,
 }
 
+/// Quantities of the training fit needed for statistical inference.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+struct FitStatistics<TX> {
+    /// Diagonal of \\((X^TWX)^{-1}\\); the last element belongs to the intercept.
+    inverse_gram_diagonal: Vec<TX>,
+    /// Weighted residual sum of squares.
+    rss: TX,
+    /// Weighted total sum of squares around the weighted mean of `y`.
+    tss: TX,
+    /// Number of observations.
+    n_observations: usize,
+    /// Numerical rank of the design matrix, including the intercept column.
+    rank: usize,
+}
+
+/// Inference for a single estimated coefficient, see [`LinearRegressionSummary`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoefficientSummary {
+    /// Estimated value of the coefficient.
+    pub estimate: f64,
+    /// Standard error of the estimate.
+    pub std_error: f64,
+    /// t-statistic of the null hypothesis that the coefficient is zero.
+    pub t_statistic: f64,
+    /// Two-sided p-value of the t-statistic.
+    pub p_value: f64,
+    /// Lower bound of the confidence interval.
+    pub conf_int_lower: f64,
+    /// Upper bound of the confidence interval.
+    pub conf_int_upper: f64,
+}
+
+/// Summary of a fitted [`LinearRegression`]: coefficient table and goodness of fit.
+/// Statistics of weighted fits treat the sample weights as inverse variances of the observations.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinearRegressionSummary {
+    /// One entry per feature, in the same order as the columns of `x`.
+    pub coefficients: Vec<CoefficientSummary>,
+    /// Inference for the intercept.
+    pub intercept: CoefficientSummary,
+    /// Confidence level of the intervals, e.g. 0.95.
+    pub confidence_level: f64,
+    /// Coefficient of determination.
+    pub r_squared: f64,
+    /// Coefficient of determination adjusted for the number of features.
+    pub adjusted_r_squared: f64,
+    /// F-statistic of the null hypothesis that all feature coefficients are zero.
+    pub f_statistic: f64,
+    /// p-value of the F-statistic.
+    pub f_p_value: f64,
+    /// Residual standard error, the estimate of the standard deviation of the error term.
+    pub residual_std_error: f64,
+    /// Number of observations used in the fit.
+    pub n_observations: usize,
+    /// Model degrees of freedom, the rank of the design matrix without the intercept.
+    pub df_model: usize,
+    /// Residual degrees of freedom.
+    pub df_residuals: usize,
+}
+
+impl fmt::Display for LinearRegressionSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lower = format!("[{:.3}", (1. - self.confidence_level) / 2.);
+        let upper = format!("{:.3}]", (1. + self.confidence_level) / 2.);
+        writeln!(
+            f,
+            "{:<10} {:>12} {:>12} {:>10} {:>10} {:>12} {:>12}",
+            "", "coef", "std err", "t", "P>|t|", lower, upper
+        )?;
+        let rows = self
+            .coefficients
+            .iter()
+            .enumerate()
+            .map(|(i, c)| (format!("x{i}"), c))
+            .chain(std::iter::once(("intercept".to_string(), &self.intercept)));
+        for (name, c) in rows {
+            writeln!(
+                f,
+                "{:<10} {:>12.4} {:>12.4} {:>10.3} {:>10.3} {:>12.4} {:>12.4}",
+                name,
+                c.estimate,
+                c.std_error,
+                c.t_statistic,
+                c.p_value,
+                c.conf_int_lower,
+                c.conf_int_upper
+            )?;
+        }
+        writeln!(
+            f,
+            "Residual standard error: {:.4} on {} degrees of freedom",
+            self.residual_std_error, self.df_residuals
+        )?;
+        writeln!(
+            f,
+            "R-squared: {:.4}, Adjusted R-squared: {:.4}",
+            self.r_squared, self.adjusted_r_squared
+        )?;
+        write!(
+            f,
+            "F-statistic: {:.4} on {} and {} DF, p-value: {:.4e}",
+            self.f_statistic, self.df_model, self.df_residuals, self.f_p_value
+        )
+    }
+}
+
 impl LinearRegressionParameters {
     /// Solver to use for estimation of regression coefficients.
     pub fn with_solver(mut self, solver: LinearRegressionSolverName) -> Self {
@@ -209,6 +340,7 @@ impl<
         Self {
             coefficients: Option::None,
             intercept: Option::None,
+            fit_statistics: Option::None,
             _phantom_ty: PhantomData,
             _phantom_y: PhantomData,
         }
@@ -246,6 +378,20 @@ impl<
         x: &X,
         y: &Y,
         parameters: LinearRegressionParameters,
+    ) -> Result<LinearRegression<TX, TY, X, Y>, Failed> {
+        Self::fit_with_sample_weight(x, y, &vec![TX::one(); x.shape().0], parameters)
+    }
+
+    /// Fits Linear Regression to your data with weighted least squares.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - target values
+    /// * `sample_weight` - non-negative weight of every observation
+    /// * `parameters` - other parameters, use `Default::default()` to set parameters to default values.
+    pub fn fit_with_sample_weight(
+        x: &X,
+        y: &Y,
+        sample_weight: &[TX],
+        parameters: LinearRegressionParameters,
     ) -> Result<LinearRegression<TX, TY, X, Y>, Failed> {
         let b = X::from_iterator(
             y.iterator(0).map(|&v| TX::from(v).unwrap()),
@@ -262,23 +408,106 @@ impl<
             ));
         }
 
-        let a = x.h_stack(&X::ones(x_nrows, 1));
-
-        let w = match parameters.solver {
-            LinearRegressionSolverName::QR => a.qr_solve_mut(b)?,
-            LinearRegressionSolverName::SVD => a.svd_solve_mut(b)?,
+        check_sample_weight(sample_weight, x_nrows)?;
+
+        let a = sqrt_weigh_rows(&x.h_stack(&X::ones(x_nrows, 1)), sample_weight);
+        let b_weighted = sqrt_weigh_rows(&b, sample_weight);
+
+        let (w, inverse_gram_diagonal, rank) = match parameters.solver {
+            LinearRegressionSolverName::QR => {
+                if x_nrows <= num_attributes {
+                    return Err(Failed::fit(
+                        "QR solver requires more observations than features, use the SVD solver",
+                    ));
+                }
+                let qr = a.qr_mut()?;
+                let r = qr.R();
+                let r_max = (0..=num_attributes)
+                    .map(|j| r.get((j, j)).abs())
+                    .fold(TX::zero(), |acc, r_jj| acc.max(r_jj));
+                let tol = TX::from_usize(x_nrows).unwrap() * r_max * TX::epsilon();
+                if (0..=num_attributes).any(|j| r.get((j, j)).abs() <= tol) {
+                    return Err(Failed::fit("X is rank deficient, use the SVD solver"));
+                }
+                let w = qr.solve(b_weighted)?;
+                (
+                    w,
+                    Self::upper_triangular_inverse_diagonal(&r),
+                    num_attributes + 1,
+                )
+            }
+            LinearRegressionSolverName::SVD => {
+                let svd = a.svd_mut()?;
+                let w = svd.solve(b_weighted)?;
+                let mut diagonal = vec![TX::zero(); num_attributes + 1];
+                let mut rank = 0;
+                for (j, &s_j) in svd.s.iter().enumerate() {
+                    if s_j > svd.tol {
+                        rank += 1;
+                        for (i, d_i) in diagonal.iter_mut().enumerate() {
+                            let v_ij = *svd.V.get((i, j)) / s_j;
+                            *d_i += v_ij * v_ij;
+                        }
+                    }
+                }
+                (w, diagonal, rank)
+            }
         };
 
         let weights = X::from_slice(w.slice(0..num_attributes, 0..1).as_ref());
+        let intercept = *w.get((num_attributes, 0));
+
+        let y_hat = x.matmul(&weights);
+        let total_weight: TX = sample_weight.iter().copied().sum();
+        let y_mean = (0..x_nrows)
+            .map(|i| sample_weight[i] * *b.get((i, 0)))
+            .sum::<TX>()
+            / total_weight;
+        let (mut rss, mut tss) = (TX::zero(), TX::zero());
+        for (i, &w_i) in sample_weight.iter().enumerate() {
+            let y_i = *b.get((i, 0));
+            let residual = y_i - *y_hat.get((i, 0)) - intercept;
+            rss += w_i * residual * residual;
+            tss += w_i * (y_i - y_mean) * (y_i - y_mean);
+        }
 
         Ok(LinearRegression {
-            intercept: Some(*w.get((num_attributes, 0))),
+            intercept: Some(intercept),
             coefficients: Some(weights),
+            fit_statistics: Some(FitStatistics {
+                inverse_gram_diagonal,
+                rss,
+                tss,
+                n_observations: sample_weight
+                    .iter()
+                    .filter(|&&w_i| w_i > TX::zero())
+                    .count(),
+                rank,
+            }),
             _phantom_ty: PhantomData,
             _phantom_y: PhantomData,
         })
     }
 
+    /// Diagonal of \\(R^{-1}R^{-T}\\) for an upper triangular \\(R\\), computed column by column with back substitution.
+    fn upper_triangular_inverse_diagonal(r: &X) -> Vec<TX> {
+        let (n, _) = r.shape();
+        let mut diagonal = vec![TX::zero(); n];
+        let mut column = vec![TX::zero(); n];
+        for j in 0..n {
+            // solve R z = e_j, z_i is zero below row j
+            for i in (0..=j).rev() {
+                let mut s = if i == j { TX::one() } else { TX::zero() };
+                for (k, z_k) in column.iter().enumerate().take(j + 1).skip(i + 1) {
+                    s -= *r.get((i, k)) * *z_k;
+                }
+                column[i] = s / *r.get((i, i));
+                diagonal[i] += column[i] * column[i];
+            }
+        }
+        diagonal
+    }
+
     /// Predict target values from `x`
     /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
     pub fn predict(&self, x: &X) -> Result<Y, Failed> {
@@ -301,12 +530,108 @@ impl<
     pub fn intercept(&self) -> &TX {
         self.intercept.as_ref().unwrap()
     }
+
+    /// Statistical summary of the fit with 95% confidence intervals, see [`LinearRegressionSummary`].
+    pub fn summary(&self) -> Result<LinearRegressionSummary, Failed> {
+        self.summary_with_confidence_level(0.95)
+    }
+
+    /// Statistical summary of the fit with confidence intervals at the given level.
+    /// * `confidence_level` - coverage of the confidence intervals, between 0 and 1.
+    pub fn summary_with_confidence_level(
+        &self,
+        confidence_level: f64,
+    ) -> Result<LinearRegressionSummary, Failed> {
+        if !(confidence_level > 0. && confidence_level < 1.) {
+            return Err(Failed::because(
+                FailedError::ParametersError,
+                &format!("confidence_level should be between 0 and 1, got {confidence_level}"),
+            ));
+        }
+        let stats = self.fit_statistics.as_ref().ok_or_else(|| {
+            Failed::because(
+                FailedError::InvalidStateError,
+                "Summary is not available, the model was not fitted",
+            )
+        })?;
+
+        let n = stats.n_observations;
+        if n <= stats.rank {
+            return Err(Failed::because(
+                FailedError::InvalidStateError,
+                &format!(
+                    "Summary requires more observations than estimated coefficients, got {n} observations and rank {}",
+                    stats.rank
+                ),
+            ));
+        }
+        let df_residuals = n - stats.rank;
+        let df_model = stats.rank - 1;
+        let df = df_residuals as f64;
+
+        let rss = stats.rss.to_f64().unwrap();
+        let tss = stats.tss.to_f64().unwrap();
+        let sigma2 = rss / df;
+        let t_critical = student_t_ppf((1. + confidence_level) / 2., df);
+
+        let coefficient_summary = |estimate: TX, inverse_gram: TX| {
+            let estimate = estimate.to_f64().unwrap();
+            let std_error = (sigma2 * inverse_gram.to_f64().unwrap()).sqrt();
+            let t_statistic = estimate / std_error;
+            CoefficientSummary {
+                estimate,
+                std_error,
+                t_statistic,
+                p_value: 2. * (1. - student_t_cdf(t_statistic.abs(), df)),
+                conf_int_lower: estimate - t_critical * std_error,
+                conf_int_upper: estimate + t_critical * std_error,
+            }
+        };
+
+        let num_attributes = stats.inverse_gram_diagonal.len() - 1;
+        let coefficients = (0..num_attributes)
+            .map(|i| {
+                coefficient_summary(
+                    *self.coefficients().get((i, 0)),
+                    stats.inverse_gram_diagonal[i],
+                )
+            })
+            .collect();
+        let intercept = coefficient_summary(
+            *self.intercept(),
+            stats.inverse_gram_diagonal[num_attributes],
+        );
+
+        let r_squared = 1. - rss / tss;
+        let adjusted_r_squared = 1. - (1. - r_squared) * (n as f64 - 1.) / df;
+        let f_statistic = ((tss - rss) / df_model as f64) / sigma2;
+        let f_p_value = if df_model > 0 {
+            f_sf(f_statistic, df_model as f64, df)
+        } else {
+            f64::NAN
+        };
+
+        Ok(LinearRegressionSummary {
+            coefficients,
+            intercept,
+            confidence_level,
+            r_squared,
+            adjusted_r_squared,
+            f_statistic,
+            f_p_value,
+            residual_std_error: sigma2.sqrt(),
+            n_observations: n,
+            df_model,
+            df_residuals,
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::linalg::basic::matrix::DenseMatrix;
+    use crate::test_datasets::{assert_weights_repeat_rows, longley};
 
     #[test]
     fn search_parameters() {
@@ -412,4 +737,207 @@ mod tests {
     //     let parameters: LinearRegressionParameters = serde_json::from_str("{}").unwrap();
     //     assert_eq!(parameters.solver, default.solver);
     // }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn ols_fit_with_sample_weight() {
+        let (x, y) = longley();
+        let sample_weight = vec![
+            1., 2., 1., 3., 1., 1., 2., 1., 1., 1., 2., 1., 1., 1., 1., 2.,
+        ];
+
+        for parameters in [
+            LinearRegressionSolverName::QR,
+            LinearRegressionSolverName::SVD,
+        ]
+        .map(|solver| LinearRegressionParameters::default().with_solver(solver))
+        {
+            assert_weights_repeat_rows(
+                &x,
+                &y,
+                &sample_weight,
+                |x_w, y_w, w| {
+                    LinearRegression::fit_with_sample_weight(x_w, y_w, w, parameters.clone())
+                        .and_then(|lr| lr.predict(&x))
+                        .unwrap()
+                },
+                |x_r, y_r| {
+                    LinearRegression::fit(x_r, y_r, parameters.clone())
+                        .and_then(|lr| lr.predict(&x))
+                        .unwrap()
+                },
+                1e-6,
+            );
+        }
+
+        assert!(LinearRegression::fit_with_sample_weight(
+            &x,
+            &y,
+            &sample_weight[1..],
+            Default::default()
+        )
+        .is_err());
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn ols_summary() {
+        let x = DenseMatrix::from_2d_array(&[&[1.], &[2.], &[3.], &[4.], &[5.]]).unwrap();
+        let y: Vec<f64> = vec![2., 4., 5., 4., 5.];
+
+        for solver in [
+            LinearRegressionSolverName::QR,
+            LinearRegressionSolverName::SVD,
+        ] {
+            let lr = LinearRegression::fit(
+                &x,
+                &y,
+                LinearRegressionParameters::default().with_solver(solver),
+            )
+            .unwrap();
+            let summary = lr.summary().unwrap();
+
+            let slope = &summary.coefficients[0];
+            assert!((slope.estimate - 0.6).abs() < 1e-10);
+            assert!((slope.std_error - 0.282_842_712_474_619).abs() < 1e-10);
+            assert!((slope.t_statistic - 2.121_320_343_559_642).abs() < 1e-9);
+            assert!((slope.p_value - 0.124_027_062_657_554_6).abs() < 1e-9);
+            assert!((slope.conf_int_lower + 0.300_131_745_291_430_4).abs() < 1e-8);
+            assert!((slope.conf_int_upper - 1.500_131_745_291_430_5).abs() < 1e-8);
+
+            let intercept = &summary.intercept;
+            assert!((intercept.estimate - 2.2).abs() < 1e-10);
+            assert!((intercept.std_error - 0.938_083_151_964_686).abs() < 1e-10);
+            assert!((intercept.p_value - 0.100_743_456_085_419_9).abs() < 1e-9);
+
+            assert!((summary.r_squared - 0.6).abs() < 1e-10);
+            assert!((summary.adjusted_r_squared - 0.466_666_666_666_666_7).abs() < 1e-10);
+            assert!((summary.f_statistic - 4.5).abs() < 1e-9);
+            // with a single feature F = t^2
+            assert!((summary.f_p_value - slope.p_value).abs() < 1e-9);
+            assert!((summary.residual_std_error - 0.8f64.sqrt()).abs() < 1e-10);
+            assert_eq!(summary.n_observations, 5);
+            assert_eq!(summary.df_model, 1);
+            assert_eq!(summary.df_residuals, 3);
+        }
+
+        let wide = LinearRegression::fit(
+            &DenseMatrix::from_2d_array(&[&[1., 2.], &[2., 1.], &[3., 5.]]).unwrap(),
+            &vec![1., 2., 3.],
+            Default::default(),
+        )
+        .unwrap();
+        assert!(wide.summary().is_err());
+
+        // observations with zero weight do not count towards the degrees of freedom
+        let x_padded =
+            DenseMatrix::from_2d_array(&[&[1.], &[2.], &[3.], &[4.], &[5.], &[6.]]).unwrap();
+        let y_padded: Vec<f64> = vec![2., 4., 5., 4., 5., 100.];
+        let summary = LinearRegression::fit_with_sample_weight(
+            &x_padded,
+            &y_padded,
+            &[1., 1., 1., 1., 1., 0.],
+            Default::default(),
+        )
+        .and_then(|lr| lr.summary())
+        .unwrap();
+        assert_eq!(summary.n_observations, 5);
+        assert_eq!(summary.df_residuals, 3);
+        assert!((summary.coefficients[0].std_error - 0.282_842_712_474_619).abs() < 1e-10);
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn ols_summary_rank_deficient() {
+        // the second column duplicates the first one
+        let x =
+            DenseMatrix::from_2d_array(&[&[1., 1.], &[2., 2.], &[3., 3.], &[4., 4.], &[5., 5.]])
+                .unwrap();
+        let y: Vec<f64> = vec![2., 4., 5., 4., 5.];
+
+        assert!(LinearRegression::fit(
+            &x,
+            &y,
+            LinearRegressionParameters::default().with_solver(LinearRegressionSolverName::QR),
+        )
+        .is_err());
+
+        let summary = LinearRegression::fit(&x, &y, Default::default())
+            .and_then(|lr| lr.summary())
+            .unwrap();
+        assert_eq!(summary.df_model, 1);
+        assert_eq!(summary.df_residuals, 3);
+        assert!(summary
+            .coefficients
+            .iter()
+            .all(|c| c.std_error.is_finite() && c.p_value.is_finite()));
+        assert!((summary.r_squared - 0.6).abs() < 1e-10);
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn ols_summary_solvers_agree() {
+        let (x, y) = longley();
+
+        let qr = LinearRegression::fit(
+            &x,
+            &y,
+            LinearRegressionParameters::default().with_solver(LinearRegressionSolverName::QR),
+        )
+        .and_then(|lr| lr.summary_with_confidence_level(0.9))
+        .unwrap();
+        let svd = LinearRegression::fit(&x, &y, Default::default())
+            .and_then(|lr| lr.summary_with_confidence_level(0.9))
+            .unwrap();
+
+        for (a, b) in qr
+            .coefficients
+            .iter()
+            .chain(std::iter::once(&qr.intercept))
+            .zip(
+                svd.coefficients
+                    .iter()
+                    .chain(std::iter::once(&svd.intercept)),
+            )
+        {
+            assert!((a.std_error - b.std_error).abs() <= 1e-6 * a.std_error);
+            assert!((a.p_value - b.p_value).abs() < 1e-6);
+            assert!(a.conf_int_lower < a.estimate && a.estimate < a.conf_int_upper);
+        }
+        assert!((qr.r_squared - svd.r_squared).abs() < 1e-8);
+        assert!((qr.f_statistic - svd.f_statistic).abs() <= 1e-6 * qr.f_statistic);
+        assert_eq!(svd.df_residuals, 9);
+
+        // statistics of a weighted fit do not depend on the scale of the weights
+        let sample_weight: Vec<f64> = (0..16).map(|i| 1. + (i % 3) as f64).collect();
+        let scaled_weight: Vec<f64> = sample_weight.iter().map(|w| 10. * w).collect();
+        let weighted =
+            LinearRegression::fit_with_sample_weight(&x, &y, &sample_weight, Default::default())
+                .and_then(|lr| lr.summary())
+                .unwrap();
+        let scaled =
+            LinearRegression::fit_with_sample_weight(&x, &y, &scaled_weight, Default::default())
+                .and_then(|lr| lr.summary())
+                .unwrap();
+        assert!((weighted.intercept.std_error - scaled.intercept.std_error).abs() < 1e-6);
+        assert!((weighted.r_squared - scaled.r_squared).abs() < 1e-10);
+
+        assert!(!format!("{qr}").is_empty());
+        assert!(LinearRegression::fit(&x, &y, Default::default())
+            .unwrap()
+            .summary_with_confidence_level(1.)
+            .is_err());
+    }
 }
@@ -5,7 +5,13 @@
 //!
 //! \\[ Pr(y=1) \approx \frac{e^{\beta_0 + \sum_{i=1}^n \beta_iX_i}}{1 + e^{\beta_0 + \sum_{i=1}^n \beta_iX_i}} \\]
 //!
-//! `smartcore` uses [limited memory BFGS](https://en.wikipedia.org/wiki/Limited-memory_BFGS) method to find estimates of regression coefficients, \\(\beta\\)
+//! By default `smartcore` uses [limited memory BFGS](https://en.wikipedia.org/wiki/Limited-memory_BFGS) method to find estimates of regression coefficients, \\(\beta\\).
+//! Stochastic average gradient (SAG, SAGA) and Newton-CG solvers are available through [`LogisticRegressionSolverName`](enum.LogisticRegressionSolverName.html).
+//! SAG and SAGA converge fastest when features have comparable scale.
+//!
+//! Coefficients are shrunk with an L2 penalty by default. Sparse models are fitted with an L1 or an elastic net
+//! penalty, see [`LogisticRegressionPenalty`](enum.LogisticRegressionPenalty.html), which is minimized by
+//! the SAGA or the proximal gradient solver.
 //!
 //! Example:
 //!
@@ -43,12 +49,19 @@
 //! let lr = LogisticRegression::fit(&x, &y, Default::default()).unwrap();
 //!
 //! let y_hat = lr.predict(&x).unwrap();
+//!
+//! // Probabilities of each class, columns are ordered like lr.classes()
+//! let y_proba = lr.predict_proba(&x).unwrap();
 //! ```
 //!
 //! ## References:
 //! * ["Pattern Recognition and Machine Learning", C.M. Bishop, Linear Models for Classification](https://www.microsoft.com/en-us/research/uploads/prod/2006/01/Bishop-Pattern-Recognition-and-Machine-Learning-2006.pdf)
 //! * ["An Introduction to Statistical Learning", James G., Witten D., Hastie T., Tibshirani R., 4.3 Logistic Regression](http://faculty.marshall.usc.edu/gareth-james/ISL/)
 //! * ["On the Limited Memory Method for Large Scale Optimization", Nocedal et al., Mathematical Programming, 1989](http://users.iems.northwestern.edu/~nocedal/PDFfiles/limited.pdf)
+//! * ["Minimizing Finite Sums with the Stochastic Average Gradient", Schmidt M., Le Roux N., Bach F., 2013](https://arxiv.org/abs/1309.2388)
+//! * ["SAGA: A Fast Incremental Gradient Method With Support for Non-Strongly Convex Composite Objectives", Defazio A., Bach F., Lacoste-Julien S., 2014](https://arxiv.org/abs/1407.0202)
+//! * ["Numerical Optimization", Nocedal J., Wright S., 7.1 Inexact Newton Methods](https://link.springer.com/book/10.1007/978-0-387-40065-5)
+//! * ["A Fast Iterative Shrinkage-Thresholding Algorithm for Linear Inverse Problems", Beck A., Teboulle M., 2009](https://epubs.siam.org/doi/10.1137/080716542)
 //!
 //! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
 //! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
@@ -59,9 +72,13 @@ use std::marker::PhantomData;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+use rand::Rng;
+
+use crate::algorithm::sample_weight::{check_sample_weight, resolve_class_weight, ClassWeight};
 use crate::api::{Predictor, SupervisedEstimator};
 use crate::error::Failed;
 use crate::linalg::basic::arrays::{Array1, Array2, MutArrayView1};
+use crate::linear::coordinate_descent::CoordinateDescentOptimizer;
 use crate::numbers::basenum::Number;
 use crate::numbers::floatnum::FloatNumber;
 use crate::numbers::realnum::RealNumber;
@@ -69,14 +86,38 @@ use crate::optimization::first_order::lbfgs::LBFGS;
 use crate::optimization::first_order::{FirstOrderOptimizer, OptimizerResult};
 use crate::optimization::line_search::Backtracking;
 use crate::optimization::FunctionOrder;
+use crate::rand_custom::get_rng_impl;
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone, Eq, PartialEq, Default)]
-/// Solver options for Logistic regression. Right now only LBFGS solver is supported.
+/// Solver options for Logistic regression.
 pub enum LogisticRegressionSolverName {
     /// Limited-memory Broyden–Fletcher–Goldfarb–Shanno method, see [LBFGS paper](http://users.iems.northwestern.edu/~nocedal/lbfgsb.html)
     #[default]
     LBFGS,
+    /// Stochastic average gradient, one randomly drawn sample per update. `max_iter` counts epochs.
+    SAG,
+    /// SAGA, an unbiased variant of stochastic average gradient. `max_iter` counts epochs.
+    SAGA,
+    /// Truncated Newton method, search directions are found with conjugate gradient using Hessian-vector products.
+    NewtonCG,
+    /// Accelerated proximal gradient method (FISTA) with backtracking line search.
+    ProximalGradient,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Default)]
+/// Penalty applied to the coefficients of Logistic regression, the intercept is never penalized.
+/// L1 and elastic net penalties are supported by the `SAGA` and `ProximalGradient` solvers only.
+pub enum LogisticRegressionPenalty {
+    /// Ridge penalty \\(\frac{\alpha}{2} \|\beta\|_2^2\\)
+    #[default]
+    L2,
+    /// Lasso penalty \\(\alpha \|\beta\|_1\\), yields sparse coefficients.
+    L1,
+    /// Mix of both penalties \\(\alpha \rho \|\beta\|_1 + \frac{\alpha (1 - \rho)}{2} \|\beta\|_2^2\\),
+    /// where \\(0 \le \rho \le 1\\) is the given l1 ratio.
+    ElasticNet(f64),
 }
 
 /// Logistic Regression parameters
@@ -89,6 +130,22 @@ pub struct LogisticRegressionParameters<T: Number + FloatNumber> {
     #[cfg_attr(feature = "serde", serde(default))]
     /// Regularization parameter.
     pub alpha: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Penalty applied to the coefficients.
+    pub penalty: LogisticRegressionPenalty,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Weights associated with classes, all classes have weight one when `None`.
+    pub class_weight: Option<ClassWeight>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Maximum number of iterations of the solver.
+    pub max_iter: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Tolerance for the stopping criterion. LBFGS and Newton-CG stop when the largest absolute component of the gradient
+    /// falls below `tol`, SAG, SAGA and proximal gradient stop when the largest change of a weight over an iteration is below `tol` times the largest weight.
+    pub tol: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Seed of the pseudo-random number generator used to draw samples in SAG and SAGA.
+    pub seed: u64,
 }
 
 /// Logistic Regression grid search parameters
@@ -136,6 +193,7 @@ impl<T: Number + FloatNumber> Iterator for LogisticRegressionSearchParametersIte
         let next = LogisticRegressionParameters {
             solver: self.logistic_regression_search_parameters.solver[self.current_solver].clone(),
             alpha: self.logistic_regression_search_parameters.alpha[self.current_alpha],
+            ..Default::default()
         };
 
         if self.current_alpha + 1 < self.logistic_regression_search_parameters.alpha.len() {
@@ -178,6 +236,8 @@ pub struct LogisticRegression<
     classes: Option<Vec<TY>>,
     num_attributes: usize,
     num_classes: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    optimizer_result: Option<OptimizerResult<TX, Vec<TX>>>,
     _phantom_tx: PhantomData<TX>,
     _phantom_y: PhantomData<Y>,
 }
@@ -190,6 +250,28 @@ trait ObjectiveFunction<T: Number + FloatNumber, X: Array2<T>> {
     #[allow(clippy::ptr_arg)]
     fn df(&self, g: &mut Vec<T>, w_bias: &Vec<T>);
 
+    /// Hessian of the objective at `w_bias` multiplied by `v`.
+    #[allow(clippy::ptr_arg)]
+    fn hessp(&self, hv: &mut Vec<T>, w_bias: &Vec<T>, v: &Vec<T>);
+
+    /// Gradient of the loss of the `i`-th sample with respect to each of its linear predictors.
+    fn sample_grad(&self, grad: &mut [T], w_bias: &[T], i: usize);
+
+    /// Upper bound of the second derivative of a single sample's loss with respect to its linear predictors.
+    fn loss_curvature(&self) -> T;
+
+    /// Number of linear predictors, one set of weights and an intercept each.
+    fn num_predictors(&self) -> usize;
+
+    /// Training data.
+    fn x(&self) -> &X;
+
+    /// L2 regularization parameter.
+    fn alpha(&self) -> T;
+
+    /// Weight of every sample in the objective.
+    fn sample_weight(&self) -> &[T];
+
     ///
     #[allow(clippy::ptr_arg)]
     fn partial_dot(w: &[T], x: &X, v_col: usize, m_row: usize) -> T {
@@ -206,6 +288,7 @@ trait ObjectiveFunction<T: Number + FloatNumber, X: Array2<T>> {
 struct BinaryObjectiveFunction<'a, T: Number + FloatNumber, X: Array2<T>> {
     x: &'a X,
     y: Vec<usize>,
+    sample_weight: Vec<T>,
     alpha: T,
     _phantom_t: PhantomData<T>,
 }
@@ -221,6 +304,31 @@ impl<T: Number + FloatNumber> LogisticRegressionParameters<T> {
         self.alpha = alpha;
         self
     }
+    /// Penalty applied to the coefficients.
+    pub fn with_penalty(mut self, penalty: LogisticRegressionPenalty) -> Self {
+        self.penalty = penalty;
+        self
+    }
+    /// Weights associated with classes.
+    pub fn with_class_weight(mut self, class_weight: ClassWeight) -> Self {
+        self.class_weight = Some(class_weight);
+        self
+    }
+    /// Maximum number of iterations of the solver.
+    pub fn with_max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+    /// Tolerance for the stopping criterion.
+    pub fn with_tol(mut self, tol: T) -> Self {
+        self.tol = tol;
+        self
+    }
+    /// Seed of the pseudo-random number generator used by SAG and SAGA.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
 }
 
 impl<T: Number + FloatNumber> Default for LogisticRegressionParameters<T> {
@@ -228,6 +336,11 @@ impl<T: Number + FloatNumber> Default for LogisticRegressionParameters<T> {
         LogisticRegressionParameters {
             solver: LogisticRegressionSolverName::default(),
             alpha: T::zero(),
+            penalty: LogisticRegressionPenalty::default(),
+            class_weight: Option::None,
+            max_iter: 1000,
+            tol: T::from_f64(1e-8).unwrap(),
+            seed: 0,
         }
     }
 }
@@ -270,7 +383,7 @@ impl<'a, T: Number + FloatNumber, X: Array2<T>> ObjectiveFunction<T, X>
 
         for i in 0..n {
             let wx = BinaryObjectiveFunction::partial_dot(w_bias, self.x, 0, i);
-            f += wx.ln_1pe() - (T::from(self.y[i]).unwrap()) * wx;
+            f += self.sample_weight[i] * (wx.ln_1pe() - (T::from(self.y[i]).unwrap()) * wx);
         }
 
         if self.alpha > T::zero() {
@@ -292,7 +405,7 @@ impl<'a, T: Number + FloatNumber, X: Array2<T>> ObjectiveFunction<T, X>
         for i in 0..n {
             let wx = BinaryObjectiveFunction::partial_dot(w_bias, self.x, 0, i);
 
-            let dyi = (T::from(self.y[i]).unwrap()) - wx.sigmoid();
+            let dyi = self.sample_weight[i] * ((T::from(self.y[i]).unwrap()) - wx.sigmoid());
             for (j, g_j) in g.iter_mut().enumerate().take(p) {
                 *g_j -= dyi * *self.x.get((i, j));
             }
@@ -306,11 +419,59 @@ impl<'a, T: Number + FloatNumber, X: Array2<T>> ObjectiveFunction<T, X>
             }
         }
     }
+
+    fn hessp(&self, hv: &mut Vec<T>, w_bias: &Vec<T>, v: &Vec<T>) {
+        hv.copy_from(&Vec::zeros(hv.len()));
+
+        let (n, p) = self.x.shape();
+
+        for i in 0..n {
+            let s = BinaryObjectiveFunction::partial_dot(w_bias, self.x, 0, i).sigmoid();
+            let xv = BinaryObjectiveFunction::partial_dot(v, self.x, 0, i);
+            let c = self.sample_weight[i] * s * (T::one() - s) * xv;
+            for (j, hv_j) in hv.iter_mut().enumerate().take(p) {
+                *hv_j += c * *self.x.get((i, j));
+            }
+            hv[p] += c;
+        }
+
+        if self.alpha > T::zero() {
+            for i in 0..p {
+                hv[i] += self.alpha * v[i];
+            }
+        }
+    }
+
+    fn sample_grad(&self, grad: &mut [T], w_bias: &[T], i: usize) {
+        let wx = BinaryObjectiveFunction::partial_dot(w_bias, self.x, 0, i);
+        grad[0] = self.sample_weight[i] * (wx.sigmoid() - T::from(self.y[i]).unwrap());
+    }
+
+    fn loss_curvature(&self) -> T {
+        T::from_f64(0.25).unwrap()
+    }
+
+    fn num_predictors(&self) -> usize {
+        1
+    }
+
+    fn x(&self) -> &X {
+        self.x
+    }
+
+    fn alpha(&self) -> T {
+        self.alpha
+    }
+
+    fn sample_weight(&self) -> &[T] {
+        &self.sample_weight
+    }
 }
 
 struct MultiClassObjectiveFunction<'a, T: Number + FloatNumber, X: Array2<T>> {
     x: &'a X,
     y: Vec<usize>,
+    sample_weight: Vec<T>,
     k: usize,
     alpha: T,
     _phantom_t: PhantomData<T>,
@@ -328,7 +489,7 @@ impl<'a, T: Number + FloatNumber + RealNumber, X: Array2<T>> ObjectiveFunction<T
                 *prob_j = MultiClassObjectiveFunction::partial_dot(w_bias, self.x, j * (p + 1), i);
             }
             prob.softmax_mut();
-            f -= prob[self.y[i]].ln();
+            f -= self.sample_weight[i] * prob[self.y[i]].ln();
         }
 
         if self.alpha > T::zero() {
@@ -359,7 +520,8 @@ impl<'a, T: Number + FloatNumber + RealNumber, X: Array2<T>> ObjectiveFunction<T
             prob.softmax_mut();
 
             for j in 0..self.k {
-                let yi = (if self.y[i] == j { T::one() } else { T::zero() }) - prob[j];
+                let yi = self.sample_weight[i]
+                    * ((if self.y[i] == j { T::one() } else { T::zero() }) - prob[j]);
 
                 for l in 0..p {
                     let pos = j * (p + 1);
@@ -379,6 +541,76 @@ impl<'a, T: Number + FloatNumber + RealNumber, X: Array2<T>> ObjectiveFunction<T
             }
         }
     }
+
+    fn hessp(&self, hv: &mut Vec<T>, w: &Vec<T>, v: &Vec<T>) {
+        hv.copy_from(&Vec::zeros(hv.len()));
+
+        let mut prob = vec![T::zero(); self.k];
+        let mut xv = vec![T::zero(); self.k];
+        let (n, p) = self.x.shape();
+
+        for i in 0..n {
+            for j in 0..self.k {
+                prob[j] = MultiClassObjectiveFunction::partial_dot(w, self.x, j * (p + 1), i);
+                xv[j] = MultiClassObjectiveFunction::partial_dot(v, self.x, j * (p + 1), i);
+            }
+
+            prob.softmax_mut();
+
+            let prob_xv: T = prob.iter().zip(xv.iter()).map(|(&a, &b)| a * b).sum();
+
+            for j in 0..self.k {
+                let c = self.sample_weight[i] * prob[j] * (xv[j] - prob_xv);
+                let pos = j * (p + 1);
+                for l in 0..p {
+                    hv[pos + l] += c * *self.x.get((i, l));
+                }
+                hv[pos + p] += c;
+            }
+        }
+
+        if self.alpha > T::zero() {
+            for i in 0..self.k {
+                for j in 0..p {
+                    let pos = i * (p + 1);
+                    hv[pos + j] += self.alpha * v[pos + j];
+                }
+            }
+        }
+    }
+
+    fn sample_grad(&self, grad: &mut [T], w_bias: &[T], i: usize) {
+        let p = self.x.shape().1;
+        for (j, grad_j) in grad.iter_mut().enumerate().take(self.k) {
+            *grad_j = MultiClassObjectiveFunction::partial_dot(w_bias, self.x, j * (p + 1), i);
+        }
+        let mut prob = grad[..self.k].to_vec();
+        prob.softmax_mut();
+        for (j, grad_j) in grad.iter_mut().enumerate().take(self.k) {
+            *grad_j = self.sample_weight[i]
+                * (prob[j] - if self.y[i] == j { T::one() } else { T::zero() });
+        }
+    }
+
+    fn loss_curvature(&self) -> T {
+        T::from_f64(0.5).unwrap()
+    }
+
+    fn num_predictors(&self) -> usize {
+        self.k
+    }
+
+    fn x(&self) -> &X {
+        self.x
+    }
+
+    fn alpha(&self) -> T {
+        self.alpha
+    }
+
+    fn sample_weight(&self) -> &[T] {
+        &self.sample_weight
+    }
 }
 
 impl<TX: Number + FloatNumber + RealNumber, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>>
@@ -392,6 +624,7 @@ impl<TX: Number + FloatNumber + RealNumber, TY: Number + Ord, X: Array2<TX>, Y:
             classes: Option::None,
             num_attributes: 0,
             num_classes: 0,
+            optimizer_result: Option::None,
             _phantom_tx: PhantomData,
             _phantom_y: PhantomData,
         }
@@ -421,6 +654,21 @@ impl<TX: Number + FloatNumber + RealNumber, TY: Number + Ord, X: Array2<TX>, Y:
         x: &X,
         y: &Y,
         parameters: LogisticRegressionParameters<TX>,
+    ) -> Result<LogisticRegression<TX, TY, X, Y>, Failed> {
+        Self::fit_with_sample_weight(x, y, &vec![TX::one(); x.shape().0], parameters)
+    }
+
+    /// Fits Logistic Regression to your data, the loss of every observation is multiplied by its weight
+    /// and by the weight of its class.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - target class values
+    /// * `sample_weight` - non-negative weight of every observation
+    /// * `parameters` - other parameters, use `Default::default()` to set parameters to default values.
+    pub fn fit_with_sample_weight(
+        x: &X,
+        y: &Y,
+        sample_weight: &[TX],
+        parameters: LogisticRegressionParameters<TX>,
     ) -> Result<LogisticRegression<TX, TY, X, Y>, Failed> {
         let (x_nrows, num_attributes) = x.shape();
         let y_nrows = y.shape();
@@ -431,6 +679,37 @@ impl<TX: Number + FloatNumber + RealNumber, TY: Number + Ord, X: Array2<TX>, Y:
             ));
         }
 
+        check_sample_weight(sample_weight, x_nrows)?;
+
+        let l1_ratio = match parameters.penalty {
+            LogisticRegressionPenalty::L2 => 0.0,
+            LogisticRegressionPenalty::L1 => 1.0,
+            LogisticRegressionPenalty::ElasticNet(l1_ratio) => {
+                if !(0.0..=1.0).contains(&l1_ratio) {
+                    return Err(Failed::fit(&format!(
+                        "l1_ratio should be in [0, 1], got {l1_ratio}"
+                    )));
+                }
+                l1_ratio
+            }
+        };
+
+        if l1_ratio > 0.0
+            && !matches!(
+                parameters.solver,
+                LogisticRegressionSolverName::SAGA | LogisticRegressionSolverName::ProximalGradient
+            )
+        {
+            return Err(Failed::fit(&format!(
+                "{:?} penalty is not supported by {:?} solver, use SAGA or ProximalGradient",
+                parameters.penalty, parameters.solver
+            )));
+        }
+
+        let l1_ratio = TX::from_f64(l1_ratio).unwrap();
+        let l1_alpha = parameters.alpha * l1_ratio;
+        let l2_alpha = parameters.alpha * (TX::one() - l1_ratio);
+
         let classes = y.unique();
 
         let k = classes.len();
@@ -442,6 +721,20 @@ impl<TX: Number + FloatNumber + RealNumber, TY: Number + Ord, X: Array2<TX>, Y:
             *yi_i = classes.iter().position(|c| yc == c).unwrap();
         }
 
+        let class_weight: Vec<TX> = match &parameters.class_weight {
+            None => vec![TX::one(); k],
+            Some(class_weight) => resolve_class_weight(class_weight, k, &yi)?
+                .into_iter()
+                .map(|w| TX::from_f64(w).unwrap())
+                .collect(),
+        };
+
+        let sample_weight: Vec<TX> = sample_weight
+            .iter()
+            .zip(yi.iter())
+            .map(|(&w_i, &yi_i)| w_i * class_weight[yi_i])
+            .collect();
+
         match k.cmp(&2) {
             Ordering::Less => Err(Failed::fit(&format!(
                 "incorrect number of classes: {k}. Should be >= 2."
@@ -452,13 +745,14 @@ impl<TX: Number + FloatNumber + RealNumber, TY: Number + Ord, X: Array2<TX>, Y:
                 let objective = BinaryObjectiveFunction {
                     x,
                     y: yi,
-                    alpha: parameters.alpha,
+                    sample_weight,
+                    alpha: l2_alpha,
                     _phantom_t: PhantomData,
                 };
 
-                let result = Self::minimize(x0, objective);
+                let result = Self::minimize(x0, objective, l1_alpha, &parameters);
 
-                let weights = X::from_iterator(result.x.into_iter(), 1, num_attributes + 1, 0);
+                let weights = X::from_iterator(result.x.iter().copied(), 1, num_attributes + 1, 0);
                 let coefficients = weights.slice(0..1, 0..num_attributes);
                 let intercept = weights.slice(0..1, num_attributes..num_attributes + 1);
 
@@ -468,6 +762,7 @@ impl<TX: Number + FloatNumber + RealNumber, TY: Number + Ord, X: Array2<TX>, Y:
                     classes: Some(classes),
                     num_attributes,
                     num_classes: k,
+                    optimizer_result: Some(result),
                     _phantom_tx: PhantomData,
                     _phantom_y: PhantomData,
                 })
@@ -478,13 +773,14 @@ impl<TX: Number + FloatNumber + RealNumber, TY: Number + Ord, X: Array2<TX>, Y:
                 let objective = MultiClassObjectiveFunction {
                     x,
                     y: yi,
+                    sample_weight,
                     k,
-                    alpha: parameters.alpha,
+                    alpha: l2_alpha,
                     _phantom_t: PhantomData,
                 };
 
-                let result = Self::minimize(x0, objective);
-                let weights = X::from_iterator(result.x.into_iter(), k, num_attributes + 1, 0);
+                let result = Self::minimize(x0, objective, l1_alpha, &parameters);
+                let weights = X::from_iterator(result.x.iter().copied(), k, num_attributes + 1, 0);
                 let coefficients = weights.slice(0..k, 0..num_attributes);
                 let intercept = weights.slice(0..k, num_attributes..num_attributes + 1);
 
@@ -494,6 +790,7 @@ impl<TX: Number + FloatNumber + RealNumber, TY: Number + Ord, X: Array2<TX>, Y:
                     classes: Some(classes),
                     num_attributes,
                     num_classes: k,
+                    optimizer_result: Some(result),
                     _phantom_tx: PhantomData,
                     _phantom_y: PhantomData,
                 })
@@ -506,25 +803,13 @@ impl<TX: Number + FloatNumber + RealNumber, TY: Number + Ord, X: Array2<TX>, Y:
     pub fn predict(&self, x: &X) -> Result<Y, Failed> {
         let n = x.shape().0;
         let mut result = Y::zeros(n);
+        let margins = self.decision_function(x)?;
         if self.num_classes == 2 {
-            let y_hat = x.ab(false, self.coefficients(), true);
-            let intercept = *self.intercept().get((0, 0));
-            for (i, y_hat_i) in y_hat.iterator(0).enumerate().take(n) {
-                result.set(
-                    i,
-                    self.classes()[usize::from(
-                        RealNumber::sigmoid(*y_hat_i + intercept) > RealNumber::half(),
-                    )],
-                );
+            for (i, margin_i) in margins.iterator(0).enumerate().take(n) {
+                result.set(i, self.classes()[usize::from(*margin_i > TX::zero())]);
             }
         } else {
-            let mut y_hat = x.matmul(&self.coefficients().transpose());
-            for r in 0..n {
-                for c in 0..self.num_classes {
-                    y_hat.set((r, c), *y_hat.get((r, c)) + *self.intercept().get((c, 0)));
-                }
-            }
-            let class_idxs = y_hat.argmax(1);
+            let class_idxs = margins.argmax(1);
             for (i, class_i) in class_idxs.iter().enumerate().take(n) {
                 result.set(i, self.classes()[*class_i]);
             }
@@ -532,6 +817,89 @@ impl<TX: Number + FloatNumber + RealNumber, TY: Number + Ord, X: Array2<TX>, Y:
         Ok(result)
     }
 
+    /// Compute raw margins, the linear predictors \\(\beta_0 + \sum_{i=1}^n \beta_iX_i\\), for samples in `x`.
+    /// Returns a _Kx1_ matrix with the margins of the second class for binary problems
+    /// and a _KxC_ matrix with one column per class, ordered like [`classes`](#method.classes), otherwise.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn decision_function(&self, x: &X) -> Result<X, Failed> {
+        let (n, num_attributes) = x.shape();
+        if num_attributes != self.num_attributes {
+            return Err(Failed::predict(&format!(
+                "expected {} features, got {num_attributes}",
+                self.num_attributes
+            )));
+        }
+
+        let mut margins = x.matmul(&self.coefficients().transpose());
+        let num_predictors = self.intercept().shape().0;
+        for r in 0..n {
+            for c in 0..num_predictors {
+                margins.set((r, c), *margins.get((r, c)) + *self.intercept().get((c, 0)));
+            }
+        }
+        Ok(margins)
+    }
+
+    /// Estimate class probabilities for samples in `x`.
+    /// Returns a _KxC_ matrix, columns are ordered like [`classes`](#method.classes).
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict_proba(&self, x: &X) -> Result<X, Failed> {
+        let margins = self.decision_function(x)?;
+        let n = margins.shape().0;
+        let mut proba = X::zeros(n, self.num_classes);
+        if self.num_classes == 2 {
+            for r in 0..n {
+                let p = RealNumber::sigmoid(*margins.get((r, 0)));
+                proba.set((r, 0), TX::one() - p);
+                proba.set((r, 1), p);
+            }
+        } else {
+            let mut row = vec![TX::zero(); self.num_classes];
+            for r in 0..n {
+                for (c, row_c) in row.iter_mut().enumerate() {
+                    *row_c = *margins.get((r, c));
+                }
+                row.softmax_mut();
+                for (c, p) in row.iter().enumerate() {
+                    proba.set((r, c), *p);
+                }
+            }
+        }
+        Ok(proba)
+    }
+
+    /// Estimate logarithm of class probabilities for samples in `x`, computed without
+    /// underflow for confident predictions. Columns are ordered like [`classes`](#method.classes).
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict_log_proba(&self, x: &X) -> Result<X, Failed> {
+        let margins = self.decision_function(x)?;
+        let n = margins.shape().0;
+        let mut log_proba = X::zeros(n, self.num_classes);
+        if self.num_classes == 2 {
+            for r in 0..n {
+                let margin = *margins.get((r, 0));
+                log_proba.set((r, 0), -FloatNumber::ln_1pe(margin));
+                log_proba.set((r, 1), -FloatNumber::ln_1pe(-margin));
+            }
+        } else {
+            for r in 0..n {
+                let mut max = *margins.get((r, 0));
+                for c in 1..self.num_classes {
+                    max = max.max(*margins.get((r, c)));
+                }
+                let mut sum = TX::zero();
+                for c in 0..self.num_classes {
+                    sum += (*margins.get((r, c)) - max).exp();
+                }
+                let log_norm = max + sum.ln();
+                for c in 0..self.num_classes {
+                    log_proba.set((r, c), *margins.get((r, c)) - log_norm);
+                }
+            }
+        }
+        Ok(log_proba)
+    }
+
     /// Get estimates regression coefficients, this create a sharable reference
     pub fn coefficients(&self) -> &X {
         self.coefficients.as_ref().unwrap()
@@ -547,32 +915,365 @@ impl<TX: Number + FloatNumber + RealNumber, TY: Number + Ord, X: Array2<TX>, Y:
         self.classes.as_ref().unwrap()
     }
 
+    /// Get the report of the solver run: objective value, number of iterations and whether the solver converged.
+    pub fn optimizer_result(&self) -> &OptimizerResult<TX, Vec<TX>> {
+        self.optimizer_result.as_ref().unwrap()
+    }
+
+    /// Minimizes the objective plus `l1` times the L1 norm of the coefficients.
+    /// Solvers other than SAGA and proximal gradient expect `l1` to be zero.
     fn minimize(
         x0: Vec<TX>,
         objective: impl ObjectiveFunction<TX, X>,
+        l1: TX,
+        parameters: &LogisticRegressionParameters<TX>,
     ) -> OptimizerResult<TX, Vec<TX>> {
-        let f = |w: &Vec<TX>| -> TX { objective.f(w) };
+        match parameters.solver {
+            LogisticRegressionSolverName::LBFGS => {
+                let f = |w: &Vec<TX>| -> TX { objective.f(w) };
 
-        let df = |g: &mut Vec<TX>, w: &Vec<TX>| objective.df(g, w);
+                let df = |g: &mut Vec<TX>, w: &Vec<TX>| objective.df(g, w);
 
-        let ls: Backtracking<TX> = Backtracking {
-            order: FunctionOrder::THIRD,
-            ..Default::default()
+                let ls: Backtracking<TX> = Backtracking {
+                    order: FunctionOrder::THIRD,
+                    ..Default::default()
+                };
+                let optimizer = LBFGS {
+                    max_iter: parameters.max_iter,
+                    g_atol: parameters.tol.to_f64().unwrap(),
+                    ..Default::default()
+                };
+
+                optimizer.optimize(&f, &df, &x0, &ls)
+            }
+            LogisticRegressionSolverName::SAG => {
+                Self::sag(x0, &objective, TX::zero(), parameters, false)
+            }
+            LogisticRegressionSolverName::SAGA => Self::sag(x0, &objective, l1, parameters, true),
+            LogisticRegressionSolverName::NewtonCG => Self::newton_cg(x0, &objective, parameters),
+            LogisticRegressionSolverName::ProximalGradient => {
+                Self::proximal_gradient(x0, &objective, l1, parameters)
+            }
+        }
+    }
+
+    /// L1 norm of the weights, intercepts excluded.
+    fn l1_norm(w: &[TX], num_attributes: usize) -> TX {
+        w.iter()
+            .enumerate()
+            .filter(|(i, _)| i % (num_attributes + 1) != num_attributes)
+            .map(|(_, w_i)| w_i.abs())
+            .sum()
+    }
+
+    /// Proximal operator of `threshold` times the L1 norm, intercepts are left untouched.
+    fn l1_prox(w: &mut [TX], threshold: TX, num_attributes: usize) {
+        for (i, w_i) in w.iter_mut().enumerate() {
+            if i % (num_attributes + 1) != num_attributes {
+                *w_i = CoordinateDescentOptimizer::soft_threshold(*w_i, threshold);
+            }
+        }
+    }
+
+    /// Accelerated proximal gradient (FISTA). The step size is found by backtracking on the
+    /// quadratic upper bound of the smooth part and momentum is restarted whenever the objective increases.
+    fn proximal_gradient(
+        mut w: Vec<TX>,
+        objective: &impl ObjectiveFunction<TX, X>,
+        l1: TX,
+        parameters: &LogisticRegressionParameters<TX>,
+    ) -> OptimizerResult<TX, Vec<TX>> {
+        let p = objective.x().shape().1;
+        let two: TX = RealNumber::two();
+
+        let mut lipschitz = TX::one();
+        let mut t = TX::one();
+        let mut v = w.clone();
+        let mut g = Vec::zeros(w.len());
+        let mut w_new = w.clone();
+        let mut f_x = objective.f(&w) + l1 * Self::l1_norm(&w, p);
+
+        let mut iterations = 0;
+        let mut converged = false;
+
+        while !converged && iterations < parameters.max_iter {
+            let f_v = objective.f(&v);
+            objective.df(&mut g, &v);
+
+            loop {
+                for ((w_new_i, &v_i), &g_i) in w_new.iter_mut().zip(v.iter()).zip(g.iter()) {
+                    *w_new_i = v_i - g_i / lipschitz;
+                }
+                Self::l1_prox(&mut w_new, l1 / lipschitz, p);
+
+                let mut linear = TX::zero();
+                let mut quadratic = TX::zero();
+                for ((&w_new_i, &v_i), &g_i) in w_new.iter().zip(v.iter()).zip(g.iter()) {
+                    linear += g_i * (w_new_i - v_i);
+                    quadratic += (w_new_i - v_i) * (w_new_i - v_i);
+                }
+                let upper_bound = f_v + linear + lipschitz / two * quadratic;
+                if objective.f(&w_new) <= upper_bound || quadratic == TX::zero() {
+                    break;
+                }
+                lipschitz *= two;
+            }
+
+            iterations += 1;
+
+            let f_new = objective.f(&w_new) + l1 * Self::l1_norm(&w_new, p);
+
+            let mut max_change = TX::zero();
+            let mut max_weight = TX::zero();
+            for (w_new_i, w_i) in w_new.iter().zip(w.iter()) {
+                max_change = max_change.max((*w_new_i - *w_i).abs());
+                max_weight = max_weight.max(w_new_i.abs());
+            }
+            converged = max_change <= parameters.tol * max_weight;
+
+            if f_new > f_x {
+                t = TX::one();
+            }
+            let t_new = (TX::one() + (TX::one() + two * two * t * t).sqrt()) / two;
+            let momentum = (t - TX::one()) / t_new;
+            for ((v_i, &w_new_i), &w_i) in v.iter_mut().zip(w_new.iter()).zip(w.iter()) {
+                *v_i = w_new_i + momentum * (w_new_i - w_i);
+            }
+            t = t_new;
+
+            w.copy_from(&w_new);
+            f_x = f_new;
+        }
+
+        OptimizerResult {
+            x: w,
+            f_x,
+            iterations,
+            converged,
+        }
+    }
+
+    /// Stochastic average gradient. Keeps the last gradient of every sample and steps along their average,
+    /// SAGA additionally corrects the step with the change of the gradient of the drawn sample
+    /// and takes a proximal step for the L1 penalty.
+    fn sag(
+        mut w: Vec<TX>,
+        objective: &impl ObjectiveFunction<TX, X>,
+        l1: TX,
+        parameters: &LogisticRegressionParameters<TX>,
+        saga: bool,
+    ) -> OptimizerResult<TX, Vec<TX>> {
+        let x = objective.x();
+        let (n, p) = x.shape();
+        let k = objective.num_predictors();
+
+        // the objective is a sum over samples while SAG minimizes their mean
+        let alpha = objective.alpha() / TX::from_usize(n).unwrap();
+        let l1_mean = l1 / TX::from_usize(n).unwrap();
+
+        let mut max_squared_norm = TX::zero();
+        for (i, sample_weight_i) in objective.sample_weight().iter().enumerate() {
+            let mut squared_norm = TX::one();
+            for j in 0..p {
+                squared_norm += *x.get((i, j)) * *x.get((i, j));
+            }
+            max_squared_norm = max_squared_norm.max(*sample_weight_i * squared_norm);
+        }
+        let lipschitz = objective.loss_curvature() * max_squared_norm + alpha;
+        let step = if saga {
+            let two: TX = RealNumber::two();
+            TX::one() / (two * lipschitz + (two * alpha).min(lipschitz))
+        } else {
+            TX::one() / lipschitz
         };
-        let optimizer: LBFGS = Default::default();
 
-        optimizer.optimize(&f, &df, &x0, &ls)
+        let mut memory = vec![TX::zero(); n * k];
+        let mut seen = vec![false; n];
+        let mut num_seen = 0;
+        let mut grad_sum = vec![TX::zero(); w.len()];
+        let mut grad = vec![TX::zero(); k];
+        let mut rng = get_rng_impl(Some(parameters.seed));
+
+        let mut iterations = 0;
+        let mut converged = false;
+
+        while !converged && iterations < parameters.max_iter {
+            let w_prev = w.clone();
+
+            for _ in 0..n {
+                let i = rng.gen_range(0..n);
+                objective.sample_grad(&mut grad, &w, i);
+
+                if !seen[i] {
+                    seen[i] = true;
+                    num_seen += 1;
+                }
+                let num_seen = TX::from_usize(num_seen).unwrap();
+
+                for (j, grad_j) in grad.iter().enumerate() {
+                    let delta = *grad_j - memory[i * k + j];
+                    memory[i * k + j] = *grad_j;
+
+                    for l in 0..=p {
+                        let pos = j * (p + 1) + l;
+                        let delta_l = if l < p { delta * *x.get((i, l)) } else { delta };
+                        let mut g = if saga {
+                            delta_l + grad_sum[pos] / num_seen
+                        } else {
+                            (grad_sum[pos] + delta_l) / num_seen
+                        };
+                        grad_sum[pos] += delta_l;
+                        if l < p {
+                            g += alpha * w[pos];
+                        }
+                        w[pos] -= step * g;
+                        if l < p && l1_mean > TX::zero() {
+                            w[pos] =
+                                CoordinateDescentOptimizer::soft_threshold(w[pos], step * l1_mean);
+                        }
+                    }
+                }
+            }
+
+            iterations += 1;
+
+            let mut max_change = TX::zero();
+            let mut max_weight = TX::zero();
+            for (w_i, w_prev_i) in w.iter().zip(w_prev.iter()) {
+                max_change = max_change.max((*w_i - *w_prev_i).abs());
+                max_weight = max_weight.max(w_i.abs());
+            }
+            converged = max_change <= parameters.tol * max_weight;
+        }
+
+        let f_x = objective.f(&w) + l1 * Self::l1_norm(&w, p);
+
+        OptimizerResult {
+            x: w,
+            f_x,
+            iterations,
+            converged,
+        }
+    }
+
+    /// Truncated Newton method. Search directions approximately solve the Newton system with conjugate gradient,
+    /// the step length is chosen by backtracking until the Armijo condition holds.
+    fn newton_cg(
+        mut w: Vec<TX>,
+        objective: &impl ObjectiveFunction<TX, X>,
+        parameters: &LogisticRegressionParameters<TX>,
+    ) -> OptimizerResult<TX, Vec<TX>> {
+        let c1 = TX::from_f64(1e-4).unwrap();
+        let mut g = Vec::zeros(w.len());
+        let mut f_x = objective.f(&w);
+
+        let mut iterations = 0;
+        let mut converged = false;
+
+        while iterations < parameters.max_iter {
+            objective.df(&mut g, &w);
+            if g.iter().fold(TX::zero(), |m, g_i| m.max(g_i.abs())) <= parameters.tol {
+                converged = true;
+                break;
+            }
+
+            iterations += 1;
+
+            let direction = Self::conjugate_gradient(objective, &w, &g);
+            let df0: TX = g.iter().zip(direction.iter()).map(|(&a, &b)| a * b).sum();
+
+            let mut step = TX::one();
+            let mut w_new = w.clone();
+            let mut accepted = false;
+            for _ in 0..50 {
+                for ((w_new_i, &w_i), &d_i) in w_new.iter_mut().zip(w.iter()).zip(direction.iter())
+                {
+                    *w_new_i = w_i + step * d_i;
+                }
+                let f_new = objective.f(&w_new);
+                if f_new <= f_x + c1 * step * df0 {
+                    f_x = f_new;
+                    accepted = true;
+                    break;
+                }
+                step *= RealNumber::half();
+            }
+
+            if !accepted {
+                // no further progress is possible within floating point precision
+                break;
+            }
+            w = w_new;
+        }
+
+        OptimizerResult {
+            x: w,
+            f_x,
+            iterations,
+            converged,
+        }
+    }
+
+    /// Approximately solves `H d = -g` with conjugate gradient, `H` is the Hessian of the objective at `w`.
+    fn conjugate_gradient(
+        objective: &impl ObjectiveFunction<TX, X>,
+        w: &Vec<TX>,
+        g: &[TX],
+    ) -> Vec<TX> {
+        let dim = g.len();
+        let g_norm: TX = g.iter().map(|g_i| g_i.abs()).sum();
+        let tol = <TX as RealNumber>::half().min(g_norm.sqrt()) * g_norm;
+
+        let mut d: Vec<TX> = Vec::zeros(dim);
+        let mut r: Vec<TX> = g.iter().map(|&g_i| -g_i).collect();
+        let mut p = r.clone();
+        let mut hp = Vec::zeros(dim);
+        let mut r_squared: TX = r.iter().map(|&r_i| r_i * r_i).sum();
+
+        for i in 0..dim.max(200) {
+            if r.iter().map(|r_i| r_i.abs()).sum::<TX>() <= tol {
+                break;
+            }
+
+            objective.hessp(&mut hp, w, &p);
+            let curvature: TX = p.iter().zip(hp.iter()).map(|(&a, &b)| a * b).sum();
+            if curvature <= TX::zero() {
+                // the Hessian is not positive definite along p, fall back to steepest descent on the first iteration
+                if i == 0 {
+                    d.copy_from(&r);
+                }
+                break;
+            }
+
+            let a = r_squared / curvature;
+            for j in 0..dim {
+                d[j] += a * p[j];
+                r[j] -= a * hp[j];
+            }
+
+            let r_squared_new: TX = r.iter().map(|&r_i| r_i * r_i).sum();
+            let beta = r_squared_new / r_squared;
+            for j in 0..dim {
+                p[j] = r[j] + beta * p[j];
+            }
+            r_squared = r_squared_new;
+        }
+
+        d
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
     use super::*;
 
     #[cfg(feature = "datasets")]
     use crate::dataset::generator::make_blobs;
-    use crate::linalg::basic::arrays::Array;
+    use crate::linalg::basic::arrays::{Array, ArrayView1};
     use crate::linalg::basic::matrix::DenseMatrix;
+    use crate::test_datasets::{assert_weights_repeat_rows, iris};
 
     #[test]
     fn search_parameters() {
@@ -619,6 +1320,7 @@ mod tests {
         let objective = MultiClassObjectiveFunction {
             x: &x,
             y: y.clone(),
+            sample_weight: vec![1.; 15],
             k: 3,
             alpha: 0.0,
             _phantom_t: PhantomData,
@@ -638,6 +1340,7 @@ mod tests {
         let objective_reg = MultiClassObjectiveFunction {
             x: &x,
             y,
+            sample_weight: vec![1.; 15],
             k: 3,
             alpha: 1.0,
             _phantom_t: PhantomData,
@@ -680,6 +1383,7 @@ mod tests {
         let objective = BinaryObjectiveFunction {
             x: &x,
             y: y.clone(),
+            sample_weight: vec![1.; 15],
             alpha: 0.0,
             _phantom_t: PhantomData,
         };
@@ -700,6 +1404,7 @@ mod tests {
         let objective_reg = BinaryObjectiveFunction {
             x: &x,
             y,
+            sample_weight: vec![1.; 15],
             alpha: 1.0,
             _phantom_t: PhantomData,
         };
@@ -907,6 +1612,393 @@ mod tests {
 
         assert!(reg_coeff_sum < coeff);
     }
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn lr_predict_proba() {
+        let x: DenseMatrix<f64> = DenseMatrix::from_2d_array(&[
+            &[1., -5.],
+            &[2., 5.],
+            &[3., -2.],
+            &[1., 2.],
+            &[2., 0.],
+            &[6., -5.],
+            &[7., 5.],
+            &[6., -2.],
+            &[7., 2.],
+            &[6., 0.],
+            &[8., -5.],
+            &[9., 5.],
+            &[10., -2.],
+            &[8., 2.],
+            &[9., 0.],
+        ])
+        .unwrap();
+        let y_binary: Vec<i32> = vec![0, 0, 1, 1, 1, 1, 1, 0, 0, 1, 1, 1, 0, 0, 1];
+        let y_multiclass: Vec<i32> = vec![0, 0, 1, 1, 2, 1, 1, 0, 0, 2, 1, 1, 0, 0, 1];
+
+        for y in [y_binary, y_multiclass] {
+            let lr = LogisticRegression::fit(
+                &x,
+                &y,
+                LogisticRegressionParameters::default().with_alpha(1.0),
+            )
+            .unwrap();
+            let k = lr.classes().len();
+
+            let y_hat = lr.predict(&x).unwrap();
+            let proba = lr.predict_proba(&x).unwrap();
+            let log_proba = lr.predict_log_proba(&x).unwrap();
+            let margins = lr.decision_function(&x).unwrap();
+
+            assert_eq!(proba.shape(), (15, k));
+            assert_eq!(log_proba.shape(), (15, k));
+            assert_eq!(margins.shape(), (15, if k == 2 { 1 } else { k }));
+
+            for (i, y_hat_i) in y_hat.iter().enumerate() {
+                let row: Vec<f64> = (0..k).map(|c| *proba.get((i, c))).collect();
+                assert!((row.iter().sum::<f64>() - 1.).abs() < 1e-8);
+                assert_eq!(lr.classes()[row.argmax()], *y_hat_i);
+                for (c, p) in row.iter().enumerate() {
+                    assert!((log_proba.get((i, c)) - p.ln()).abs() < 1e-8);
+                }
+            }
+        }
+
+        let lr = LogisticRegression::fit(
+            &x,
+            &vec![0, 0, 1, 1, 1, 1, 1, 0, 0, 1, 1, 1, 0, 0, 1],
+            Default::default(),
+        )
+        .unwrap();
+        let x_wrong: DenseMatrix<f64> = DenseMatrix::from_2d_array(&[&[1., 2., 3.]]).unwrap();
+        assert!(lr.predict_proba(&x_wrong).is_err());
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn lr_solvers_agree() {
+        let x: DenseMatrix<f64> = DenseMatrix::from_2d_array(&[
+            &[1., -5.],
+            &[2., 5.],
+            &[3., -2.],
+            &[1., 2.],
+            &[2., 0.],
+            &[6., -5.],
+            &[7., 5.],
+            &[6., -2.],
+            &[7., 2.],
+            &[6., 0.],
+            &[8., -5.],
+            &[9., 5.],
+            &[10., -2.],
+            &[8., 2.],
+            &[9., 0.],
+        ])
+        .unwrap();
+        let y_binary: Vec<i32> = vec![0, 0, 1, 1, 1, 1, 1, 0, 0, 1, 1, 1, 0, 0, 1];
+        let y_multiclass: Vec<i32> = vec![0, 0, 1, 1, 2, 1, 1, 0, 0, 2, 1, 1, 0, 0, 1];
+
+        for y in [y_binary, y_multiclass] {
+            let parameters = LogisticRegressionParameters::default()
+                .with_alpha(1.0)
+                .with_max_iter(10000);
+            let expected = LogisticRegression::fit(&x, &y, parameters.clone()).unwrap();
+            assert!(expected.optimizer_result().converged);
+
+            for solver in [
+                LogisticRegressionSolverName::SAG,
+                LogisticRegressionSolverName::SAGA,
+                LogisticRegressionSolverName::NewtonCG,
+            ] {
+                let lr =
+                    LogisticRegression::fit(&x, &y, parameters.clone().with_solver(solver.clone()))
+                        .unwrap();
+                let result = lr.optimizer_result();
+
+                assert!(result.converged, "{solver:?} did not converge");
+                assert!((result.f_x - expected.optimizer_result().f_x).abs() < 1e-6);
+                assert!(lr
+                    .coefficients()
+                    .approximate_eq(expected.coefficients(), 1e-3));
+                assert!(lr.intercept().approximate_eq(expected.intercept(), 1e-3));
+            }
+        }
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn lr_l1_penalty() {
+        // setosa and versicolor
+        let (x, y) = iris();
+        let rows: Vec<usize> = (0..12).collect();
+        let x = x.take(&rows, 0);
+        let y: Vec<i32> = y[..12].iter().map(|&y_i| y_i as i32).collect();
+
+        let parameters = LogisticRegressionParameters::default()
+            .with_alpha(2.0)
+            .with_penalty(LogisticRegressionPenalty::L1)
+            .with_max_iter(100000);
+
+        let lr = LogisticRegression::fit(
+            &x,
+            &y,
+            parameters
+                .clone()
+                .with_solver(LogisticRegressionSolverName::ProximalGradient),
+        )
+        .unwrap();
+        assert!(lr.optimizer_result().converged);
+
+        let w = &lr.optimizer_result().x;
+        assert!(w[..4].contains(&0.));
+        assert!(w[..4].iter().any(|&w_i| w_i != 0.));
+
+        // optimality conditions of the L1 penalized objective
+        let objective = BinaryObjectiveFunction {
+            x: &x,
+            y: y.iter().map(|&y_i| y_i as usize).collect(),
+            sample_weight: vec![1.; 12],
+            alpha: 0.0,
+            _phantom_t: PhantomData,
+        };
+        let mut g = vec![0.; 5];
+        objective.df(&mut g, w);
+        for j in 0..4 {
+            if w[j] == 0. {
+                assert!(g[j].abs() <= 2.0 + 1e-4);
+            } else {
+                assert!((g[j] + 2.0 * w[j].signum()).abs() < 1e-4);
+            }
+        }
+        assert!(g[4].abs() < 1e-4);
+
+        let saga = LogisticRegression::fit(
+            &x,
+            &y,
+            parameters.with_solver(LogisticRegressionSolverName::SAGA),
+        )
+        .unwrap();
+        assert!((saga.optimizer_result().f_x - lr.optimizer_result().f_x).abs() < 1e-4);
+    }
+
+    #[test]
+    fn lr_elastic_net_penalty() {
+        let x: DenseMatrix<f64> = DenseMatrix::from_2d_array(&[
+            &[1., -5.],
+            &[2., 5.],
+            &[3., -2.],
+            &[1., 2.],
+            &[2., 0.],
+            &[6., -5.],
+            &[7., 5.],
+            &[6., -2.],
+            &[7., 2.],
+            &[6., 0.],
+            &[8., -5.],
+            &[9., 5.],
+            &[10., -2.],
+            &[8., 2.],
+            &[9., 0.],
+        ])
+        .unwrap();
+        let y: Vec<i32> = vec![0, 0, 1, 1, 2, 1, 1, 0, 0, 2, 1, 1, 0, 0, 1];
+
+        let l2 = LogisticRegression::fit(
+            &x,
+            &y,
+            LogisticRegressionParameters::default().with_alpha(1.0),
+        )
+        .unwrap();
+        let elastic_net = LogisticRegression::fit(
+            &x,
+            &y,
+            LogisticRegressionParameters::default()
+                .with_alpha(1.0)
+                .with_penalty(LogisticRegressionPenalty::ElasticNet(0.0))
+                .with_solver(LogisticRegressionSolverName::ProximalGradient)
+                .with_max_iter(100000),
+        )
+        .unwrap();
+        assert!(elastic_net
+            .coefficients()
+            .approximate_eq(l2.coefficients(), 1e-3));
+
+        let sparse = LogisticRegression::fit(
+            &x,
+            &y,
+            LogisticRegressionParameters::default()
+                .with_alpha(1.0)
+                .with_penalty(LogisticRegressionPenalty::ElasticNet(0.5))
+                .with_solver(LogisticRegressionSolverName::ProximalGradient),
+        )
+        .unwrap();
+        let l1_norm: f64 = sparse.coefficients().abs().iter().sum();
+        let l2_l1_norm: f64 = l2.coefficients().abs().iter().sum();
+        assert!(l1_norm < l2_l1_norm);
+
+        assert!(LogisticRegression::fit(
+            &x,
+            &y,
+            LogisticRegressionParameters::default().with_penalty(LogisticRegressionPenalty::L1),
+        )
+        .is_err());
+        assert!(LogisticRegression::fit(
+            &x,
+            &y,
+            LogisticRegressionParameters::default()
+                .with_penalty(LogisticRegressionPenalty::ElasticNet(1.5))
+                .with_solver(LogisticRegressionSolverName::SAGA),
+        )
+        .is_err());
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn lr_fit_with_sample_weight() {
+        let x: DenseMatrix<f64> = DenseMatrix::from_2d_array(&[
+            &[1., -5.],
+            &[2., 5.],
+            &[3., -2.],
+            &[1., 2.],
+            &[2., 0.],
+            &[6., -5.],
+            &[7., 5.],
+            &[6., -2.],
+            &[7., 2.],
+            &[6., 0.],
+            &[8., -5.],
+            &[9., 5.],
+            &[10., -2.],
+            &[8., 2.],
+            &[9., 0.],
+        ])
+        .unwrap();
+        let y: Vec<i32> = vec![0, 0, 1, 1, 2, 1, 1, 0, 0, 2, 1, 1, 0, 0, 1];
+
+        // integer weights are equivalent to repeated observations
+        let sample_weight = vec![1., 2., 1., 3., 1., 1., 2., 1., 1., 1., 2., 1., 1., 1., 1.];
+        let parameters = LogisticRegressionParameters::default().with_alpha(1.0);
+        let parameters_of = |lr: LogisticRegression<f64, i32, DenseMatrix<f64>, Vec<i32>>| {
+            lr.coefficients()
+                .iterator(0)
+                .chain(lr.intercept().iterator(0))
+                .copied()
+                .collect()
+        };
+        assert_weights_repeat_rows(
+            &x,
+            &y,
+            &sample_weight,
+            |x_w, y_w, w| {
+                LogisticRegression::fit_with_sample_weight(x_w, y_w, w, parameters.clone())
+                    .map(parameters_of)
+                    .unwrap()
+            },
+            |x_r, y_r| {
+                LogisticRegression::fit(x_r, y_r, parameters.clone())
+                    .map(parameters_of)
+                    .unwrap()
+            },
+            1e-4,
+        );
+
+        // 6 samples of class 0, 7 of class 1 and 2 of class 2
+        let balanced = LogisticRegression::fit(
+            &x,
+            &y,
+            parameters.clone().with_class_weight(ClassWeight::Balanced),
+        )
+        .unwrap();
+        let class_weight: Vec<f64> = y
+            .iter()
+            .map(|&c| 15. / (3. * [6., 7., 2.][c as usize]))
+            .collect();
+        let expected =
+            LogisticRegression::fit_with_sample_weight(&x, &y, &class_weight, parameters.clone())
+                .unwrap();
+        assert!(balanced
+            .coefficients()
+            .approximate_eq(expected.coefficients(), 1e-6));
+
+        let custom = LogisticRegression::fit_with_sample_weight(
+            &x,
+            &y,
+            &sample_weight,
+            parameters
+                .clone()
+                .with_class_weight(ClassWeight::Custom(HashMap::from([(2, 5.)]))),
+        )
+        .unwrap();
+        let combined_weight: Vec<f64> = sample_weight
+            .iter()
+            .zip(y.iter())
+            .map(|(&w, &c)| if c == 2 { 5. * w } else { w })
+            .collect();
+        let expected = LogisticRegression::fit_with_sample_weight(
+            &x,
+            &y,
+            &combined_weight,
+            parameters.clone(),
+        )
+        .unwrap();
+        assert!(custom
+            .coefficients()
+            .approximate_eq(expected.coefficients(), 1e-6));
+
+        assert!(LogisticRegression::fit(
+            &x,
+            &y,
+            parameters
+                .clone()
+                .with_class_weight(ClassWeight::Custom(HashMap::from([(3, 5.,)]))),
+        )
+        .is_err());
+
+        assert!(
+            LogisticRegression::fit_with_sample_weight(&x, &y, &[-1.; 15], parameters).is_err()
+        );
+    }
+
+    #[test]
+    fn lr_newton_cg_iterations() {
+        let x: DenseMatrix<f64> = DenseMatrix::from_2d_array(&[
+            &[1., -5.],
+            &[2., 5.],
+            &[3., -2.],
+            &[1., 2.],
+            &[2., 0.],
+            &[6., -5.],
+            &[7., 5.],
+            &[6., -2.],
+        ])
+        .unwrap();
+        let y: Vec<i32> = vec![0, 0, 1, 1, 1, 0, 1, 0];
+
+        let parameters = LogisticRegressionParameters::default()
+            .with_solver(LogisticRegressionSolverName::NewtonCG)
+            .with_alpha(0.1);
+
+        let lr = LogisticRegression::fit(&x, &y, parameters.clone()).unwrap();
+        assert!(lr.optimizer_result().converged);
+        assert!(lr.optimizer_result().iterations < 20);
+
+        let lr = LogisticRegression::fit(&x, &y, parameters.with_max_iter(1)).unwrap();
+        assert!(!lr.optimizer_result().converged);
+        assert_eq!(lr.optimizer_result().iterations, 1);
+    }
+
     #[cfg_attr(
         all(target_arch = "wasm32", not(target_os = "wasi")),
         wasm_bindgen_test::wasm_bindgen_test
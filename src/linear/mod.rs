@@ -21,9 +21,72 @@
 //! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
 
 pub mod bg_solver;
+pub mod coordinate_descent;
 pub mod elastic_net;
+pub mod elastic_net_cv;
+pub mod glm;
+pub mod huber;
 pub mod lasso;
+pub mod lasso_cv;
 pub mod lasso_optimizer;
 pub mod linear_regression;
 pub mod logistic_regression;
+pub mod quantile_regression;
+pub mod ransac;
+pub mod ridge_cv;
 pub mod ridge_regression;
+pub mod theil_sen;
+
+use crate::linalg::basic::arrays::Array2;
+use crate::numbers::realnum::RealNumber;
+
+/// Weighted mean and standard deviation of every column of `x`.
+pub(crate) fn weighted_col_mean_std<T: RealNumber, X: Array2<T>>(
+    x: &X,
+    sample_weight: &[T],
+) -> (Vec<T>, Vec<T>) {
+    let (_, p) = x.shape();
+    let total: T = sample_weight.iter().copied().sum();
+    let mut col_mean = vec![T::zero(); p];
+    let mut col_std = vec![T::zero(); p];
+    for j in 0..p {
+        for (i, w_i) in sample_weight.iter().enumerate() {
+            col_mean[j] += *w_i * *x.get((i, j));
+        }
+        col_mean[j] /= total;
+        for (i, w_i) in sample_weight.iter().enumerate() {
+            let d = *x.get((i, j)) - col_mean[j];
+            col_std[j] += *w_i * d * d;
+        }
+        col_std[j] = (col_std[j] / total).sqrt();
+    }
+    (col_mean, col_std)
+}
+
+/// Multiplies every row of `x` by the square root of its weight. Least squares on the result
+/// minimizes the weighted sum of squared residuals.
+pub(crate) fn sqrt_weigh_rows<T: RealNumber, X: Array2<T>>(x: &X, sample_weight: &[T]) -> X {
+    let (n, p) = x.shape();
+    let mut weighted = x.clone();
+    for (i, w_i) in sample_weight.iter().enumerate().take(n) {
+        let sqrt_w = w_i.sqrt();
+        for j in 0..p {
+            weighted.set((i, j), sqrt_w * *x.get((i, j)));
+        }
+    }
+    weighted
+}
+
+/// Median of `values`, which are reordered in the process. `values` must not be empty.
+pub(crate) fn median<T: RealNumber>(values: &mut [T]) -> T {
+    let n = values.len();
+    let cmp = |a: &T, b: &T| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal);
+    let (lower, upper, _) = values.select_nth_unstable_by(n / 2, cmp);
+    let upper = *upper;
+    if n % 2 == 1 {
+        upper
+    } else {
+        let lower = lower.iter().copied().fold(T::neg_infinity(), T::max);
+        (lower + upper) / T::two()
+    }
+}
@@ -0,0 +1,832 @@
+//! # Quantile Regression
+//!
+//! [Linear regression](../linear_regression/index.html) estimates the conditional mean of the response. Quantile regression estimates
+//! the conditional \\(\tau\\)-quantile instead, e.g. the median for \\(\tau = 0.5\\). Fitting two models for low and high quantiles gives a prediction interval
+//! that, unlike the one implied by least squares, adapts to skewed errors and to noise that grows with the features.
+//!
+//! The coefficients minimize the [pinball loss](../../metrics/mean_pinball_loss/index.html) with an L1 penalty on all coefficients but the intercept
+//!
+//! \\[\frac{1}{n} \sum_{i=1}^n \rho_{\tau}(y_i - x_i^T\beta - \beta_0) + \alpha \lVert \beta \rVert_1, \quad \rho_{\tau}(r) = \max(\tau r, (\tau - 1) r)\\]
+//!
+//! The objective is not differentiable, `smartcore` offers two solvers:
+//!
+//! * _Simplex_, the problem is written as a linear program and solved exactly with the simplex method. Memory grows with the square of the number of observations,
+//!   so this solver suits data sets of up to a few thousand observations.
+//! * _IRLS_, the majorize-minimize algorithm of Hunter and Lange. Both absolute values are replaced by quadratics that touch them at the current estimate,
+//!   so every iteration is a weighted ridge regression. The solution is approximate, residuals and coefficients that are zero at the optimum are only close to zero.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::basic::matrix::DenseMatrix;
+//! use smartcore::linear::quantile_regression::*;
+//! use smartcore::metrics::mean_pinball_loss;
+//!
+//! // daily demand given price and temperature
+//! let x = DenseMatrix::from_2d_array(&[
+//!             &[1.0, 15.], &[1.2, 18.], &[0.9, 21.], &[1.1, 24.], &[1.3, 19.],
+//!             &[0.8, 22.], &[1.0, 26.], &[1.2, 16.], &[0.9, 17.], &[1.1, 23.],
+//!         ]).unwrap();
+//! let y: Vec<f64> = vec![52., 48., 71., 66., 45., 80., 70., 41., 58., 69.];
+//!
+//! // 90% prediction interval
+//! let lower = QuantileRegressor::fit(
+//!     &x,
+//!     &y,
+//!     QuantileRegressorParameters::default().with_quantile(0.05).with_alpha(0.),
+//! ).unwrap();
+//! let upper = QuantileRegressor::fit(
+//!     &x,
+//!     &y,
+//!     QuantileRegressorParameters::default().with_quantile(0.95).with_alpha(0.),
+//! ).unwrap();
+//!
+//! let y_lower = lower.predict(&x).unwrap();
+//! let y_upper = upper.predict(&x).unwrap();
+//! let loss = mean_pinball_loss(&y, &y_upper, 0.95);
+//! ```
+//!
+//! ## References:
+//!
+//! * ["Regression Quantiles", Koenker R., Bassett G., 1978](https://doi.org/10.2307/1913643)
+//! * ["Quantile Regression via an MM Algorithm", Hunter D.R., Lange K., 2000](https://doi.org/10.1080/10618600.2000.10474866)
+//! * ["Linear Programming and Extensions", Dantzig G.B., 1963](https://doi.org/10.1515/9781400884179)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::api::{Predictor, SupervisedEstimator};
+use crate::error::Failed;
+use crate::linalg::basic::arrays::{Array1, Array2};
+use crate::linalg::traits::cholesky::CholeskyDecomposable;
+use crate::linear::{median, sqrt_weigh_rows};
+use crate::numbers::basenum::Number;
+use crate::numbers::floatnum::FloatNumber;
+use crate::numbers::realnum::RealNumber;
+
+/// Number of consecutive degenerate pivots after which the simplex solver switches to Bland's rule, which cannot cycle.
+const MAX_DEGENERATE_PIVOTS: usize = 50;
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+/// Solver to use for estimation of regression coefficients.
+pub enum QuantileRegressorSolverName {
+    /// Exact solution of the linear program with the simplex method
+    #[default]
+    Simplex,
+    /// Majorize-minimize algorithm, a sequence of weighted ridge regressions
+    IRLS,
+}
+
+/// Quantile regression parameters
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct QuantileRegressorParameters<T: Number + FloatNumber> {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Solver to use for estimation of regression coefficients.
+    pub solver: QuantileRegressorSolverName,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Quantile to estimate, strictly between 0 and 1.
+    pub quantile: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Strength of the L1 penalty on the coefficients.
+    pub alpha: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Maximum number of pivots of the simplex method or iterations of IRLS.
+    pub max_iter: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// IRLS stops when no coefficient changes by more than `tol` relative to the largest coefficient.
+    /// Residuals are smoothed below `tol` times the median absolute deviation of `y`. Not used by the simplex method.
+    pub tol: T,
+}
+
+impl<T: Number + FloatNumber> QuantileRegressorParameters<T> {
+    /// Solver to use for estimation of regression coefficients.
+    pub fn with_solver(mut self, solver: QuantileRegressorSolverName) -> Self {
+        self.solver = solver;
+        self
+    }
+    /// Quantile to estimate, strictly between 0 and 1.
+    pub fn with_quantile(mut self, quantile: T) -> Self {
+        self.quantile = quantile;
+        self
+    }
+    /// Strength of the L1 penalty on the coefficients.
+    pub fn with_alpha(mut self, alpha: T) -> Self {
+        self.alpha = alpha;
+        self
+    }
+    /// Maximum number of pivots of the simplex method or iterations of IRLS.
+    pub fn with_max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+    /// Tolerance of IRLS.
+    pub fn with_tol(mut self, tol: T) -> Self {
+        self.tol = tol;
+        self
+    }
+}
+
+impl<T: Number + FloatNumber> Default for QuantileRegressorParameters<T> {
+    fn default() -> Self {
+        QuantileRegressorParameters {
+            solver: QuantileRegressorSolverName::default(),
+            quantile: T::from_f64(0.5).unwrap(),
+            alpha: T::one(),
+            max_iter: 10000,
+            tol: T::from_f64(1e-6).unwrap(),
+        }
+    }
+}
+
+/// Quantile regression grid search parameters
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct QuantileRegressorSearchParameters<T: Number + FloatNumber> {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Quantile to estimate, strictly between 0 and 1.
+    pub quantile: Vec<T>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Strength of the L1 penalty on the coefficients.
+    pub alpha: Vec<T>,
+}
+
+/// Quantile regression grid search iterator
+pub struct QuantileRegressorSearchParametersIterator<T: Number + FloatNumber> {
+    quantile_regressor_search_parameters: QuantileRegressorSearchParameters<T>,
+    current_quantile: usize,
+    current_alpha: usize,
+}
+
+impl<T: Number + FloatNumber> IntoIterator for QuantileRegressorSearchParameters<T> {
+    type Item = QuantileRegressorParameters<T>;
+    type IntoIter = QuantileRegressorSearchParametersIterator<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        QuantileRegressorSearchParametersIterator {
+            quantile_regressor_search_parameters: self,
+            current_quantile: 0,
+            current_alpha: 0,
+        }
+    }
+}
+
+impl<T: Number + FloatNumber> Iterator for QuantileRegressorSearchParametersIterator<T> {
+    type Item = QuantileRegressorParameters<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.current_quantile == self.quantile_regressor_search_parameters.quantile.len()
+            && self.current_alpha == self.quantile_regressor_search_parameters.alpha.len()
+        {
+            return None;
+        }
+
+        let next = QuantileRegressorParameters {
+            quantile: self.quantile_regressor_search_parameters.quantile[self.current_quantile],
+            alpha: self.quantile_regressor_search_parameters.alpha[self.current_alpha],
+            ..Default::default()
+        };
+
+        if self.current_quantile + 1 < self.quantile_regressor_search_parameters.quantile.len() {
+            self.current_quantile += 1;
+        } else if self.current_alpha + 1 < self.quantile_regressor_search_parameters.alpha.len() {
+            self.current_quantile = 0;
+            self.current_alpha += 1;
+        } else {
+            self.current_quantile += 1;
+            self.current_alpha += 1;
+        }
+
+        Some(next)
+    }
+}
+
+impl<T: Number + FloatNumber> Default for QuantileRegressorSearchParameters<T> {
+    fn default() -> Self {
+        let default_params = QuantileRegressorParameters::default();
+
+        QuantileRegressorSearchParameters {
+            quantile: vec![default_params.quantile],
+            alpha: vec![default_params.alpha],
+        }
+    }
+}
+
+/// Quantile regression
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+pub struct QuantileRegressor<
+    TX: Number + FloatNumber + RealNumber,
+    TY: Number,
+    X: Array2<TX> + CholeskyDecomposable<TX>,
+    Y: Array1<TY>,
+> {
+    coefficients: Option<X>,
+    intercept: Option<TX>,
+    n_iter: usize,
+    _phantom_ty: PhantomData<TY>,
+    _phantom_y: PhantomData<Y>,
+}
+
+impl<
+        TX: Number + FloatNumber + RealNumber,
+        TY: Number,
+        X: Array2<TX> + CholeskyDecomposable<TX>,
+        Y: Array1<TY>,
+    > PartialEq for QuantileRegressor<TX, TY, X, Y>
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.intercept() == other.intercept()
+            && self.coefficients().shape() == other.coefficients().shape()
+            && self
+                .coefficients()
+                .iterator(0)
+                .zip(other.coefficients().iterator(0))
+                .all(|(&a, &b)| (a - b).abs() <= TX::epsilon())
+    }
+}
+
+impl<
+        TX: Number + FloatNumber + RealNumber,
+        TY: Number,
+        X: Array2<TX> + CholeskyDecomposable<TX>,
+        Y: Array1<TY>,
+    > SupervisedEstimator<X, Y, QuantileRegressorParameters<TX>>
+    for QuantileRegressor<TX, TY, X, Y>
+{
+    fn new() -> Self {
+        Self {
+            coefficients: Option::None,
+            intercept: Option::None,
+            n_iter: 0,
+            _phantom_ty: PhantomData,
+            _phantom_y: PhantomData,
+        }
+    }
+
+    fn fit(x: &X, y: &Y, parameters: QuantileRegressorParameters<TX>) -> Result<Self, Failed> {
+        QuantileRegressor::fit(x, y, parameters)
+    }
+}
+
+impl<
+        TX: Number + FloatNumber + RealNumber,
+        TY: Number,
+        X: Array2<TX> + CholeskyDecomposable<TX>,
+        Y: Array1<TY>,
+    > Predictor<X, Y> for QuantileRegressor<TX, TY, X, Y>
+{
+    fn predict(&self, x: &X) -> Result<Y, Failed> {
+        self.predict(x)
+    }
+}
+
+impl<
+        TX: Number + FloatNumber + RealNumber,
+        TY: Number,
+        X: Array2<TX> + CholeskyDecomposable<TX>,
+        Y: Array1<TY>,
+    > QuantileRegressor<TX, TY, X, Y>
+{
+    /// Fits quantile regression to your data.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - target values
+    /// * `parameters` - other parameters, use `Default::default()` to set parameters to default values.
+    pub fn fit(
+        x: &X,
+        y: &Y,
+        parameters: QuantileRegressorParameters<TX>,
+    ) -> Result<QuantileRegressor<TX, TY, X, Y>, Failed> {
+        let (n, p) = x.shape();
+
+        if y.shape() != n {
+            return Err(Failed::fit("Number of rows in X should = len(y)"));
+        }
+        if n == 0 {
+            return Err(Failed::fit("X should have at least one row"));
+        }
+        if parameters.quantile <= TX::zero() || parameters.quantile >= TX::one() {
+            return Err(Failed::fit(&format!(
+                "quantile should be strictly between 0 and 1, got {}",
+                parameters.quantile
+            )));
+        }
+        if parameters.alpha < TX::zero() {
+            return Err(Failed::fit(&format!(
+                "alpha should be >= 0, got {}",
+                parameters.alpha
+            )));
+        }
+
+        let y: Vec<TX> = y.iterator(0).map(|&v| TX::from(v).unwrap()).collect();
+
+        let (beta, n_iter) = match parameters.solver {
+            QuantileRegressorSolverName::Simplex => Self::simplex(x, &y, &parameters)?,
+            QuantileRegressorSolverName::IRLS => Self::irls(x, &y, &parameters)?,
+        };
+
+        Ok(QuantileRegressor {
+            coefficients: Some(X::from_iterator(beta[..p].iter().copied(), p, 1, 0)),
+            intercept: Some(beta[p]),
+            n_iter,
+            _phantom_ty: PhantomData,
+            _phantom_y: PhantomData,
+        })
+    }
+
+    /// Solves the linear program
+    ///
+    /// minimize \\(\alpha \sum_j (\beta^+_j + \beta^-_j) + \frac{1}{n} \sum_i (\tau u_i + (1 - \tau) v_i)\\)
+    /// subject to \\(X(\beta^+ - \beta^-) + \beta^+_0 - \beta^-_0 + u - v = y\\) and all variables \\(\geq 0\\)
+    ///
+    /// with the simplex method. The slack of every row, \\(u_i\\) or \\(v_i\\) depending on the sign of \\(y_i\\), gives an initial feasible basis.
+    /// The column of every negative part is the negated column of its positive part, so only the positive parts are kept in the tableau.
+    fn simplex(
+        x: &X,
+        y: &[TX],
+        parameters: &QuantileRegressorParameters<TX>,
+    ) -> Result<(Vec<TX>, usize), Failed> {
+        let (n, p) = x.shape();
+        // columns of the coefficients, the intercept and the slacks u
+        let width = p + 1 + n;
+        let n_tx = TX::from_usize(n).unwrap();
+        let tau = parameters.quantile;
+        let cost = |column: usize, sign: TX| -> TX {
+            if column < p {
+                parameters.alpha
+            } else if column == p {
+                TX::zero()
+            } else if sign > TX::zero() {
+                tau / n_tx
+            } else {
+                (TX::one() - tau) / n_tx
+            }
+        };
+        let twin_cost = |column: usize| cost(column, TX::one()) + cost(column, -TX::one());
+
+        let mut tableau = vec![TX::zero(); n * width];
+        let mut rhs = vec![TX::zero(); n];
+        // basic variable of every row, a column and the sign of the part
+        let mut basis = vec![(0, TX::one()); n];
+        for i in 0..n {
+            let sign = if y[i] >= TX::zero() {
+                TX::one()
+            } else {
+                -TX::one()
+            };
+            let row = &mut tableau[i * width..(i + 1) * width];
+            for (j, v) in row.iter_mut().enumerate().take(p) {
+                *v = sign * *x.get((i, j));
+            }
+            row[p] = sign;
+            row[p + 1 + i] = sign;
+            rhs[i] = y[i].abs();
+            basis[i] = (p + 1 + i, sign);
+        }
+
+        let mut reduced_cost: Vec<TX> = (0..width)
+            .map(|j| {
+                (0..n).fold(cost(j, TX::one()), |d, i| {
+                    let (column, sign) = basis[i];
+                    d - cost(column, sign) * tableau[i * width + j]
+                })
+            })
+            .collect();
+
+        let cost_scale = parameters.alpha.max(TX::one() / n_tx);
+        let optimality_tol = cost_scale * TX::from_f64(1e-10).unwrap();
+        let pivot_tol = TX::from_f64(1e-11).unwrap();
+        let mut degenerate_pivots = 0;
+        let mut column = vec![TX::zero(); n];
+
+        let mut n_iter = 0;
+        loop {
+            let bland = degenerate_pivots >= MAX_DEGENERATE_PIVOTS;
+
+            // entering variable, Dantzig's rule or Bland's rule after a run of degenerate pivots
+            let mut entering: Option<(usize, TX, TX)> = None;
+            'columns: for (j, &d_j) in reduced_cost.iter().enumerate() {
+                for sign in [TX::one(), -TX::one()] {
+                    let d = if sign > TX::zero() {
+                        d_j
+                    } else {
+                        twin_cost(j) - d_j
+                    };
+                    if d < -optimality_tol && entering.is_none_or(|(_, _, best)| d < best) {
+                        entering = Some((j, sign, d));
+                        if bland {
+                            break 'columns;
+                        }
+                    }
+                }
+            }
+            let Some((entering_column, entering_sign, entering_cost)) = entering else {
+                break;
+            };
+
+            if n_iter == parameters.max_iter {
+                return Err(Failed::fit(&format!(
+                    "The simplex method did not reach the optimum in {} pivots, increase max_iter",
+                    parameters.max_iter
+                )));
+            }
+            n_iter += 1;
+
+            // leaving variable, minimum ratio test
+            for (i, c) in column.iter_mut().enumerate() {
+                *c = entering_sign * tableau[i * width + entering_column];
+            }
+            let mut leaving: Option<usize> = None;
+            for i in 0..n {
+                if column[i] <= pivot_tol {
+                    continue;
+                }
+                leaving = match leaving {
+                    None => Some(i),
+                    Some(r) => {
+                        let ratio = rhs[i] / column[i];
+                        let best_ratio = rhs[r] / column[r];
+                        let better = if bland {
+                            ratio < best_ratio
+                                || (ratio == best_ratio
+                                    && Self::order(basis[i]) < Self::order(basis[r]))
+                        } else {
+                            ratio < best_ratio || (ratio == best_ratio && column[i] > column[r])
+                        };
+                        if better {
+                            Some(i)
+                        } else {
+                            Some(r)
+                        }
+                    }
+                };
+            }
+            let Some(r) = leaving else {
+                return Err(Failed::fit(
+                    "The linear program of quantile regression is unbounded",
+                ));
+            };
+
+            // pivot
+            let pivot = column[r];
+            for v in tableau[r * width..(r + 1) * width].iter_mut() {
+                *v /= pivot;
+            }
+            rhs[r] /= pivot;
+            let pivot_row = tableau[r * width..(r + 1) * width].to_vec();
+            let rhs_r = rhs[r];
+            for (i, row) in tableau.chunks_mut(width).enumerate() {
+                let factor = column[i];
+                if i != r && factor != TX::zero() {
+                    for (v, &pv) in row.iter_mut().zip(pivot_row.iter()) {
+                        *v -= factor * pv;
+                    }
+                    // guard against round-off pushing a basic variable below zero
+                    rhs[i] = (rhs[i] - factor * rhs_r).max(TX::zero());
+                }
+            }
+            for (d, &pv) in reduced_cost.iter_mut().zip(pivot_row.iter()) {
+                *d -= entering_cost * pv;
+            }
+            basis[r] = (entering_column, entering_sign);
+
+            if rhs_r > TX::zero() {
+                degenerate_pivots = 0;
+            } else {
+                degenerate_pivots += 1;
+            }
+        }
+
+        let mut beta = vec![TX::zero(); p + 1];
+        for (i, &(column, sign)) in basis.iter().enumerate() {
+            if column <= p {
+                beta[column] += sign * rhs[i];
+            }
+        }
+        Ok((beta, n_iter))
+    }
+
+    /// Position of a variable in the fixed order used by Bland's rule.
+    fn order((column, sign): (usize, TX)) -> usize {
+        2 * column + usize::from(sign < TX::zero())
+    }
+
+    /// Majorize-minimize algorithm. At the current estimate, \\(|r| \leq \frac{r^2}{2 (|r_0| + \epsilon)} + \frac{|r_0| + \epsilon}{2}\\),
+    /// so minimizing the quadratic majorizer, a weighted ridge regression, never increases the smoothed objective.
+    fn irls(
+        x: &X,
+        y: &[TX],
+        parameters: &QuantileRegressorParameters<TX>,
+    ) -> Result<(Vec<TX>, usize), Failed> {
+        let (n, p) = x.shape();
+        let n_tx = TX::from_usize(n).unwrap();
+        let two = <TX as RealNumber>::two();
+        let a = x.h_stack(&X::ones(n, 1));
+
+        let mut beta = vec![TX::zero(); p + 1];
+        let mut sorted_y = y.to_vec();
+        beta[p] = median(&mut sorted_y);
+        let mut abs_deviation: Vec<TX> = y.iter().map(|&v| (v - beta[p]).abs()).collect();
+        let mad = median(&mut abs_deviation);
+        let epsilon = parameters.tol * if mad > TX::zero() { mad } else { TX::one() };
+
+        // gradient of the linear part of the pinball loss, (tau - 1/2) / n * A^T 1
+        let a_t = a.transpose();
+        let linear_term: Vec<TX> = (0..=p)
+            .map(|j| {
+                (0..n).fold(TX::zero(), |s, i| s + *a.get((i, j)))
+                    * (parameters.quantile - <TX as RealNumber>::half())
+                    / n_tx
+            })
+            .collect();
+
+        let mut n_iter = 0;
+        while n_iter < parameters.max_iter {
+            n_iter += 1;
+
+            let weights: Vec<TX> = (0..n)
+                .map(|i| {
+                    let fitted = (0..=p).fold(TX::zero(), |s, j| s + *a.get((i, j)) * beta[j]);
+                    TX::one() / (two * n_tx * ((y[i] - fitted).abs() + epsilon))
+                })
+                .collect();
+            let weighted_a = sqrt_weigh_rows(&a, &weights);
+            let mut lhs = weighted_a.transpose().matmul(&weighted_a);
+            for (j, b_j) in beta.iter().enumerate().take(p) {
+                lhs.add_element_mut((j, j), parameters.alpha / (b_j.abs() + epsilon));
+            }
+            let weighted_y = X::from_iterator(
+                y.iter().zip(weights.iter()).map(|(&y_i, &w_i)| y_i * w_i),
+                n,
+                1,
+                0,
+            );
+            let mut b = a_t.matmul(&weighted_y);
+            for (j, l) in linear_term.iter().enumerate() {
+                b.add_element_mut((j, 0), *l);
+            }
+            let solution = lhs.cholesky_solve_mut(b)?;
+
+            let mut max_change = TX::zero();
+            let mut max_beta = TX::zero();
+            for (j, b_j) in beta.iter_mut().enumerate() {
+                let new_b_j = *solution.get((j, 0));
+                max_change = max_change.max((new_b_j - *b_j).abs());
+                max_beta = max_beta.max(new_b_j.abs());
+                *b_j = new_b_j;
+            }
+            if max_change <= parameters.tol * max_beta.max(TX::one()) {
+                break;
+            }
+        }
+
+        Ok((beta, n_iter))
+    }
+
+    /// Predict target values from `x`
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict(&self, x: &X) -> Result<Y, Failed> {
+        let (nrows, ncols) = x.shape();
+        let (n_coefficients, _) = self.coefficients().shape();
+        if ncols != n_coefficients {
+            return Err(Failed::predict(&format!(
+                "Number of features of X {ncols} doesn't match number of coefficients {n_coefficients}"
+            )));
+        }
+        let y_hat = x.matmul(self.coefficients());
+        Ok(Y::from_iterator(
+            y_hat
+                .iterator(0)
+                .map(|&v| TY::from(v + *self.intercept()).unwrap()),
+            nrows,
+        ))
+    }
+
+    /// Get estimates regression coefficients
+    pub fn coefficients(&self) -> &X {
+        self.coefficients.as_ref().unwrap()
+    }
+
+    /// Get estimate of intercept
+    pub fn intercept(&self) -> &TX {
+        self.intercept.as_ref().unwrap()
+    }
+
+    /// Number of pivots of the simplex method or iterations of IRLS
+    pub fn n_iter(&self) -> usize {
+        self.n_iter
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::basic::arrays::Array;
+    use crate::linalg::basic::matrix::DenseMatrix;
+    use crate::metrics::mean_pinball_loss;
+
+    fn demand() -> (DenseMatrix<f64>, Vec<f64>) {
+        let x = DenseMatrix::from_2d_array(&[
+            &[1.0, 15.],
+            &[1.2, 18.],
+            &[0.9, 21.],
+            &[1.1, 24.],
+            &[1.3, 19.],
+            &[0.8, 22.],
+            &[1.0, 26.],
+            &[1.2, 16.],
+            &[0.9, 17.],
+            &[1.1, 23.],
+            &[1.4, 20.],
+            &[0.7, 25.],
+            &[1.0, 18.],
+            &[1.3, 27.],
+            &[0.8, 14.],
+            &[1.1, 21.],
+        ])
+        .unwrap();
+        let y: Vec<f64> = vec![
+            52., 48., 71., 66., 45., 80., 70., 41., 58., 69., 47., 88., 55., 60., 62., 64.,
+        ];
+        (x, y)
+    }
+
+    fn objective(
+        x: &DenseMatrix<f64>,
+        y: &[f64],
+        beta: &[f64],
+        intercept: f64,
+        quantile: f64,
+        alpha: f64,
+    ) -> f64 {
+        let (n, p) = x.shape();
+        let loss = (0..n)
+            .map(|i| {
+                let r = y[i] - intercept - (0..p).map(|j| x.get((i, j)) * beta[j]).sum::<f64>();
+                r.max(0.) * quantile - r.min(0.) * (1. - quantile)
+            })
+            .sum::<f64>()
+            / n as f64;
+        loss + alpha * beta.iter().map(|b| b.abs()).sum::<f64>()
+    }
+
+    #[test]
+    fn search_parameters() {
+        let parameters = QuantileRegressorSearchParameters {
+            quantile: vec![0.1, 0.9],
+            alpha: vec![0.],
+        };
+        let mut iter = parameters.into_iter();
+        let next = iter.next().unwrap();
+        assert_eq!(next.quantile, 0.1);
+        assert_eq!(next.alpha, 0.);
+        let next = iter.next().unwrap();
+        assert_eq!(next.quantile, 0.9);
+        assert_eq!(next.alpha, 0.);
+        assert!(iter.next().is_none());
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn quantile_regression_simplex_optimality() {
+        let (x, y) = demand();
+
+        for quantile in [0.1, 0.5, 0.9] {
+            let qr = QuantileRegressor::fit(
+                &x,
+                &y,
+                QuantileRegressorParameters::default()
+                    .with_quantile(quantile)
+                    .with_alpha(0.),
+            )
+            .unwrap();
+
+            // with an intercept, at most a fraction tau of the observations lies below the fitted quantile
+            // and at most a fraction 1 - tau above it
+            let y_hat = qr.predict(&x).unwrap();
+            let n_below = y
+                .iter()
+                .zip(y_hat.iter())
+                .filter(|(&y, &y_hat)| y < y_hat - 1e-8)
+                .count();
+            let n_above = y
+                .iter()
+                .zip(y_hat.iter())
+                .filter(|(&y, &y_hat)| y > y_hat + 1e-8)
+                .count();
+            assert!(n_below as f64 <= quantile * 16. + 1e-8, "{quantile}");
+            assert!(n_above as f64 <= (1. - quantile) * 16. + 1e-8, "{quantile}");
+
+            // no small perturbation of the solution improves the objective
+            let beta = vec![
+                *qr.coefficients().get((0, 0)),
+                *qr.coefficients().get((1, 0)),
+            ];
+            let optimum = objective(&x, &y, &beta, *qr.intercept(), quantile, 0.);
+            for j in 0..3 {
+                for delta in [-1e-3, 1e-3] {
+                    let mut perturbed = beta.clone();
+                    let mut intercept = *qr.intercept();
+                    if j < 2 {
+                        perturbed[j] += delta;
+                    } else {
+                        intercept += delta;
+                    }
+                    assert!(
+                        objective(&x, &y, &perturbed, intercept, quantile, 0.) >= optimum - 1e-12
+                    );
+                }
+            }
+            assert!(
+                (mean_pinball_loss(&y, &y_hat, quantile) - optimum).abs() < 1e-10,
+                "{quantile}"
+            );
+        }
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn quantile_regression_solvers_agree() {
+        let (x, y) = demand();
+
+        for alpha in [0., 0.5] {
+            let simplex = QuantileRegressor::fit(
+                &x,
+                &y,
+                QuantileRegressorParameters::default()
+                    .with_quantile(0.8)
+                    .with_alpha(alpha),
+            )
+            .unwrap();
+            let irls = QuantileRegressor::fit(
+                &x,
+                &y,
+                QuantileRegressorParameters::default()
+                    .with_solver(QuantileRegressorSolverName::IRLS)
+                    .with_quantile(0.8)
+                    .with_alpha(alpha),
+            )
+            .unwrap();
+
+            let value = |qr: &QuantileRegressor<f64, f64, DenseMatrix<f64>, Vec<f64>>| {
+                let beta = vec![
+                    *qr.coefficients().get((0, 0)),
+                    *qr.coefficients().get((1, 0)),
+                ];
+                objective(&x, &y, &beta, *qr.intercept(), 0.8, alpha)
+            };
+            assert!(value(&irls) >= value(&simplex) - 1e-10);
+            assert!(value(&irls) - value(&simplex) < 1e-4, "{alpha}");
+        }
+
+        // a strong penalty removes all features, leaving the quantile of y
+        let qr = QuantileRegressor::fit(
+            &x,
+            &y,
+            QuantileRegressorParameters::default().with_alpha(100.),
+        )
+        .unwrap();
+        assert_eq!(*qr.coefficients().get((0, 0)), 0.);
+        assert_eq!(*qr.coefficients().get((1, 0)), 0.);
+        let n_below = y.iter().filter(|&&v| v < *qr.intercept()).count();
+        assert!(n_below <= 8);
+
+        assert!(
+            QuantileRegressor::<f64, f64, DenseMatrix<f64>, Vec<f64>>::fit(
+                &x,
+                &y,
+                QuantileRegressorParameters::default().with_quantile(1.)
+            )
+            .is_err()
+        );
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde() {
+        let (x, y) = demand();
+
+        let qr = QuantileRegressor::fit(&x, &y, Default::default()).unwrap();
+
+        let deserialized_qr: QuantileRegressor<f64, f64, DenseMatrix<f64>, Vec<f64>> =
+            serde_json::from_str(&serde_json::to_string(&qr).unwrap()).unwrap();
+
+        assert_eq!(qr, deserialized_qr);
+    }
+}
@@ -0,0 +1,472 @@
+//! # RANSAC Regression
+//!
+//! RANdom SAmple Consensus (RANSAC) fits a base regressor on many small random subsets of the data and keeps the model that agrees with the largest number of observations.
+//! An observation agrees with a model, and is called an inlier, when the absolute difference between its target value and the prediction is at most `residual_threshold`.
+//! The final model is fit on all inliers of the best candidate, so outliers, no matter how extreme, do not influence it.
+//!
+//! Every trial
+//!
+//! 1. draws `min_samples` observations at random, without replacement,
+//! 2. fits the base estimator on them, skipping the trial if the fit fails,
+//! 3. counts the inliers among all observations. Ties are resolved in favor of the smaller sum of squared residuals of the inliers.
+//!
+//! `RANSACRegressor` wraps any [`SupervisedEstimator`](../../api/trait.SupervisedEstimator.html), e.g. [linear regression](../linear_regression/index.html)
+//! or a [decision tree](../../tree/decision_tree_regressor/index.html).
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::basic::matrix::DenseMatrix;
+//! use smartcore::linear::linear_regression::*;
+//! use smartcore::linear::ransac::*;
+//!
+//! let x = DenseMatrix::from_2d_array(&[
+//!             &[1.], &[2.], &[3.], &[4.], &[5.], &[6.], &[7.], &[8.], &[9.], &[10.],
+//!         ]).unwrap();
+//! // y = 2x + 1, the last two observations are outliers
+//! let y: Vec<f64> = vec![3.1, 4.9, 7.0, 9.1, 10.9, 13.0, 15.1, 16.9, 60.0, -20.0];
+//!
+//! let ransac: RANSACRegressor<f64, f64, _, _, LinearRegression<f64, f64, _, _>, _> =
+//!     RANSACRegressor::fit(&x, &y, RANSACRegressorParameters::default().with_residual_threshold(1.)).unwrap();
+//!
+//! let y_hat = ransac.predict(&x).unwrap();
+//! let inliers = ransac.inlier_mask();
+//! ```
+//!
+//! ## References:
+//!
+//! * ["Random Sample Consensus: A Paradigm for Model Fitting with Applications to Image Analysis and Automated Cartography", Fischler M.A., Bolles R.C., 1981](https://dl.acm.org/doi/10.1145/358669.358692)
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+use rand::Rng;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::api::{Predictor, SupervisedEstimator};
+use crate::error::Failed;
+use crate::linalg::basic::arrays::{Array1, Array2};
+use crate::linear::median;
+use crate::numbers::basenum::Number;
+use crate::rand_custom::get_rng_impl;
+
+/// RANSAC regression parameters
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct RANSACRegressorParameters<P> {
+    /// Parameters of the base estimator.
+    pub estimator_parameters: P,
+    /// Number of observations drawn in every trial, the number of features plus one if not set.
+    pub min_samples: Option<usize>,
+    /// Largest absolute residual of an inlier, the median absolute deviation of `y` if not set.
+    pub residual_threshold: Option<f64>,
+    /// Maximum number of random trials.
+    pub max_trials: usize,
+    /// Stop the trials as soon as a model with at least this many inliers is found.
+    pub stop_n_inliers: Option<usize>,
+    /// Seed of the random number generator.
+    pub seed: u64,
+}
+
+impl<P> RANSACRegressorParameters<P> {
+    /// Parameters of the base estimator.
+    pub fn with_estimator_parameters(mut self, estimator_parameters: P) -> Self {
+        self.estimator_parameters = estimator_parameters;
+        self
+    }
+    /// Number of observations drawn in every trial.
+    pub fn with_min_samples(mut self, min_samples: usize) -> Self {
+        self.min_samples = Some(min_samples);
+        self
+    }
+    /// Largest absolute residual of an inlier.
+    pub fn with_residual_threshold(mut self, residual_threshold: f64) -> Self {
+        self.residual_threshold = Some(residual_threshold);
+        self
+    }
+    /// Maximum number of random trials.
+    pub fn with_max_trials(mut self, max_trials: usize) -> Self {
+        self.max_trials = max_trials;
+        self
+    }
+    /// Stop the trials as soon as a model with at least this many inliers is found.
+    pub fn with_stop_n_inliers(mut self, stop_n_inliers: usize) -> Self {
+        self.stop_n_inliers = Some(stop_n_inliers);
+        self
+    }
+    /// Seed of the random number generator.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+}
+
+impl<P: Default> Default for RANSACRegressorParameters<P> {
+    fn default() -> Self {
+        RANSACRegressorParameters {
+            estimator_parameters: P::default(),
+            min_samples: None,
+            residual_threshold: None,
+            max_trials: 100,
+            stop_n_inliers: None,
+            seed: 0,
+        }
+    }
+}
+
+/// RANSAC regression
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+pub struct RANSACRegressor<
+    TX: Number,
+    TY: Number,
+    X: Array2<TX>,
+    Y: Array1<TY>,
+    E: SupervisedEstimator<X, Y, P>,
+    P: Clone,
+> {
+    estimator: Option<E>,
+    inlier_mask: Option<Vec<bool>>,
+    n_trials: usize,
+    _phantom_tx: PhantomData<TX>,
+    _phantom_ty: PhantomData<TY>,
+    _phantom_x: PhantomData<X>,
+    _phantom_y: PhantomData<Y>,
+    _phantom_p: PhantomData<P>,
+}
+
+impl<
+        TX: Number,
+        TY: Number,
+        X: Array2<TX>,
+        Y: Array1<TY>,
+        E: SupervisedEstimator<X, Y, P>,
+        P: Clone,
+    > SupervisedEstimator<X, Y, RANSACRegressorParameters<P>>
+    for RANSACRegressor<TX, TY, X, Y, E, P>
+{
+    fn new() -> Self {
+        Self {
+            estimator: Option::None,
+            inlier_mask: Option::None,
+            n_trials: 0,
+            _phantom_tx: PhantomData,
+            _phantom_ty: PhantomData,
+            _phantom_x: PhantomData,
+            _phantom_y: PhantomData,
+            _phantom_p: PhantomData,
+        }
+    }
+
+    fn fit(x: &X, y: &Y, parameters: RANSACRegressorParameters<P>) -> Result<Self, Failed> {
+        RANSACRegressor::fit(x, y, parameters)
+    }
+}
+
+impl<
+        TX: Number,
+        TY: Number,
+        X: Array2<TX>,
+        Y: Array1<TY>,
+        E: SupervisedEstimator<X, Y, P>,
+        P: Clone,
+    > Predictor<X, Y> for RANSACRegressor<TX, TY, X, Y, E, P>
+{
+    fn predict(&self, x: &X) -> Result<Y, Failed> {
+        self.predict(x)
+    }
+}
+
+impl<
+        TX: Number,
+        TY: Number,
+        X: Array2<TX>,
+        Y: Array1<TY>,
+        E: SupervisedEstimator<X, Y, P>,
+        P: Clone,
+    > RANSACRegressor<TX, TY, X, Y, E, P>
+{
+    /// Fits RANSAC regression to your data.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - target values
+    /// * `parameters` - parameters of RANSAC and of the base estimator.
+    pub fn fit(
+        x: &X,
+        y: &Y,
+        parameters: RANSACRegressorParameters<P>,
+    ) -> Result<RANSACRegressor<TX, TY, X, Y, E, P>, Failed> {
+        let (n, p) = x.shape();
+
+        if y.shape() != n {
+            return Err(Failed::fit("Number of rows in X should = len(y)"));
+        }
+
+        let min_samples = parameters.min_samples.unwrap_or(p + 1);
+        if min_samples == 0 || min_samples > n {
+            return Err(Failed::fit(&format!(
+                "min_samples should be between 1 and the number of rows {n}, got {min_samples}"
+            )));
+        }
+
+        let y_f64: Vec<f64> = y.iterator(0).map(|v| v.to_f64().unwrap()).collect();
+        let residual_threshold = match parameters.residual_threshold {
+            Some(threshold) => threshold,
+            None => {
+                let mut sorted_y = y_f64.clone();
+                let y_median = median(&mut sorted_y);
+                let mut abs_deviation: Vec<f64> =
+                    y_f64.iter().map(|v| (v - y_median).abs()).collect();
+                median(&mut abs_deviation)
+            }
+        };
+        if !residual_threshold.is_finite() || residual_threshold < 0. {
+            return Err(Failed::fit(&format!(
+                "residual_threshold should be >= 0, got {residual_threshold}"
+            )));
+        }
+
+        let mut rng = get_rng_impl(Some(parameters.seed));
+        let mut indices: Vec<usize> = (0..n).collect();
+        // number of inliers, sum of their squared residuals and inlier mask of the best model
+        let mut best: Option<(usize, f64, Vec<bool>)> = None;
+        let mut n_trials = 0;
+
+        while n_trials < parameters.max_trials {
+            n_trials += 1;
+
+            // partial Fisher-Yates shuffle, the first min_samples indices are a sample without replacement
+            for i in 0..min_samples {
+                let j = rng.gen_range(i..n);
+                indices.swap(i, j);
+            }
+            let rows = &indices[..min_samples];
+
+            let Ok(model) = E::fit(
+                &x.take(rows, 0),
+                &Self::take_y(y, rows),
+                parameters.estimator_parameters.clone(),
+            ) else {
+                continue;
+            };
+            let Ok(y_hat) = model.predict(x) else {
+                continue;
+            };
+
+            let mut n_inliers = 0;
+            let mut rss = 0f64;
+            let inlier_mask: Vec<bool> = y_f64
+                .iter()
+                .zip(y_hat.iterator(0))
+                .map(|(y_i, y_hat_i)| {
+                    let residual = (y_i - y_hat_i.to_f64().unwrap()).abs();
+                    let inlier = residual <= residual_threshold;
+                    if inlier {
+                        n_inliers += 1;
+                        rss += residual * residual;
+                    }
+                    inlier
+                })
+                .collect();
+
+            let better = match &best {
+                Some((best_n_inliers, best_rss, _)) => {
+                    n_inliers > *best_n_inliers || (n_inliers == *best_n_inliers && rss < *best_rss)
+                }
+                None => n_inliers > 0,
+            };
+            if better {
+                best = Some((n_inliers, rss, inlier_mask));
+            }
+
+            if let (Some((best_n_inliers, _, _)), Some(stop_n_inliers)) =
+                (&best, parameters.stop_n_inliers)
+            {
+                if *best_n_inliers >= stop_n_inliers {
+                    break;
+                }
+            }
+        }
+
+        let Some((_, _, inlier_mask)) = best else {
+            return Err(Failed::fit(
+                "RANSAC could not find a model with any inliers, consider a larger residual_threshold",
+            ));
+        };
+
+        let inliers: Vec<usize> = (0..n).filter(|&i| inlier_mask[i]).collect();
+        let estimator = E::fit(
+            &x.take(&inliers, 0),
+            &Self::take_y(y, &inliers),
+            parameters.estimator_parameters,
+        )?;
+
+        Ok(RANSACRegressor {
+            estimator: Some(estimator),
+            inlier_mask: Some(inlier_mask),
+            n_trials,
+            _phantom_tx: PhantomData,
+            _phantom_ty: PhantomData,
+            _phantom_x: PhantomData,
+            _phantom_y: PhantomData,
+            _phantom_p: PhantomData,
+        })
+    }
+
+    fn take_y(y: &Y, rows: &[usize]) -> Y {
+        Y::from_iterator(rows.iter().map(|&i| *y.get(i)), rows.len())
+    }
+
+    /// Predict target values from `x` with the base estimator fitted on the inliers.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict(&self, x: &X) -> Result<Y, Failed> {
+        self.estimator().predict(x)
+    }
+
+    /// Get the base estimator fitted on the inliers
+    pub fn estimator(&self) -> &E {
+        self.estimator.as_ref().unwrap()
+    }
+
+    /// Training observations that are inliers of the best model
+    pub fn inlier_mask(&self) -> &Vec<bool> {
+        self.inlier_mask.as_ref().unwrap()
+    }
+
+    /// Number of random trials run by `fit`
+    pub fn n_trials(&self) -> usize {
+        self.n_trials
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::basic::arrays::Array;
+    use crate::linalg::basic::matrix::DenseMatrix;
+    use crate::linear::linear_regression::{LinearRegression, LinearRegressionParameters};
+    use crate::tree::decision_tree_regressor::{
+        DecisionTreeRegressor, DecisionTreeRegressorParameters,
+    };
+
+    type LinearRANSAC = RANSACRegressor<
+        f64,
+        f64,
+        DenseMatrix<f64>,
+        Vec<f64>,
+        LinearRegression<f64, f64, DenseMatrix<f64>, Vec<f64>>,
+        LinearRegressionParameters,
+    >;
+
+    fn data_with_outliers() -> (DenseMatrix<f64>, Vec<f64>) {
+        let x = DenseMatrix::from_2d_array(&[
+            &[1., 0.5],
+            &[2., 1.5],
+            &[3., 0.2],
+            &[4., 2.2],
+            &[5., 1.1],
+            &[6., 0.8],
+            &[7., 1.9],
+            &[8., 0.3],
+            &[9., 1.4],
+            &[10., 2.5],
+            &[11., 0.9],
+            &[12., 1.7],
+            &[13., 0.6],
+            &[14., 2.1],
+            &[15., 1.2],
+        ])
+        .unwrap();
+        // y = 2 x_1 - x_2 + 1 with small noise, the last three observations are outliers
+        let mut y: Vec<f64> = (0..15)
+            .map(|i| {
+                let noise = [
+                    0.1, -0.2, 0.05, 0.15, -0.1, 0.2, -0.05, 0.1, -0.15, 0.05, 0.1, -0.1,
+                ][i % 12];
+                2. * x.get((i, 0)) - x.get((i, 1)) + 1. + noise
+            })
+            .collect();
+        y[12] += 40.;
+        y[13] -= 35.;
+        y[14] += 50.;
+        (x, y)
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn ransac_fit_predict() {
+        let (x, y) = data_with_outliers();
+
+        let ransac = LinearRANSAC::fit(
+            &x,
+            &y,
+            RANSACRegressorParameters::default().with_residual_threshold(1.),
+        )
+        .unwrap();
+
+        assert_eq!(&ransac.inlier_mask()[12..], &[false, false, false]);
+        assert!(ransac.inlier_mask()[..12].iter().all(|&inlier| inlier));
+        assert_eq!(ransac.n_trials(), 100);
+
+        let lr = ransac.estimator();
+        assert!((lr.coefficients().get((0, 0)) - 2.).abs() < 0.1);
+        assert!((lr.coefficients().get((1, 0)) + 1.).abs() < 0.3);
+
+        let y_hat = ransac.predict(&x).unwrap();
+        assert!((0..12).all(|i| (y[i] - y_hat[i]).abs() < 1.));
+
+        let stopped = LinearRANSAC::fit(
+            &x,
+            &y,
+            RANSACRegressorParameters::default()
+                .with_residual_threshold(1.)
+                .with_stop_n_inliers(12),
+        )
+        .unwrap();
+        assert!(stopped.n_trials() < 100);
+        assert_eq!(stopped.inlier_mask(), ransac.inlier_mask());
+
+        assert!(LinearRANSAC::fit(
+            &x,
+            &y,
+            RANSACRegressorParameters::default().with_min_samples(16)
+        )
+        .is_err());
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn ransac_any_estimator() {
+        let (x, y) = data_with_outliers();
+
+        let ransac: RANSACRegressor<
+            f64,
+            f64,
+            DenseMatrix<f64>,
+            Vec<f64>,
+            DecisionTreeRegressor<f64, f64, DenseMatrix<f64>, Vec<f64>>,
+            DecisionTreeRegressorParameters,
+        > = RANSACRegressor::fit(
+            &x,
+            &y,
+            RANSACRegressorParameters::default()
+                .with_min_samples(10)
+                .with_residual_threshold(2.),
+        )
+        .unwrap();
+
+        let y_hat = ransac.predict(&x).unwrap();
+        assert_eq!(y_hat.len(), 15);
+        assert!(ransac
+            .inlier_mask()
+            .iter()
+            .zip(y.iter().zip(y_hat.iter()))
+            .filter(|(&inlier, _)| inlier)
+            .all(|(_, (y_i, y_hat_i))| (y_i - y_hat_i).abs() <= 2.));
+    }
+}
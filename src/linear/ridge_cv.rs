@@ -0,0 +1,534 @@
+//! # Ridge Regression with Cross-Validation
+//!
+//! [Ridge regression](../ridge_regression/index.html) with the regularization parameter \\(\alpha\\) chosen automatically by cross-validation.
+//!
+//! By default every candidate \\(\alpha\\) is scored with efficient leave-one-out cross-validation.
+//! Given the [SVD](../../linalg/traits/svd/index.html) of the (centered) data matrix \\(X = USV^T\\), the hat matrix of ridge regression is
+//!
+//! \\[H_{\alpha} = U \space diag \left( \frac{s_j^2}{s_j^2 + \alpha} \right) U^T\\]
+//!
+//! and the leave-one-out residuals are \\(e_i / (1 - h_{ii})\\), where \\(e_i\\) are the residuals of the model fitted on all observations.
+//! A single decomposition is therefore enough to score the whole grid of \\(\alpha\\) values without refitting the model \\(N\\) times.
+//! Regular k-fold cross-validation with [`KFold`](../../model_selection/struct.KFold.html) can be used instead by setting `cv`.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::basic::matrix::DenseMatrix;
+//! use smartcore::linear::ridge_cv::*;
+//!
+//! // Longley dataset (https://www.statsmodels.org/stable/datasets/generated/longley.html)
+//! let x = DenseMatrix::from_2d_array(&[
+//!               &[234.289, 235.6, 159.0, 107.608, 1947., 60.323],
+//!               &[259.426, 232.5, 145.6, 108.632, 1948., 61.122],
+//!               &[258.054, 368.2, 161.6, 109.773, 1949., 60.171],
+//!               &[284.599, 335.1, 165.0, 110.929, 1950., 61.187],
+//!               &[328.975, 209.9, 309.9, 112.075, 1951., 63.221],
+//!               &[346.999, 193.2, 359.4, 113.270, 1952., 63.639],
+//!               &[365.385, 187.0, 354.7, 115.094, 1953., 64.989],
+//!               &[363.112, 357.8, 335.0, 116.219, 1954., 63.761],
+//!               &[397.469, 290.4, 304.8, 117.388, 1955., 66.019],
+//!               &[419.180, 282.2, 285.7, 118.734, 1956., 67.857],
+//!               &[442.769, 293.6, 279.8, 120.445, 1957., 68.169],
+//!               &[444.546, 468.1, 263.7, 121.950, 1958., 66.513],
+//!               &[482.704, 381.3, 255.2, 123.366, 1959., 68.655],
+//!               &[502.601, 393.1, 251.4, 125.368, 1960., 69.564],
+//!               &[518.173, 480.6, 257.2, 127.852, 1961., 69.331],
+//!               &[554.894, 400.7, 282.7, 130.081, 1962., 70.551],
+//!          ]).unwrap();
+//!
+//! let y: Vec<f64> = vec![83.0, 88.5, 88.2, 89.5, 96.2, 98.1, 99.0,
+//!           100.0, 101.2, 104.6, 108.4, 110.8, 112.6, 114.2, 115.7, 116.9];
+//!
+//! let ridge = RidgeCV::fit(&x, &y,
+//!                 RidgeCVParameters::default().with_alphas(vec![0.01, 0.1, 1.0, 10.0])).unwrap();
+//!
+//! let alpha = ridge.alpha();
+//! let y_hat = ridge.predict(&x).unwrap();
+//! ```
+//!
+//! ## References:
+//!
+//! * ["An Introduction to Statistical Learning", James G., Witten D., Hastie T., Tibshirani R., 5.1.2 Leave-One-Out Cross-Validation](http://faculty.marshall.usc.edu/gareth-james/ISL/)
+//! * ["The Elements of Statistical Learning", Hastie T., Tibshirani R., Friedman J., 7.10 Cross-Validation](https://hastie.su.domains/ElemStatLearn/)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use std::fmt::Debug;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::api::{Predictor, SupervisedEstimator};
+use crate::error::Failed;
+use crate::linalg::basic::arrays::{Array1, Array2};
+use crate::linalg::traits::cholesky::CholeskyDecomposable;
+use crate::linalg::traits::svd::SVDDecomposable;
+use crate::linear::ridge_regression::{
+    RidgeRegression, RidgeRegressionParameters, RidgeRegressionSolverName,
+};
+use crate::model_selection::{BaseKFold, KFold};
+use crate::numbers::basenum::Number;
+use crate::numbers::realnum::RealNumber;
+
+/// Ridge Regression cross-validation parameters
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct RidgeCVParameters<T: Number + RealNumber> {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Candidate values of the regularization parameter.
+    pub alphas: Vec<T>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The cross-validation splitting strategy. Efficient leave-one-out cross-validation is used when not set.
+    pub cv: Option<KFold>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Solver to use for estimation of regression coefficients.
+    pub solver: RidgeRegressionSolverName,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// If true the regressors X will be normalized before regression
+    /// by subtracting the mean and dividing by the standard deviation.
+    pub normalize: bool,
+}
+
+impl<T: Number + RealNumber> RidgeCVParameters<T> {
+    /// Candidate values of the regularization parameter.
+    pub fn with_alphas(mut self, alphas: Vec<T>) -> Self {
+        self.alphas = alphas;
+        self
+    }
+    /// The cross-validation splitting strategy.
+    pub fn with_cv(mut self, cv: KFold) -> Self {
+        self.cv = Some(cv);
+        self
+    }
+    /// Solver to use for estimation of regression coefficients.
+    pub fn with_solver(mut self, solver: RidgeRegressionSolverName) -> Self {
+        self.solver = solver;
+        self
+    }
+    /// If True, the regressors X will be normalized before regression by subtracting the mean and dividing by the standard deviation.
+    pub fn with_normalize(mut self, normalize: bool) -> Self {
+        self.normalize = normalize;
+        self
+    }
+}
+
+impl<T: Number + RealNumber> Default for RidgeCVParameters<T> {
+    fn default() -> Self {
+        RidgeCVParameters {
+            alphas: vec![
+                T::from_f64(0.1).unwrap(),
+                T::from_f64(1.0).unwrap(),
+                T::from_f64(10.0).unwrap(),
+            ],
+            cv: None,
+            solver: RidgeRegressionSolverName::default(),
+            normalize: true,
+        }
+    }
+}
+
+/// Ridge regression with built-in cross-validation
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+pub struct RidgeCV<
+    TX: Number + RealNumber,
+    TY: Number,
+    X: Array2<TX> + CholeskyDecomposable<TX> + SVDDecomposable<TX>,
+    Y: Array1<TY>,
+> {
+    model: Option<RidgeRegression<TX, TY, X, Y>>,
+    alpha: Option<TX>,
+    mse_path: Option<Vec<f64>>,
+}
+
+impl<
+        TX: Number + RealNumber,
+        TY: Number,
+        X: Array2<TX> + CholeskyDecomposable<TX> + SVDDecomposable<TX>,
+        Y: Array1<TY>,
+    > PartialEq for RidgeCV<TX, TY, X, Y>
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.alpha == other.alpha && self.model == other.model
+    }
+}
+
+impl<
+        TX: Number + RealNumber,
+        TY: Number,
+        X: Array2<TX> + CholeskyDecomposable<TX> + SVDDecomposable<TX>,
+        Y: Array1<TY>,
+    > SupervisedEstimator<X, Y, RidgeCVParameters<TX>> for RidgeCV<TX, TY, X, Y>
+{
+    fn new() -> Self {
+        Self {
+            model: Option::None,
+            alpha: Option::None,
+            mse_path: Option::None,
+        }
+    }
+
+    fn fit(x: &X, y: &Y, parameters: RidgeCVParameters<TX>) -> Result<Self, Failed> {
+        RidgeCV::fit(x, y, parameters)
+    }
+}
+
+impl<
+        TX: Number + RealNumber,
+        TY: Number,
+        X: Array2<TX> + CholeskyDecomposable<TX> + SVDDecomposable<TX>,
+        Y: Array1<TY>,
+    > Predictor<X, Y> for RidgeCV<TX, TY, X, Y>
+{
+    fn predict(&self, x: &X) -> Result<Y, Failed> {
+        self.predict(x)
+    }
+}
+
+impl<
+        TX: Number + RealNumber,
+        TY: Number,
+        X: Array2<TX> + CholeskyDecomposable<TX> + SVDDecomposable<TX>,
+        Y: Array1<TY>,
+    > RidgeCV<TX, TY, X, Y>
+{
+    /// Selects the regularization parameter by cross-validation and fits ridge regression to your data.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - target values
+    /// * `parameters` - other parameters, use `Default::default()` to set parameters to default values.
+    pub fn fit(
+        x: &X,
+        y: &Y,
+        parameters: RidgeCVParameters<TX>,
+    ) -> Result<RidgeCV<TX, TY, X, Y>, Failed> {
+        let (n, p) = x.shape();
+
+        if y.shape() != n {
+            return Err(Failed::fit("Number of rows in X should = len(y)"));
+        }
+
+        if parameters.alphas.is_empty() {
+            return Err(Failed::fit("alphas should not be empty"));
+        }
+
+        if !parameters
+            .alphas
+            .iter()
+            .all(|&alpha| alpha.is_finite() && alpha >= TX::zero())
+        {
+            return Err(Failed::fit("alpha should be finite and >= 0"));
+        }
+
+        if n <= p && parameters.alphas.iter().any(|&alpha| alpha == TX::zero()) {
+            return Err(Failed::fit(
+                "Number of rows in X should be >= number of columns in X when alpha = 0",
+            ));
+        }
+
+        let mse_path = match &parameters.cv {
+            None => Self::loo_mse(x, y, &parameters)?,
+            Some(cv) => Self::kfold_mse(x, y, &parameters, cv)?,
+        };
+
+        let mut best = 0;
+        for (k, mse) in mse_path.iter().enumerate() {
+            if *mse < mse_path[best] {
+                best = k;
+            }
+        }
+        let alpha = parameters.alphas[best];
+
+        let model = RidgeRegression::fit(
+            x,
+            y,
+            RidgeRegressionParameters::default()
+                .with_alpha(alpha)
+                .with_solver(parameters.solver)
+                .with_normalize(parameters.normalize),
+        )?;
+
+        Ok(RidgeCV {
+            model: Some(model),
+            alpha: Some(alpha),
+            mse_path: Some(mse_path),
+        })
+    }
+
+    /// Predict target values from `x`
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict(&self, x: &X) -> Result<Y, Failed> {
+        self.model.as_ref().unwrap().predict(x)
+    }
+
+    /// Get estimates regression coefficients
+    pub fn coefficients(&self) -> &X {
+        self.model.as_ref().unwrap().coefficients()
+    }
+
+    /// Get estimate of intercept
+    pub fn intercept(&self) -> &TX {
+        self.model.as_ref().unwrap().intercept()
+    }
+
+    /// Regularization parameter chosen by cross-validation
+    pub fn alpha(&self) -> TX {
+        self.alpha.unwrap()
+    }
+
+    /// Mean squared cross-validation error for every candidate value of alpha, in the order of `alphas`
+    pub fn mse_path(&self) -> &Vec<f64> {
+        self.mse_path.as_ref().unwrap()
+    }
+
+    /// Leave-one-out errors for all alphas from a single SVD of the design matrix.
+    fn loo_mse(x: &X, y: &Y, parameters: &RidgeCVParameters<TX>) -> Result<Vec<f64>, Failed> {
+        let (n, p) = x.shape();
+
+        let y: Vec<TX> = y.iterator(0).map(|&v| TX::from(v).unwrap()).collect();
+
+        // with an intercept the data is centered and the hat matrix gains a constant 1/n term
+        let (x, y, h_0) = if parameters.normalize {
+            let scaled_x = Self::rescale_x(x)?;
+            let y_mean = y.iter().fold(TX::zero(), |acc, &v| acc + v) / TX::from_usize(n).unwrap();
+            let y_c: Vec<TX> = y.iter().map(|&v| v - y_mean).collect();
+            (scaled_x, y_c, TX::one() / TX::from_usize(n).unwrap())
+        } else {
+            (x.clone(), y, TX::zero())
+        };
+
+        let svd = x.svd()?;
+        let k = svd.s.len().min(p);
+
+        let u_t_y: Vec<TX> = (0..k)
+            .map(|j| (0..n).fold(TX::zero(), |acc, i| acc + *svd.U.get((i, j)) * y[i]))
+            .collect();
+
+        let mut mse_path = Vec::with_capacity(parameters.alphas.len());
+        for &alpha in parameters.alphas.iter() {
+            let shrinkage: Vec<TX> = svd.s[..k]
+                .iter()
+                .map(|&s| {
+                    let s2 = s * s;
+                    if s2 + alpha > TX::zero() {
+                        s2 / (s2 + alpha)
+                    } else {
+                        TX::zero()
+                    }
+                })
+                .collect();
+
+            let mut sse = 0f64;
+            for (i, y_i) in y.iter().enumerate() {
+                let mut y_hat = TX::zero();
+                let mut h_ii = h_0;
+                for j in 0..k {
+                    let u_ij = *svd.U.get((i, j));
+                    y_hat += u_ij * shrinkage[j] * u_t_y[j];
+                    h_ii += u_ij * u_ij * shrinkage[j];
+                }
+                let e = ((*y_i - y_hat) / (TX::one() - h_ii)).to_f64().unwrap();
+                sse += e * e;
+            }
+            mse_path.push(sse / n as f64);
+        }
+
+        Ok(mse_path)
+    }
+
+    /// K-fold cross-validation errors, the model is refitted on every fold for every alpha.
+    fn kfold_mse(
+        x: &X,
+        y: &Y,
+        parameters: &RidgeCVParameters<TX>,
+        cv: &KFold,
+    ) -> Result<Vec<f64>, Failed> {
+        let splits: Vec<(Vec<usize>, Vec<usize>)> = cv.split(x).collect();
+
+        let mut mse_path = Vec::with_capacity(parameters.alphas.len());
+        for &alpha in parameters.alphas.iter() {
+            let ridge_parameters = RidgeRegressionParameters::default()
+                .with_alpha(alpha)
+                .with_solver(parameters.solver.clone())
+                .with_normalize(parameters.normalize);
+
+            let mut mse = 0f64;
+            for (train_idx, test_idx) in splits.iter() {
+                let model = RidgeRegression::fit(
+                    &x.take(train_idx, 0),
+                    &y.take(train_idx),
+                    ridge_parameters.clone(),
+                )?;
+                let y_hat = model.predict(&x.take(test_idx, 0))?;
+
+                let sse = test_idx
+                    .iter()
+                    .zip(y_hat.iterator(0))
+                    .fold(0f64, |acc, (&i, &v)| {
+                        let e = y.get(i).to_f64().unwrap() - v.to_f64().unwrap();
+                        acc + e * e
+                    });
+                mse += sse / test_idx.len() as f64;
+            }
+            mse_path.push(mse / splits.len() as f64);
+        }
+
+        Ok(mse_path)
+    }
+
+    fn rescale_x(x: &X) -> Result<X, Failed> {
+        let col_mean: Vec<TX> = x
+            .mean_by(0)
+            .iter()
+            .map(|&v| TX::from_f64(v).unwrap())
+            .collect();
+        let col_std: Vec<TX> = x
+            .std_dev(0)
+            .iter()
+            .map(|&v| TX::from_f64(v).unwrap())
+            .collect();
+
+        for (i, col_std_i) in col_std.iter().enumerate() {
+            if (*col_std_i - TX::zero()).abs() < TX::epsilon() {
+                return Err(Failed::fit(&format!("Cannot rescale constant column {i}")));
+            }
+        }
+
+        let mut scaled_x = x.clone();
+        scaled_x.scale_mut(&col_mean, &col_std, 0);
+        Ok(scaled_x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::mean_absolute_error;
+    use crate::test_datasets::longley;
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn ridge_cv_fit_predict() {
+        let (x, y) = longley();
+
+        let alphas = vec![0.001, 0.01, 0.1, 1.0, 10.0, 100.0];
+
+        let ridge = RidgeCV::fit(
+            &x,
+            &y,
+            RidgeCVParameters::default().with_alphas(alphas.clone()),
+        )
+        .unwrap();
+
+        assert_eq!(ridge.mse_path().len(), alphas.len());
+        assert!(alphas.contains(&ridge.alpha()));
+        assert!(ridge.alpha() < 100.0);
+
+        let y_hat = ridge.predict(&x).unwrap();
+        assert!(mean_absolute_error(&y_hat, &y) < 2.0);
+
+        let ridge_kfold = RidgeCV::fit(
+            &x,
+            &y,
+            RidgeCVParameters::default()
+                .with_alphas(alphas.clone())
+                .with_cv(KFold::default().with_n_splits(4).with_shuffle(false)),
+        )
+        .unwrap();
+
+        assert_eq!(ridge_kfold.mse_path().len(), alphas.len());
+        assert!(alphas.contains(&ridge_kfold.alpha()));
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn ridge_cv_loo_matches_refitting() {
+        let (x, y) = longley();
+        let n = y.len();
+
+        let alphas = vec![0.01, 1.0, 100.0];
+
+        let ridge = RidgeCV::fit(
+            &x,
+            &y,
+            RidgeCVParameters::default()
+                .with_alphas(alphas.clone())
+                .with_normalize(false),
+        )
+        .unwrap();
+
+        let cv = KFold::default().with_n_splits(n).with_shuffle(false);
+        let ridge_refit = RidgeCV::fit(
+            &x,
+            &y,
+            RidgeCVParameters::default()
+                .with_alphas(alphas)
+                .with_normalize(false)
+                .with_solver(RidgeRegressionSolverName::SVD)
+                .with_cv(cv),
+        )
+        .unwrap();
+
+        for (loo, refit) in ridge.mse_path().iter().zip(ridge_refit.mse_path()) {
+            assert!((loo - refit).abs() / refit < 1e-4);
+        }
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn ridge_cv_wide_data() {
+        let (x, y) = longley();
+        // fewer observations than features
+        let rows: Vec<usize> = (0..5).collect();
+        let (x, y) = (x.take(&rows, 0), y.take(&rows));
+
+        let alphas = vec![0.1, 1.0, 10.0];
+
+        let ridge = RidgeCV::fit(
+            &x,
+            &y,
+            RidgeCVParameters::default()
+                .with_alphas(alphas.clone())
+                .with_normalize(false),
+        )
+        .unwrap();
+
+        let cv = KFold::default().with_n_splits(5).with_shuffle(false);
+        let ridge_refit = RidgeCV::fit(
+            &x,
+            &y,
+            RidgeCVParameters::default()
+                .with_alphas(alphas.clone())
+                .with_normalize(false)
+                .with_solver(RidgeRegressionSolverName::SVD)
+                .with_cv(cv),
+        )
+        .unwrap();
+
+        for (loo, refit) in ridge.mse_path().iter().zip(ridge_refit.mse_path()) {
+            assert!((loo - refit).abs() / refit < 1e-4);
+        }
+
+        assert!(RidgeCV::fit(
+            &x,
+            &y,
+            RidgeCVParameters::default().with_alphas(vec![0.0, 1.0])
+        )
+        .is_err());
+        assert!(RidgeCV::fit(
+            &x,
+            &y,
+            RidgeCVParameters::default().with_alphas(vec![1.0, f64::NAN])
+        )
+        .is_err());
+    }
+}
@@ -62,11 +62,13 @@ use std::marker::PhantomData;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+use crate::algorithm::sample_weight::check_sample_weight;
 use crate::api::{Predictor, SupervisedEstimator};
 use crate::error::Failed;
 use crate::linalg::basic::arrays::{Array1, Array2};
 use crate::linalg::traits::cholesky::CholeskyDecomposable;
 use crate::linalg::traits::svd::SVDDecomposable;
+use crate::linear::{sqrt_weigh_rows, weighted_col_mean_std};
 use crate::numbers::basenum::Number;
 use crate::numbers::realnum::RealNumber;
 
@@ -291,13 +293,27 @@ impl<
         y: &Y,
         parameters: RidgeRegressionParameters<TX>,
     ) -> Result<RidgeRegression<TX, TY, X, Y>, Failed> {
-        //w = inv(X^t X + alpha*Id) * X.T y
+        Self::fit_with_sample_weight(x, y, &vec![TX::one(); x.shape().0], parameters)
+    }
+
+    /// Fits ridge regression to your data, minimizing the weighted sum of squared residuals plus the penalty.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - target values
+    /// * `sample_weight` - non-negative weight of every observation
+    /// * `parameters` - other parameters, use `Default::default()` to set parameters to default values.
+    pub fn fit_with_sample_weight(
+        x: &X,
+        y: &Y,
+        sample_weight: &[TX],
+        parameters: RidgeRegressionParameters<TX>,
+    ) -> Result<RidgeRegression<TX, TY, X, Y>, Failed> {
+        //w = inv(X^t W X + alpha*Id) * X.T W y
 
         let (n, p) = x.shape();
 
-        if n <= p {
+        if n <= p && parameters.alpha == TX::zero() {
             return Err(Failed::fit(
-                "Number of rows in X should be >= number of columns in X",
+                "Number of rows in X should be >= number of columns in X when alpha = 0",
             ));
         }
 
@@ -305,15 +321,21 @@ impl<
             return Err(Failed::fit("Number of rows in X should = len(y)"));
         }
 
-        let y_column = X::from_iterator(
-            y.iterator(0).map(|&v| TX::from(v).unwrap()),
-            y.shape(),
-            1,
-            0,
+        check_sample_weight(sample_weight, n)?;
+
+        let y_column = sqrt_weigh_rows(
+            &X::from_iterator(
+                y.iterator(0).map(|&v| TX::from(v).unwrap()),
+                y.shape(),
+                1,
+                0,
+            ),
+            sample_weight,
         );
 
         let (w, b) = if parameters.normalize {
-            let (scaled_x, col_mean, col_std) = Self::rescale_x(x)?;
+            let (scaled_x, col_mean, col_std) = Self::rescale_x(x, sample_weight)?;
+            let scaled_x = sqrt_weigh_rows(&scaled_x, sample_weight);
             let x_t = scaled_x.transpose();
             let x_t_y = x_t.matmul(&y_column);
             let mut x_t_x = x_t.matmul(&scaled_x);
@@ -337,13 +359,21 @@ impl<
                 b += *w.get((i, 0)) * *col_mean_i;
             }
 
-            let b = TX::from_f64(y.mean_by()).unwrap() - b;
+            let y_mean = y
+                .iterator(0)
+                .zip(sample_weight.iter())
+                .map(|(&y_i, &w_i)| TX::from(y_i).unwrap() * w_i)
+                .sum::<TX>()
+                / sample_weight.iter().copied().sum::<TX>();
+
+            let b = y_mean - b;
 
             (w, b)
         } else {
+            let x = sqrt_weigh_rows(x, sample_weight);
             let x_t = x.transpose();
             let x_t_y = x_t.matmul(&y_column);
-            let mut x_t_x = x_t.matmul(x);
+            let mut x_t_x = x_t.matmul(&x);
 
             for i in 0..p {
                 x_t_x.add_element_mut((i, i), parameters.alpha);
@@ -365,17 +395,8 @@ impl<
         })
     }
 
-    fn rescale_x(x: &X) -> Result<(X, Vec<TX>, Vec<TX>), Failed> {
-        let col_mean: Vec<TX> = x
-            .mean_by(0)
-            .iter()
-            .map(|&v| TX::from_f64(v).unwrap())
-            .collect();
-        let col_std: Vec<TX> = x
-            .std_dev(0)
-            .iter()
-            .map(|&v| TX::from_f64(v).unwrap())
-            .collect();
+    fn rescale_x(x: &X, sample_weight: &[TX]) -> Result<(X, Vec<TX>, Vec<TX>), Failed> {
+        let (col_mean, col_std) = weighted_col_mean_std(x, sample_weight);
 
         for (i, col_std_i) in col_std.iter().enumerate() {
             if (*col_std_i - TX::zero()).abs() < TX::epsilon() {
@@ -416,6 +437,7 @@ mod tests {
     use super::*;
     use crate::linalg::basic::matrix::DenseMatrix;
     use crate::metrics::mean_absolute_error;
+    use crate::test_datasets::{assert_weights_repeat_rows, longley};
 
     #[test]
     fn search_parameters() {
@@ -528,4 +550,47 @@ mod tests {
 
     //     assert_eq!(lr, deserialized_lr);
     // }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn ridge_fit_with_sample_weight() {
+        let (x, y) = longley();
+        let sample_weight = vec![
+            1., 2., 1., 3., 1., 1., 2., 1., 1., 1., 2., 1., 1., 1., 1., 2.,
+        ];
+
+        for parameters in [true, false].map(|normalize| {
+            RidgeRegressionParameters::default()
+                .with_alpha(0.1)
+                .with_normalize(normalize)
+        }) {
+            assert_weights_repeat_rows(
+                &x,
+                &y,
+                &sample_weight,
+                |x_w, y_w, w| {
+                    RidgeRegression::fit_with_sample_weight(x_w, y_w, w, parameters.clone())
+                        .and_then(|lr| lr.predict(&x))
+                        .unwrap()
+                },
+                |x_r, y_r| {
+                    RidgeRegression::fit(x_r, y_r, parameters.clone())
+                        .and_then(|lr| lr.predict(&x))
+                        .unwrap()
+                },
+                1e-6,
+            );
+        }
+
+        assert!(RidgeRegression::fit_with_sample_weight(
+            &x,
+            &y,
+            &sample_weight[1..],
+            Default::default()
+        )
+        .is_err());
+    }
 }
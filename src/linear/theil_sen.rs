@@ -0,0 +1,565 @@
+//! # Theil-Sen Regression
+//!
+//! Theil-Sen regression fits [least squares](../linear_regression/index.html) on many small subsets of the observations and takes the spatial median
+//! of the resulting coefficient vectors. Unlike the coordinate-wise median, the spatial median
+//!
+//! \\[\hat{\beta} = \underset{\beta}{\mathrm{argmin}} \sum_{s=1}^{S} \lVert \beta_s - \beta \rVert_2\\]
+//!
+//! is invariant to rotations of the coefficient space. With subsets of \\(m + 1\\) observations, where \\(m\\) is the number of features, the estimator tolerates
+//! a fraction of arbitrarily corrupted observations up to \\(1 - 2^{-1/(m+1)}\\), about 29% for simple linear regression.
+//! Larger subsets trade robustness for statistical efficiency.
+//!
+//! When the number of subsets \\(\binom{n}{k}\\) exceeds `max_subpopulation`, `max_subpopulation` subsets are drawn at random.
+//! The spatial median is computed with the modified Weiszfeld algorithm of Vardi and Zhang.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::basic::matrix::DenseMatrix;
+//! use smartcore::linear::theil_sen::*;
+//!
+//! let x = DenseMatrix::from_2d_array(&[
+//!             &[1.], &[2.], &[3.], &[4.], &[5.], &[6.], &[7.], &[8.], &[9.], &[10.],
+//!         ]).unwrap();
+//! // y = 2x + 1, the last observation is an outlier
+//! let y: Vec<f64> = vec![3.1, 4.9, 7.0, 9.1, 10.9, 13.0, 15.1, 16.9, 19.0, 60.0];
+//!
+//! let theil_sen = TheilSenRegressor::fit(&x, &y, Default::default()).unwrap();
+//!
+//! let y_hat = theil_sen.predict(&x).unwrap();
+//! ```
+//!
+//! ## References:
+//!
+//! * ["Theil-Sen Estimators in a Multiple Linear Regression Model", Dang X., Peng H., Wang X., Zhang H., 2008](https://home.olemiss.edu/~xdang/papers/MTSE.pdf)
+//! * ["The multivariate L1-median and associated data depth", Vardi Y., Zhang C.H., 2000](https://www.pnas.org/doi/10.1073/pnas.97.4.1423)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+use rand::Rng;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::api::{Predictor, SupervisedEstimator};
+use crate::error::Failed;
+use crate::linalg::basic::arrays::{Array1, Array2};
+use crate::linalg::traits::svd::SVDDecomposable;
+use crate::numbers::basenum::Number;
+use crate::numbers::realnum::RealNumber;
+use crate::rand_custom::get_rng_impl;
+
+/// Theil-Sen regression parameters
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct TheilSenRegressorParameters {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Number of observations in every subset, the number of features plus one if not set.
+    pub n_subsamples: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Largest number of subsets, random subsets are drawn when there are more.
+    pub max_subpopulation: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Maximum number of iterations of the spatial median.
+    pub max_iter: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// The spatial median stops when it moves by less than `tol`.
+    pub tol: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Seed of the random number generator used to draw subsets.
+    pub seed: u64,
+}
+
+impl TheilSenRegressorParameters {
+    /// Number of observations in every subset.
+    pub fn with_n_subsamples(mut self, n_subsamples: usize) -> Self {
+        self.n_subsamples = Some(n_subsamples);
+        self
+    }
+    /// Largest number of subsets.
+    pub fn with_max_subpopulation(mut self, max_subpopulation: usize) -> Self {
+        self.max_subpopulation = max_subpopulation;
+        self
+    }
+    /// Maximum number of iterations of the spatial median.
+    pub fn with_max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+    /// Tolerance for the stopping criterion of the spatial median.
+    pub fn with_tol(mut self, tol: f64) -> Self {
+        self.tol = tol;
+        self
+    }
+    /// Seed of the random number generator used to draw subsets.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+}
+
+impl Default for TheilSenRegressorParameters {
+    fn default() -> Self {
+        TheilSenRegressorParameters {
+            n_subsamples: None,
+            max_subpopulation: 10000,
+            max_iter: 300,
+            tol: 1e-3,
+            seed: 0,
+        }
+    }
+}
+
+/// Theil-Sen regression
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+pub struct TheilSenRegressor<
+    TX: Number + RealNumber,
+    TY: Number,
+    X: Array2<TX> + SVDDecomposable<TX>,
+    Y: Array1<TY>,
+> {
+    coefficients: Option<X>,
+    intercept: Option<TX>,
+    n_subpopulation: usize,
+    n_iter: usize,
+    _phantom_ty: PhantomData<TY>,
+    _phantom_y: PhantomData<Y>,
+}
+
+impl<TX: Number + RealNumber, TY: Number, X: Array2<TX> + SVDDecomposable<TX>, Y: Array1<TY>>
+    PartialEq for TheilSenRegressor<TX, TY, X, Y>
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.intercept() == other.intercept()
+            && self.coefficients().shape() == other.coefficients().shape()
+            && self
+                .coefficients()
+                .iterator(0)
+                .zip(other.coefficients().iterator(0))
+                .all(|(&a, &b)| (a - b).abs() <= TX::epsilon())
+    }
+}
+
+impl<TX: Number + RealNumber, TY: Number, X: Array2<TX> + SVDDecomposable<TX>, Y: Array1<TY>>
+    SupervisedEstimator<X, Y, TheilSenRegressorParameters> for TheilSenRegressor<TX, TY, X, Y>
+{
+    fn new() -> Self {
+        Self {
+            coefficients: Option::None,
+            intercept: Option::None,
+            n_subpopulation: 0,
+            n_iter: 0,
+            _phantom_ty: PhantomData,
+            _phantom_y: PhantomData,
+        }
+    }
+
+    fn fit(x: &X, y: &Y, parameters: TheilSenRegressorParameters) -> Result<Self, Failed> {
+        TheilSenRegressor::fit(x, y, parameters)
+    }
+}
+
+impl<TX: Number + RealNumber, TY: Number, X: Array2<TX> + SVDDecomposable<TX>, Y: Array1<TY>>
+    Predictor<X, Y> for TheilSenRegressor<TX, TY, X, Y>
+{
+    fn predict(&self, x: &X) -> Result<Y, Failed> {
+        self.predict(x)
+    }
+}
+
+impl<TX: Number + RealNumber, TY: Number, X: Array2<TX> + SVDDecomposable<TX>, Y: Array1<TY>>
+    TheilSenRegressor<TX, TY, X, Y>
+{
+    /// Fits Theil-Sen regression to your data.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - target values
+    /// * `parameters` - other parameters, use `Default::default()` to set parameters to default values.
+    pub fn fit(
+        x: &X,
+        y: &Y,
+        parameters: TheilSenRegressorParameters,
+    ) -> Result<TheilSenRegressor<TX, TY, X, Y>, Failed> {
+        let (n, p) = x.shape();
+
+        if y.shape() != n {
+            return Err(Failed::fit("Number of rows in X should = len(y)"));
+        }
+
+        let n_subsamples = parameters.n_subsamples.unwrap_or(p + 1);
+        if n_subsamples <= p || n_subsamples > n {
+            return Err(Failed::fit(&format!(
+                "n_subsamples should be between the number of features plus one {} and the number of rows {n}, got {n_subsamples}",
+                p + 1
+            )));
+        }
+        if parameters.max_subpopulation == 0 {
+            return Err(Failed::fit("max_subpopulation should be > 0"));
+        }
+
+        // subsets where least squares fails or is not finite are skipped
+        let fit_subset = |rows: &[usize]| -> Option<Vec<TX>> {
+            let a = x.take(rows, 0).h_stack(&X::ones(rows.len(), 1));
+            let b = X::from_iterator(
+                rows.iter().map(|&i| TX::from(*y.get(i)).unwrap()),
+                rows.len(),
+                1,
+                0,
+            );
+            // the solution is in the first rows of the result
+            let solution = a.svd_solve_mut(b).ok()?;
+            let beta: Vec<TX> = (0..=p).map(|j| *solution.get((j, 0))).collect();
+            beta.iter().all(|b_j| b_j.is_finite()).then_some(beta)
+        };
+
+        let mut solutions = Vec::new();
+        if n_combinations(n, n_subsamples) <= parameters.max_subpopulation {
+            let mut rows: Vec<usize> = (0..n_subsamples).collect();
+            loop {
+                solutions.extend(fit_subset(&rows));
+                if !next_combination(&mut rows, n) {
+                    break;
+                }
+            }
+        } else {
+            let mut rng = get_rng_impl(Some(parameters.seed));
+            let mut indices: Vec<usize> = (0..n).collect();
+            for _ in 0..parameters.max_subpopulation {
+                // partial Fisher-Yates shuffle, the first n_subsamples indices are a sample without replacement
+                for i in 0..n_subsamples {
+                    let j = rng.gen_range(i..n);
+                    indices.swap(i, j);
+                }
+                solutions.extend(fit_subset(&indices[..n_subsamples]));
+            }
+        }
+
+        if solutions.is_empty() {
+            return Err(Failed::fit("Least squares failed on every subset"));
+        }
+
+        let tol = TX::from_f64(parameters.tol).unwrap();
+        let (beta, n_iter) = spatial_median(&solutions, parameters.max_iter, tol);
+
+        Ok(TheilSenRegressor {
+            coefficients: Some(X::from_iterator(beta[..p].iter().copied(), p, 1, 0)),
+            intercept: Some(beta[p]),
+            n_subpopulation: solutions.len(),
+            n_iter,
+            _phantom_ty: PhantomData,
+            _phantom_y: PhantomData,
+        })
+    }
+
+    /// Predict target values from `x`
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict(&self, x: &X) -> Result<Y, Failed> {
+        let (nrows, ncols) = x.shape();
+        let (n_coefficients, _) = self.coefficients().shape();
+        if ncols != n_coefficients {
+            return Err(Failed::predict(&format!(
+                "Number of features of X {ncols} doesn't match number of coefficients {n_coefficients}"
+            )));
+        }
+        let y_hat = x.matmul(self.coefficients());
+        Ok(Y::from_iterator(
+            y_hat
+                .iterator(0)
+                .map(|&v| TY::from(v + *self.intercept()).unwrap()),
+            nrows,
+        ))
+    }
+
+    /// Get estimates regression coefficients
+    pub fn coefficients(&self) -> &X {
+        self.coefficients.as_ref().unwrap()
+    }
+
+    /// Get estimate of intercept
+    pub fn intercept(&self) -> &TX {
+        self.intercept.as_ref().unwrap()
+    }
+
+    /// Number of subsets least squares was fit on, subsets where the fit failed are not counted
+    pub fn n_subpopulation(&self) -> usize {
+        self.n_subpopulation
+    }
+
+    /// Number of iterations of the spatial median
+    pub fn n_iter(&self) -> usize {
+        self.n_iter
+    }
+}
+
+/// Binomial coefficient, saturating at `usize::MAX`.
+fn n_combinations(n: usize, k: usize) -> usize {
+    let k = k.min(n - k);
+    let mut c: usize = 1;
+    for i in 0..k {
+        // c * (n - i) / (i + 1) is always an integer
+        c = match c.checked_mul(n - i) {
+            Some(v) => v / (i + 1),
+            None => return usize::MAX,
+        };
+    }
+    c
+}
+
+/// Advances `rows` to the next combination of `rows.len()` out of `n` indices in lexicographic order.
+/// Returns `false` after the last combination.
+fn next_combination(rows: &mut [usize], n: usize) -> bool {
+    let k = rows.len();
+    let Some(i) = (0..k).rev().find(|&i| rows[i] < n - k + i) else {
+        return false;
+    };
+    rows[i] += 1;
+    for j in i + 1..k {
+        rows[j] = rows[j - 1] + 1;
+    }
+    true
+}
+
+/// Spatial median of `points` with the modified Weiszfeld algorithm, which remains well defined
+/// when an iterate coincides with one of the points. Starts at the mean and returns the median with the number of iterations.
+fn spatial_median<T: Number + RealNumber>(
+    points: &[Vec<T>],
+    max_iter: usize,
+    tol: T,
+) -> (Vec<T>, usize) {
+    let d = points[0].len();
+    let n = T::from_usize(points.len()).unwrap();
+    let mut median: Vec<T> = (0..d)
+        .map(|j| points.iter().fold(T::zero(), |s, point| s + point[j]) / n)
+        .collect();
+
+    let mut n_iter = 0;
+    while n_iter < max_iter {
+        n_iter += 1;
+
+        let mut n_coincident = T::zero();
+        let mut inverse_distance_sum = T::zero();
+        let mut weighted_sum = vec![T::zero(); d];
+        let mut direction = vec![T::zero(); d];
+        for point in points {
+            let distance = point
+                .iter()
+                .zip(median.iter())
+                .fold(T::zero(), |s, (&a, &b)| s + (a - b) * (a - b))
+                .sqrt();
+            if distance > T::zero() {
+                inverse_distance_sum += T::one() / distance;
+                for j in 0..d {
+                    weighted_sum[j] += point[j] / distance;
+                    direction[j] += (point[j] - median[j]) / distance;
+                }
+            } else {
+                n_coincident += T::one();
+            }
+        }
+        if inverse_distance_sum == T::zero() {
+            // all points coincide with the current iterate
+            break;
+        }
+
+        let weiszfeld: Vec<T> = weighted_sum
+            .iter()
+            .map(|&v| v / inverse_distance_sum)
+            .collect();
+        let next: Vec<T> = if n_coincident > T::zero() {
+            let r = direction.iter().fold(T::zero(), |s, &v| s + v * v).sqrt();
+            let eta_over_r = if r > T::zero() {
+                n_coincident / r
+            } else {
+                T::one()
+            };
+            let weight_weiszfeld = (T::one() - eta_over_r).max(T::zero());
+            let weight_current = eta_over_r.min(T::one());
+            weiszfeld
+                .iter()
+                .zip(median.iter())
+                .map(|(&w, &m)| weight_weiszfeld * w + weight_current * m)
+                .collect()
+        } else {
+            weiszfeld
+        };
+
+        let step = next
+            .iter()
+            .zip(median.iter())
+            .fold(T::zero(), |s, (&a, &b)| s + (a - b) * (a - b))
+            .sqrt();
+        median = next;
+        if step < tol {
+            break;
+        }
+    }
+
+    (median, n_iter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::basic::arrays::Array;
+    use crate::linalg::basic::matrix::DenseMatrix;
+    use crate::linear::linear_regression::LinearRegression;
+
+    #[test]
+    fn combinations() {
+        assert_eq!(n_combinations(15, 3), 455);
+        assert_eq!(n_combinations(5, 5), 1);
+        assert_eq!(n_combinations(1000, 500), usize::MAX);
+
+        let mut rows = vec![0, 1];
+        let mut all = vec![rows.clone()];
+        while next_combination(&mut rows, 4) {
+            all.push(rows.clone());
+        }
+        assert_eq!(
+            all,
+            vec![
+                vec![0, 1],
+                vec![0, 2],
+                vec![0, 3],
+                vec![1, 2],
+                vec![1, 3],
+                vec![2, 3]
+            ]
+        );
+    }
+
+    #[test]
+    fn spatial_median_of_points() {
+        // the spatial median of the corners of a square and a distant point is pulled only slightly towards it
+        let points: Vec<Vec<f64>> = vec![
+            vec![0., 0.],
+            vec![1., 0.],
+            vec![0., 1.],
+            vec![1., 1.],
+            vec![100., 100.],
+        ];
+        let (median, _) = spatial_median(&points, 1000, 1e-12);
+        assert!((median[0] - median[1]).abs() < 1e-8);
+        assert!(median[0] > 0.5 && median[0] < 1.);
+
+        // the median of three collinear points is the middle one, an iterate may land on it
+        let points: Vec<Vec<f64>> = vec![vec![0., 0.], vec![1., 1.], vec![5., 5.]];
+        let (median, _) = spatial_median(&points, 1000, 1e-12);
+        assert!((median[0] - 1.).abs() < 1e-6 && (median[1] - 1.).abs() < 1e-6);
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn theil_sen_fit_predict() {
+        let x = DenseMatrix::from_2d_array(&[
+            &[1., 0.5],
+            &[2., 1.5],
+            &[3., 0.2],
+            &[4., 2.2],
+            &[5., 1.1],
+            &[6., 0.8],
+            &[7., 1.9],
+            &[8., 0.3],
+            &[9., 1.4],
+            &[10., 2.5],
+            &[11., 0.9],
+            &[12., 1.7],
+            &[13., 0.6],
+            &[14., 2.1],
+            &[15., 1.2],
+        ])
+        .unwrap();
+        // y = 2 x_1 - x_2 + 1 with small noise, the last three observations are outliers
+        let mut y: Vec<f64> = (0..15)
+            .map(|i| {
+                let noise = [
+                    0.1, -0.2, 0.05, 0.15, -0.1, 0.2, -0.05, 0.1, -0.15, 0.05, 0.1, -0.1,
+                ][i % 12];
+                2. * x.get((i, 0)) - x.get((i, 1)) + 1. + noise
+            })
+            .collect();
+        y[12] += 40.;
+        y[13] -= 35.;
+        y[14] += 50.;
+
+        let ols = LinearRegression::fit(&x, &y, Default::default()).unwrap();
+        assert!((ols.coefficients().get((0, 0)) - 2.).abs() > 0.5);
+
+        let theil_sen = TheilSenRegressor::fit(&x, &y, Default::default()).unwrap();
+        assert_eq!(theil_sen.n_subpopulation(), 455);
+        assert!((theil_sen.coefficients().get((0, 0)) - 2.).abs() < 0.2);
+        assert!((theil_sen.coefficients().get((1, 0)) + 1.).abs() < 0.5);
+
+        let y_hat = theil_sen.predict(&x).unwrap();
+        assert!((0..12).all(|i| (y[i] - y_hat[i]).abs() < 1.));
+
+        // random subsets when there are more than max_subpopulation
+        let sampled = TheilSenRegressor::fit(
+            &x,
+            &y,
+            TheilSenRegressorParameters::default().with_max_subpopulation(200),
+        )
+        .unwrap();
+        assert_eq!(sampled.n_subpopulation(), 200);
+        assert!((sampled.coefficients().get((0, 0)) - 2.).abs() < 0.2);
+
+        assert!(
+            TheilSenRegressor::<f64, f64, DenseMatrix<f64>, Vec<f64>>::fit(
+                &x,
+                &y,
+                TheilSenRegressorParameters::default().with_n_subsamples(2)
+            )
+            .is_err()
+        );
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde() {
+        let x = DenseMatrix::from_2d_array(&[&[1.], &[2.], &[3.], &[4.], &[5.]]).unwrap();
+        let y: Vec<f64> = vec![3., 5., 7., 9., 11.];
+
+        let theil_sen = TheilSenRegressor::fit(&x, &y, Default::default()).unwrap();
+
+        let deserialized_theil_sen: TheilSenRegressor<f64, f64, DenseMatrix<f64>, Vec<f64>> =
+            serde_json::from_str(&serde_json::to_string(&theil_sen).unwrap()).unwrap();
+
+        assert_eq!(theil_sen, deserialized_theil_sen);
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn theil_sen_skips_failed_subsets() {
+        let x = DenseMatrix::from_2d_array(&[&[1.], &[2.], &[3.], &[4.], &[5.]]).unwrap();
+        let y: Vec<f64> = vec![3., 5., f64::NAN, 9., 11.];
+
+        // 4 of the 10 pairs contain the third observation
+        let theil_sen: TheilSenRegressor<f64, f64, DenseMatrix<f64>, Vec<f64>> =
+            TheilSenRegressor::fit(&x, &y, Default::default()).unwrap();
+        assert_eq!(theil_sen.n_subpopulation(), 6);
+        assert!((theil_sen.coefficients().get((0, 0)) - 2.).abs() < 1e-6);
+        assert!((theil_sen.intercept() - 1.).abs() < 1e-6);
+
+        let y: Vec<f64> = vec![f64::NAN; 5];
+        assert!(
+            TheilSenRegressor::<f64, f64, DenseMatrix<f64>, Vec<f64>>::fit(
+                &x,
+                &y,
+                Default::default()
+            )
+            .is_err()
+        );
+    }
+}
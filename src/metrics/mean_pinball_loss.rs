@@ -0,0 +1,118 @@
+//! # Mean Pinball Loss
+//!
+//! The pinball loss measures the quality of predictions of the \\(\tau\\)-quantile of the target. Underestimates are penalized with weight \\(\tau\\)
+//! and overestimates with weight \\(1 - \tau\\), so the loss is minimized in expectation by the true quantile.
+//! It is the natural error measure of [quantile regression](../../linear/quantile_regression/index.html) and of prediction intervals.
+//!
+//! \\[L_{\tau}(y, \hat{y}) = \frac{1}{n_{samples}} \sum_{i=1}^{n_{samples}} \max\left(\tau (y_i - \hat{y}_i), (\tau - 1)(y_i - \hat{y}_i)\right) \\]
+//!
+//! where \\(\hat{y}\\) are predictions, \\(y\\) are true target values and \\(\tau \in [0, 1]\\) is the quantile.
+//! For \\(\tau = 0.5\\) the pinball loss is half the mean absolute error.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::metrics::mean_pinball_loss::MeanPinballLoss;
+//! use smartcore::metrics::Metrics;
+//! let y_pred: Vec<f64> = vec![3., -0.5, 2., 7.];
+//! let y_true: Vec<f64> = vec![2.5, 0.0, 2., 8.];
+//!
+//! let loss: f64 = MeanPinballLoss::new_with(0.9).get_score(&y_true, &y_pred);
+//! ```
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use std::marker::PhantomData;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::linalg::basic::arrays::ArrayView1;
+use crate::numbers::basenum::Number;
+use crate::numbers::floatnum::FloatNumber;
+
+use crate::metrics::Metrics;
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+/// Mean Pinball Loss
+pub struct MeanPinballLoss<T> {
+    /// quantile of the target the predictions are evaluated for, between 0 and 1
+    pub quantile: f64,
+    _phantom: PhantomData<T>,
+}
+
+impl<T: Number + FloatNumber> Metrics<T> for MeanPinballLoss<T> {
+    /// create a typed object to call MeanPinballLoss functions for the median
+    fn new() -> Self {
+        Self {
+            quantile: 0.5,
+            _phantom: PhantomData,
+        }
+    }
+    /// create a typed object to call MeanPinballLoss functions for the given quantile
+    fn new_with(quantile: f64) -> Self {
+        Self {
+            quantile,
+            _phantom: PhantomData,
+        }
+    }
+    /// Computes mean pinball loss
+    /// * `y_true` - Ground truth (correct) target values.
+    /// * `y_pred` - Estimated target values.
+    fn get_score(&self, y_true: &dyn ArrayView1<T>, y_pred: &dyn ArrayView1<T>) -> f64 {
+        if y_true.shape() != y_pred.shape() {
+            panic!(
+                "The vector sizes don't match: {} != {}",
+                y_true.shape(),
+                y_pred.shape()
+            );
+        }
+        if !(0. ..=1.).contains(&self.quantile) {
+            panic!("quantile should be between 0 and 1, got {}", self.quantile);
+        }
+
+        let n = y_true.shape();
+        let mut loss = 0f64;
+        for i in 0..n {
+            let residual = (*y_true.get(i) - *y_pred.get(i)).to_f64().unwrap();
+            loss += if residual >= 0. {
+                self.quantile * residual
+            } else {
+                (self.quantile - 1.) * residual
+            };
+        }
+
+        loss / n as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn mean_pinball_loss() {
+        let y_true: Vec<f64> = vec![3., -0.5, 2., 7.];
+        let y_pred: Vec<f64> = vec![2.5, 0.0, 2., 8.];
+
+        let median: f64 = MeanPinballLoss::new().get_score(&y_true, &y_pred);
+        let upper: f64 = MeanPinballLoss::new_with(0.9).get_score(&y_true, &y_pred);
+        let perfect: f64 = MeanPinballLoss::new_with(0.9).get_score(&y_true, &y_true);
+
+        assert!((median - 0.25).abs() < 1e-8);
+        assert!((upper - 0.15).abs() < 1e-8);
+        assert!(perfect.abs() < 1e-8);
+    }
+
+    #[test]
+    #[should_panic(expected = "quantile should be between 0 and 1")]
+    fn mean_pinball_loss_invalid_quantile() {
+        let y_true: Vec<f64> = vec![3., -0.5, 2., 7.];
+        MeanPinballLoss::new_with(1.5).get_score(&y_true, &y_true);
+    }
+}
@@ -0,0 +1,165 @@
+//! # Mean Tweedie Deviance
+//!
+//! The deviance measures how far predictions are from the targets under the likelihood of a distribution from the Tweedie family,
+//! so it is the natural error measure for models of counts, claim severities and other non-negative, skewed targets.
+//!
+//! \\[D(y, \hat{y}) = \frac{1}{n_{samples}} \sum_{i=1}^{n_{samples}} 2 \left( \frac{\max(y_i, 0)^{2-p}}{(1-p)(2-p)} - \frac{y_i \hat{y}_i^{1-p}}{1-p} + \frac{\hat{y}_i^{2-p}}{2-p} \right) \\]
+//!
+//! where \\(\hat{y}\\) are predictions, \\(y\\) are true target values and \\(p\\) is the power of the variance function.
+//! Special cases are \\(p = 0\\), the mean squared error, \\(p = 1\\), the Poisson deviance and \\(p = 2\\), the Gamma deviance.
+//! Values of \\(p\\) between 0 and 1 do not correspond to any distribution and are not supported.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::metrics::mean_tweedie_deviance::MeanTweedieDeviance;
+//! use smartcore::metrics::Metrics;
+//! let y_pred: Vec<f64> = vec![0.5, 0.5, 2., 2.];
+//! let y_true: Vec<f64> = vec![2., 0., 1., 4.];
+//!
+//! let poisson_deviance: f64 = MeanTweedieDeviance::new_with(1.).get_score(&y_true, &y_pred);
+//! ```
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use std::marker::PhantomData;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::linalg::basic::arrays::ArrayView1;
+use crate::numbers::basenum::Number;
+use crate::numbers::floatnum::FloatNumber;
+
+use crate::metrics::Metrics;
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+/// Mean Tweedie Deviance
+pub struct MeanTweedieDeviance<T> {
+    /// power of the variance function, 0 for squared error, 1 for Poisson and 2 for Gamma deviance
+    pub power: f64,
+    _phantom: PhantomData<T>,
+}
+
+/// Checks that `power` is a valid Tweedie power and that `y` lies in the support of the distribution.
+/// `strict` additionally requires `y > 0` where the distribution allows `y = 0`, as needed for predicted means.
+pub(crate) fn check_tweedie_domain(power: f64, y: f64, strict: bool) -> Result<(), String> {
+    if power > 0. && power < 1. {
+        return Err(format!("Tweedie power should be 0 or >= 1, got {power}"));
+    }
+    let valid = if power == 0. {
+        y.is_finite()
+    } else if power < 2. && !strict {
+        y >= 0.
+    } else {
+        y > 0.
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "{y} is out of the domain of the Tweedie distribution with power {power}"
+        ))
+    }
+}
+
+/// Deviance of a single observation `y` from the mean `mu` of a Tweedie distribution with the given `power`.
+pub(crate) fn tweedie_unit_deviance(y: f64, mu: f64, power: f64) -> f64 {
+    if power == 0. {
+        (y - mu) * (y - mu)
+    } else if power == 1. {
+        let y_ln_y = if y > 0. { y * (y / mu).ln() } else { 0. };
+        2. * (y_ln_y - y + mu)
+    } else if power == 2. {
+        2. * ((mu / y).ln() + y / mu - 1.)
+    } else {
+        2. * (y.max(0.).powf(2. - power) / ((1. - power) * (2. - power))
+            - y * mu.powf(1. - power) / (1. - power)
+            + mu.powf(2. - power) / (2. - power))
+    }
+}
+
+impl<T: Number + FloatNumber> Metrics<T> for MeanTweedieDeviance<T> {
+    /// create a typed object to call MeanTweedieDeviance functions, equal to the mean squared error
+    fn new() -> Self {
+        Self {
+            power: 0.,
+            _phantom: PhantomData,
+        }
+    }
+    /// create a typed object to call MeanTweedieDeviance functions with the given power
+    fn new_with(power: f64) -> Self {
+        Self {
+            power,
+            _phantom: PhantomData,
+        }
+    }
+    /// Computes mean Tweedie deviance
+    /// * `y_true` - Ground truth (correct) target values.
+    /// * `y_pred` - Estimated target values.
+    fn get_score(&self, y_true: &dyn ArrayView1<T>, y_pred: &dyn ArrayView1<T>) -> f64 {
+        if y_true.shape() != y_pred.shape() {
+            panic!(
+                "The vector sizes don't match: {} != {}",
+                y_true.shape(),
+                y_pred.shape()
+            );
+        }
+
+        let n = y_true.shape();
+        let mut deviance = 0f64;
+        for i in 0..n {
+            let y = y_true.get(i).to_f64().unwrap();
+            let mu = y_pred.get(i).to_f64().unwrap();
+            if let Err(msg) = check_tweedie_domain(self.power, y, false)
+                .and_then(|_| check_tweedie_domain(self.power, mu, true))
+            {
+                panic!("{msg}");
+            }
+            deviance += tweedie_unit_deviance(y, mu, self.power);
+        }
+
+        deviance / n as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn mean_tweedie_deviance() {
+        let y_true: Vec<f64> = vec![2., 0., 1., 4.];
+        let y_pred: Vec<f64> = vec![0.5, 0.5, 2., 2.];
+
+        let squared: f64 = MeanTweedieDeviance::new().get_score(&y_true, &y_pred);
+        let poisson: f64 = MeanTweedieDeviance::new_with(1.).get_score(&y_true, &y_pred);
+        let compound: f64 = MeanTweedieDeviance::new_with(1.5).get_score(&y_true, &y_pred);
+        let perfect: f64 = MeanTweedieDeviance::new_with(1.5).get_score(&y_pred, &y_pred);
+
+        assert!((squared - 1.875).abs() < 1e-8);
+        assert!((poisson - 1.426_015_131_959_808_4).abs() < 1e-8);
+        assert!((compound - 1.778_174_593_052_023_2).abs() < 1e-8);
+        assert!(perfect.abs() < 1e-8);
+
+        let y_true: Vec<f64> = vec![2., 0.5, 1., 4.];
+        let gamma: f64 = MeanTweedieDeviance::new_with(2.).get_score(&y_true, &y_pred);
+        let inverse_gaussian: f64 = MeanTweedieDeviance::new_with(3.).get_score(&y_true, &y_pred);
+
+        assert!((gamma - 1.056_852_819_440_054_6).abs() < 1e-8);
+        assert!((inverse_gaussian - 1.25).abs() < 1e-8);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of the domain")]
+    fn mean_gamma_deviance_zero_target() {
+        let y_true: Vec<f64> = vec![2., 0., 1., 4.];
+        let y_pred: Vec<f64> = vec![0.5, 0.5, 2., 2.];
+        MeanTweedieDeviance::new_with(2.).get_score(&y_true, &y_pred);
+    }
+}
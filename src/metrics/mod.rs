@@ -65,8 +65,12 @@ pub mod distance;
 pub mod f1;
 /// Mean absolute error regression loss.
 pub mod mean_absolute_error;
+/// Mean pinball loss for quantile regression.
+pub mod mean_pinball_loss;
 /// Mean squared error regression loss.
 pub mod mean_squared_error;
+/// Mean Tweedie, Poisson and Gamma deviance regression loss.
+pub mod mean_tweedie_deviance;
 /// Computes the precision.
 pub mod precision;
 /// Coefficient of determination (R2).
@@ -161,6 +165,26 @@ impl<T: Number + FloatNumber> RegressionMetrics<T> {
     pub fn r2() -> r2::R2<T> {
         r2::R2::<T>::new()
     }
+
+    /// Mean Tweedie deviance with the given power, see [mean Tweedie deviance](mean_tweedie_deviance/index.html).
+    pub fn mean_tweedie_deviance(power: f64) -> mean_tweedie_deviance::MeanTweedieDeviance<T> {
+        mean_tweedie_deviance::MeanTweedieDeviance::new_with(power)
+    }
+
+    /// Mean Poisson deviance, see [mean Tweedie deviance](mean_tweedie_deviance/index.html).
+    pub fn mean_poisson_deviance() -> mean_tweedie_deviance::MeanTweedieDeviance<T> {
+        mean_tweedie_deviance::MeanTweedieDeviance::new_with(1.)
+    }
+
+    /// Mean Gamma deviance, see [mean Tweedie deviance](mean_tweedie_deviance/index.html).
+    pub fn mean_gamma_deviance() -> mean_tweedie_deviance::MeanTweedieDeviance<T> {
+        mean_tweedie_deviance::MeanTweedieDeviance::new_with(2.)
+    }
+
+    /// Mean pinball loss of the given quantile, see [mean pinball loss](mean_pinball_loss/index.html).
+    pub fn mean_pinball_loss(quantile: f64) -> mean_pinball_loss::MeanPinballLoss<T> {
+        mean_pinball_loss::MeanPinballLoss::new_with(quantile)
+    }
 }
 
 impl<T: Number + Ord> ClusterMetrics<T> {
@@ -253,6 +277,50 @@ pub fn r2<T: Number + FloatNumber, V: ArrayView1<T>>(y_true: &V, y_pred: &V) ->
     RegressionMetrics::<T>::r2().get_score(y_true, y_pred)
 }
 
+/// Computes mean Tweedie deviance, see [mean Tweedie deviance](mean_tweedie_deviance/index.html).
+/// * `y_true` - Ground truth (correct) target values.
+/// * `y_pred` - Estimated target values.
+/// * `power` - power of the variance function, 0 or >= 1.
+pub fn mean_tweedie_deviance<T: Number + FloatNumber, V: ArrayView1<T>>(
+    y_true: &V,
+    y_pred: &V,
+    power: f64,
+) -> f64 {
+    RegressionMetrics::<T>::mean_tweedie_deviance(power).get_score(y_true, y_pred)
+}
+
+/// Computes mean Poisson deviance, see [mean Tweedie deviance](mean_tweedie_deviance/index.html).
+/// * `y_true` - Ground truth (correct) non-negative target values.
+/// * `y_pred` - Estimated positive target values.
+pub fn mean_poisson_deviance<T: Number + FloatNumber, V: ArrayView1<T>>(
+    y_true: &V,
+    y_pred: &V,
+) -> f64 {
+    RegressionMetrics::<T>::mean_poisson_deviance().get_score(y_true, y_pred)
+}
+
+/// Computes mean Gamma deviance, see [mean Tweedie deviance](mean_tweedie_deviance/index.html).
+/// * `y_true` - Ground truth (correct) positive target values.
+/// * `y_pred` - Estimated positive target values.
+pub fn mean_gamma_deviance<T: Number + FloatNumber, V: ArrayView1<T>>(
+    y_true: &V,
+    y_pred: &V,
+) -> f64 {
+    RegressionMetrics::<T>::mean_gamma_deviance().get_score(y_true, y_pred)
+}
+
+/// Computes mean pinball loss, see [mean pinball loss](mean_pinball_loss/index.html).
+/// * `y_true` - Ground truth (correct) target values.
+/// * `y_pred` - Estimated quantiles of the target values.
+/// * `quantile` - quantile the predictions estimate, between 0 and 1.
+pub fn mean_pinball_loss<T: Number + FloatNumber, V: ArrayView1<T>>(
+    y_true: &V,
+    y_pred: &V,
+    quantile: f64,
+) -> f64 {
+    RegressionMetrics::<T>::mean_pinball_loss(quantile).get_score(y_true, y_pred)
+}
+
 /// Homogeneity metric of a cluster labeling given a ground truth (range is between 0.0 and 1.0).
 /// A cluster result satisfies homogeneity if all of its clusters contain only data points which are members of a single class.
 /// * `labels_true` - ground truth class labels to be used as a reference.
@@ -3,12 +3,17 @@
 //! Defines k-fold cross validator.
 use std::fmt::{Debug, Display};
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 use crate::linalg::basic::arrays::Array2;
 use crate::model_selection::BaseKFold;
 use crate::rand_custom::get_rng_impl;
 use rand::seq::SliceRandom;
 
 /// K-Folds cross-validator
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
 pub struct KFold {
     /// Number of folds. Must be at least 2.
     pub n_splits: usize, // cannot exceed std::usize::MAX
@@ -8,3 +8,6 @@ pub mod realnum;
 
 /// implementation for `FloatNumber`
 pub mod floatnum;
+
+/// special functions and distributions used for statistical inference
+pub(crate) mod special;
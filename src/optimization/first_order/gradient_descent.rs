@@ -89,6 +89,7 @@ impl<T: FloatNumber> FirstOrderOptimizer<T> for GradientDescent {
             x,
             f_x,
             iterations: iter,
+            converged: gnorm <= gtol,
         }
     }
 }
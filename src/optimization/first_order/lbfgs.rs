@@ -268,6 +268,7 @@ impl<T: FloatNumber + RealNumber> FirstOrderOptimizer<T> for LBFGS {
             x: state.x,
             f_x: state.x_f,
             iterations: state.iteration,
+            converged,
         }
     }
 }
@@ -6,6 +6,9 @@ pub mod lbfgs;
 use std::clone::Clone;
 use std::fmt::Debug;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 use crate::linalg::basic::arrays::Array1;
 use crate::numbers::floatnum::FloatNumber;
 use crate::optimization::line_search::LineSearchMethod;
@@ -23,13 +26,16 @@ pub trait FirstOrderOptimizer<T: FloatNumber> {
     ) -> OptimizerResult<T, X>;
 }
 
-///
+/// Outcome of an optimization run, including the convergence report.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
 pub struct OptimizerResult<T: FloatNumber, X: Array1<T>> {
-    ///
+    /// Solution found by the optimizer.
     pub x: X,
-    ///
+    /// Value of the objective function at `x`.
     pub f_x: T,
-    ///
+    /// Number of iterations performed.
     pub iterations: usize,
+    /// Whether the stopping criterion was met before the iteration limit.
+    pub converged: bool,
 }
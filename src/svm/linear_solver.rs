@@ -0,0 +1,515 @@
+//! Coordinate descent solvers of linear SVM problems, ports of the solvers of liblinear.
+//!
+//! Observations are stored as sparse rows, so that the cost of an update is proportional to the number of non-zero features.
+//! The intercept is fitted as the weight of an extra feature with a constant value, and is regularized like other weights.
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::linalg::basic::arrays::Array2;
+use crate::numbers::basenum::Number;
+use crate::numbers::realnum::RealNumber;
+
+/// Projected gradients smaller than this are treated as zero.
+const EPSILON: f64 = 1e-12;
+/// Maximum number of halvings of the step of the line search of the L1 solver.
+const MAX_LINE_SEARCH: usize = 20;
+
+/// Training data as sparse rows of `(feature, value)` pairs.
+pub(crate) struct Problem {
+    pub(crate) rows: Vec<Vec<(usize, f64)>>,
+    /// number of features, including the constant feature of the intercept
+    pub(crate) n_features: usize,
+}
+
+impl Problem {
+    /// Sparse rows of `x`, with a constant feature `intercept_scaling` appended when it is set.
+    pub(crate) fn new<TX: Number + RealNumber, X: Array2<TX>>(
+        x: &X,
+        intercept_scaling: Option<f64>,
+    ) -> Self {
+        let (n, p) = x.shape();
+        let rows = (0..n)
+            .map(|i| {
+                let mut row: Vec<(usize, f64)> = (0..p)
+                    .filter_map(|j| {
+                        let v = x.get((i, j)).to_f64().unwrap();
+                        (v != 0.).then_some((j, v))
+                    })
+                    .collect();
+                if let Some(scaling) = intercept_scaling {
+                    row.push((p, scaling));
+                }
+                row
+            })
+            .collect();
+        Problem {
+            rows,
+            n_features: p + usize::from(intercept_scaling.is_some()),
+        }
+    }
+
+    fn dot(&self, w: &[f64], i: usize) -> f64 {
+        self.rows[i].iter().map(|&(j, v)| w[j] * v).sum()
+    }
+
+    fn add(&self, w: &mut [f64], i: usize, d: f64) {
+        for &(j, v) in self.rows[i].iter() {
+            w[j] += d * v;
+        }
+    }
+
+    fn squared_norm(&self, i: usize) -> f64 {
+        self.rows[i].iter().map(|&(_, v)| v * v).sum()
+    }
+}
+
+/// Minimizes \\(\frac{1}{2} \lVert w \rVert^2 + \sum_i C_i \xi_i\\) for the hinge loss \\(\xi_i = \max(0, 1 - y_i w^T x_i)\\),
+/// or its square when `squared`, by coordinate descent in the dual with shrinking. Labels `y` are 1 or -1.
+/// Returns the weights and the number of iterations.
+pub(crate) fn solve_svc_dual<R: Rng>(
+    problem: &Problem,
+    y: &[f64],
+    c: &[f64],
+    squared: bool,
+    tol: f64,
+    max_iter: usize,
+    rng: &mut R,
+) -> (Vec<f64>, usize) {
+    let mut w = vec![0f64; problem.n_features];
+    // observations with zero weight do not take part in the problem
+    let mut index: Vec<usize> = (0..y.len()).filter(|&i| c[i] > 0.).collect();
+    let l = index.len();
+
+    let (diag, upper_bound): (Vec<f64>, Vec<f64>) = c
+        .iter()
+        .map(|&c_i| {
+            if squared {
+                (0.5 / c_i, f64::INFINITY)
+            } else {
+                (0., c_i)
+            }
+        })
+        .unzip();
+    let qd: Vec<f64> = (0..y.len())
+        .map(|i| diag[i] + problem.squared_norm(i))
+        .collect();
+    let mut alpha = vec![0f64; y.len()];
+
+    let mut pg_max_old = f64::INFINITY;
+    let mut pg_min_old = f64::NEG_INFINITY;
+    let mut active_size = l;
+    let mut iter = 0;
+
+    while iter < max_iter {
+        let mut pg_max_new = f64::NEG_INFINITY;
+        let mut pg_min_new = f64::INFINITY;
+
+        index[..active_size].shuffle(rng);
+
+        let mut s = 0;
+        while s < active_size {
+            let i = index[s];
+            let g = y[i] * problem.dot(&w, i) - 1. + alpha[i] * diag[i];
+
+            let mut pg = 0.;
+            if alpha[i] == 0. {
+                if g > pg_max_old {
+                    active_size -= 1;
+                    index.swap(s, active_size);
+                    continue;
+                } else if g < 0. {
+                    pg = g;
+                }
+            } else if alpha[i] == upper_bound[i] {
+                if g < pg_min_old {
+                    active_size -= 1;
+                    index.swap(s, active_size);
+                    continue;
+                } else if g > 0. {
+                    pg = g;
+                }
+            } else {
+                pg = g;
+            }
+
+            pg_max_new = pg_max_new.max(pg);
+            pg_min_new = pg_min_new.min(pg);
+
+            if pg.abs() > EPSILON && qd[i] > 0. {
+                let alpha_old = alpha[i];
+                alpha[i] = (alpha[i] - g / qd[i]).max(0.).min(upper_bound[i]);
+                problem.add(&mut w, i, (alpha[i] - alpha_old) * y[i]);
+            }
+            s += 1;
+        }
+
+        iter += 1;
+
+        if pg_max_new - pg_min_new <= tol {
+            if active_size == l {
+                break;
+            }
+            active_size = l;
+            pg_max_old = f64::INFINITY;
+            pg_min_old = f64::NEG_INFINITY;
+            continue;
+        }
+        pg_max_old = if pg_max_new <= 0. {
+            f64::INFINITY
+        } else {
+            pg_max_new
+        };
+        pg_min_old = if pg_min_new >= 0. {
+            f64::NEG_INFINITY
+        } else {
+            pg_min_new
+        };
+    }
+
+    (w, iter)
+}
+
+/// Minimizes \\(\lVert w \rVert_1 + \sum_i C_i \max(0, 1 - y_i w^T x_i)^2\\) by coordinate descent with Newton directions
+/// and a line search in the primal, with shrinking. Labels `y` are 1 or -1.
+/// Returns the weights and the number of iterations.
+pub(crate) fn solve_svc_l1<R: Rng>(
+    problem: &Problem,
+    y: &[f64],
+    c: &[f64],
+    tol: f64,
+    max_iter: usize,
+    rng: &mut R,
+) -> (Vec<f64>, usize) {
+    let l = y.len();
+    let w_size = problem.n_features;
+    let sigma = 0.01;
+
+    // columns of the data multiplied by the labels
+    let mut columns: Vec<Vec<(usize, f64)>> = vec![Vec::new(); w_size];
+    for (i, row) in problem.rows.iter().enumerate() {
+        for &(j, v) in row.iter() {
+            columns[j].push((i, y[i] * v));
+        }
+    }
+    let xj_sq: Vec<f64> = columns
+        .iter()
+        .map(|column| column.iter().map(|&(i, v)| c[i] * v * v).sum())
+        .collect();
+
+    let mut w = vec![0f64; w_size];
+    // b_i = 1 - y_i w^T x_i
+    let mut b = vec![1f64; l];
+    let mut index: Vec<usize> = (0..w_size).collect();
+
+    let n_positive = y.iter().filter(|&&y_i| y_i > 0.).count();
+    let tol = tol * n_positive.min(l - n_positive).max(1) as f64 / l as f64;
+
+    let mut g_max_old = f64::INFINITY;
+    let mut g_norm1_init = -1.;
+    let mut active_size = w_size;
+    let mut iter = 0;
+
+    while iter < max_iter {
+        let mut g_max_new = 0f64;
+        let mut g_norm1_new = 0.;
+
+        index[..active_size].shuffle(rng);
+
+        let mut s = 0;
+        while s < active_size {
+            let j = index[s];
+            let mut g_loss = 0.;
+            let mut h = 0.;
+            for &(i, v) in columns[j].iter() {
+                if b[i] > 0. {
+                    let tmp = c[i] * v;
+                    g_loss -= tmp * b[i];
+                    h += tmp * v;
+                }
+            }
+            g_loss *= 2.;
+            let g = g_loss;
+            let h = (2. * h).max(EPSILON);
+
+            let gp = g + 1.;
+            let gn = g - 1.;
+            let violation = if w[j] == 0. {
+                if gp < 0. {
+                    -gp
+                } else if gn > 0. {
+                    gn
+                } else if gp > g_max_old / l as f64 && gn < -g_max_old / l as f64 {
+                    active_size -= 1;
+                    index.swap(s, active_size);
+                    continue;
+                } else {
+                    0.
+                }
+            } else if w[j] > 0. {
+                gp.abs()
+            } else {
+                gn.abs()
+            };
+
+            g_max_new = g_max_new.max(violation);
+            g_norm1_new += violation;
+            s += 1;
+
+            // Newton direction
+            let mut d = if gp < h * w[j] {
+                -gp / h
+            } else if gn > h * w[j] {
+                -gn / h
+            } else {
+                -w[j]
+            };
+
+            if d.abs() < EPSILON {
+                continue;
+            }
+
+            let mut delta = (w[j] + d).abs() - w[j].abs() + g * d;
+            let mut d_old = 0.;
+            let mut loss_old = 0.;
+            let mut n_line_search = 0;
+            while n_line_search < MAX_LINE_SEARCH {
+                let d_diff = d_old - d;
+                let mut cond = (w[j] + d).abs() - w[j].abs() - sigma * delta;
+
+                let approximate_cond = xj_sq[j] * d * d + g_loss * d + cond;
+                if approximate_cond <= 0. {
+                    for &(i, v) in columns[j].iter() {
+                        b[i] += d_diff * v;
+                    }
+                    break;
+                }
+
+                let mut loss_new = 0.;
+                for &(i, v) in columns[j].iter() {
+                    if n_line_search == 0 && b[i] > 0. {
+                        loss_old += c[i] * b[i] * b[i];
+                    }
+                    b[i] += d_diff * v;
+                    if b[i] > 0. {
+                        loss_new += c[i] * b[i] * b[i];
+                    }
+                }
+
+                cond += loss_new - loss_old;
+                if cond <= 0. {
+                    break;
+                }
+                d_old = d;
+                d *= 0.5;
+                delta *= 0.5;
+                n_line_search += 1;
+            }
+
+            w[j] += d;
+
+            // recompute b if the line search failed
+            if n_line_search >= MAX_LINE_SEARCH {
+                b.iter_mut().for_each(|b_i| *b_i = 1.);
+                for (k, column) in columns.iter().enumerate() {
+                    if w[k] != 0. {
+                        for &(i, v) in column.iter() {
+                            b[i] -= w[k] * v;
+                        }
+                    }
+                }
+            }
+        }
+
+        if iter == 0 {
+            g_norm1_init = g_norm1_new;
+        }
+        iter += 1;
+
+        if g_norm1_new <= tol * g_norm1_init {
+            if active_size == w_size {
+                break;
+            }
+            active_size = w_size;
+            g_max_old = f64::INFINITY;
+            continue;
+        }
+        g_max_old = g_max_new;
+    }
+
+    (w, iter)
+}
+
+/// Minimizes \\(\frac{1}{2} \lVert w \rVert^2 + C \sum_i \xi_i\\) for the \\(\epsilon\\)-insensitive loss
+/// \\(\xi_i = \max(0, \lvert y_i - w^T x_i \rvert - \epsilon)\\), or its square when `squared`, by coordinate descent
+/// in the dual with shrinking. Returns the weights and the number of iterations.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn solve_svr_dual<R: Rng>(
+    problem: &Problem,
+    y: &[f64],
+    c: f64,
+    epsilon: f64,
+    squared: bool,
+    tol: f64,
+    max_iter: usize,
+    rng: &mut R,
+) -> (Vec<f64>, usize) {
+    let l = y.len();
+    let (lambda, upper_bound) = if squared {
+        (0.5 / c, f64::INFINITY)
+    } else {
+        (0., c)
+    };
+    let qd: Vec<f64> = (0..l).map(|i| problem.squared_norm(i)).collect();
+
+    let mut w = vec![0f64; problem.n_features];
+    let mut beta = vec![0f64; l];
+    let mut index: Vec<usize> = (0..l).collect();
+
+    let mut g_max_old = f64::INFINITY;
+    let mut g_norm1_init = -1.;
+    let mut active_size = l;
+    let mut iter = 0;
+
+    while iter < max_iter {
+        let mut g_max_new = 0f64;
+        let mut g_norm1_new = 0.;
+
+        index[..active_size].shuffle(rng);
+
+        let mut s = 0;
+        while s < active_size {
+            let i = index[s];
+            let g = -y[i] + lambda * beta[i] + problem.dot(&w, i);
+            let h = qd[i] + lambda;
+
+            let gp = g + epsilon;
+            let gn = g - epsilon;
+            let violation = if beta[i] == 0. {
+                if gp < 0. {
+                    -gp
+                } else if gn > 0. {
+                    gn
+                } else if gp > g_max_old && gn < -g_max_old {
+                    active_size -= 1;
+                    index.swap(s, active_size);
+                    continue;
+                } else {
+                    0.
+                }
+            } else if beta[i] >= upper_bound {
+                if gp > 0. {
+                    gp
+                } else if gp < -g_max_old {
+                    active_size -= 1;
+                    index.swap(s, active_size);
+                    continue;
+                } else {
+                    0.
+                }
+            } else if beta[i] <= -upper_bound {
+                if gn < 0. {
+                    -gn
+                } else if gn > g_max_old {
+                    active_size -= 1;
+                    index.swap(s, active_size);
+                    continue;
+                } else {
+                    0.
+                }
+            } else if beta[i] > 0. {
+                gp.abs()
+            } else {
+                gn.abs()
+            };
+
+            g_max_new = g_max_new.max(violation);
+            g_norm1_new += violation;
+            s += 1;
+
+            if h <= 0. {
+                continue;
+            }
+
+            // Newton direction
+            let d = if gp < h * beta[i] {
+                -gp / h
+            } else if gn > h * beta[i] {
+                -gn / h
+            } else {
+                -beta[i]
+            };
+
+            if d.abs() < EPSILON {
+                continue;
+            }
+
+            let beta_old = beta[i];
+            beta[i] = (beta[i] + d).max(-upper_bound).min(upper_bound);
+            let d = beta[i] - beta_old;
+            if d != 0. {
+                problem.add(&mut w, i, d);
+            }
+        }
+
+        if iter == 0 {
+            g_norm1_init = g_norm1_new;
+        }
+        iter += 1;
+
+        if g_norm1_new <= tol * g_norm1_init {
+            if active_size == l {
+                break;
+            }
+            active_size = l;
+            g_max_old = f64::INFINITY;
+            continue;
+        }
+        g_max_old = g_max_new;
+    }
+
+    (w, iter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::basic::matrix::DenseMatrix;
+    use crate::rand_custom::get_rng_impl;
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn problem_is_sparse() {
+        let x = DenseMatrix::from_2d_array(&[&[1., 0., 2.], &[0., 0., 3.]]).unwrap();
+        let problem = Problem::new(&x, Some(10.));
+
+        assert_eq!(problem.n_features, 4);
+        assert_eq!(problem.rows[0], vec![(0, 1.), (2, 2.), (3, 10.)]);
+        assert_eq!(problem.rows[1], vec![(2, 3.), (3, 10.)]);
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn solve_svc_dual_two_points() {
+        // the maximal margin separator of -1 and 1 is w = 1
+        let x = DenseMatrix::from_2d_array(&[&[-1.], &[1.]]).unwrap();
+        let problem = Problem::new(&x, None);
+        let (w, _) = solve_svc_dual(
+            &problem,
+            &[-1., 1.],
+            &[10., 10.],
+            false,
+            1e-6,
+            1000,
+            &mut get_rng_impl(Some(0)),
+        );
+
+        assert!((w[0] - 1.).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,614 @@
+//! # Linear Support Vector Classifier
+//!
+//! Linear Support Vector Classifier (LinearSVC) fits the same decision function as [SVC](../svc/index.html) with a linear kernel,
+//! \\(f(x) = \langle w, x \rangle + b\\), but it stores the weights \\(w\\) instead of the support vectors and is trained by
+//! coordinate descent, as in liblinear. Both training and prediction scale linearly with the number of observations,
+//! so LinearSVC can be trained on hundreds of thousands of observations, for example on text features.
+//!
+//! The optimization problem is
+//!
+//! \\[\underset{w}{minimize} \space \space R(w) + C\sum_{i=1}^m \xi(w; x_i, y_i) \\]
+//!
+//! where the loss \\(\xi\\) is either the hinge loss \\(\max(0, 1 - y_i \langle w, x_i \rangle)\\) or its square,
+//! and the penalty \\(R\\) is either \\(\frac{1}{2}\lVert w \rVert_2^2\\) or \\(\lVert w \rVert_1\\):
+//!
+//! * With the L2 penalty, the problem is solved by dual coordinate descent with shrinking.
+//! * With the L1 penalty and the squared hinge loss, the problem is solved in the primal by coordinate descent with Newton directions.
+//!   The L1 penalty gives sparse weights. The L1 penalty is not supported with the hinge loss.
+//!
+//! When `fit_intercept` is set, the intercept is the weight of an extra feature with the constant value `intercept_scaling`.
+//! This weight is regularized like other weights, increase `intercept_scaling` to reduce the effect of the penalty on the intercept.
+//!
+//! Problems with more than two classes are reduced to one binary problem for every class against all other classes,
+//! and a new point is assigned to the class with the largest decision value.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::basic::matrix::DenseMatrix;
+//! use smartcore::svm::linear_svc::{LinearSVC, LinearSVCParameters};
+//!
+//! // Iris dataset
+//! let x = DenseMatrix::from_2d_array(&[
+//!            &[5.1, 3.5, 1.4, 0.2],
+//!            &[4.9, 3.0, 1.4, 0.2],
+//!            &[4.7, 3.2, 1.3, 0.2],
+//!            &[4.6, 3.1, 1.5, 0.2],
+//!            &[5.0, 3.6, 1.4, 0.2],
+//!            &[5.4, 3.9, 1.7, 0.4],
+//!            &[4.6, 3.4, 1.4, 0.3],
+//!            &[5.0, 3.4, 1.5, 0.2],
+//!            &[4.4, 2.9, 1.4, 0.2],
+//!            &[4.9, 3.1, 1.5, 0.1],
+//!            &[7.0, 3.2, 4.7, 1.4],
+//!            &[6.4, 3.2, 4.5, 1.5],
+//!            &[6.9, 3.1, 4.9, 1.5],
+//!            &[5.5, 2.3, 4.0, 1.3],
+//!            &[6.5, 2.8, 4.6, 1.5],
+//!            &[5.7, 2.8, 4.5, 1.3],
+//!            &[6.3, 3.3, 4.7, 1.6],
+//!            &[4.9, 2.4, 3.3, 1.0],
+//!            &[6.6, 2.9, 4.6, 1.3],
+//!            &[5.2, 2.7, 3.9, 1.4],
+//!         ]).unwrap();
+//! let y: Vec<i32> = vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1];
+//!
+//! let params = LinearSVCParameters::default().with_c(10.0).with_seed(Some(1));
+//! let svc = LinearSVC::fit(&x, &y, params).unwrap();
+//!
+//! let y_hat = svc.predict(&x).unwrap();
+//! ```
+//!
+//! ## References:
+//!
+//! * ["LIBLINEAR: A Library for Large Linear Classification", Fan R.E., Chang K.W., Hsieh C.J., Wang X.R., Lin C.J., 2008](https://www.jmlr.org/papers/volume9/fan08a/fan08a.pdf)
+//! * ["A Dual Coordinate Descent Method for Large-scale Linear SVM", Hsieh C.J., Chang K.W., Lin C.J., Keerthi S.S., Sundararajan S., 2008](https://www.csie.ntu.edu.tw/~cjlin/papers/cddual.pdf)
+//! * ["A Comparison of Optimization Methods and Software for Large-scale L1-regularized Linear Classification", Yuan G.X., Chang K.W., Hsieh C.J., Lin C.J., 2010](https://www.jmlr.org/papers/volume11/yuan10c/yuan10c.pdf)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::algorithm::sample_weight::{check_sample_weight, resolve_class_weight, ClassWeight};
+use crate::api::{Predictor, SupervisedEstimator};
+use crate::error::{Failed, FailedError};
+use crate::linalg::basic::arrays::{Array1, Array2};
+use crate::numbers::basenum::Number;
+use crate::numbers::realnum::RealNumber;
+use crate::rand_custom::get_rng_impl;
+use crate::svm::linear_solver::{solve_svc_dual, solve_svc_l1, Problem};
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+/// Loss of observations that violate the margin.
+pub enum LinearSVCLoss {
+    /// \\(\max(0, 1 - y_i f(x_i))\\), as in [SVC](../svc/index.html)
+    Hinge,
+    /// \\(\max(0, 1 - y_i f(x_i))^2\\), differentiable
+    #[default]
+    SquaredHinge,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+/// Penalty applied to the weights.
+pub enum LinearSVCPenalty {
+    /// \\(\lVert w \rVert_1\\), gives sparse weights, only with the squared hinge loss
+    L1,
+    /// \\(\frac{1}{2}\lVert w \rVert_2^2\\)
+    #[default]
+    L2,
+}
+
+/// LinearSVC Parameters
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct LinearSVCParameters<T: Number + RealNumber> {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Regularization parameter.
+    pub c: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Loss of observations that violate the margin.
+    pub loss: LinearSVCLoss,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Penalty applied to the weights.
+    pub penalty: LinearSVCPenalty,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Weights associated with classes, all classes have weight one when `None`.
+    pub class_weight: Option<ClassWeight>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Tolerance for stopping criterion.
+    pub tol: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Maximum number of passes over the data.
+    pub max_iter: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Whether to fit an intercept.
+    pub fit_intercept: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Value of the constant feature whose weight is the intercept.
+    pub intercept_scaling: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Seed of the pseudo-random number generator that shuffles the coordinates.
+    pub seed: Option<u64>,
+}
+
+impl<T: Number + RealNumber> LinearSVCParameters<T> {
+    /// Regularization parameter.
+    pub fn with_c(mut self, c: T) -> Self {
+        self.c = c;
+        self
+    }
+    /// Loss of observations that violate the margin.
+    pub fn with_loss(mut self, loss: LinearSVCLoss) -> Self {
+        self.loss = loss;
+        self
+    }
+    /// Penalty applied to the weights.
+    pub fn with_penalty(mut self, penalty: LinearSVCPenalty) -> Self {
+        self.penalty = penalty;
+        self
+    }
+    /// Weights associated with classes.
+    pub fn with_class_weight(mut self, class_weight: ClassWeight) -> Self {
+        self.class_weight = Some(class_weight);
+        self
+    }
+    /// Tolerance for stopping criterion.
+    pub fn with_tol(mut self, tol: T) -> Self {
+        self.tol = tol;
+        self
+    }
+    /// Maximum number of passes over the data.
+    pub fn with_max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+    /// Whether to fit an intercept.
+    pub fn with_fit_intercept(mut self, fit_intercept: bool) -> Self {
+        self.fit_intercept = fit_intercept;
+        self
+    }
+    /// Value of the constant feature whose weight is the intercept.
+    pub fn with_intercept_scaling(mut self, intercept_scaling: T) -> Self {
+        self.intercept_scaling = intercept_scaling;
+        self
+    }
+    /// Seed of the pseudo-random number generator.
+    pub fn with_seed(mut self, seed: Option<u64>) -> Self {
+        self.seed = seed;
+        self
+    }
+}
+
+impl<T: Number + RealNumber> Default for LinearSVCParameters<T> {
+    fn default() -> Self {
+        LinearSVCParameters {
+            c: T::one(),
+            loss: LinearSVCLoss::default(),
+            penalty: LinearSVCPenalty::default(),
+            class_weight: Option::None,
+            tol: T::from_f64(1e-4).unwrap(),
+            max_iter: 1000,
+            fit_intercept: true,
+            intercept_scaling: T::one(),
+            seed: Option::None,
+        }
+    }
+}
+
+/// Linear Support Vector Classifier
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+pub struct LinearSVC<TX: Number + RealNumber, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>> {
+    coefficients: Option<X>,
+    intercept: Option<X>,
+    classes: Option<Vec<TY>>,
+    n_iter: Vec<usize>,
+    _phantom_tx: PhantomData<TX>,
+    _phantom_y: PhantomData<Y>,
+}
+
+impl<TX: Number + RealNumber, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>>
+    SupervisedEstimator<X, Y, LinearSVCParameters<TX>> for LinearSVC<TX, TY, X, Y>
+{
+    fn new() -> Self {
+        Self {
+            coefficients: Option::None,
+            intercept: Option::None,
+            classes: Option::None,
+            n_iter: Vec::new(),
+            _phantom_tx: PhantomData,
+            _phantom_y: PhantomData,
+        }
+    }
+
+    fn fit(x: &X, y: &Y, parameters: LinearSVCParameters<TX>) -> Result<Self, Failed> {
+        LinearSVC::fit(x, y, parameters)
+    }
+}
+
+impl<TX: Number + RealNumber, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>> Predictor<X, Y>
+    for LinearSVC<TX, TY, X, Y>
+{
+    fn predict(&self, x: &X) -> Result<Y, Failed> {
+        self.predict(x)
+    }
+}
+
+impl<TX: Number + RealNumber, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>>
+    LinearSVC<TX, TY, X, Y>
+{
+    /// Fits LinearSVC to your data.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - class labels
+    /// * `parameters` - other parameters, use `Default::default()` to set parameters to default values.
+    pub fn fit(
+        x: &X,
+        y: &Y,
+        parameters: LinearSVCParameters<TX>,
+    ) -> Result<LinearSVC<TX, TY, X, Y>, Failed> {
+        Self::fit_with_sample_weight(x, y, &vec![TX::one(); x.shape().0], parameters)
+    }
+
+    /// Fits LinearSVC to your data, the regularization parameter of every observation is multiplied by its weight
+    /// and by the weight of its class.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - class labels
+    /// * `sample_weight` - non-negative weight of every observation
+    /// * `parameters` - other parameters, use `Default::default()` to set parameters to default values.
+    pub fn fit_with_sample_weight(
+        x: &X,
+        y: &Y,
+        sample_weight: &[TX],
+        parameters: LinearSVCParameters<TX>,
+    ) -> Result<LinearSVC<TX, TY, X, Y>, Failed> {
+        let (n, p) = x.shape();
+
+        if n != y.shape() {
+            return Err(Failed::fit(
+                "Number of rows of X doesn\'t match number of rows of Y",
+            ));
+        }
+
+        if parameters.c <= TX::zero() {
+            return Err(Failed::because(
+                FailedError::ParametersError,
+                "C should be > 0",
+            ));
+        }
+
+        if parameters.penalty == LinearSVCPenalty::L1 && parameters.loss == LinearSVCLoss::Hinge {
+            return Err(Failed::because(
+                FailedError::ParametersError,
+                "L1 penalty is not supported with the hinge loss, use the squared hinge loss",
+            ));
+        }
+
+        check_sample_weight(sample_weight, n)?;
+
+        let classes = y.unique();
+        let k = classes.len();
+        if k < 2 {
+            return Err(Failed::fit(&format!("Incorrect number of classes: {k}")));
+        }
+
+        let y_idx: Vec<usize> = y
+            .iterator(0)
+            .map(|y_i| classes.binary_search(y_i).unwrap())
+            .collect();
+
+        let class_weight: Vec<f64> = match &parameters.class_weight {
+            None => vec![1.; k],
+            Some(class_weight) => resolve_class_weight(class_weight, k, &y_idx)?,
+        };
+
+        let c = parameters.c.to_f64().unwrap();
+        let sample_c: Vec<f64> = sample_weight
+            .iter()
+            .zip(y_idx.iter())
+            .map(|(w_i, &y_idx_i)| c * w_i.to_f64().unwrap() * class_weight[y_idx_i])
+            .collect();
+
+        let intercept_scaling = parameters
+            .fit_intercept
+            .then(|| parameters.intercept_scaling.to_f64().unwrap());
+        let problem = Problem::new(x, intercept_scaling);
+        let tol = parameters.tol.to_f64().unwrap();
+        let mut rng = get_rng_impl(parameters.seed);
+
+        // with two classes the second class is the positive one
+        let positive_classes: Vec<usize> = if k == 2 { vec![1] } else { (0..k).collect() };
+
+        let mut coefficients = X::zeros(positive_classes.len(), p);
+        let mut intercept = X::zeros(positive_classes.len(), 1);
+        let mut n_iter = Vec::with_capacity(positive_classes.len());
+        for (r, &positive) in positive_classes.iter().enumerate() {
+            let y_binary: Vec<f64> = y_idx
+                .iter()
+                .map(|&y_idx_i| if y_idx_i == positive { 1. } else { -1. })
+                .collect();
+
+            let (w, iter) = match parameters.penalty {
+                LinearSVCPenalty::L2 => solve_svc_dual(
+                    &problem,
+                    &y_binary,
+                    &sample_c,
+                    parameters.loss == LinearSVCLoss::SquaredHinge,
+                    tol,
+                    parameters.max_iter,
+                    &mut rng,
+                ),
+                LinearSVCPenalty::L1 => solve_svc_l1(
+                    &problem,
+                    &y_binary,
+                    &sample_c,
+                    tol,
+                    parameters.max_iter,
+                    &mut rng,
+                ),
+            };
+
+            for (j, w_j) in w.iter().take(p).enumerate() {
+                coefficients.set((r, j), TX::from_f64(*w_j).unwrap());
+            }
+            if let Some(scaling) = intercept_scaling {
+                intercept.set((r, 0), TX::from_f64(w[p] * scaling).unwrap());
+            }
+            n_iter.push(iter);
+        }
+
+        Ok(LinearSVC {
+            coefficients: Some(coefficients),
+            intercept: Some(intercept),
+            classes: Some(classes),
+            n_iter,
+            _phantom_tx: PhantomData,
+            _phantom_y: PhantomData,
+        })
+    }
+
+    /// Predict class labels for samples in `x`.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict(&self, x: &X) -> Result<Y, Failed> {
+        let n = x.shape().0;
+        let mut result = Y::zeros(n);
+        let margins = self.decision_function(x)?;
+        if self.classes().len() == 2 {
+            for (i, margin_i) in margins.iterator(0).enumerate().take(n) {
+                result.set(i, self.classes()[usize::from(*margin_i > TX::zero())]);
+            }
+        } else {
+            let class_idxs = margins.argmax(1);
+            for (i, class_i) in class_idxs.iter().enumerate().take(n) {
+                result.set(i, self.classes()[*class_i]);
+            }
+        }
+        Ok(result)
+    }
+
+    /// Evaluates the decision function \\(\langle w, x \rangle + b\\) for samples in `x`.
+    /// Returns a _Kx1_ matrix with the decision values of the second class for binary problems
+    /// and a _KxC_ matrix with one column per class, ordered like [`classes`](#method.classes), otherwise.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn decision_function(&self, x: &X) -> Result<X, Failed> {
+        let (n, num_attributes) = x.shape();
+        let (num_predictors, p) = self.coefficients().shape();
+        if num_attributes != p {
+            return Err(Failed::predict(&format!(
+                "expected {p} features, got {num_attributes}"
+            )));
+        }
+
+        let mut margins = x.matmul(&self.coefficients().transpose());
+        for r in 0..n {
+            for c in 0..num_predictors {
+                margins.set((r, c), *margins.get((r, c)) + *self.intercept().get((c, 0)));
+            }
+        }
+        Ok(margins)
+    }
+
+    /// Get the weights, a _1xM_ matrix for binary problems and a _CxM_ matrix with one row per class otherwise.
+    pub fn coefficients(&self) -> &X {
+        self.coefficients.as_ref().unwrap()
+    }
+
+    /// Get the intercepts, a _1x1_ matrix for binary problems and a _Cx1_ matrix with one row per class otherwise.
+    pub fn intercept(&self) -> &X {
+        self.intercept.as_ref().unwrap()
+    }
+
+    /// Get classes, ordered. With two classes the second class is the positive one.
+    pub fn classes(&self) -> &Vec<TY> {
+        self.classes.as_ref().unwrap()
+    }
+
+    /// Number of passes over the data of the solver of every binary problem.
+    pub fn n_iter(&self) -> &Vec<usize> {
+        &self.n_iter
+    }
+}
+
+impl<TX: Number + RealNumber, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>> PartialEq
+    for LinearSVC<TX, TY, X, Y>
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.classes == other.classes
+            && self.coefficients().shape() == other.coefficients().shape()
+            && self
+                .coefficients()
+                .iterator(0)
+                .zip(other.coefficients().iterator(0))
+                .chain(
+                    self.intercept()
+                        .iterator(0)
+                        .zip(other.intercept().iterator(0)),
+                )
+                .all(|(a, b)| (*a - *b).abs() <= TX::epsilon())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::basic::arrays::Array;
+    use crate::linalg::basic::matrix::DenseMatrix;
+    use crate::metrics::accuracy;
+    use crate::test_datasets::iris;
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn linear_svc_fit_predict() {
+        let (x, y) = iris();
+
+        for (loss, penalty) in [
+            (LinearSVCLoss::Hinge, LinearSVCPenalty::L2),
+            (LinearSVCLoss::SquaredHinge, LinearSVCPenalty::L2),
+            (LinearSVCLoss::SquaredHinge, LinearSVCPenalty::L1),
+        ] {
+            let params = LinearSVCParameters::default()
+                .with_c(10.)
+                .with_loss(loss.clone())
+                .with_penalty(penalty.clone())
+                .with_max_iter(10000)
+                .with_seed(Some(1));
+            let svc = LinearSVC::fit(&x, &y, params).unwrap();
+
+            assert_eq!(svc.coefficients().shape(), (3, 4));
+            assert_eq!(svc.intercept().shape(), (3, 1));
+            let y_hat = svc.predict(&x).unwrap();
+            let acc = accuracy(&y, &y_hat);
+            assert!(acc >= 0.9, "{loss:?} {penalty:?} accuracy {acc}");
+        }
+
+        assert!(LinearSVC::<f64, u32, DenseMatrix<f64>, Vec<u32>>::fit(
+            &x,
+            &y,
+            LinearSVCParameters::default()
+                .with_loss(LinearSVCLoss::Hinge)
+                .with_penalty(LinearSVCPenalty::L1)
+        )
+        .is_err());
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn linear_svc_optimality() {
+        // no coordinate step decreases the primal objective at the solution
+        let (x, y) = iris();
+        let y: Vec<u32> = y.iter().map(|&y_i| u32::from(y_i == 2)).collect();
+        let c = 0.5;
+
+        for (loss, penalty) in [
+            (LinearSVCLoss::Hinge, LinearSVCPenalty::L2),
+            (LinearSVCLoss::SquaredHinge, LinearSVCPenalty::L2),
+            (LinearSVCLoss::SquaredHinge, LinearSVCPenalty::L1),
+        ] {
+            let params = LinearSVCParameters::default()
+                .with_c(c)
+                .with_loss(loss.clone())
+                .with_penalty(penalty.clone())
+                .with_tol(1e-8)
+                .with_max_iter(100000)
+                .with_seed(Some(3));
+            let svc = LinearSVC::fit(&x, &y, params).unwrap();
+
+            // weights followed by the intercept, which is regularized as the weight of a constant feature
+            let mut w: Vec<f64> = svc.coefficients().iterator(0).copied().collect();
+            w.push(*svc.intercept().get((0, 0)));
+            let objective = |w: &[f64]| {
+                let loss: f64 = (0..18)
+                    .map(|i| {
+                        let y_i = if y[i] == 1 { 1. } else { -1. };
+                        let f = (0..4).map(|j| w[j] * x.get((i, j))).sum::<f64>() + w[4];
+                        let xi = (1. - y_i * f).max(0.);
+                        if loss == LinearSVCLoss::Hinge {
+                            xi
+                        } else {
+                            xi * xi
+                        }
+                    })
+                    .sum();
+                let penalty = if penalty == LinearSVCPenalty::L2 {
+                    0.5 * w.iter().map(|w_j| w_j * w_j).sum::<f64>()
+                } else {
+                    w.iter().map(|w_j| w_j.abs()).sum::<f64>()
+                };
+                penalty + c * loss
+            };
+
+            let optimum = objective(&w);
+            for j in 0..5 {
+                for step in [-1e-3, 1e-3] {
+                    let mut w_step = w.clone();
+                    w_step[j] += step;
+                    assert!(
+                        objective(&w_step) >= optimum - 1e-6,
+                        "{loss:?} {penalty:?} {j} {step}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn linear_svc_l1_is_sparse() {
+        // only the first feature is informative
+        let x = DenseMatrix::from_2d_array(&[
+            &[-2.0, 0.3, -0.1],
+            &[-1.5, -0.2, 0.4],
+            &[-1.0, 0.1, 0.2],
+            &[-0.5, -0.4, -0.3],
+            &[0.5, 0.3, 0.1],
+            &[1.0, -0.1, -0.4],
+            &[1.5, 0.4, 0.3],
+            &[2.0, -0.3, -0.2],
+        ])
+        .unwrap();
+        let y: Vec<i32> = vec![0, 0, 0, 0, 1, 1, 1, 1];
+
+        let params = LinearSVCParameters::default()
+            .with_c(0.5)
+            .with_penalty(LinearSVCPenalty::L1)
+            .with_seed(Some(1));
+        let svc = LinearSVC::fit(&x, &y, params).unwrap();
+
+        assert!(*svc.coefficients().get((0, 0)) > 0.);
+        assert_eq!(*svc.coefficients().get((0, 1)), 0.);
+        assert_eq!(*svc.coefficients().get((0, 2)), 0.);
+        assert_eq!(svc.predict(&x).unwrap(), y);
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    #[cfg(feature = "serde")]
+    fn linear_svc_serde() {
+        let (x, y) = iris();
+        let svc =
+            LinearSVC::fit(&x, &y, LinearSVCParameters::default().with_seed(Some(1))).unwrap();
+
+        let deserialized_svc: LinearSVC<f64, u32, DenseMatrix<f64>, Vec<u32>> =
+            serde_json::from_str(&serde_json::to_string(&svc).unwrap()).unwrap();
+
+        assert_eq!(svc, deserialized_svc);
+    }
+}
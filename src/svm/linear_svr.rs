@@ -0,0 +1,440 @@
+//! # Linear Support Vector Regression
+//!
+//! Linear Support Vector Regression (LinearSVR) fits the same function as [SVR](../svr/index.html) with a linear kernel,
+//! \\(f(x) = \langle w, x \rangle + b\\), but it stores the weights \\(w\\) instead of the support vectors and is trained by
+//! dual coordinate descent, as in liblinear. Both training and prediction scale linearly with the number of observations.
+//!
+//! The optimization problem is
+//!
+//! \\[\underset{w}{minimize} \space \space \frac{1}{2}\lVert w \rVert^2 + C\sum_{i=1}^m \xi(w; x_i, y_i) \\]
+//!
+//! where the loss \\(\xi\\) is either the \\(\epsilon\\)-insensitive loss \\(\max(0, \lvert y_i - \langle w, x_i \rangle \rvert - \epsilon)\\),
+//! as in SVR, or its square.
+//!
+//! When `fit_intercept` is set, the intercept is the weight of an extra feature with the constant value `intercept_scaling`.
+//! This weight is regularized like other weights, increase `intercept_scaling` to reduce the effect of the penalty on the intercept.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::basic::matrix::DenseMatrix;
+//! use smartcore::svm::linear_svr::{LinearSVR, LinearSVRParameters};
+//!
+//! // Longley dataset (https://www.statsmodels.org/stable/datasets/generated/longley.html)
+//! let x = DenseMatrix::from_2d_array(&[
+//!               &[234.289, 235.6, 159.0, 107.608, 1947., 60.323],
+//!               &[259.426, 232.5, 145.6, 108.632, 1948., 61.122],
+//!               &[258.054, 368.2, 161.6, 109.773, 1949., 60.171],
+//!               &[284.599, 335.1, 165.0, 110.929, 1950., 61.187],
+//!               &[328.975, 209.9, 309.9, 112.075, 1951., 63.221],
+//!               &[346.999, 193.2, 359.4, 113.270, 1952., 63.639],
+//!               &[365.385, 187.0, 354.7, 115.094, 1953., 64.989],
+//!               &[363.112, 357.8, 335.0, 116.219, 1954., 63.761],
+//!               &[397.469, 290.4, 304.8, 117.388, 1955., 66.019],
+//!               &[419.180, 282.2, 285.7, 118.734, 1956., 67.857],
+//!               &[442.769, 293.6, 279.8, 120.445, 1957., 68.169],
+//!               &[444.546, 468.1, 263.7, 121.950, 1958., 66.513],
+//!               &[482.704, 381.3, 255.2, 123.366, 1959., 68.655],
+//!               &[502.601, 393.1, 251.4, 125.368, 1960., 69.564],
+//!               &[518.173, 480.6, 257.2, 127.852, 1961., 69.331],
+//!               &[554.894, 400.7, 282.7, 130.081, 1962., 70.551],
+//!          ]).unwrap();
+//!
+//! let y: Vec<f64> = vec![83.0, 88.5, 88.2, 89.5, 96.2, 98.1, 99.0,
+//!           100.0, 101.2, 104.6, 108.4, 110.8, 112.6, 114.2, 115.7, 116.9];
+//!
+//! let params = LinearSVRParameters::default().with_epsilon(0.5).with_seed(Some(1));
+//! let svr = LinearSVR::fit(&x, &y, params).unwrap();
+//!
+//! let y_hat = svr.predict(&x).unwrap();
+//! ```
+//!
+//! ## References:
+//!
+//! * ["LIBLINEAR: A Library for Large Linear Classification", Fan R.E., Chang K.W., Hsieh C.J., Wang X.R., Lin C.J., 2008](https://www.jmlr.org/papers/volume9/fan08a/fan08a.pdf)
+//! * ["Large-scale Linear Support Vector Regression", Ho C.H., Lin C.J., 2012](https://www.jmlr.org/papers/volume13/ho12a/ho12a.pdf)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::api::{Predictor, SupervisedEstimator};
+use crate::error::{Failed, FailedError};
+use crate::linalg::basic::arrays::{Array1, Array2};
+use crate::numbers::basenum::Number;
+use crate::numbers::realnum::RealNumber;
+use crate::rand_custom::get_rng_impl;
+use crate::svm::linear_solver::{solve_svr_dual, Problem};
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+/// Loss of observations outside of the \\(\epsilon\\)-tube.
+pub enum LinearSVRLoss {
+    /// \\(\max(0, \lvert y_i - f(x_i) \rvert - \epsilon)\\), as in [SVR](../svr/index.html)
+    #[default]
+    EpsilonInsensitive,
+    /// \\(\max(0, \lvert y_i - f(x_i) \rvert - \epsilon)^2\\), differentiable
+    SquaredEpsilonInsensitive,
+}
+
+/// LinearSVR Parameters
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct LinearSVRParameters<T: Number + RealNumber> {
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Epsilon in the epsilon-SVR model.
+    pub epsilon: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Regularization parameter.
+    pub c: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Loss of observations outside of the epsilon-tube.
+    pub loss: LinearSVRLoss,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Tolerance for stopping criterion.
+    pub tol: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Maximum number of passes over the data.
+    pub max_iter: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Whether to fit an intercept.
+    pub fit_intercept: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Value of the constant feature whose weight is the intercept.
+    pub intercept_scaling: T,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Seed of the pseudo-random number generator that shuffles the coordinates.
+    pub seed: Option<u64>,
+}
+
+impl<T: Number + RealNumber> LinearSVRParameters<T> {
+    /// Epsilon in the epsilon-SVR model.
+    pub fn with_epsilon(mut self, epsilon: T) -> Self {
+        self.epsilon = epsilon;
+        self
+    }
+    /// Regularization parameter.
+    pub fn with_c(mut self, c: T) -> Self {
+        self.c = c;
+        self
+    }
+    /// Loss of observations outside of the epsilon-tube.
+    pub fn with_loss(mut self, loss: LinearSVRLoss) -> Self {
+        self.loss = loss;
+        self
+    }
+    /// Tolerance for stopping criterion.
+    pub fn with_tol(mut self, tol: T) -> Self {
+        self.tol = tol;
+        self
+    }
+    /// Maximum number of passes over the data.
+    pub fn with_max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+    /// Whether to fit an intercept.
+    pub fn with_fit_intercept(mut self, fit_intercept: bool) -> Self {
+        self.fit_intercept = fit_intercept;
+        self
+    }
+    /// Value of the constant feature whose weight is the intercept.
+    pub fn with_intercept_scaling(mut self, intercept_scaling: T) -> Self {
+        self.intercept_scaling = intercept_scaling;
+        self
+    }
+    /// Seed of the pseudo-random number generator.
+    pub fn with_seed(mut self, seed: Option<u64>) -> Self {
+        self.seed = seed;
+        self
+    }
+}
+
+impl<T: Number + RealNumber> Default for LinearSVRParameters<T> {
+    fn default() -> Self {
+        LinearSVRParameters {
+            epsilon: T::zero(),
+            c: T::one(),
+            loss: LinearSVRLoss::default(),
+            tol: T::from_f64(1e-4).unwrap(),
+            max_iter: 1000,
+            fit_intercept: true,
+            intercept_scaling: T::one(),
+            seed: Option::None,
+        }
+    }
+}
+
+/// Linear Support Vector Regression
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+pub struct LinearSVR<TX: Number + RealNumber, TY: Number, X: Array2<TX>, Y: Array1<TY>> {
+    coefficients: Option<X>,
+    intercept: Option<TX>,
+    n_iter: usize,
+    _phantom_ty: PhantomData<TY>,
+    _phantom_y: PhantomData<Y>,
+}
+
+impl<TX: Number + RealNumber, TY: Number, X: Array2<TX>, Y: Array1<TY>>
+    SupervisedEstimator<X, Y, LinearSVRParameters<TX>> for LinearSVR<TX, TY, X, Y>
+{
+    fn new() -> Self {
+        Self {
+            coefficients: Option::None,
+            intercept: Option::None,
+            n_iter: 0,
+            _phantom_ty: PhantomData,
+            _phantom_y: PhantomData,
+        }
+    }
+
+    fn fit(x: &X, y: &Y, parameters: LinearSVRParameters<TX>) -> Result<Self, Failed> {
+        LinearSVR::fit(x, y, parameters)
+    }
+}
+
+impl<TX: Number + RealNumber, TY: Number, X: Array2<TX>, Y: Array1<TY>> Predictor<X, Y>
+    for LinearSVR<TX, TY, X, Y>
+{
+    fn predict(&self, x: &X) -> Result<Y, Failed> {
+        self.predict(x)
+    }
+}
+
+impl<TX: Number + RealNumber, TY: Number, X: Array2<TX>, Y: Array1<TY>> LinearSVR<TX, TY, X, Y> {
+    /// Fits LinearSVR to your data.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - target values
+    /// * `parameters` - other parameters, use `Default::default()` to set parameters to default values.
+    pub fn fit(
+        x: &X,
+        y: &Y,
+        parameters: LinearSVRParameters<TX>,
+    ) -> Result<LinearSVR<TX, TY, X, Y>, Failed> {
+        let (n, p) = x.shape();
+
+        if n != y.shape() {
+            return Err(Failed::fit(
+                "Number of rows of X doesn\'t match number of rows of Y",
+            ));
+        }
+
+        if parameters.c <= TX::zero() {
+            return Err(Failed::because(
+                FailedError::ParametersError,
+                "C should be > 0",
+            ));
+        }
+
+        if parameters.epsilon < TX::zero() {
+            return Err(Failed::because(
+                FailedError::ParametersError,
+                "epsilon should be >= 0",
+            ));
+        }
+
+        let intercept_scaling = parameters
+            .fit_intercept
+            .then(|| parameters.intercept_scaling.to_f64().unwrap());
+        let problem = Problem::new(x, intercept_scaling);
+        let y: Vec<f64> = y.iterator(0).map(|y_i| y_i.to_f64().unwrap()).collect();
+
+        let (w, n_iter) = solve_svr_dual(
+            &problem,
+            &y,
+            parameters.c.to_f64().unwrap(),
+            parameters.epsilon.to_f64().unwrap(),
+            parameters.loss == LinearSVRLoss::SquaredEpsilonInsensitive,
+            parameters.tol.to_f64().unwrap(),
+            parameters.max_iter,
+            &mut get_rng_impl(parameters.seed),
+        );
+
+        let coefficients = X::from_iterator(
+            w.iter().take(p).map(|w_j| TX::from_f64(*w_j).unwrap()),
+            p,
+            1,
+            0,
+        );
+        let intercept =
+            intercept_scaling.map_or(TX::zero(), |scaling| TX::from_f64(w[p] * scaling).unwrap());
+
+        Ok(LinearSVR {
+            coefficients: Some(coefficients),
+            intercept: Some(intercept),
+            n_iter,
+            _phantom_ty: PhantomData,
+            _phantom_y: PhantomData,
+        })
+    }
+
+    /// Predict target values from `x`
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict(&self, x: &X) -> Result<Y, Failed> {
+        let (n, num_attributes) = x.shape();
+        let p = self.coefficients().shape().0;
+        if num_attributes != p {
+            return Err(Failed::predict(&format!(
+                "expected {p} features, got {num_attributes}"
+            )));
+        }
+
+        let y_hat = x.matmul(self.coefficients());
+        Ok(Y::from_iterator(
+            (0..n).map(|i| TY::from(*y_hat.get((i, 0)) + *self.intercept()).unwrap()),
+            n,
+        ))
+    }
+
+    /// Get the weights, a _Mx1_ matrix
+    pub fn coefficients(&self) -> &X {
+        self.coefficients.as_ref().unwrap()
+    }
+
+    /// Get the intercept
+    pub fn intercept(&self) -> &TX {
+        self.intercept.as_ref().unwrap()
+    }
+
+    /// Number of passes over the data of the solver.
+    pub fn n_iter(&self) -> usize {
+        self.n_iter
+    }
+}
+
+impl<TX: Number + RealNumber, TY: Number, X: Array2<TX>, Y: Array1<TY>> PartialEq
+    for LinearSVR<TX, TY, X, Y>
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.coefficients().shape() == other.coefficients().shape()
+            && (*self.intercept() - *other.intercept()).abs() <= TX::epsilon()
+            && self
+                .coefficients()
+                .iterator(0)
+                .zip(other.coefficients().iterator(0))
+                .all(|(a, b)| (*a - *b).abs() <= TX::epsilon())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::basic::arrays::Array;
+    use crate::linalg::basic::matrix::DenseMatrix;
+    use crate::metrics::mean_squared_error;
+    use crate::test_datasets::longley;
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn linear_svr_fit_predict() {
+        // y = 2 x_1 - x_2 + 3 with small deviations
+        let x = DenseMatrix::from_2d_array(&[
+            &[0.0, 1.0],
+            &[1.0, 0.0],
+            &[2.0, 3.0],
+            &[3.0, 1.0],
+            &[4.0, 2.0],
+            &[5.0, 5.0],
+            &[6.0, 1.0],
+            &[7.0, 4.0],
+        ])
+        .unwrap();
+        let y: Vec<f64> = vec![2.1, 4.9, 3.9, 8.1, 9.0, 7.9, 14.1, 13.0];
+
+        for loss in [
+            LinearSVRLoss::EpsilonInsensitive,
+            LinearSVRLoss::SquaredEpsilonInsensitive,
+        ] {
+            let params = LinearSVRParameters::default()
+                .with_c(100.)
+                .with_epsilon(0.1)
+                .with_loss(loss.clone())
+                .with_intercept_scaling(10.)
+                .with_tol(1e-6)
+                .with_max_iter(100000)
+                .with_seed(Some(1));
+            let svr: LinearSVR<f64, f64, DenseMatrix<f64>, Vec<f64>> =
+                LinearSVR::fit(&x, &y, params).unwrap();
+
+            assert!(
+                (svr.coefficients().get((0, 0)) - 2.).abs() < 0.1,
+                "{loss:?}"
+            );
+            assert!(
+                (svr.coefficients().get((1, 0)) + 1.).abs() < 0.1,
+                "{loss:?}"
+            );
+            assert!((svr.intercept() - 3.).abs() < 0.3, "{loss:?}");
+
+            let y_hat = svr.predict(&x).unwrap();
+            assert!(mean_squared_error(&y, &y_hat) < 0.05, "{loss:?}");
+        }
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn linear_svr_epsilon_tube() {
+        // the smallest weight that keeps all observations within the epsilon-tube, set by the observation (1, 1.2)
+        let x = DenseMatrix::from_2d_array(&[&[0.0], &[1.0], &[2.0], &[3.0], &[4.0]]).unwrap();
+        let y: Vec<f64> = vec![0.0, 1.2, 2.0, 2.8, 4.0];
+
+        let params = LinearSVRParameters::default()
+            .with_c(1000.)
+            .with_epsilon(0.25)
+            .with_fit_intercept(false)
+            .with_tol(1e-8)
+            .with_max_iter(100000)
+            .with_seed(Some(1));
+        let svr: LinearSVR<f64, f64, DenseMatrix<f64>, Vec<f64>> =
+            LinearSVR::fit(&x, &y, params).unwrap();
+
+        assert!((svr.coefficients().get((0, 0)) - 0.95).abs() < 1e-3);
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn linear_svr_longley() {
+        let (x, y) = longley();
+
+        let params = LinearSVRParameters::default()
+            .with_epsilon(0.5)
+            .with_seed(Some(1));
+        let svr: LinearSVR<f64, f64, DenseMatrix<f64>, Vec<f64>> =
+            LinearSVR::fit(&x, &y, params).unwrap();
+        let y_hat = svr.predict(&x).unwrap();
+
+        assert!(mean_squared_error(&y, &y_hat) < 2.5);
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    #[cfg(feature = "serde")]
+    fn linear_svr_serde() {
+        let (x, y) = longley();
+        let svr =
+            LinearSVR::fit(&x, &y, LinearSVRParameters::default().with_seed(Some(1))).unwrap();
+
+        let deserialized_svr: LinearSVR<f64, f64, DenseMatrix<f64>, Vec<f64>> =
+            serde_json::from_str(&serde_json::to_string(&svr).unwrap()).unwrap();
+
+        assert_eq!(svr, deserialized_svr);
+    }
+}
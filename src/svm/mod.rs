@@ -8,6 +8,14 @@
 //!
 //! SVM is memory efficient since it uses only a subset of training data to find a decision boundary. This subset is called support vectors.
 //!
+//! Estimators:
+//!
+//! * [SVC](svc/index.html) and [SVR](svr/index.html), classification and \\(\epsilon\\)-regression regularized by the parameter \\(C\\)
+//! * [NuSVC](nu_svc/index.html) and [NuSVR](nu_svr/index.html), the same problems parameterized by the fraction of support vectors \\(\nu\\)
+//! * [OneClassSVM](one_class_svm/index.html), unsupervised novelty and outlier detection
+//! * [LinearSVC](linear_svc/index.html) and [LinearSVR](linear_svr/index.html), linear classification and regression
+//!   trained by coordinate descent, for data sets too large for kernel methods
+//!
 //! In SVM distance between a data point and the support vectors is defined by the kernel function.
 //! `smartcore` supports multiple kernel functions but you can always define a new kernel function by implementing the `Kernel` trait. Not all functions can be a kernel.
 //! Building a new kernel requires a good mathematical understanding of the [Mercer theorem](https://en.wikipedia.org/wiki/Mercer%27s_theorem)
@@ -19,9 +27,49 @@
 //! * *Polynomial*, \\( K(x, x') = (\gamma\langle x, x' \rangle + r)^d\\), where \\(d\\) is polynomial degree, \\(\gamma\\) is a kernel coefficient and \\(r\\) is an independent term in the kernel function.
 //! * *RBF (Gaussian)*, \\( K(x, x') = e^{-\gamma \lVert x - x' \rVert ^2} \\), where \\(\gamma\\) is kernel coefficient
 //! * *Sigmoid (hyperbolic tangent)*, \\( K(x, x') = \tanh ( \gamma \langle x, x' \rangle + r ) \\), where \\(\gamma\\) is kernel coefficient and \\(r\\) is an independent term in the kernel function.
+//! * *Laplacian*, \\( K(x, x') = e^{-\gamma \lVert x - x' \rVert_1} \\), where \\(\gamma\\) is kernel coefficient
+//! * *Chi-squared*, \\( K(x, x') = e^{-\gamma \sum_k (x_k - x'_k)^2 / (x_k + x'_k)} \\) for non-negative features such as histograms, where \\(\gamma\\) is kernel coefficient
+//!
+//! Kernels that are not functions of numeric features, for example string or graph kernels, can be used in two ways:
+//!
+//! * [`PrecomputedKernel`](struct.PrecomputedKernel.html) reads kernel values from a precomputed Gram matrix that is passed in place of the data.
+//! * [`CustomKernel`](struct.CustomKernel.html) wraps a closure. Its name is serialized in place of the closure, and deserialized models
+//!   look the closure up by name.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::basic::matrix::DenseMatrix;
+//! use smartcore::svm::{Kernels, PrecomputedKernel};
+//! use smartcore::svm::svc::{SVC, SVCParameters};
+//!
+//! // Gram matrix of a linear kernel of the points 0, 1, 3 and 4
+//! let gram = DenseMatrix::from_2d_array(&[
+//!            &[0., 0., 0., 0.],
+//!            &[0., 1., 3., 4.],
+//!            &[0., 3., 9., 12.],
+//!            &[0., 4., 12., 16.],
+//!         ]).unwrap();
+//! let y = vec![-1, -1, 1, 1];
+//!
+//! let x = PrecomputedKernel::prepend_index(&gram);
+//! let params = SVCParameters::default().with_c(10.0).with_kernel(Kernels::precomputed());
+//! let svc = SVC::fit(&x, &y, &params).unwrap();
+//!
+//! // kernel values of the point 5 and the training points
+//! let x_test = PrecomputedKernel::prepend_index(&DenseMatrix::from_2d_array(&[&[0., 5., 15., 20.]]).unwrap());
+//! let y_hat = svc.predict(&x_test).unwrap();
+//! ```
 //!
 //! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
 //! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+mod linear_solver;
+pub mod linear_svc;
+pub mod linear_svr;
+pub mod nu_svc;
+pub mod nu_svr;
+pub mod one_class_svm;
+mod smo;
 /// search parameters
 pub mod svc;
 pub mod svr;
@@ -29,12 +77,28 @@ pub mod svr;
 // pub mod search;
 
 use core::fmt::Debug;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock, RwLock};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use crate::error::{Failed, FailedError};
-use crate::linalg::basic::arrays::{Array1, ArrayView1};
+use crate::linalg::basic::arrays::{Array1, Array2, ArrayView1};
+use crate::numbers::basenum::Number;
+
+/// Solver of the optimization problem of support vector machines.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+pub enum SVMSolverName {
+    /// Fast approximate solver, LaSVM for SVC and SMO without shrinking for SVR
+    #[default]
+    Approximate,
+    /// Exact sequential minimal optimization of libsvm, with second order working set selection,
+    /// shrinking and a kernel cache. Results match libsvm up to the stopping tolerance.
+    SMO,
+}
 
 /// Defines a kernel function.
 /// This is a object-safe trait.
@@ -70,6 +134,25 @@ impl Kernels {
     pub fn sigmoid() -> SigmoidKernel {
         SigmoidKernel::default()
     }
+    /// Return a default Laplacian
+    pub fn laplacian() -> LaplacianKernel {
+        LaplacianKernel::default()
+    }
+    /// Return a default chi-squared
+    pub fn chi_squared() -> ChiSquaredKernel {
+        ChiSquaredKernel::default()
+    }
+    /// Return a kernel that reads a precomputed Gram matrix
+    pub fn precomputed() -> PrecomputedKernel {
+        PrecomputedKernel
+    }
+    /// Return a kernel defined by a function, see [`CustomKernel`](struct.CustomKernel.html)
+    pub fn custom<F: Fn(&[f64], &[f64]) -> f64 + Send + Sync + 'static>(
+        name: &str,
+        function: F,
+    ) -> Result<CustomKernel, Failed> {
+        CustomKernel::new(name, function)
+    }
 }
 
 /// Linear Kernel
@@ -193,6 +276,174 @@ impl SigmoidKernel {
     }
 }
 
+/// Laplacian kernel
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct LaplacianKernel {
+    /// kernel coefficient
+    pub gamma: Option<f64>,
+}
+
+impl LaplacianKernel {
+    /// assign gamma parameter to kernel (required)
+    /// ```rust
+    /// use smartcore::svm::LaplacianKernel;
+    /// let knl = LaplacianKernel::default().with_gamma(0.7);
+    /// ```
+    pub fn with_gamma(mut self, gamma: f64) -> Self {
+        self.gamma = Some(gamma);
+        self
+    }
+}
+
+/// Exponential chi-squared kernel, for non-negative features
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChiSquaredKernel {
+    /// kernel coefficient
+    pub gamma: Option<f64>,
+}
+
+impl Default for ChiSquaredKernel {
+    fn default() -> Self {
+        Self { gamma: Some(1f64) }
+    }
+}
+
+impl ChiSquaredKernel {
+    /// assign gamma parameter to kernel
+    /// ```rust
+    /// use smartcore::svm::ChiSquaredKernel;
+    /// let knl = ChiSquaredKernel::default().with_gamma(0.7);
+    /// ```
+    pub fn with_gamma(mut self, gamma: f64) -> Self {
+        self.gamma = Some(gamma);
+        self
+    }
+}
+
+/// Kernel that reads kernel values from a precomputed Gram matrix, as the precomputed kernel of libsvm.
+///
+/// The data passed to an estimator are rows \\([i, K(x, x_1), ..., K(x, x_n)]\\) where \\(x_1, ..., x_n\\) are the training observations
+/// and \\(i\\) is the index of the observation \\(x\\) among the training observations. The index of observations that
+/// are not training observations is ignored. Use [`prepend_index`](#method.prepend_index) to build the rows from a Gram matrix.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PrecomputedKernel;
+
+impl PrecomputedKernel {
+    /// Prepends the index of every row to the _NxM_ matrix of kernel values \\(K(x_i, x'_j)\\) of observations \\(x_i\\)
+    /// and the \\(M\\) training observations \\(x'_j\\).
+    pub fn prepend_index<T: Number, X: Array2<T>>(gram: &X) -> X {
+        let (n, m) = gram.shape();
+        X::from_iterator(
+            (0..n).flat_map(|i| {
+                std::iter::once(T::from_usize(i).unwrap())
+                    .chain((0..m).map(move |j| *gram.get((i, j))))
+            }),
+            n,
+            m + 1,
+            0,
+        )
+    }
+}
+
+type KernelFunction = Arc<dyn Fn(&[f64], &[f64]) -> f64 + Send + Sync>;
+
+/// Functions of custom kernels by name, deserialized kernels look their function up here.
+fn kernel_functions() -> &'static RwLock<HashMap<String, KernelFunction>> {
+    static FUNCTIONS: OnceLock<RwLock<HashMap<String, KernelFunction>>> = OnceLock::new();
+    FUNCTIONS.get_or_init(Default::default)
+}
+
+/// Kernel defined by a function.
+///
+/// A closure can not be serialized, so the kernel serializes its name only. Creating a kernel registers its function under
+/// the name in a registry shared by the whole process, and a deserialized kernel finds the function by its name. Create the kernel
+/// again, for example when the application starts, before using a deserialized model.
+///
+/// Every name refers to a single function: creating a second kernel with a name that is already registered fails, so that models
+/// deserialized later do not silently pick up a different function. Use [`CustomKernel::replace`] to change the function on purpose.
+/// ```rust
+/// use smartcore::svm::{CustomKernel, Kernel};
+/// let knl = CustomKernel::new("min", |x_i, x_j| {
+///     x_i.iter().zip(x_j.iter()).map(|(a, b)| a.min(*b)).sum()
+/// }).unwrap();
+/// assert_eq!(knl.apply(&vec![1., 3.], &vec![2., 2.]).unwrap(), 3.);
+/// assert!(CustomKernel::new("min", |_, _| 0.).is_err());
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone)]
+pub struct CustomKernel {
+    name: String,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    function: Option<KernelFunction>,
+}
+
+impl CustomKernel {
+    /// Creates a kernel named `name` and registers its function under the name.
+    /// Fails when a function is already registered under `name`.
+    pub fn new<F: Fn(&[f64], &[f64]) -> f64 + Send + Sync + 'static>(
+        name: &str,
+        function: F,
+    ) -> Result<Self, Failed> {
+        let function: KernelFunction = Arc::new(function);
+        match kernel_functions().write().unwrap().entry(name.to_string()) {
+            Entry::Occupied(_) => {
+                return Err(Failed::because(
+                    FailedError::ParametersError,
+                    &format!(
+                        "function of kernel {name} is already registered, use CustomKernel::replace(..) to change it"
+                    ),
+                ))
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(Arc::clone(&function));
+            }
+        }
+        Ok(CustomKernel {
+            name: name.to_string(),
+            function: Some(function),
+        })
+    }
+
+    /// Creates a kernel named `name` and registers its function under the name, replacing the function
+    /// registered before. Deserialized kernels with this name use the new function from now on.
+    pub fn replace<F: Fn(&[f64], &[f64]) -> f64 + Send + Sync + 'static>(
+        name: &str,
+        function: F,
+    ) -> Self {
+        let function: KernelFunction = Arc::new(function);
+        kernel_functions()
+            .write()
+            .unwrap()
+            .insert(name.to_string(), Arc::clone(&function));
+        CustomKernel {
+            name: name.to_string(),
+            function: Some(function),
+        }
+    }
+
+    /// Name of the kernel
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Debug for CustomKernel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CustomKernel")
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+impl PartialEq for CustomKernel {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
 #[cfg_attr(all(feature = "serde", not(target_arch = "wasm32")), typetag::serde)]
 impl Kernel for LinearKernel {
     fn apply(&self, x_i: &Vec<f64>, x_j: &Vec<f64>) -> Result<f64, Failed> {
@@ -242,6 +493,80 @@ impl Kernel for SigmoidKernel {
     }
 }
 
+#[cfg_attr(all(feature = "serde", not(target_arch = "wasm32")), typetag::serde)]
+impl Kernel for LaplacianKernel {
+    fn apply(&self, x_i: &Vec<f64>, x_j: &Vec<f64>) -> Result<f64, Failed> {
+        if self.gamma.is_none() {
+            return Err(Failed::because(
+                FailedError::ParametersError,
+                "gamma should be set, use {Kernel}::default().with_gamma(..)",
+            ));
+        }
+        let distance: f64 = x_i.iter().zip(x_j.iter()).map(|(a, b)| (a - b).abs()).sum();
+        Ok((-self.gamma.unwrap() * distance).exp())
+    }
+}
+
+#[cfg_attr(all(feature = "serde", not(target_arch = "wasm32")), typetag::serde)]
+impl Kernel for ChiSquaredKernel {
+    fn apply(&self, x_i: &Vec<f64>, x_j: &Vec<f64>) -> Result<f64, Failed> {
+        if self.gamma.is_none() {
+            return Err(Failed::because(
+                FailedError::ParametersError,
+                "gamma should be set, use {Kernel}::default().with_gamma(..)",
+            ));
+        }
+        let mut distance = 0f64;
+        for (a, b) in x_i.iter().zip(x_j.iter()) {
+            if *a < 0. || *b < 0. {
+                return Err(Failed::because(
+                    FailedError::ParametersError,
+                    "chi-squared kernel is defined for non-negative features only",
+                ));
+            }
+            if a + b > 0. {
+                distance += (a - b) * (a - b) / (a + b);
+            }
+        }
+        Ok((-self.gamma.unwrap() * distance).exp())
+    }
+}
+
+#[cfg_attr(all(feature = "serde", not(target_arch = "wasm32")), typetag::serde)]
+impl Kernel for PrecomputedKernel {
+    fn apply(&self, x_i: &Vec<f64>, x_j: &Vec<f64>) -> Result<f64, Failed> {
+        let index = x_j.first().map(|&j| j as usize + 1);
+        index
+            .and_then(|j| x_i.get(j).copied())
+            .ok_or_else(|| {
+                Failed::because(
+                    FailedError::ParametersError,
+                    "rows of a precomputed kernel should be the index of the observation followed by kernel values, \
+                     use PrecomputedKernel::prepend_index(..)",
+                )
+            })
+    }
+}
+
+#[cfg_attr(all(feature = "serde", not(target_arch = "wasm32")), typetag::serde)]
+impl Kernel for CustomKernel {
+    fn apply(&self, x_i: &Vec<f64>, x_j: &Vec<f64>) -> Result<f64, Failed> {
+        if let Some(function) = &self.function {
+            return Ok(function(x_i, x_j));
+        }
+        match kernel_functions().read().unwrap().get(&self.name) {
+            Some(function) => Ok(function(x_i, x_j)),
+            None => Err(Failed::because(
+                FailedError::ParametersError,
+                &format!(
+                    "function of kernel {} is not registered, create the kernel with CustomKernel::new(..)",
+                    self.name
+                ),
+            )),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -312,4 +637,115 @@ mod tests {
 
         assert!((0.3969f64 - result) < 1e-4);
     }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn laplacian_kernel() {
+        let v1 = vec![1., 2., 3.];
+        let v2 = vec![4., 5., 6.];
+
+        let result = Kernels::laplacian()
+            .with_gamma(0.1)
+            .apply(&v1, &v2)
+            .unwrap();
+
+        assert!(((-0.9f64).exp() - result).abs() < 1e-12);
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn chi_squared_kernel() {
+        let v1 = vec![1., 0., 3.];
+        let v2 = vec![3., 0., 1.];
+
+        let result = Kernels::chi_squared()
+            .with_gamma(0.5)
+            .apply(&v1, &v2)
+            .unwrap();
+
+        assert!(((-1f64).exp() - result).abs() < 1e-12);
+        assert!(Kernels::chi_squared().apply(&vec![-1., 2.], &v2).is_err());
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn precomputed_kernel() {
+        use crate::linalg::basic::arrays::Array;
+        use crate::linalg::basic::matrix::DenseMatrix;
+
+        let gram = DenseMatrix::from_2d_array(&[&[1., 2.], &[2., 5.]]).unwrap();
+        let x = PrecomputedKernel::prepend_index(&gram);
+
+        assert_eq!(x.shape(), (2, 3));
+        assert_eq!(*x.get((1, 0)), 1.);
+
+        let rows: Vec<Vec<f64>> = (0..2)
+            .map(|i| x.get_row(i).iterator(0).copied().collect())
+            .collect();
+        let knl = Kernels::precomputed();
+        assert_eq!(knl.apply(&rows[0], &rows[1]).unwrap(), 2.);
+        assert_eq!(knl.apply(&rows[1], &rows[1]).unwrap(), 5.);
+        assert!(knl.apply(&rows[0], &vec![2.]).is_err());
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn custom_kernel() {
+        let knl = Kernels::custom("test_min", |x_i, x_j| {
+            x_i.iter().zip(x_j.iter()).map(|(a, b)| a.min(*b)).sum()
+        })
+        .unwrap();
+
+        assert_eq!(knl.name(), "test_min");
+        assert_eq!(knl.apply(&vec![1., 5., 3.], &vec![4., 2., 6.]).unwrap(), 6.);
+
+        // a registered name is not silently taken over by another function
+        assert!(Kernels::custom("test_min", |_, _| 0.).is_err());
+
+        let replaced = CustomKernel::replace("test_min", |x_i, x_j| {
+            x_i.iter().zip(x_j.iter()).map(|(a, b)| a.max(*b)).sum()
+        });
+        assert_eq!(
+            replaced
+                .apply(&vec![1., 5., 3.], &vec![4., 2., 6.])
+                .unwrap(),
+            15.
+        );
+        assert_eq!(knl.apply(&vec![1., 5., 3.], &vec![4., 2., 6.]).unwrap(), 6.);
+    }
+
+    #[cfg(all(feature = "serde", not(target_arch = "wasm32")))]
+    #[test]
+    fn custom_kernel_serde() {
+        let knl: Box<dyn Kernel> = Box::new(
+            Kernels::custom("test_product", |x_i, x_j| {
+                x_i.iter().zip(x_j.iter()).map(|(a, b)| a * b).product()
+            })
+            .unwrap(),
+        );
+
+        let serialized = serde_json::to_string(&knl).unwrap();
+        let deserialized: Box<dyn Kernel> = serde_json::from_str(&serialized).unwrap();
+
+        assert_eq!(
+            deserialized.apply(&vec![1., 2.], &vec![3., 4.]).unwrap(),
+            24.
+        );
+
+        let unregistered: Box<dyn Kernel> =
+            serde_json::from_str(&serialized.replace("test_product", "test_unknown")).unwrap();
+        assert!(unregistered.apply(&vec![1.], &vec![1.]).is_err());
+    }
 }
@@ -0,0 +1,506 @@
+//! # Nu-Support Vector Classifier.
+//!
+//! \\(\nu\\)-SVC is a reparameterization of the [support vector classifier](../svc/index.html) where the regularization parameter \\(C\\)
+//! is replaced by \\(\nu \in (0, 1]\\). The parameter \\(\nu\\) is an upper bound on the fraction of margin errors and a lower bound on the
+//! fraction of support vectors, which is easier to choose than \\(C\\). The optimization problem is
+//!
+//! \\[\underset{w, b, \zeta, \rho}{minimize} \space \space \frac{1}{2} \lVert \vec{w} \rVert^2 - \nu \rho + \frac{1}{m}\sum_{i=1}^m \zeta_i \\]
+//!
+//! subject to:
+//!
+//! \\[y_i(\langle\vec{w}, \vec{x}_i \rangle + b) \geq \rho - \zeta_i \\]
+//! \\[\zeta_i \geq 0, \rho \geq 0 \space for \space any \space i = 1, ... , m\\]
+//!
+//! The problem is solved exactly by sequential minimal optimization, as in libsvm. Not every \\(\nu\\) is feasible, for every pair of classes
+//! with \\(m_1\\) and \\(m_2\\) observations \\(\nu (m_1 + m_2) / 2 \leq \min(m_1, m_2)\\) should hold.
+//! Problems with more than two classes are reduced to binary problems with the one-vs-one strategy.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::basic::matrix::DenseMatrix;
+//! use smartcore::svm::Kernels;
+//! use smartcore::svm::nu_svc::{NuSVC, NuSVCParameters};
+//!
+//! let x = DenseMatrix::from_2d_array(&[
+//!            &[5.1, 3.5, 1.4, 0.2],
+//!            &[4.9, 3.0, 1.4, 0.2],
+//!            &[4.7, 3.2, 1.3, 0.2],
+//!            &[4.6, 3.1, 1.5, 0.2],
+//!            &[7.0, 3.2, 4.7, 1.4],
+//!            &[6.4, 3.2, 4.5, 1.5],
+//!            &[6.9, 3.1, 4.9, 1.5],
+//!            &[5.5, 2.3, 4.0, 1.3],
+//!         ]).unwrap();
+//! let y = vec![0, 0, 0, 0, 1, 1, 1, 1];
+//!
+//! let params = &NuSVCParameters::default().with_nu(0.5).with_kernel(Kernels::linear());
+//! let svc = NuSVC::fit(&x, &y, params).unwrap();
+//!
+//! let y_hat = svc.predict(&x).unwrap();
+//! ```
+//!
+//! ## References:
+//!
+//! * ["New Support Vector Algorithms", Scholkopf B., Smola A.J., Williamson R.C., Bartlett P.L., 2000](https://alex.smola.org/papers/2000/SchSmoWilBar00.pdf)
+//! * ["Training nu-Support Vector Classifiers: Theory and Algorithms", Chang C.C., Lin C.J., 2001](https://www.csie.ntu.edu.tw/~cjlin/papers/nusvmtheory.pdf)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::api::{PredictorBorrow, SupervisedEstimatorBorrow};
+use crate::error::{Failed, FailedError};
+use crate::linalg::basic::arrays::{Array1, Array2};
+use crate::numbers::basenum::Number;
+use crate::numbers::realnum::RealNumber;
+use crate::svm::smo::{KernelCache, Solver};
+use crate::svm::svc::{decision_values, predict_classes, BinaryClassifier};
+use crate::svm::Kernel;
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+/// NuSVC Parameters
+pub struct NuSVCParameters<TX: Number + RealNumber> {
+    /// Upper bound on the fraction of margin errors and lower bound on the fraction of support vectors, in \\((0, 1]\\).
+    pub nu: TX,
+    /// Tolerance for stopping criterion.
+    pub tol: TX,
+    /// The kernel function.
+    #[cfg_attr(
+        all(feature = "serde", target_arch = "wasm32"),
+        serde(skip_serializing, skip_deserializing)
+    )]
+    pub kernel: Option<Box<dyn Kernel>>,
+    /// Whether the solver uses the shrinking heuristic.
+    pub shrinking: bool,
+    /// Size of the kernel cache, in megabytes.
+    pub cache_size: f64,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "TX: Serialize, TY: Serialize, X: Serialize, Y: Serialize",
+        deserialize = "TX: Deserialize<'de>, TY: Deserialize<'de>, X: Deserialize<'de>, Y: Deserialize<'de>",
+    ))
+)]
+/// Nu-Support Vector Classifier
+pub struct NuSVC<'a, TX: Number + RealNumber, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>> {
+    classes: Option<Vec<TY>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    parameters: Option<&'a NuSVCParameters<TX>>,
+    classifiers: Option<Vec<BinaryClassifier<TX>>>,
+    phantomdata: PhantomData<(X, Y)>,
+}
+
+impl<TX: Number + RealNumber> NuSVCParameters<TX> {
+    /// Upper bound on the fraction of margin errors and lower bound on the fraction of support vectors.
+    pub fn with_nu(mut self, nu: TX) -> Self {
+        self.nu = nu;
+        self
+    }
+    /// Tolerance for stopping criterion.
+    pub fn with_tol(mut self, tol: TX) -> Self {
+        self.tol = tol;
+        self
+    }
+    /// The kernel function.
+    pub fn with_kernel<K: Kernel + 'static>(mut self, kernel: K) -> Self {
+        self.kernel = Some(Box::new(kernel));
+        self
+    }
+    /// Whether the solver uses the shrinking heuristic.
+    pub fn with_shrinking(mut self, shrinking: bool) -> Self {
+        self.shrinking = shrinking;
+        self
+    }
+    /// Size of the kernel cache, in megabytes.
+    pub fn with_cache_size(mut self, cache_size: f64) -> Self {
+        self.cache_size = cache_size;
+        self
+    }
+}
+
+impl<TX: Number + RealNumber> Default for NuSVCParameters<TX> {
+    fn default() -> Self {
+        NuSVCParameters {
+            nu: TX::half(),
+            tol: TX::from_f64(1e-3).unwrap(),
+            kernel: Option::None,
+            shrinking: true,
+            cache_size: 200.,
+        }
+    }
+}
+
+impl<'a, TX: Number + RealNumber, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>>
+    SupervisedEstimatorBorrow<'a, X, Y, NuSVCParameters<TX>> for NuSVC<'a, TX, TY, X, Y>
+{
+    fn new() -> Self {
+        Self {
+            classes: Option::None,
+            parameters: Option::None,
+            classifiers: Option::None,
+            phantomdata: PhantomData,
+        }
+    }
+    fn fit(x: &'a X, y: &'a Y, parameters: &'a NuSVCParameters<TX>) -> Result<Self, Failed> {
+        NuSVC::fit(x, y, parameters)
+    }
+}
+
+impl<'a, TX: Number + RealNumber, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>>
+    PredictorBorrow<'a, X, TX> for NuSVC<'a, TX, TY, X, Y>
+{
+    fn predict(&self, x: &'a X) -> Result<Vec<TX>, Failed> {
+        self.predict(x)
+    }
+}
+
+impl<'a, TX: Number + RealNumber, TY: Number + Ord, X: Array2<TX> + 'a, Y: Array1<TY> + 'a>
+    NuSVC<'a, TX, TY, X, Y>
+{
+    /// Fits NuSVC to your data.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - class labels
+    /// * `parameters` - optional parameters, use `Default::default()` to set parameters to default values.
+    pub fn fit(
+        x: &'a X,
+        y: &'a Y,
+        parameters: &'a NuSVCParameters<TX>,
+    ) -> Result<NuSVC<'a, TX, TY, X, Y>, Failed> {
+        let (n, _) = x.shape();
+
+        if parameters.kernel.is_none() {
+            return Err(Failed::because(
+                FailedError::ParametersError,
+                "kernel should be defined at this point, please use `with_kernel()`",
+            ));
+        }
+
+        let nu = parameters.nu.to_f64().unwrap();
+        if nu <= 0. || nu > 1. {
+            return Err(Failed::because(
+                FailedError::ParametersError,
+                &format!("nu should be in (0, 1], got {nu}"),
+            ));
+        }
+
+        if n != y.shape() {
+            return Err(Failed::fit(
+                "Number of rows of X doesn\'t match number of rows of Y",
+            ));
+        }
+
+        let classes = y.unique();
+
+        if classes.len() < 2 {
+            return Err(Failed::fit(&format!(
+                "Incorrect number of classes: {}",
+                classes.len()
+            )));
+        }
+
+        let k = classes.len();
+        let y_idx: Vec<usize> = y
+            .iterator(0)
+            .map(|y_i| classes.binary_search(y_i).unwrap())
+            .collect();
+
+        let mut classifiers = Vec::with_capacity(k * (k - 1) / 2);
+        for negative in 0..k {
+            for positive in negative + 1..k {
+                let rows: Vec<usize> = (0..n)
+                    .filter(|&i| y_idx[i] == positive || y_idx[i] == negative)
+                    .collect();
+                let y_binary: Vec<f64> = rows
+                    .iter()
+                    .map(|&i| if y_idx[i] == positive { 1. } else { -1. })
+                    .collect();
+
+                let n_positive = y_binary.iter().filter(|&&y_i| y_i > 0.).count();
+                let n_negative = rows.len() - n_positive;
+                if nu * rows.len() as f64 / 2. > n_positive.min(n_negative) as f64 {
+                    return Err(Failed::fit(&format!(
+                        "nu {nu} is infeasible for classes {:?} and {:?}, \
+                         nu should be at most 2 * min(n_1, n_2) / (n_1 + n_2)",
+                        classes[negative], classes[positive]
+                    )));
+                }
+
+                let mut classifier = Self::fit_binary(&x.take(&rows, 0), &y_binary, parameters)?;
+                classifier.positive = positive;
+                classifier.negative = Some(negative);
+                classifiers.push(classifier);
+            }
+        }
+
+        Ok(NuSVC::<'a> {
+            classes: Some(classes),
+            parameters: Some(parameters),
+            classifiers: Some(classifiers),
+            phantomdata: PhantomData,
+        })
+    }
+
+    /// Solves a binary problem with labels 1 and -1.
+    fn fit_binary(
+        x: &X,
+        y: &[f64],
+        parameters: &NuSVCParameters<TX>,
+    ) -> Result<BinaryClassifier<TX>, Failed> {
+        let (n, _) = x.shape();
+        let rows: Vec<Vec<f64>> = (0..n)
+            .map(|i| {
+                x.get_row(i)
+                    .iterator(0)
+                    .map(|e| e.to_f64().unwrap())
+                    .collect()
+            })
+            .collect();
+
+        // a feasible initial solution, nu * n / 2 for either class
+        let nu = parameters.nu.to_f64().unwrap();
+        let mut sum = [nu * n as f64 / 2.; 2];
+        let alpha: Vec<f64> = y
+            .iter()
+            .map(|&y_i| {
+                let sum = &mut sum[usize::from(y_i < 0.)];
+                let alpha = sum.min(1.);
+                *sum -= alpha;
+                alpha
+            })
+            .collect();
+
+        let mut cache = KernelCache::new(
+            &rows,
+            parameters.kernel.as_ref().unwrap().as_ref(),
+            parameters.cache_size,
+        );
+        let solution = Solver::new(
+            &mut cache,
+            (0..n).collect(),
+            y.to_vec(),
+            vec![0.; n],
+            vec![1.; n],
+            alpha,
+            parameters.tol.to_f64().unwrap(),
+            parameters.shrinking,
+        )
+        .nu()
+        .solve()?;
+
+        if !(solution.r.is_finite() && solution.r > 0.) {
+            return Err(Failed::fit(&format!(
+                "The problem is degenerate, the margin scale r should be positive, got {}",
+                solution.r
+            )));
+        }
+
+        // the decision function of the nu formulation is scaled by 1 / r
+        let mut instances = Vec::new();
+        let mut w = Vec::new();
+        for (i, &alpha) in solution.alpha.iter().enumerate() {
+            if alpha > 0. {
+                instances.push(x.get_row(i).iterator(0).copied().collect());
+                w.push(TX::from(alpha * y[i] / solution.r).unwrap());
+            }
+        }
+
+        Ok(BinaryClassifier {
+            positive: 1,
+            negative: Some(0),
+            instances,
+            w,
+            b: TX::from(-solution.rho / solution.r).unwrap(),
+            sigmoid: None,
+        })
+    }
+
+    /// Predicts estimated class labels from `x`
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict(&self, x: &'a X) -> Result<Vec<TX>, Failed> {
+        let decision_values = self.decision_function_multiclass(x)?;
+        let classes = self.classes();
+        let classifiers = self.classifiers.as_ref().unwrap();
+
+        Ok(
+            predict_classes(classifiers, classes.len(), &decision_values)
+                .into_iter()
+                .map(|c| TX::from(classes[c]).unwrap())
+                .collect(),
+        )
+    }
+
+    /// Evaluates the decision function for the rows in `x`. Positive values predict the second class.
+    /// Only defined for problems with two classes, see [`decision_function_multiclass`](#method.decision_function_multiclass).
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn decision_function(&self, x: &'a X) -> Result<Vec<TX>, Failed> {
+        if self.classifiers.as_ref().unwrap().len() != 1 {
+            return Err(Failed::predict(&format!(
+                "decision_function is only defined for two classes, got {} classes, use decision_function_multiclass",
+                self.classes().len()
+            )));
+        }
+
+        let decision_values = self.decision_function_multiclass(x)?;
+        let (n, _) = x.shape();
+        Ok((0..n).map(|i| *decision_values.get((i, 0))).collect())
+    }
+
+    /// Evaluates the decision functions of the one-vs-one classifiers for the rows in `x`.
+    /// Returns a _KxP_ matrix with \\(P = k(k-1)/2\\) columns ordered by pairs of classes \\((0, 1), (0, 2), ..., (1, 2), ...\\),
+    /// positive values vote for the second class of the pair.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn decision_function_multiclass(&self, x: &'a X) -> Result<X, Failed> {
+        let kernel = self.parameters.as_ref().unwrap().kernel.as_ref().unwrap();
+        Ok(decision_values(
+            self.classifiers.as_ref().unwrap(),
+            kernel.as_ref(),
+            x,
+        ))
+    }
+
+    /// Get classes, ordered. With two classes the second class is the positive one.
+    pub fn classes(&self) -> &Vec<TY> {
+        self.classes.as_ref().unwrap()
+    }
+}
+
+impl<'a, TX: Number + RealNumber, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>> PartialEq
+    for NuSVC<'a, TX, TY, X, Y>
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.classes == other.classes
+            && self.classifiers.as_ref().unwrap().len() == other.classifiers.as_ref().unwrap().len()
+            && self
+                .classifiers
+                .as_ref()
+                .unwrap()
+                .iter()
+                .zip(other.classifiers.as_ref().unwrap().iter())
+                .all(|(a, b)| a.approximate_eq(b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::ToPrimitive;
+
+    use super::*;
+    use crate::linalg::basic::arrays::Array;
+    use crate::linalg::basic::matrix::DenseMatrix;
+    use crate::metrics::accuracy;
+    use crate::svm::Kernels;
+    use crate::test_datasets::iris;
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn nu_svc_fit_predict() {
+        let (x, y) = iris();
+
+        let params = NuSVCParameters::default()
+            .with_nu(0.3)
+            .with_kernel(Kernels::rbf().with_gamma(0.5));
+        let svc = NuSVC::fit(&x, &y, &params).unwrap();
+        assert_eq!(
+            svc.decision_function_multiclass(&x).unwrap().shape(),
+            (18, 3)
+        );
+
+        let y_hat: Vec<u32> = svc
+            .predict(&x)
+            .unwrap()
+            .iter()
+            .map(|e| e.to_u32().unwrap())
+            .collect();
+        let acc = accuracy(&y, &y_hat);
+        assert!(acc >= 0.9, "accuracy ({acc}) is not larger or equal to 0.9");
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn nu_svc_fraction_of_support_vectors() {
+        let (x, y) = iris();
+        let y: Vec<u32> = y.iter().map(|&c| u32::from(c > 0)).collect();
+
+        for nu in [0.2, 0.4, 0.6] {
+            let params = NuSVCParameters::default()
+                .with_nu(nu)
+                .with_kernel(Kernels::linear());
+            let svc = NuSVC::fit(&x, &y, &params).unwrap();
+            let decision = svc.decision_function(&x).unwrap();
+
+            // nu bounds the fraction of support vectors from below and of margin errors from above
+            let n_support_vectors = svc.classifiers.as_ref().unwrap()[0].instances.len();
+            let n_margin_errors = y
+                .iter()
+                .zip(decision.iter())
+                .filter(|(&label, &f)| (2. * label as f64 - 1.) * f < 1. - 1e-3)
+                .count();
+            assert!(n_support_vectors as f64 >= nu * 18. - 1e-8);
+            assert!(n_margin_errors as f64 <= nu * 18. + 1e-8);
+        }
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn nu_svc_infeasible_nu() {
+        let (x, y) = iris();
+        let y: Vec<u32> = y.iter().map(|&c| u32::from(c > 0)).collect();
+
+        // 6 and 12 observations, nu can be at most 2 / 3
+        let params = NuSVCParameters::default()
+            .with_nu(0.7)
+            .with_kernel(Kernels::linear());
+        assert!(NuSVC::fit(&x, &y, &params).is_err());
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn nu_svc_degenerate_problem() {
+        // the linear kernel of identical zero observations vanishes, so does r
+        let x = DenseMatrix::from_2d_array(&[&[0.], &[0.], &[0.], &[0.]]).unwrap();
+        let y: Vec<u32> = vec![0, 0, 1, 1];
+
+        let params = NuSVCParameters::default().with_kernel(Kernels::linear());
+        assert!(NuSVC::fit(&x, &y, &params).is_err());
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    #[cfg(all(feature = "serde", not(target_arch = "wasm32")))]
+    fn nu_svc_serde() {
+        let (x, y) = iris();
+
+        let params = NuSVCParameters::default().with_kernel(Kernels::linear());
+        let svc = NuSVC::fit(&x, &y, &params).unwrap();
+
+        let deserialized_svc: NuSVC<f64, u32, _, _> =
+            serde_json::from_str(&serde_json::to_string(&svc).unwrap()).unwrap();
+
+        assert_eq!(svc, deserialized_svc);
+    }
+}
@@ -0,0 +1,379 @@
+//! # Nu-Support Vector Regression.
+//!
+//! \\(\nu\\)-SVR is a reparameterization of [\\(\epsilon\\)-SVR](../svr/index.html) where the width \\(\epsilon\\) of the insensitive tube
+//! is found by the optimization and the parameter \\(\nu \in (0, 1]\\) controls the number of support vectors instead.
+//! \\(\nu\\) is an upper bound on the fraction of observations outside of the tube and a lower bound on the fraction of support vectors.
+//! The optimization problem is
+//!
+//! \\[\underset{w, b, \zeta, \zeta^*, \epsilon}{minimize} \space \space \frac{1}{2} \lVert \vec{w} \rVert^2 + C \left(\nu \epsilon + \frac{1}{m}\sum_{i=1}^m (\zeta_i + \zeta_i^*)\right) \\]
+//!
+//! subject to:
+//!
+//! \\[\langle\vec{w}, \vec{x}_i \rangle + b - y_i \leq \epsilon + \zeta_i \\]
+//! \\[y_i - \langle\vec{w}, \vec{x}_i \rangle - b \leq \epsilon + \zeta_i^* \\]
+//! \\[\zeta_i, \zeta_i^* \geq 0, \epsilon \geq 0 \space for \space any \space i = 1, ... , m\\]
+//!
+//! The problem is solved exactly by sequential minimal optimization, as in libsvm.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::basic::matrix::DenseMatrix;
+//! use smartcore::svm::Kernels;
+//! use smartcore::svm::nu_svr::{NuSVR, NuSVRParameters};
+//!
+//! let x = DenseMatrix::from_2d_array(&[
+//!            &[1.0], &[2.0], &[3.0], &[4.0], &[5.0], &[6.0], &[7.0], &[8.0],
+//!         ]).unwrap();
+//! let y: Vec<f64> = vec![1.1, 1.9, 3.2, 3.9, 5.1, 6.0, 6.8, 8.1];
+//!
+//! let params = &NuSVRParameters::default().with_nu(0.5).with_c(10.0).with_kernel(Kernels::linear());
+//! let svr = NuSVR::fit(&x, &y, params).unwrap();
+//!
+//! let y_hat = svr.predict(&x).unwrap();
+//! ```
+//!
+//! ## References:
+//!
+//! * ["New Support Vector Algorithms", Scholkopf B., Smola A.J., Williamson R.C., Bartlett P.L., 2000](https://alex.smola.org/papers/2000/SchSmoWilBar00.pdf)
+//! * ["LIBSVM: A Library for Support Vector Machines", Chang C.C., Lin C.J., 2011](https://www.csie.ntu.edu.tw/~cjlin/papers/libsvm.pdf)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+use num_traits::float::Float;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::api::{PredictorBorrow, SupervisedEstimatorBorrow};
+use crate::error::{Failed, FailedError};
+use crate::linalg::basic::arrays::{Array1, Array2, MutArray};
+use crate::numbers::basenum::Number;
+use crate::numbers::floatnum::FloatNumber;
+use crate::svm::smo::{KernelCache, Solver};
+use crate::svm::Kernel;
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+/// NuSVR Parameters
+pub struct NuSVRParameters<T: Number + FloatNumber + PartialOrd> {
+    /// Upper bound on the fraction of observations outside of the tube and lower bound on the fraction of support vectors, in \\((0, 1]\\).
+    pub nu: T,
+    /// Regularization parameter.
+    pub c: T,
+    /// Tolerance for stopping criterion.
+    pub tol: T,
+    /// The kernel function.
+    #[cfg_attr(
+        all(feature = "serde", target_arch = "wasm32"),
+        serde(skip_serializing, skip_deserializing)
+    )]
+    pub kernel: Option<Box<dyn Kernel>>,
+    /// Whether the solver uses the shrinking heuristic.
+    pub shrinking: bool,
+    /// Size of the kernel cache, in megabytes.
+    pub cache_size: f64,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+/// Nu-Support Vector Regression
+pub struct NuSVR<'a, T: Number + FloatNumber + PartialOrd, X: Array2<T>, Y: Array1<T>> {
+    instances: Option<Vec<Vec<f64>>>,
+    #[cfg_attr(feature = "serde", serde(skip_deserializing))]
+    parameters: Option<&'a NuSVRParameters<T>>,
+    w: Option<Vec<T>>,
+    b: T,
+    epsilon: T,
+    phantom: PhantomData<(X, Y)>,
+}
+
+impl<T: Number + FloatNumber + PartialOrd> NuSVRParameters<T> {
+    /// Upper bound on the fraction of observations outside of the tube and lower bound on the fraction of support vectors.
+    pub fn with_nu(mut self, nu: T) -> Self {
+        self.nu = nu;
+        self
+    }
+    /// Regularization parameter.
+    pub fn with_c(mut self, c: T) -> Self {
+        self.c = c;
+        self
+    }
+    /// Tolerance for stopping criterion.
+    pub fn with_tol(mut self, tol: T) -> Self {
+        self.tol = tol;
+        self
+    }
+    /// The kernel function.
+    pub fn with_kernel<K: Kernel + 'static>(mut self, kernel: K) -> Self {
+        self.kernel = Some(Box::new(kernel));
+        self
+    }
+    /// Whether the solver uses the shrinking heuristic.
+    pub fn with_shrinking(mut self, shrinking: bool) -> Self {
+        self.shrinking = shrinking;
+        self
+    }
+    /// Size of the kernel cache, in megabytes.
+    pub fn with_cache_size(mut self, cache_size: f64) -> Self {
+        self.cache_size = cache_size;
+        self
+    }
+}
+
+impl<T: Number + FloatNumber + PartialOrd> Default for NuSVRParameters<T> {
+    fn default() -> Self {
+        NuSVRParameters {
+            nu: T::from_f64(0.5).unwrap(),
+            c: T::one(),
+            tol: T::from_f64(1e-3).unwrap(),
+            kernel: Option::None,
+            shrinking: true,
+            cache_size: 200.,
+        }
+    }
+}
+
+impl<'a, T: Number + FloatNumber + PartialOrd, X: Array2<T>, Y: Array1<T>>
+    SupervisedEstimatorBorrow<'a, X, Y, NuSVRParameters<T>> for NuSVR<'a, T, X, Y>
+{
+    fn new() -> Self {
+        Self {
+            instances: Option::None,
+            parameters: Option::None,
+            w: Option::None,
+            b: T::zero(),
+            epsilon: T::zero(),
+            phantom: PhantomData,
+        }
+    }
+    fn fit(x: &'a X, y: &'a Y, parameters: &'a NuSVRParameters<T>) -> Result<Self, Failed> {
+        NuSVR::fit(x, y, parameters)
+    }
+}
+
+impl<'a, T: Number + FloatNumber + PartialOrd, X: Array2<T>, Y: Array1<T>> PredictorBorrow<'a, X, T>
+    for NuSVR<'a, T, X, Y>
+{
+    fn predict(&self, x: &'a X) -> Result<Vec<T>, Failed> {
+        self.predict(x)
+    }
+}
+
+impl<'a, T: Number + FloatNumber + PartialOrd, X: Array2<T>, Y: Array1<T>> NuSVR<'a, T, X, Y> {
+    /// Fits NuSVR to your data.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - target values
+    /// * `parameters` - optional parameters, use `Default::default()` to set parameters to default values.
+    pub fn fit(
+        x: &'a X,
+        y: &'a Y,
+        parameters: &'a NuSVRParameters<T>,
+    ) -> Result<NuSVR<'a, T, X, Y>, Failed> {
+        let (n, _) = x.shape();
+
+        if n != y.shape() {
+            return Err(Failed::fit(
+                "Number of rows of X doesn\'t match number of rows of Y",
+            ));
+        }
+
+        if parameters.kernel.is_none() {
+            return Err(Failed::because(
+                FailedError::ParametersError,
+                "kernel should be defined at this point, please use `with_kernel()`",
+            ));
+        }
+
+        let nu = parameters.nu.to_f64().unwrap();
+        if nu <= 0. || nu > 1. {
+            return Err(Failed::because(
+                FailedError::ParametersError,
+                &format!("nu should be in (0, 1], got {nu}"),
+            ));
+        }
+
+        let rows: Vec<Vec<f64>> = (0..n)
+            .map(|i| {
+                x.get_row(i)
+                    .iterator(0)
+                    .map(|e| e.to_f64().unwrap())
+                    .collect()
+            })
+            .collect();
+        let targets: Vec<f64> = y.iterator(0).map(|e| e.to_f64().unwrap()).collect();
+        let c = parameters.c.to_f64().unwrap();
+
+        // a feasible initial solution, alpha_i = alpha_i^* with the sum C * nu * n / 2
+        let mut sum = c * nu * n as f64 / 2.;
+        let mut alpha = vec![0.; 2 * n];
+        for i in 0..n {
+            alpha[i] = sum.min(c);
+            alpha[n + i] = alpha[i];
+            sum -= alpha[i];
+        }
+
+        // variable i < n is alpha_i and variable n + i is alpha_i^* of the same observation
+        let mut cache = KernelCache::new(
+            &rows,
+            parameters.kernel.as_ref().unwrap().as_ref(),
+            parameters.cache_size,
+        );
+        let solution = Solver::new(
+            &mut cache,
+            (0..n).chain(0..n).collect(),
+            (0..2 * n).map(|i| if i < n { 1. } else { -1. }).collect(),
+            targets
+                .iter()
+                .map(|y_i| -y_i)
+                .chain(targets.iter().copied())
+                .collect(),
+            vec![c; 2 * n],
+            alpha,
+            parameters.tol.to_f64().unwrap(),
+            parameters.shrinking,
+        )
+        .nu()
+        .solve()?;
+
+        let mut instances = Vec::new();
+        let mut w = Vec::new();
+        for (i, row) in rows.into_iter().enumerate() {
+            let coefficient = solution.alpha[i] - solution.alpha[n + i];
+            if coefficient != 0. {
+                instances.push(row);
+                w.push(T::from(coefficient).unwrap());
+            }
+        }
+
+        Ok(NuSVR {
+            instances: Some(instances),
+            parameters: Some(parameters),
+            w: Some(w),
+            b: T::from(-solution.rho).unwrap(),
+            epsilon: T::from(-solution.r).unwrap(),
+            phantom: PhantomData,
+        })
+    }
+
+    /// Predict target values from `x`
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict(&self, x: &'a X) -> Result<Vec<T>, Failed> {
+        let (n, _) = x.shape();
+
+        let mut y_hat: Vec<T> = Vec::<T>::zeros(n);
+
+        let mut x_i = Vec::with_capacity(n);
+        for i in 0..n {
+            x_i.clear();
+            x_i.extend(x.get_row(i).iterator(0).copied());
+            y_hat.set(i, self.predict_for_row(&x_i));
+        }
+
+        Ok(y_hat)
+    }
+
+    /// Width of the insensitive tube found by the optimization.
+    pub fn epsilon(&self) -> T {
+        self.epsilon
+    }
+
+    fn predict_for_row(&self, x: &[T]) -> T {
+        let kernel = self.parameters.as_ref().unwrap().kernel.as_ref().unwrap();
+        let mut f = self.b;
+
+        let xi: Vec<_> = x.iter().map(|e| e.to_f64().unwrap()).collect();
+        for (w, instance) in self
+            .w
+            .as_ref()
+            .unwrap()
+            .iter()
+            .zip(self.instances.as_ref().unwrap().iter())
+        {
+            f += *w * T::from(kernel.apply(&xi, instance).unwrap()).unwrap();
+        }
+
+        f
+    }
+}
+
+impl<'a, T: Number + FloatNumber + PartialOrd, X: Array2<T>, Y: Array1<T>> PartialEq
+    for NuSVR<'a, T, X, Y>
+{
+    fn eq(&self, other: &Self) -> bool {
+        let (w, other_w) = (self.w.as_ref().unwrap(), other.w.as_ref().unwrap());
+        let (instances, other_instances) = (
+            self.instances.as_ref().unwrap(),
+            other.instances.as_ref().unwrap(),
+        );
+        (self.b - other.b).abs() <= T::epsilon() * T::two()
+            && w.len() == other_w.len()
+            && instances.len() == other_instances.len()
+            && w.iter()
+                .zip(other_w.iter())
+                .all(|(a, b)| (*a - *b).abs() <= T::epsilon())
+            && instances
+                .iter()
+                .zip(other_instances.iter())
+                .all(|(a, b)| a.approximate_eq(b, f64::epsilon()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::mean_squared_error;
+    use crate::svm::Kernels;
+    use crate::test_datasets::sine;
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn nu_svr_fit_predict() {
+        let (x, y) = sine();
+
+        for nu in [0.2, 0.5, 0.8] {
+            let params = NuSVRParameters::default()
+                .with_nu(nu)
+                .with_c(10.)
+                .with_kernel(Kernels::rbf().with_gamma(0.5));
+            let svr = NuSVR::fit(&x, &y, &params).unwrap();
+            let y_hat = svr.predict(&x).unwrap();
+
+            assert!(mean_squared_error(&y, &y_hat) < 0.05);
+
+            // nu bounds the fraction of support vectors from below and of observations outside of the tube from above
+            let epsilon = svr.epsilon();
+            let n_outside = y
+                .iter()
+                .zip(y_hat.iter())
+                .filter(|(y, y_hat)| (*y - *y_hat).abs() > epsilon + 1e-3)
+                .count();
+            assert!(epsilon >= 0.);
+            assert!(svr.w.as_ref().unwrap().len() as f64 >= nu * 30. - 1e-8);
+            assert!(n_outside as f64 <= nu * 30. + 1e-8);
+        }
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    #[cfg(all(feature = "serde", not(target_arch = "wasm32")))]
+    fn nu_svr_serde() {
+        let (x, y) = sine();
+
+        let params = NuSVRParameters::default().with_kernel(Kernels::rbf().with_gamma(0.5));
+        let svr = NuSVR::fit(&x, &y, &params).unwrap();
+
+        let deserialized_svr: NuSVR<f64, _, _> =
+            serde_json::from_str(&serde_json::to_string(&svr).unwrap()).unwrap();
+
+        assert_eq!(svr, deserialized_svr);
+    }
+}
@@ -0,0 +1,396 @@
+//! # One-Class Support Vector Machine
+//!
+//! One-class SVM is an unsupervised algorithm for novelty and outlier detection. It estimates the support of the distribution of the
+//! training data, a region of the input space that contains most of the observations, by separating the data from the origin of the
+//! feature space with the largest margin. New points outside of this region are novelties.
+//!
+//! \\[\underset{w, \zeta, \rho}{minimize} \space \space \frac{1}{2} \lVert \vec{w} \rVert^2 - \rho + \frac{1}{\nu m}\sum_{i=1}^m \zeta_i \\]
+//!
+//! subject to:
+//!
+//! \\[\langle\vec{w}, \phi(\vec{x}_i) \rangle \geq \rho - \zeta_i \\]
+//! \\[\zeta_i \geq 0 \space for \space any \space i = 1, ... , m\\]
+//!
+//! The parameter \\(\nu \in (0, 1]\\) is an upper bound on the fraction of training observations outside of the region and a lower bound
+//! on the fraction of support vectors. The decision function \\(f(x) = \sum_i \alpha_i K(x_i, x) - \rho\\) is positive for inliers
+//! and negative for outliers. The [RBF kernel](../struct.RBFKernel.html) is the usual choice of kernel.
+//!
+//! The problem is solved exactly by sequential minimal optimization, as in libsvm.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::basic::matrix::DenseMatrix;
+//! use smartcore::svm::Kernels;
+//! use smartcore::svm::one_class_svm::{OneClassSVM, OneClassSVMParameters};
+//!
+//! // CPU load and memory usage of a server
+//! let x = DenseMatrix::from_2d_array(&[
+//!            &[0.51, 0.42], &[0.48, 0.45], &[0.55, 0.40], &[0.50, 0.47],
+//!            &[0.46, 0.43], &[0.53, 0.44], &[0.49, 0.41], &[0.52, 0.46],
+//!         ]).unwrap();
+//!
+//! let params = OneClassSVMParameters::default()
+//!     .with_nu(0.1)
+//!     .with_kernel(Kernels::rbf().with_gamma(10.0));
+//! let detector = OneClassSVM::fit(&x, params).unwrap();
+//!
+//! let new_metrics = DenseMatrix::from_2d_array(&[&[0.50, 0.44], &[0.97, 0.95]]).unwrap();
+//! // 1 for inliers, -1 for outliers
+//! let labels: Vec<i32> = detector.predict(&new_metrics).unwrap();
+//! ```
+//!
+//! ## References:
+//!
+//! * ["Estimating the Support of a High-Dimensional Distribution", Scholkopf B., Platt J.C., Shawe-Taylor J., Smola A.J., Williamson R.C., 2001](https://www.microsoft.com/en-us/research/wp-content/uploads/2016/02/tr-99-87.pdf)
+//! * ["LIBSVM: A Library for Support Vector Machines", Chang C.C., Lin C.J., 2011](https://www.csie.ntu.edu.tw/~cjlin/papers/libsvm.pdf)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::api::{Predictor, UnsupervisedEstimator};
+use crate::error::{Failed, FailedError};
+use crate::linalg::basic::arrays::{Array1, Array2};
+use crate::numbers::basenum::Number;
+use crate::numbers::realnum::RealNumber;
+use crate::svm::smo::{KernelCache, Solver};
+use crate::svm::Kernel;
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+/// OneClassSVM Parameters
+pub struct OneClassSVMParameters<T: Number + RealNumber> {
+    /// Upper bound on the fraction of outliers in the training data and lower bound on the fraction of support vectors, in \\((0, 1]\\).
+    pub nu: T,
+    /// Tolerance for stopping criterion.
+    pub tol: T,
+    /// The kernel function.
+    #[cfg_attr(
+        all(feature = "serde", target_arch = "wasm32"),
+        serde(skip_serializing, skip_deserializing)
+    )]
+    pub kernel: Option<Box<dyn Kernel>>,
+    /// Whether the solver uses the shrinking heuristic.
+    pub shrinking: bool,
+    /// Size of the kernel cache, in megabytes.
+    pub cache_size: f64,
+}
+
+/// One-Class Support Vector Machine
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+pub struct OneClassSVM<TX: Number + RealNumber, TY: Number, X: Array2<TX>, Y: Array1<TY>> {
+    parameters: OneClassSVMParameters<TX>,
+    instances: Vec<Vec<TX>>,
+    w: Vec<TX>,
+    rho: TX,
+    _phantom_ty: PhantomData<TY>,
+    _phantom_x: PhantomData<X>,
+    _phantom_y: PhantomData<Y>,
+}
+
+impl<T: Number + RealNumber> OneClassSVMParameters<T> {
+    /// Upper bound on the fraction of outliers in the training data and lower bound on the fraction of support vectors.
+    pub fn with_nu(mut self, nu: T) -> Self {
+        self.nu = nu;
+        self
+    }
+    /// Tolerance for stopping criterion.
+    pub fn with_tol(mut self, tol: T) -> Self {
+        self.tol = tol;
+        self
+    }
+    /// The kernel function.
+    pub fn with_kernel<K: Kernel + 'static>(mut self, kernel: K) -> Self {
+        self.kernel = Some(Box::new(kernel));
+        self
+    }
+    /// Whether the solver uses the shrinking heuristic.
+    pub fn with_shrinking(mut self, shrinking: bool) -> Self {
+        self.shrinking = shrinking;
+        self
+    }
+    /// Size of the kernel cache, in megabytes.
+    pub fn with_cache_size(mut self, cache_size: f64) -> Self {
+        self.cache_size = cache_size;
+        self
+    }
+}
+
+impl<T: Number + RealNumber> Default for OneClassSVMParameters<T> {
+    fn default() -> Self {
+        OneClassSVMParameters {
+            nu: T::half(),
+            tol: T::from_f64(1e-3).unwrap(),
+            kernel: Option::None,
+            shrinking: true,
+            cache_size: 200.,
+        }
+    }
+}
+
+impl<TX: Number + RealNumber, TY: Number, X: Array2<TX>, Y: Array1<TY>>
+    UnsupervisedEstimator<X, OneClassSVMParameters<TX>> for OneClassSVM<TX, TY, X, Y>
+{
+    fn fit(x: &X, parameters: OneClassSVMParameters<TX>) -> Result<Self, Failed> {
+        OneClassSVM::fit(x, parameters)
+    }
+}
+
+impl<TX: Number + RealNumber, TY: Number, X: Array2<TX>, Y: Array1<TY>> Predictor<X, Y>
+    for OneClassSVM<TX, TY, X, Y>
+{
+    fn predict(&self, x: &X) -> Result<Y, Failed> {
+        self.predict(x)
+    }
+}
+
+impl<TX: Number + RealNumber, TY: Number, X: Array2<TX>, Y: Array1<TY>> OneClassSVM<TX, TY, X, Y> {
+    /// Fits the one-class SVM to the training data.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `parameters` - parameters of the algorithm, the kernel is required.
+    pub fn fit(
+        x: &X,
+        parameters: OneClassSVMParameters<TX>,
+    ) -> Result<OneClassSVM<TX, TY, X, Y>, Failed> {
+        let (n, _) = x.shape();
+
+        if parameters.kernel.is_none() {
+            return Err(Failed::because(
+                FailedError::ParametersError,
+                "kernel should be defined at this point, please use `with_kernel()`",
+            ));
+        }
+
+        let nu = parameters.nu.to_f64().unwrap();
+        if nu <= 0. || nu > 1. {
+            return Err(Failed::because(
+                FailedError::ParametersError,
+                &format!("nu should be in (0, 1], got {nu}"),
+            ));
+        }
+
+        if n == 0 {
+            return Err(Failed::fit("training data is empty"));
+        }
+
+        let rows: Vec<Vec<f64>> = (0..n)
+            .map(|i| {
+                x.get_row(i)
+                    .iterator(0)
+                    .map(|e| e.to_f64().unwrap())
+                    .collect()
+            })
+            .collect();
+
+        // a feasible initial solution with the sum nu * n
+        let n_bounded = (nu * n as f64) as usize;
+        let mut alpha = vec![0.; n];
+        alpha[..n_bounded].iter_mut().for_each(|a| *a = 1.);
+        if n_bounded < n {
+            alpha[n_bounded] = nu * n as f64 - n_bounded as f64;
+        }
+
+        let solution = {
+            let mut cache = KernelCache::new(
+                &rows,
+                parameters.kernel.as_ref().unwrap().as_ref(),
+                parameters.cache_size,
+            );
+            Solver::new(
+                &mut cache,
+                (0..n).collect(),
+                vec![1.; n],
+                vec![0.; n],
+                vec![1.; n],
+                alpha,
+                parameters.tol.to_f64().unwrap(),
+                parameters.shrinking,
+            )
+            .solve()?
+        };
+
+        let mut instances = Vec::new();
+        let mut w = Vec::new();
+        for (i, &alpha) in solution.alpha.iter().enumerate() {
+            if alpha > 0. {
+                instances.push(x.get_row(i).iterator(0).copied().collect());
+                w.push(TX::from(alpha).unwrap());
+            }
+        }
+
+        Ok(OneClassSVM {
+            parameters,
+            instances,
+            w,
+            rho: TX::from(solution.rho).unwrap(),
+            _phantom_ty: PhantomData,
+            _phantom_x: PhantomData,
+            _phantom_y: PhantomData,
+        })
+    }
+
+    /// Predicts whether the rows in `x` are inliers, labeled 1, or outliers, labeled -1.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict(&self, x: &X) -> Result<Y, Failed> {
+        let outlier = TY::from_i8(-1)
+            .ok_or_else(|| Failed::predict("outliers are labeled -1, use a signed label type"))?;
+
+        let decision = self.decision_function(x)?;
+        Ok(Y::from_iterator(
+            decision
+                .into_iter()
+                .map(|f| if f >= TX::zero() { TY::one() } else { outlier }),
+            x.shape().0,
+        ))
+    }
+
+    /// Evaluates the decision function for the rows in `x`, positive values for inliers and negative values for outliers.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn decision_function(&self, x: &X) -> Result<Vec<TX>, Failed> {
+        let kernel = self.parameters.kernel.as_ref().unwrap();
+        let (n, _) = x.shape();
+
+        let instances: Vec<Vec<f64>> = self
+            .instances
+            .iter()
+            .map(|instance| instance.iter().map(|e| e.to_f64().unwrap()).collect())
+            .collect();
+
+        let mut decision = Vec::with_capacity(n);
+        for i in 0..n {
+            let row: Vec<f64> = x
+                .get_row(i)
+                .iterator(0)
+                .map(|e| e.to_f64().unwrap())
+                .collect();
+            let mut f = -self.rho;
+            for (w, instance) in self.w.iter().zip(instances.iter()) {
+                f += *w * TX::from(kernel.apply(&row, instance)?).unwrap();
+            }
+            decision.push(f);
+        }
+
+        Ok(decision)
+    }
+
+    /// Offset \\(\rho\\) of the decision function.
+    pub fn rho(&self) -> TX {
+        self.rho
+    }
+
+    /// Number of support vectors.
+    pub fn n_support_vectors(&self) -> usize {
+        self.instances.len()
+    }
+}
+
+impl<TX: Number + RealNumber, TY: Number, X: Array2<TX>, Y: Array1<TY>> PartialEq
+    for OneClassSVM<TX, TY, X, Y>
+{
+    fn eq(&self, other: &Self) -> bool {
+        (self.rho - other.rho).abs() <= TX::epsilon() * TX::two()
+            && self.instances == other.instances
+            && self.w.len() == other.w.len()
+            && self
+                .w
+                .iter()
+                .zip(other.w.iter())
+                .all(|(a, b)| (*a - *b).abs() <= TX::epsilon())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linalg::basic::matrix::DenseMatrix;
+    use crate::svm::Kernels;
+
+    /// a 6x6 grid around the origin and two distant points
+    fn server_metrics() -> DenseMatrix<f64> {
+        let mut rows: Vec<Vec<f64>> = (0..36)
+            .map(|i| vec![(i % 6) as f64 / 5. - 0.5, (i / 6) as f64 / 5. - 0.5])
+            .collect();
+        rows.push(vec![4., 4.]);
+        rows.push(vec![-3.5, 4.5]);
+        DenseMatrix::from_iterator(rows.into_iter().flatten(), 38, 2, 0)
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn one_class_svm_fit_predict() {
+        let x = server_metrics();
+
+        for nu in [0.1, 0.3] {
+            let params = OneClassSVMParameters::default()
+                .with_nu(nu)
+                .with_kernel(Kernels::rbf().with_gamma(0.5));
+            let detector: OneClassSVM<f64, i32, DenseMatrix<f64>, Vec<i32>> =
+                OneClassSVM::fit(&x, params).unwrap();
+            let labels = detector.predict(&x).unwrap();
+
+            // the distant points are outliers, the center of the grid is an inlier
+            assert_eq!(labels[36], -1);
+            assert_eq!(labels[37], -1);
+            assert_eq!(labels[14], 1);
+
+            // nu bounds the fraction of outliers from above and of support vectors from below
+            let n_outliers = labels.iter().filter(|&&label| label < 0).count();
+            assert!(n_outliers as f64 <= nu * 38. + 1.);
+            assert!(detector.n_support_vectors() as f64 >= nu * 38. - 1e-8);
+        }
+
+        let novelties = DenseMatrix::from_2d_array(&[&[0.05, -0.05], &[2.5, -3.]]).unwrap();
+        let params = OneClassSVMParameters::default()
+            .with_nu(0.1)
+            .with_kernel(Kernels::rbf().with_gamma(0.5));
+        let detector: OneClassSVM<f64, i32, DenseMatrix<f64>, Vec<i32>> =
+            OneClassSVM::fit(&x, params).unwrap();
+        let decision = detector.decision_function(&novelties).unwrap();
+        assert!(decision[0] > 0.);
+        assert!(decision[1] < 0.);
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn one_class_svm_unsigned_labels() {
+        let x = server_metrics();
+        let params = OneClassSVMParameters::default().with_kernel(Kernels::rbf().with_gamma(0.5));
+        let detector: OneClassSVM<f64, u32, DenseMatrix<f64>, Vec<u32>> =
+            OneClassSVM::fit(&x, params).unwrap();
+        assert!(detector.predict(&x).is_err());
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    #[cfg(all(feature = "serde", not(target_arch = "wasm32")))]
+    fn one_class_svm_serde() {
+        let x = server_metrics();
+        let params = OneClassSVMParameters::default().with_kernel(Kernels::rbf().with_gamma(0.5));
+        let detector: OneClassSVM<f64, i32, DenseMatrix<f64>, Vec<i32>> =
+            OneClassSVM::fit(&x, params).unwrap();
+
+        let deserialized: OneClassSVM<f64, i32, DenseMatrix<f64>, Vec<i32>> =
+            serde_json::from_str(&serde_json::to_string(&detector).unwrap()).unwrap();
+
+        assert_eq!(detector, deserialized);
+        assert_eq!(
+            detector.predict(&x).unwrap(),
+            deserialized.predict(&x).unwrap()
+        );
+    }
+}
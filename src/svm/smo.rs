@@ -0,0 +1,826 @@
+//! # Sequential Minimal Optimization
+//!
+//! Exact solver of the dual problem of support vector machines, a port of the solver of libsvm.
+//! Solves
+//!
+//! \\[\underset{\alpha}{minimize} \space \space \frac{1}{2} \alpha^T Q \alpha + p^T \alpha \\]
+//!
+//! subject to \\(y^T \alpha = \Delta\\) and \\(0 \leq \alpha_i \leq C_i\\), where \\(y_i \in \\{1, -1\\}\\) and \\(Q_{ij} = y_i y_j K(x_i, x_j)\\).
+//!
+//! The \\(\nu\\) formulations have the additional constraint that \\(\sum_{i: y_i = 1} \alpha_i\\) and \\(\sum_{i: y_i = -1} \alpha_i\\) are fixed,
+//! their pairs of variables are chosen among variables of the same sign.
+//!
+//! Every iteration updates the pair of variables chosen by working set selection with second order information (WSS3).
+//! Variables that are likely to stay at a bound are temporarily removed from the problem (shrinking) and
+//! rows of the kernel matrix are kept in a least recently used cache.
+//!
+//! ## References:
+//!
+//! * ["Working Set Selection Using Second Order Information for Training Support Vector Machines", Fan R.E., Chen P.H., Lin C.J., 2005](https://www.jmlr.org/papers/volume6/fan05a/fan05a.pdf)
+//! * ["LIBSVM: A Library for Support Vector Machines", Chang C.C., Lin C.J., 2011](https://www.csie.ntu.edu.tw/~cjlin/papers/libsvm.pdf)
+//!
+//! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
+//! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use crate::error::Failed;
+use crate::svm::Kernel;
+
+/// Curvature used in place of non-positive second derivatives, for kernels that are not positive semi-definite.
+const TAU: f64 = 1e-12;
+
+/// Rows of the kernel matrix of the training data, least recently used rows are evicted
+/// when the cache exceeds its size.
+pub(crate) struct KernelCache<'a> {
+    x: &'a [Vec<f64>],
+    kernel: &'a dyn Kernel,
+    rows: Vec<Option<Rc<Vec<f64>>>>,
+    /// cached rows, the least recently used first
+    lru: VecDeque<usize>,
+    capacity: usize,
+}
+
+impl<'a> KernelCache<'a> {
+    /// Creates a cache of at most `cache_size` megabytes for the kernel matrix of `x`.
+    pub(crate) fn new(x: &'a [Vec<f64>], kernel: &'a dyn Kernel, cache_size: f64) -> Self {
+        let row_size = (x.len() * std::mem::size_of::<f64>()).max(1) as f64;
+        let capacity = ((cache_size * 1024. * 1024.) / row_size).max(2.) as usize;
+        KernelCache {
+            x,
+            kernel,
+            rows: vec![None; x.len()],
+            lru: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.x.len()
+    }
+
+    fn apply(&self, i: usize, j: usize) -> Result<f64, Failed> {
+        self.kernel.apply(&self.x[i], &self.x[j])
+    }
+
+    /// Row `i` of the kernel matrix.
+    fn row(&mut self, i: usize) -> Result<Rc<Vec<f64>>, Failed> {
+        if let Some(row) = &self.rows[i] {
+            let row = Rc::clone(row);
+            if let Some(position) = self.lru.iter().position(|&j| j == i) {
+                self.lru.remove(position);
+            }
+            self.lru.push_back(i);
+            return Ok(row);
+        }
+
+        let row = Rc::new(
+            (0..self.len())
+                .map(|j| self.apply(i, j))
+                .collect::<Result<Vec<f64>, Failed>>()?,
+        );
+        if self.lru.len() >= self.capacity {
+            if let Some(evicted) = self.lru.pop_front() {
+                self.rows[evicted] = None;
+            }
+        }
+        self.rows[i] = Some(Rc::clone(&row));
+        self.lru.push_back(i);
+
+        Ok(row)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AlphaStatus {
+    LowerBound,
+    UpperBound,
+    Free,
+}
+
+/// Solution of the dual problem.
+#[derive(Debug)]
+pub(crate) struct Solution {
+    /// optimal dual variables
+    pub alpha: Vec<f64>,
+    /// offset of the decision function \\(f(x) = \sum_i y_i \alpha_i K(x_i, x) - \rho\\)
+    pub rho: f64,
+    /// scale \\(r\\) of the \\(\nu\\) formulation, zero for the standard formulation
+    pub r: f64,
+}
+
+/// SMO solver of the dual problem, see the [module documentation](index.html).
+pub(crate) struct Solver<'a, 'b> {
+    cache: &'b mut KernelCache<'a>,
+    /// row of the kernel matrix of every variable, several variables can share a row
+    index: Vec<usize>,
+    y: Vec<f64>,
+    p: Vec<f64>,
+    c: Vec<f64>,
+    alpha: Vec<f64>,
+    tol: f64,
+    shrinking: bool,
+    /// diagonal of Q
+    qd: Vec<f64>,
+    /// gradient of the objective
+    g: Vec<f64>,
+    /// gradient contribution of the variables at the upper bound, \\(\bar{G}_j = \sum_{i: \alpha_i = C_i} C_i Q_{ij}\\)
+    g_bar: Vec<f64>,
+    status: Vec<AlphaStatus>,
+    active: Vec<bool>,
+    active_set: Vec<usize>,
+    unshrink: bool,
+    /// solve the \\(\nu\\) formulation
+    nu: bool,
+}
+
+impl<'a, 'b> Solver<'a, 'b> {
+    /// * `cache` - kernel matrix of the training data
+    /// * `index` - row of the kernel matrix of every variable
+    /// * `y` - sign of every variable, 1 or -1
+    /// * `p` - linear term of the objective
+    /// * `c` - upper bound of every variable
+    /// * `alpha` - feasible initial solution
+    /// * `tol` - tolerance of the stopping criterion, the maximal violation of the KKT conditions
+    /// * `shrinking` - whether to use the shrinking heuristic
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(
+        cache: &'b mut KernelCache<'a>,
+        index: Vec<usize>,
+        y: Vec<f64>,
+        p: Vec<f64>,
+        c: Vec<f64>,
+        alpha: Vec<f64>,
+        tol: f64,
+        shrinking: bool,
+    ) -> Self {
+        let l = index.len();
+        Solver {
+            cache,
+            index,
+            y,
+            p,
+            c,
+            alpha,
+            tol,
+            shrinking,
+            qd: Vec::with_capacity(l),
+            g: Vec::with_capacity(l),
+            g_bar: vec![0.; l],
+            status: vec![AlphaStatus::Free; l],
+            active: vec![true; l],
+            active_set: (0..l).collect(),
+            unshrink: false,
+            nu: false,
+        }
+    }
+
+    /// Solve the \\(\nu\\) formulation, where the sums of the variables of either sign stay at their initial values.
+    pub(crate) fn nu(mut self) -> Self {
+        self.nu = true;
+        self
+    }
+
+    /// Element \\(Q_{ij}\\) given the kernel row of variable `i`.
+    fn q(&self, i: usize, kernel_row: &[f64], j: usize) -> f64 {
+        self.y[i] * self.y[j] * kernel_row[self.index[j]]
+    }
+
+    fn update_alpha_status(&mut self, i: usize) {
+        self.status[i] = if self.alpha[i] >= self.c[i] {
+            AlphaStatus::UpperBound
+        } else if self.alpha[i] <= 0. {
+            AlphaStatus::LowerBound
+        } else {
+            AlphaStatus::Free
+        };
+    }
+
+    fn is_upper_bound(&self, i: usize) -> bool {
+        self.status[i] == AlphaStatus::UpperBound
+    }
+
+    fn is_lower_bound(&self, i: usize) -> bool {
+        self.status[i] == AlphaStatus::LowerBound
+    }
+
+    fn is_free(&self, i: usize) -> bool {
+        self.status[i] == AlphaStatus::Free
+    }
+
+    /// Solves the dual problem.
+    pub(crate) fn solve(mut self) -> Result<Solution, Failed> {
+        let l = self.index.len();
+
+        for i in 0..l {
+            let k = self.cache.apply(self.index[i], self.index[i])?;
+            self.qd.push(k);
+            self.update_alpha_status(i);
+        }
+
+        self.g = self.p.clone();
+        for i in 0..l {
+            if !self.is_lower_bound(i) {
+                let row = self.cache.row(self.index[i])?;
+                let alpha_i = self.alpha[i];
+                for j in 0..l {
+                    self.g[j] += alpha_i * self.q(i, &row, j);
+                }
+                if self.is_upper_bound(i) {
+                    for j in 0..l {
+                        self.g_bar[j] += self.c[i] * self.q(i, &row, j);
+                    }
+                }
+            }
+        }
+
+        let max_iter = usize::max(10_000_000, 100 * l);
+        let mut counter = l.min(1000) + 1;
+        let mut n_iter = 0;
+
+        while n_iter < max_iter {
+            counter -= 1;
+            if counter == 0 {
+                counter = l.min(1000);
+                if self.shrinking {
+                    self.do_shrinking()?;
+                }
+            }
+
+            let (i, j) = match self.select_working_set()? {
+                Some(pair) => pair,
+                None => {
+                    // the problem is optimal on the active set, check all variables
+                    self.reconstruct_gradient()?;
+                    self.activate_all();
+                    match self.select_working_set()? {
+                        Some(pair) => {
+                            counter = 1;
+                            pair
+                        }
+                        None => break,
+                    }
+                }
+            };
+
+            n_iter += 1;
+            self.update(i, j)?;
+        }
+
+        if n_iter >= max_iter {
+            self.reconstruct_gradient()?;
+            self.activate_all();
+        }
+
+        let (rho, r) = if self.nu {
+            self.calculate_rho_nu()
+        } else {
+            (self.calculate_rho(), 0.)
+        };
+
+        Ok(Solution {
+            alpha: self.alpha,
+            rho,
+            r,
+        })
+    }
+
+    /// Optimizes the subproblem of variables `i` and `j`.
+    fn update(&mut self, i: usize, j: usize) -> Result<(), Failed> {
+        let q_i = self.cache.row(self.index[i])?;
+        let q_j = self.cache.row(self.index[j])?;
+
+        let c_i = self.c[i];
+        let c_j = self.c[j];
+        let old_alpha_i = self.alpha[i];
+        let old_alpha_j = self.alpha[j];
+        let q_ij = self.q(i, &q_i, j);
+
+        if self.y[i] != self.y[j] {
+            let mut quad_coef = self.qd[i] + self.qd[j] + 2. * q_ij;
+            if quad_coef <= 0. {
+                quad_coef = TAU;
+            }
+            let delta = (-self.g[i] - self.g[j]) / quad_coef;
+            let diff = self.alpha[i] - self.alpha[j];
+            self.alpha[i] += delta;
+            self.alpha[j] += delta;
+
+            if diff > 0. {
+                if self.alpha[j] < 0. {
+                    self.alpha[j] = 0.;
+                    self.alpha[i] = diff;
+                }
+            } else if self.alpha[i] < 0. {
+                self.alpha[i] = 0.;
+                self.alpha[j] = -diff;
+            }
+            if diff > c_i - c_j {
+                if self.alpha[i] > c_i {
+                    self.alpha[i] = c_i;
+                    self.alpha[j] = c_i - diff;
+                }
+            } else if self.alpha[j] > c_j {
+                self.alpha[j] = c_j;
+                self.alpha[i] = c_j + diff;
+            }
+        } else {
+            let mut quad_coef = self.qd[i] + self.qd[j] - 2. * q_ij;
+            if quad_coef <= 0. {
+                quad_coef = TAU;
+            }
+            let delta = (self.g[i] - self.g[j]) / quad_coef;
+            let sum = self.alpha[i] + self.alpha[j];
+            self.alpha[i] -= delta;
+            self.alpha[j] += delta;
+
+            if sum > c_i {
+                if self.alpha[i] > c_i {
+                    self.alpha[i] = c_i;
+                    self.alpha[j] = sum - c_i;
+                }
+            } else if self.alpha[j] < 0. {
+                self.alpha[j] = 0.;
+                self.alpha[i] = sum;
+            }
+            if sum > c_j {
+                if self.alpha[j] > c_j {
+                    self.alpha[j] = c_j;
+                    self.alpha[i] = sum - c_j;
+                }
+            } else if self.alpha[i] < 0. {
+                self.alpha[i] = 0.;
+                self.alpha[j] = sum;
+            }
+        }
+
+        let delta_alpha_i = self.alpha[i] - old_alpha_i;
+        let delta_alpha_j = self.alpha[j] - old_alpha_j;
+        for &k in self.active_set.iter() {
+            self.g[k] += self.q(i, &q_i, k) * delta_alpha_i + self.q(j, &q_j, k) * delta_alpha_j;
+        }
+
+        let upper_i = self.is_upper_bound(i);
+        let upper_j = self.is_upper_bound(j);
+        self.update_alpha_status(i);
+        self.update_alpha_status(j);
+        let l = self.index.len();
+        if upper_i != self.is_upper_bound(i) {
+            let sign = if upper_i { -1. } else { 1. };
+            for k in 0..l {
+                self.g_bar[k] += sign * c_i * self.q(i, &q_i, k);
+            }
+        }
+        if upper_j != self.is_upper_bound(j) {
+            let sign = if upper_j { -1. } else { 1. };
+            for k in 0..l {
+                self.g_bar[k] += sign * c_j * self.q(j, &q_j, k);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Working set selection with second order information, returns `None` when the KKT conditions hold within `tol`.
+    fn select_working_set(&mut self) -> Result<Option<(usize, usize)>, Failed> {
+        if self.nu {
+            return self.select_working_set_nu();
+        }
+
+        let mut g_max = f64::NEG_INFINITY;
+        let mut g_max2 = f64::NEG_INFINITY;
+        let mut g_max_idx = None;
+
+        for &t in self.active_set.iter() {
+            if self.y[t] > 0. {
+                if !self.is_upper_bound(t) && -self.g[t] >= g_max {
+                    g_max = -self.g[t];
+                    g_max_idx = Some(t);
+                }
+            } else if !self.is_lower_bound(t) && self.g[t] >= g_max {
+                g_max = self.g[t];
+                g_max_idx = Some(t);
+            }
+        }
+
+        let q_i = match g_max_idx {
+            Some(i) => Some(self.cache.row(self.index[i])?),
+            None => None,
+        };
+
+        let mut g_min_idx = None;
+        let mut obj_diff_min = f64::INFINITY;
+        for &j in self.active_set.iter() {
+            let (grad_diff, quad_coef) = if self.y[j] > 0. {
+                if self.is_lower_bound(j) {
+                    continue;
+                }
+                g_max2 = g_max2.max(self.g[j]);
+                let grad_diff = g_max + self.g[j];
+                match (g_max_idx, &q_i) {
+                    (Some(i), Some(q_i)) if grad_diff > 0. => (
+                        grad_diff,
+                        self.qd[i] + self.qd[j] - 2. * self.y[i] * self.q(i, q_i, j),
+                    ),
+                    _ => continue,
+                }
+            } else {
+                if self.is_upper_bound(j) {
+                    continue;
+                }
+                g_max2 = g_max2.max(-self.g[j]);
+                let grad_diff = g_max - self.g[j];
+                match (g_max_idx, &q_i) {
+                    (Some(i), Some(q_i)) if grad_diff > 0. => (
+                        grad_diff,
+                        self.qd[i] + self.qd[j] + 2. * self.y[i] * self.q(i, q_i, j),
+                    ),
+                    _ => continue,
+                }
+            };
+
+            let obj_diff = if quad_coef > 0. {
+                -(grad_diff * grad_diff) / quad_coef
+            } else {
+                -(grad_diff * grad_diff) / TAU
+            };
+            if obj_diff <= obj_diff_min {
+                g_min_idx = Some(j);
+                obj_diff_min = obj_diff;
+            }
+        }
+
+        if g_max + g_max2 < self.tol {
+            return Ok(None);
+        }
+
+        Ok(g_max_idx.zip(g_min_idx))
+    }
+
+    /// Working set selection of the \\(\nu\\) formulation, both variables have the same sign.
+    fn select_working_set_nu(&mut self) -> Result<Option<(usize, usize)>, Failed> {
+        // the most violating variables of either sign
+        let mut g_max_p = f64::NEG_INFINITY;
+        let mut g_max_p2 = f64::NEG_INFINITY;
+        let mut g_max_p_idx = None;
+        let mut g_max_n = f64::NEG_INFINITY;
+        let mut g_max_n2 = f64::NEG_INFINITY;
+        let mut g_max_n_idx = None;
+
+        for &t in self.active_set.iter() {
+            if self.y[t] > 0. {
+                if !self.is_upper_bound(t) && -self.g[t] >= g_max_p {
+                    g_max_p = -self.g[t];
+                    g_max_p_idx = Some(t);
+                }
+            } else if !self.is_lower_bound(t) && self.g[t] >= g_max_n {
+                g_max_n = self.g[t];
+                g_max_n_idx = Some(t);
+            }
+        }
+
+        let q_ip = match g_max_p_idx {
+            Some(i) => Some(self.cache.row(self.index[i])?),
+            None => None,
+        };
+        let q_in = match g_max_n_idx {
+            Some(i) => Some(self.cache.row(self.index[i])?),
+            None => None,
+        };
+
+        let mut g_min_idx = None;
+        let mut obj_diff_min = f64::INFINITY;
+        for &j in self.active_set.iter() {
+            let (grad_diff, quad_coef) = if self.y[j] > 0. {
+                if self.is_lower_bound(j) {
+                    continue;
+                }
+                g_max_p2 = g_max_p2.max(self.g[j]);
+                let grad_diff = g_max_p + self.g[j];
+                match (g_max_p_idx, &q_ip) {
+                    (Some(i), Some(q_i)) if grad_diff > 0. => {
+                        (grad_diff, self.qd[i] + self.qd[j] - 2. * self.q(i, q_i, j))
+                    }
+                    _ => continue,
+                }
+            } else {
+                if self.is_upper_bound(j) {
+                    continue;
+                }
+                g_max_n2 = g_max_n2.max(-self.g[j]);
+                let grad_diff = g_max_n - self.g[j];
+                match (g_max_n_idx, &q_in) {
+                    (Some(i), Some(q_i)) if grad_diff > 0. => {
+                        (grad_diff, self.qd[i] + self.qd[j] - 2. * self.q(i, q_i, j))
+                    }
+                    _ => continue,
+                }
+            };
+
+            let obj_diff = if quad_coef > 0. {
+                -(grad_diff * grad_diff) / quad_coef
+            } else {
+                -(grad_diff * grad_diff) / TAU
+            };
+            if obj_diff <= obj_diff_min {
+                g_min_idx = Some(j);
+                obj_diff_min = obj_diff;
+            }
+        }
+
+        if f64::max(g_max_p + g_max_p2, g_max_n + g_max_n2) < self.tol {
+            return Ok(None);
+        }
+
+        Ok(g_min_idx.and_then(|j| {
+            let i = if self.y[j] > 0. {
+                g_max_p_idx
+            } else {
+                g_max_n_idx
+            };
+            i.map(|i| (i, j))
+        }))
+    }
+
+    fn be_shrunk(&self, i: usize, g_max1: f64, g_max2: f64) -> bool {
+        if self.is_upper_bound(i) {
+            if self.y[i] > 0. {
+                -self.g[i] > g_max1
+            } else {
+                -self.g[i] > g_max2
+            }
+        } else if self.is_lower_bound(i) {
+            if self.y[i] > 0. {
+                self.g[i] > g_max2
+            } else {
+                self.g[i] > g_max1
+            }
+        } else {
+            false
+        }
+    }
+
+    /// Removes variables that are likely to stay at their bounds from the active set.
+    fn do_shrinking(&mut self) -> Result<(), Failed> {
+        if self.nu {
+            return self.do_shrinking_nu();
+        }
+
+        // maximal violations of the KKT conditions, -y_i grad_i over I_up and y_i grad_i over I_low
+        let mut g_max1 = f64::NEG_INFINITY;
+        let mut g_max2 = f64::NEG_INFINITY;
+
+        for &i in self.active_set.iter() {
+            let (up, low) = if self.y[i] > 0. {
+                (-self.g[i], self.g[i])
+            } else {
+                (self.g[i], -self.g[i])
+            };
+            let (up_allowed, low_allowed) = if self.y[i] > 0. {
+                (!self.is_upper_bound(i), !self.is_lower_bound(i))
+            } else {
+                (!self.is_lower_bound(i), !self.is_upper_bound(i))
+            };
+            if up_allowed && up >= g_max1 {
+                g_max1 = up;
+            }
+            if low_allowed && low >= g_max2 {
+                g_max2 = low;
+            }
+        }
+
+        if !self.unshrink && g_max1 + g_max2 <= self.tol * 10. {
+            self.unshrink = true;
+            self.reconstruct_gradient()?;
+            self.activate_all();
+        }
+
+        for i in 0..self.index.len() {
+            if self.active[i] && self.be_shrunk(i, g_max1, g_max2) {
+                self.active[i] = false;
+            }
+        }
+        self.active_set = (0..self.index.len()).filter(|&i| self.active[i]).collect();
+
+        Ok(())
+    }
+
+    /// Shrinking of the \\(\nu\\) formulation, the violations are measured separately for either sign.
+    fn do_shrinking_nu(&mut self) -> Result<(), Failed> {
+        // maximal -grad_i and grad_i of positive (1, 2) and negative (3, 4) variables
+        let mut g_max1 = f64::NEG_INFINITY;
+        let mut g_max2 = f64::NEG_INFINITY;
+        let mut g_max3 = f64::NEG_INFINITY;
+        let mut g_max4 = f64::NEG_INFINITY;
+
+        for &i in self.active_set.iter() {
+            if !self.is_upper_bound(i) {
+                if self.y[i] > 0. {
+                    g_max1 = g_max1.max(-self.g[i]);
+                } else {
+                    g_max4 = g_max4.max(-self.g[i]);
+                }
+            }
+            if !self.is_lower_bound(i) {
+                if self.y[i] > 0. {
+                    g_max2 = g_max2.max(self.g[i]);
+                } else {
+                    g_max3 = g_max3.max(self.g[i]);
+                }
+            }
+        }
+
+        if !self.unshrink && f64::max(g_max1 + g_max2, g_max3 + g_max4) <= self.tol * 10. {
+            self.unshrink = true;
+            self.reconstruct_gradient()?;
+            self.activate_all();
+        }
+
+        for i in 0..self.index.len() {
+            if self.active[i] && self.be_shrunk_nu(i, [g_max1, g_max2, g_max3, g_max4]) {
+                self.active[i] = false;
+            }
+        }
+        self.active_set = (0..self.index.len()).filter(|&i| self.active[i]).collect();
+
+        Ok(())
+    }
+
+    fn be_shrunk_nu(&self, i: usize, g_max: [f64; 4]) -> bool {
+        if self.is_upper_bound(i) {
+            if self.y[i] > 0. {
+                -self.g[i] > g_max[0]
+            } else {
+                -self.g[i] > g_max[3]
+            }
+        } else if self.is_lower_bound(i) {
+            if self.y[i] > 0. {
+                self.g[i] > g_max[1]
+            } else {
+                self.g[i] > g_max[2]
+            }
+        } else {
+            false
+        }
+    }
+
+    fn activate_all(&mut self) {
+        self.active.iter_mut().for_each(|a| *a = true);
+        self.active_set = (0..self.index.len()).collect();
+    }
+
+    /// Recomputes the gradient of the inactive variables.
+    fn reconstruct_gradient(&mut self) -> Result<(), Failed> {
+        let l = self.index.len();
+        if self.active_set.len() == l {
+            return Ok(());
+        }
+
+        let inactive: Vec<usize> = (0..l).filter(|&j| !self.active[j]).collect();
+        for &j in inactive.iter() {
+            self.g[j] = self.g_bar[j] + self.p[j];
+        }
+
+        let n_free = self.active_set.iter().filter(|&&j| self.is_free(j)).count();
+
+        if n_free * l > 2 * self.active_set.len() * inactive.len() {
+            for &i in inactive.iter() {
+                let q_i = self.cache.row(self.index[i])?;
+                for &j in self.active_set.iter() {
+                    if self.is_free(j) {
+                        self.g[i] += self.alpha[j] * self.q(i, &q_i, j);
+                    }
+                }
+            }
+        } else {
+            for &i in self.active_set.clone().iter() {
+                if self.is_free(i) {
+                    let q_i = self.cache.row(self.index[i])?;
+                    for &j in inactive.iter() {
+                        self.g[j] += self.alpha[i] * self.q(i, &q_i, j);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Offset of the decision function, the average over free variables or the middle of the feasible interval.
+    fn calculate_rho(&self) -> f64 {
+        let mut n_free = 0;
+        let mut sum_free = 0.;
+        let mut ub = f64::INFINITY;
+        let mut lb = f64::NEG_INFINITY;
+
+        for &i in self.active_set.iter() {
+            let y_g = self.y[i] * self.g[i];
+            if self.is_upper_bound(i) {
+                if self.y[i] < 0. {
+                    ub = ub.min(y_g);
+                } else {
+                    lb = lb.max(y_g);
+                }
+            } else if self.is_lower_bound(i) {
+                if self.y[i] > 0. {
+                    ub = ub.min(y_g);
+                } else {
+                    lb = lb.max(y_g);
+                }
+            } else {
+                n_free += 1;
+                sum_free += y_g;
+            }
+        }
+
+        if n_free > 0 {
+            sum_free / n_free as f64
+        } else {
+            (ub + lb) / 2.
+        }
+    }
+
+    /// Offset and scale of the \\(\nu\\) formulation, from the offsets of the positive and the negative variables.
+    fn calculate_rho_nu(&self) -> (f64, f64) {
+        // upper bound, lower bound, sum over free variables and number of free variables of either sign
+        let mut bounds = [[f64::INFINITY, f64::NEG_INFINITY, 0., 0.]; 2];
+
+        for &i in self.active_set.iter() {
+            let b = &mut bounds[usize::from(self.y[i] < 0.)];
+            if self.is_upper_bound(i) {
+                b[1] = b[1].max(self.g[i]);
+            } else if self.is_lower_bound(i) {
+                b[0] = b[0].min(self.g[i]);
+            } else {
+                b[2] += self.g[i];
+                b[3] += 1.;
+            }
+        }
+
+        let [r1, r2] = bounds.map(|[ub, lb, sum_free, n_free]| {
+            if n_free > 0. {
+                sum_free / n_free
+            } else {
+                (ub + lb) / 2.
+            }
+        });
+
+        ((r1 - r2) / 2., (r1 + r2) / 2.)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::svm::Kernels;
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn kernel_cache_evicts_least_recently_used() {
+        let x: Vec<Vec<f64>> = (0..4).map(|i| vec![i as f64]).collect();
+        let kernel = Kernels::linear();
+        let mut cache = KernelCache::new(&x, &kernel, 0.);
+        assert_eq!(cache.capacity, 2);
+
+        assert_eq!(*cache.row(1).unwrap(), vec![0., 1., 2., 3.]);
+        cache.row(2).unwrap();
+        cache.row(1).unwrap();
+        cache.row(3).unwrap();
+        assert!(cache.rows[1].is_some());
+        assert!(cache.rows[2].is_none());
+        assert_eq!(*cache.row(3).unwrap(), vec![0., 3., 6., 9.]);
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn solve_two_points() {
+        // the maximal margin hyperplane of 0 and 2 is x = 1, w = 1 and alpha = w^2 / 2
+        let x: Vec<Vec<f64>> = vec![vec![0.], vec![2.]];
+        let kernel = Kernels::linear();
+        for shrinking in [false, true] {
+            let mut cache = KernelCache::new(&x, &kernel, 1.);
+            let solution = Solver::new(
+                &mut cache,
+                vec![0, 1],
+                vec![-1., 1.],
+                vec![-1., -1.],
+                vec![100., 100.],
+                vec![0., 0.],
+                1e-3,
+                shrinking,
+            )
+            .solve()
+            .unwrap();
+
+            assert!((solution.alpha[0] - 0.5).abs() < 1e-8);
+            assert!((solution.alpha[1] - 0.5).abs() < 1e-8);
+            assert!((solution.rho - 1.).abs() < 1e-8);
+        }
+    }
+}
@@ -1,6 +1,7 @@
 //! # Support Vector Classifier.
 //!
 //! Support Vector Classifier (SVC) is a binary classifier that uses an optimal hyperplane to separate the points in the input variable space by their class.
+//! Problems with more than two classes are reduced to several binary problems, see [multiclass classification](#multiclass-classification).
 //!
 //! During training, SVC chooses a Maximal-Margin hyperplane that can separate all training instances with the largest margin.
 //! The margin is calculated as the perpendicular distance from the boundary to only the closest points. Hence, only these points are relevant in defining
@@ -24,6 +25,34 @@
 //! The optimizer reaches accuracies similar to that of a real SVM after performing two passes through the training examples. You can choose the number of passes
 //! through the data that the algorithm takes by changing the `epoch` parameter of the classifier.
 //!
+//! When exact solutions are required, for example to reproduce results of libsvm, set `solver` to [`SVMSolverName::SMO`](../enum.SVMSolverName.html).
+//! This solver implements sequential minimal optimization with second order working set selection, the shrinking heuristic and
+//! a kernel cache of `cache_size` megabytes, and stops when the violation of the optimality conditions is below `tol`.
+//!
+//! ## Multiclass classification
+//!
+//! Class labels can be any ordered values, see [`classes`](struct.SVC.html#method.classes). With two classes the second class is the positive one.
+//! With \\(k > 2\\) classes SVC trains several binary classifiers, chosen by the `multiclass_strategy` parameter:
+//!
+//! * _One-vs-one_, the default, as in libsvm. A classifier is trained for each of the \\(k(k-1)/2\\) pairs of classes on the observations of these two classes,
+//!   and a new point is assigned to the class that wins the most pairwise votes.
+//! * _One-vs-rest_. A classifier is trained for each class against all other classes, and a new point is assigned to the class with the largest decision value.
+//!
+//! One-vs-one trains more classifiers, but each of them on a fraction of the data, so it is usually faster for kernel SVMs.
+//!
+//! ## Sample and class weights
+//!
+//! The penalty of every training observation is \\(C_i = C w_i c_{y_i}\\), where \\(w_i\\) is its weight passed to
+//! [`fit_with_sample_weight`](struct.SVC.html#method.fit_with_sample_weight) and \\(c_{y_i}\\) is the weight of its class set by the `class_weight` parameter.
+//! On imbalanced problems [`ClassWeight::Balanced`](../../algorithm/sample_weight/enum.ClassWeight.html) penalizes errors on the minority class more, so that the
+//! classifier does not predict the majority class everywhere.
+//!
+//! ## Probability estimates
+//!
+//! With the `probability` parameter set, SVC fits Platt's sigmoid \\(P(y = 1 \mid f) = 1 / (1 + e^{Af + B})\\) to decision values \\(f\\)
+//! obtained by 5-fold cross-validation of every binary classifier, and [`predict_proba`](struct.SVC.html#method.predict_proba) returns class probabilities.
+//! One-vs-one pairwise probabilities are combined by pairwise coupling. The argmax of the probabilities may disagree with `predict` for points close to the boundary.
+//!
 //! Example:
 //!
 //! ```
@@ -64,10 +93,45 @@
 //! let y_hat = svc.predict(&x).unwrap();
 //! ```
 //!
+//! Multiclass example:
+//!
+//! ```
+//! use smartcore::linalg::basic::matrix::DenseMatrix;
+//! use smartcore::svm::Kernels;
+//! use smartcore::svm::svc::{MultiClassStrategy, SVC, SVCParameters};
+//!
+//! let x = DenseMatrix::from_2d_array(&[
+//!            &[5.1, 3.5, 1.4, 0.2],
+//!            &[4.9, 3.0, 1.4, 0.2],
+//!            &[4.7, 3.2, 1.3, 0.2],
+//!            &[7.0, 3.2, 4.7, 1.4],
+//!            &[6.4, 3.2, 4.5, 1.5],
+//!            &[6.9, 3.1, 4.9, 1.5],
+//!            &[6.3, 3.3, 6.0, 2.5],
+//!            &[5.8, 2.7, 5.1, 1.9],
+//!            &[7.1, 3.0, 5.9, 2.1],
+//!         ]).unwrap();
+//! let y: Vec<u32> = vec![0, 0, 0, 1, 1, 1, 2, 2, 2];
+//!
+//! let params = &SVCParameters::default()
+//!     .with_c(200.0)
+//!     .with_kernel(Kernels::linear())
+//!     .with_multiclass_strategy(MultiClassStrategy::OneVsRest);
+//! let svc = SVC::fit(&x, &y, params).unwrap();
+//!
+//! let y_hat = svc.predict(&x).unwrap();
+//! assert_eq!(svc.classes(), &vec![0, 1, 2]);
+//! ```
+//!
 //! ## References:
 //!
 //! * ["Support Vector Machines", Kowalczyk A., 2017](https://www.svm-tutorial.com/2017/10/support-vector-machines-succinctly-released/)
 //! * ["Fast Kernel Classifiers with Online and Active Learning", Bordes A., Ertekin S., Weston J., Bottou L., 2005](https://www.jmlr.org/papers/volume6/bordes05a/bordes05a.pdf)
+//! * ["Working Set Selection Using Second Order Information for Training Support Vector Machines", Fan R.E., Chen P.H., Lin C.J., 2005](https://www.jmlr.org/papers/volume6/fan05a/fan05a.pdf)
+//! * ["A comparison of methods for multiclass support vector machines", Hsu C.W., Lin C.J., 2002](https://www.csie.ntu.edu.tw/~cjlin/papers/multisvm.pdf)
+//! * ["Probabilistic Outputs for Support Vector Machines and Comparisons to Regularized Likelihood Methods", Platt J., 1999](https://www.researchgate.net/publication/2594015)
+//! * ["A note on Platt's probabilistic outputs for support vector machines", Lin H.T., Lin C.J., Weng R.C., 2007](https://www.csie.ntu.edu.tw/~cjlin/papers/plattprob.pdf)
+//! * ["Probability Estimates for Multi-class Classification by Pairwise Coupling", Wu T.F., Lin C.J., Weng R.C., 2004](https://www.jmlr.org/papers/volume5/wu04a/wu04a.pdf)
 //!
 //! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
 //! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
@@ -82,22 +146,48 @@ use rand::seq::SliceRandom;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+use crate::algorithm::sample_weight::{check_sample_weight, resolve_class_weight, ClassWeight};
 use crate::api::{PredictorBorrow, SupervisedEstimatorBorrow};
 use crate::error::{Failed, FailedError};
 use crate::linalg::basic::arrays::{Array1, Array2, MutArray};
 use crate::numbers::basenum::Number;
 use crate::numbers::realnum::RealNumber;
 use crate::rand_custom::get_rng_impl;
-use crate::svm::Kernel;
+use crate::svm::smo::{KernelCache, Solver};
+use crate::svm::{Kernel, SVMSolverName};
+
+/// Number of cross-validation folds used to fit the sigmoid of probability estimates.
+const PROBABILITY_FOLDS: usize = 5;
+/// Pairwise probabilities are clipped to `[MIN_PROBABILITY, 1 - MIN_PROBABILITY]` before coupling.
+const MIN_PROBABILITY: f64 = 1e-7;
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+/// Reduction of a problem with more than two classes to binary problems.
+pub enum MultiClassStrategy {
+    /// One classifier for every pair of classes, the class with most votes wins
+    #[default]
+    OneVsOne,
+    /// One classifier for every class against all other classes, the class with the largest decision value wins
+    OneVsRest,
+}
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug)]
 /// SVC Parameters
 pub struct SVCParameters<TX: Number + RealNumber, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>> {
-    /// Number of epochs.
+    /// Number of epochs of the approximate solver.
     pub epoch: usize,
+    /// Solver of the optimization problem.
+    pub solver: SVMSolverName,
+    /// Whether the SMO solver uses the shrinking heuristic.
+    pub shrinking: bool,
+    /// Size of the kernel cache of the SMO solver, in megabytes.
+    pub cache_size: f64,
     /// Regularization parameter.
     pub c: TX,
+    /// Weights associated with classes, all classes have weight one when `None`.
+    pub class_weight: Option<ClassWeight>,
     /// Tolerance for stopping criterion.
     pub tol: TX,
     /// The kernel function.
@@ -106,6 +196,11 @@ pub struct SVCParameters<TX: Number + RealNumber, TY: Number + Ord, X: Array2<TX
         serde(skip_serializing, skip_deserializing)
     )]
     pub kernel: Option<Box<dyn Kernel>>,
+    /// Reduction of a problem with more than two classes to binary problems.
+    pub multiclass_strategy: MultiClassStrategy,
+    /// Fit a sigmoid to cross-validated decision values so that [`predict_proba`](struct.SVC.html#method.predict_proba) can estimate class probabilities.
+    /// Fitting takes about six times longer.
+    pub probability: bool,
     /// Unused parameter.
     m: PhantomData<(X, Y, TY)>,
     /// Controls the pseudo random number generation for shuffling the data for probability estimates
@@ -124,14 +219,27 @@ pub struct SVCParameters<TX: Number + RealNumber, TY: Number + Ord, X: Array2<TX
 /// Support Vector Classifier
 pub struct SVC<'a, TX: Number + RealNumber, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>> {
     classes: Option<Vec<TY>>,
-    instances: Option<Vec<Vec<TX>>>,
     #[cfg_attr(feature = "serde", serde(skip))]
     parameters: Option<&'a SVCParameters<TX, TY, X, Y>>,
-    w: Option<Vec<TX>>,
-    b: Option<TX>,
+    classifiers: Option<Vec<BinaryClassifier<TX>>>,
     phantomdata: PhantomData<(X, Y)>,
 }
 
+/// Decision function of one of the binary problems a multiclass problem is reduced to.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialEq)]
+pub(crate) struct BinaryClassifier<TX: Number + RealNumber> {
+    /// index of the class with positive decision values
+    pub(crate) positive: usize,
+    /// index of the class with negative decision values, `None` for all other classes
+    pub(crate) negative: Option<usize>,
+    pub(crate) instances: Vec<Vec<TX>>,
+    pub(crate) w: Vec<TX>,
+    pub(crate) b: TX,
+    /// coefficients \\(A, B\\) of the sigmoid \\(P(positive \mid f) = 1 / (1 + e^{Af + B})\\)
+    pub(crate) sigmoid: Option<(TX, TX)>,
+}
+
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug)]
 struct SupportVector<TX: Number + RealNumber> {
@@ -151,7 +259,10 @@ struct Cache<TX: Number + RealNumber, TY: Number + Ord, X: Array2<TX>, Y: Array1
 
 struct Optimizer<'a, TX: Number + RealNumber, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>> {
     x: &'a X,
-    y: &'a Y,
+    /// labels, either 1 or -1
+    y: &'a [TX],
+    /// regularization parameter of every observation
+    c: &'a [f64],
     parameters: &'a SVCParameters<TX, TY, X, Y>,
     svmin: usize,
     svmax: usize,
@@ -165,16 +276,36 @@ struct Optimizer<'a, TX: Number + RealNumber, TY: Number + Ord, X: Array2<TX>, Y
 impl<TX: Number + RealNumber, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>>
     SVCParameters<TX, TY, X, Y>
 {
-    /// Number of epochs.
+    /// Number of epochs of the approximate solver.
     pub fn with_epoch(mut self, epoch: usize) -> Self {
         self.epoch = epoch;
         self
     }
+    /// Solver of the optimization problem.
+    pub fn with_solver(mut self, solver: SVMSolverName) -> Self {
+        self.solver = solver;
+        self
+    }
+    /// Whether the SMO solver uses the shrinking heuristic.
+    pub fn with_shrinking(mut self, shrinking: bool) -> Self {
+        self.shrinking = shrinking;
+        self
+    }
+    /// Size of the kernel cache of the SMO solver, in megabytes.
+    pub fn with_cache_size(mut self, cache_size: f64) -> Self {
+        self.cache_size = cache_size;
+        self
+    }
     /// Regularization parameter.
     pub fn with_c(mut self, c: TX) -> Self {
         self.c = c;
         self
     }
+    /// Weights associated with classes.
+    pub fn with_class_weight(mut self, class_weight: ClassWeight) -> Self {
+        self.class_weight = Some(class_weight);
+        self
+    }
     /// Tolerance for stopping criterion.
     pub fn with_tol(mut self, tol: TX) -> Self {
         self.tol = tol;
@@ -191,6 +322,18 @@ impl<TX: Number + RealNumber, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>>
         self.seed = seed;
         self
     }
+
+    /// Reduction of a problem with more than two classes to binary problems.
+    pub fn with_multiclass_strategy(mut self, multiclass_strategy: MultiClassStrategy) -> Self {
+        self.multiclass_strategy = multiclass_strategy;
+        self
+    }
+
+    /// Estimate class probabilities with Platt scaling.
+    pub fn with_probability(mut self, probability: bool) -> Self {
+        self.probability = probability;
+        self
+    }
 }
 
 impl<TX: Number + RealNumber, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>> Default
@@ -199,9 +342,15 @@ impl<TX: Number + RealNumber, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>> De
     fn default() -> Self {
         SVCParameters {
             epoch: 2,
+            solver: SVMSolverName::default(),
+            shrinking: true,
+            cache_size: 200.,
             c: TX::one(),
+            class_weight: Option::None,
             tol: TX::from_f64(1e-3).unwrap(),
             kernel: Option::None,
+            multiclass_strategy: MultiClassStrategy::default(),
+            probability: false,
             m: PhantomData,
             seed: Option::None,
         }
@@ -214,10 +363,8 @@ impl<'a, TX: Number + RealNumber, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>
     fn new() -> Self {
         Self {
             classes: Option::None,
-            instances: Option::None,
             parameters: Option::None,
-            w: Option::None,
-            b: Option::None,
+            classifiers: Option::None,
             phantomdata: PhantomData,
         }
     }
@@ -249,6 +396,21 @@ impl<'a, TX: Number + RealNumber, TY: Number + Ord, X: Array2<TX> + 'a, Y: Array
         x: &'a X,
         y: &'a Y,
         parameters: &'a SVCParameters<TX, TY, X, Y>,
+    ) -> Result<SVC<'a, TX, TY, X, Y>, Failed> {
+        Self::fit_with_sample_weight(x, y, &vec![TX::one(); x.shape().0], parameters)
+    }
+
+    /// Fits SVC to your data, the regularization parameter of every observation is multiplied by its weight
+    /// and by the weight of its class.
+    /// * `x` - _NxM_ matrix with _N_ observations and _M_ features in each observation.
+    /// * `y` - class labels
+    /// * `sample_weight` - non-negative weight of every observation
+    /// * `parameters` - optional parameters, use `Default::default()` to set parameters to default values.
+    pub fn fit_with_sample_weight(
+        x: &'a X,
+        y: &'a Y,
+        sample_weight: &[TX],
+        parameters: &'a SVCParameters<TX, TY, X, Y>,
     ) -> Result<SVC<'a, TX, TY, X, Y>, Failed> {
         let (n, _) = x.shape();
 
@@ -267,58 +429,226 @@ impl<'a, TX: Number + RealNumber, TY: Number + Ord, X: Array2<TX> + 'a, Y: Array
 
         let classes = y.unique();
 
-        if classes.len() != 2 {
+        if classes.len() < 2 {
             return Err(Failed::fit(&format!(
                 "Incorrect number of classes: {}",
                 classes.len()
             )));
         }
 
-        // Make sure class labels are either 1 or -1
-        for e in y.iterator(0) {
-            let y_v = e.to_i32().unwrap();
-            if y_v != -1 && y_v != 1 {
-                return Err(Failed::because(
-                    FailedError::ParametersError,
-                    "Class labels must be 1 or -1",
-                ));
-            }
-        }
+        check_sample_weight(sample_weight, n)?;
 
-        let optimizer: Optimizer<'_, TX, TY, X, Y> = Optimizer::new(x, y, parameters);
+        let k = classes.len();
+        let y_idx: Vec<usize> = y
+            .iterator(0)
+            .map(|y_i| classes.binary_search(y_i).unwrap())
+            .collect();
+
+        let class_weight: Vec<f64> = match &parameters.class_weight {
+            None => vec![1.; k],
+            Some(class_weight) => resolve_class_weight(class_weight, k, &y_idx)?,
+        };
 
-        let (support_vectors, weight, b) = optimizer.optimize();
+        let c = parameters.c.to_f64().unwrap();
+        let sample_c: Vec<f64> = sample_weight
+            .iter()
+            .zip(y_idx.iter())
+            .map(|(w_i, &y_idx_i)| c * w_i.to_f64().unwrap() * class_weight[y_idx_i])
+            .collect();
+
+        // positive and negative class of every binary problem, with two classes the second class is the positive one
+        let problems: Vec<(usize, Option<usize>)> = match (k, &parameters.multiclass_strategy) {
+            (2, _) | (_, MultiClassStrategy::OneVsOne) => (0..k)
+                .flat_map(|negative| {
+                    (negative + 1..k).map(move |positive| (positive, Some(negative)))
+                })
+                .collect(),
+            (_, MultiClassStrategy::OneVsRest) => (0..k).map(|positive| (positive, None)).collect(),
+        };
+
+        let mut classifiers = Vec::with_capacity(problems.len());
+        for (positive, negative) in problems {
+            let rows: Vec<usize> = (0..n)
+                .filter(|&i| y_idx[i] == positive || negative.is_none_or(|c| y_idx[i] == c))
+                .collect();
+            let x_binary = x.take(&rows, 0);
+            let y_binary: Vec<TX> = rows
+                .iter()
+                .map(|&i| {
+                    if y_idx[i] == positive {
+                        TX::one()
+                    } else {
+                        -TX::one()
+                    }
+                })
+                .collect();
+            let c_binary: Vec<f64> = rows.iter().map(|&i| sample_c[i]).collect();
+
+            let mut classifier = Self::fit_binary(&x_binary, &y_binary, &c_binary, parameters)?;
+            classifier.positive = positive;
+            classifier.negative = negative;
+
+            if parameters.probability {
+                let decision_values =
+                    Self::cross_val_decision_values(&x_binary, &y_binary, &c_binary, parameters)?;
+                let (a, b) = platt_scaling(&decision_values, &y_binary);
+                classifier.sigmoid = Some((TX::from(a).unwrap(), TX::from(b).unwrap()));
+            }
+
+            classifiers.push(classifier);
+        }
 
         Ok(SVC::<'a> {
             classes: Some(classes),
-            instances: Some(support_vectors),
             parameters: Some(parameters),
-            w: Some(weight),
-            b: Some(b),
+            classifiers: Some(classifiers),
             phantomdata: PhantomData,
         })
     }
 
-    /// Predicts estimated class labels from `x`
-    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
-    pub fn predict(&self, x: &'a X) -> Result<Vec<TX>, Failed> {
-        let mut y_hat: Vec<TX> = self.decision_function(x)?;
+    /// Solves a binary problem with labels 1 and -1 and regularization parameter `c` of every observation.
+    fn fit_binary(
+        x: &X,
+        y: &[TX],
+        c: &[f64],
+        parameters: &SVCParameters<TX, TY, X, Y>,
+    ) -> Result<BinaryClassifier<TX>, Failed> {
+        let (instances, w, b) = match parameters.solver {
+            SVMSolverName::Approximate => {
+                let optimizer: Optimizer<'_, TX, TY, X, Y> = Optimizer::new(x, y, c, parameters);
+                optimizer.optimize()
+            }
+            SVMSolverName::SMO => Self::fit_binary_smo(x, y, c, parameters)?,
+        };
 
-        for i in 0..y_hat.len() {
-            let cls_idx = match *y_hat.get(i).unwrap() > TX::zero() {
-                false => TX::from(self.classes.as_ref().unwrap()[0]).unwrap(),
-                true => TX::from(self.classes.as_ref().unwrap()[1]).unwrap(),
-            };
+        Ok(BinaryClassifier {
+            positive: 1,
+            negative: Some(0),
+            instances,
+            w,
+            b,
+            sigmoid: None,
+        })
+    }
 
-            y_hat.set(i, cls_idx);
+    /// Solves a binary problem with the exact SMO solver, returns support vectors, their weights and the intercept.
+    #[allow(clippy::type_complexity)]
+    fn fit_binary_smo(
+        x: &X,
+        y: &[TX],
+        c: &[f64],
+        parameters: &SVCParameters<TX, TY, X, Y>,
+    ) -> Result<(Vec<Vec<TX>>, Vec<TX>, TX), Failed> {
+        let (n, _) = x.shape();
+        let rows: Vec<Vec<f64>> = (0..n)
+            .map(|i| {
+                x.get_row(i)
+                    .iterator(0)
+                    .map(|e| e.to_f64().unwrap())
+                    .collect()
+            })
+            .collect();
+        let mut cache = KernelCache::new(
+            &rows,
+            parameters.kernel.as_ref().unwrap().as_ref(),
+            parameters.cache_size,
+        );
+        let solution = Solver::new(
+            &mut cache,
+            (0..n).collect(),
+            y.iter().map(|y_i| y_i.to_f64().unwrap()).collect(),
+            vec![-1.; n],
+            c.to_vec(),
+            vec![0.; n],
+            parameters.tol.to_f64().unwrap(),
+            parameters.shrinking,
+        )
+        .solve()?;
+
+        let mut instances = Vec::new();
+        let mut w = Vec::new();
+        for (i, &alpha) in solution.alpha.iter().enumerate() {
+            if alpha > 0. {
+                instances.push(x.get_row(i).iterator(0).copied().collect());
+                w.push(TX::from(alpha).unwrap() * y[i]);
+            }
         }
 
-        Ok(y_hat)
+        Ok((instances, w, TX::from(-solution.rho).unwrap()))
+    }
+
+    /// Decision values of a binary problem, every observation is scored by a classifier
+    /// that did not see it in training, as in libsvm.
+    fn cross_val_decision_values(
+        x: &X,
+        y: &[TX],
+        c: &[f64],
+        parameters: &SVCParameters<TX, TY, X, Y>,
+    ) -> Result<Vec<f64>, Failed> {
+        let (n, _) = x.shape();
+        let n_folds = PROBABILITY_FOLDS.min(n);
+        let kernel = parameters.kernel.as_ref().unwrap().as_ref();
+
+        let mut permutation: Vec<usize> = (0..n).collect();
+        permutation.shuffle(&mut get_rng_impl(parameters.seed));
+
+        let mut decision_values = vec![0f64; n];
+        for fold in 0..n_folds {
+            let (test, train): (Vec<usize>, Vec<usize>) =
+                (0..n).partition(|&i| permutation[i] % n_folds == fold);
+            let y_train: Vec<TX> = train.iter().map(|&i| y[i]).collect();
+            let c_train: Vec<f64> = train.iter().map(|&i| c[i]).collect();
+
+            let positive = y_train.iter().any(|&v| v > TX::zero());
+            let negative = y_train.iter().any(|&v| v < TX::zero());
+            if !(positive && negative) {
+                // a training fold with a single class predicts it with certainty
+                let value = if positive { 1. } else { -1. };
+                for &i in test.iter() {
+                    decision_values[i] = value;
+                }
+                continue;
+            }
+
+            let classifier = Self::fit_binary(&x.take(&train, 0), &y_train, &c_train, parameters)?;
+            let mut row = Vec::new();
+            for &i in test.iter() {
+                row.clear();
+                row.extend(x.get_row(i).iterator(0).copied());
+                decision_values[i] = classifier.decision_value(kernel, &row).to_f64().unwrap();
+            }
+        }
+
+        Ok(decision_values)
+    }
+
+    /// Predicts estimated class labels from `x`
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict(&self, x: &'a X) -> Result<Vec<TX>, Failed> {
+        let decision_values = self.decision_function_multiclass(x)?;
+        let classes = self.classes();
+        let classifiers = self.classifiers.as_ref().unwrap();
+
+        Ok(
+            predict_classes(classifiers, classes.len(), &decision_values)
+                .into_iter()
+                .map(|c| TX::from(classes[c]).unwrap())
+                .collect(),
+        )
     }
 
-    /// Evaluates the decision function for the rows in `x`
+    /// Evaluates the decision function for the rows in `x`. Positive values predict the second class.
+    /// Only defined for problems with two classes, see [`decision_function_multiclass`](#method.decision_function_multiclass).
     /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
     pub fn decision_function(&self, x: &'a X) -> Result<Vec<TX>, Failed> {
+        let classifiers = self.classifiers.as_ref().unwrap();
+        if classifiers.len() != 1 {
+            return Err(Failed::predict(&format!(
+                "decision_function is only defined for two classes, got {} classes, use decision_function_multiclass",
+                self.classes().len()
+            )));
+        }
+
         let (n, _) = x.shape();
         let mut y_hat: Vec<TX> = Array1::zeros(n);
 
@@ -326,37 +656,80 @@ impl<'a, TX: Number + RealNumber, TY: Number + Ord, X: Array2<TX> + 'a, Y: Array
         for i in 0..n {
             row.clear();
             row.extend(x.get_row(i).iterator(0).copied());
-            let row_pred: TX = self.predict_for_row(&row);
+            let row_pred: TX = self.predict_for_row(&classifiers[0], &row);
             y_hat.set(i, row_pred);
         }
 
         Ok(y_hat)
     }
 
-    fn predict_for_row(&self, x: &[TX]) -> TX {
-        let mut f = self.b.unwrap();
+    /// Evaluates the decision functions of all binary classifiers for the rows in `x`.
+    /// Returns a _KxP_ matrix. With two classes \\(P = 1\\) and the column is the [`decision_function`](#method.decision_function).
+    /// With one-vs-one \\(P = k(k-1)/2\\), the columns are ordered by pairs of classes \\((0, 1), (0, 2), ..., (1, 2), ...\\)
+    /// and positive values vote for the second class of the pair. With one-vs-rest \\(P = k\\), one column per class.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn decision_function_multiclass(&self, x: &'a X) -> Result<X, Failed> {
+        let kernel = self.parameters.as_ref().unwrap().kernel.as_ref().unwrap();
+        Ok(decision_values(
+            self.classifiers.as_ref().unwrap(),
+            kernel.as_ref(),
+            x,
+        ))
+    }
 
-        let xi: Vec<_> = x.iter().map(|e| e.to_f64().unwrap()).collect();
-        for i in 0..self.instances.as_ref().unwrap().len() {
-            let xj: Vec<_> = self.instances.as_ref().unwrap()[i]
+    /// Estimates class probabilities for the rows in `x`, the model should be fitted with `probability` set to `true`.
+    /// Returns a _KxC_ matrix, columns are ordered like [`classes`](#method.classes).
+    /// With one-vs-one the pairwise probabilities are combined by pairwise coupling, with one-vs-rest they are normalized to sum to one.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict_proba(&self, x: &'a X) -> Result<X, Failed> {
+        let classifiers = self.classifiers.as_ref().unwrap();
+        if classifiers.iter().any(|c| c.sigmoid.is_none()) {
+            return Err(Failed::predict(
+                "probability estimates are not available, fit SVC with `with_probability(true)`",
+            ));
+        }
+
+        let decision_values = self.decision_function_multiclass(x)?;
+        let k = self.classes().len();
+        let (n, _) = x.shape();
+        let mut proba = X::zeros(n, k);
+
+        let mut pairwise = vec![vec![0f64; k]; k];
+        for i in 0..n {
+            let p: Vec<f64> = classifiers
                 .iter()
-                .map(|e| e.to_f64().unwrap())
+                .enumerate()
+                .map(|(j, c)| c.probability(*decision_values.get((i, j))))
                 .collect();
-            f += self.w.as_ref().unwrap()[i]
-                * TX::from(
-                    self.parameters
-                        .as_ref()
-                        .unwrap()
-                        .kernel
-                        .as_ref()
-                        .unwrap()
-                        .apply(&xi, &xj)
-                        .unwrap(),
-                )
-                .unwrap();
+            let class_proba = if classifiers.len() == 1 {
+                vec![1. - p[0], p[0]]
+            } else if classifiers[0].negative.is_some() {
+                for (c, &p_c) in classifiers.iter().zip(p.iter()) {
+                    let p_c = p_c.clamp(MIN_PROBABILITY, 1. - MIN_PROBABILITY);
+                    pairwise[c.positive][c.negative.unwrap()] = p_c;
+                    pairwise[c.negative.unwrap()][c.positive] = 1. - p_c;
+                }
+                pairwise_coupling(&pairwise)
+            } else {
+                let total: f64 = p.iter().sum();
+                p.iter().map(|p_c| p_c / total).collect()
+            };
+            for (c, p_c) in class_proba.into_iter().enumerate() {
+                proba.set((i, c), TX::from(p_c).unwrap());
+            }
         }
 
-        f
+        Ok(proba)
+    }
+
+    /// Get classes, ordered. With two classes the second class is the positive one.
+    pub fn classes(&self) -> &Vec<TY> {
+        self.classes.as_ref().unwrap()
+    }
+
+    fn predict_for_row(&self, classifier: &BinaryClassifier<TX>, x: &[TX]) -> TX {
+        let kernel = self.parameters.as_ref().unwrap().kernel.as_ref().unwrap();
+        classifier.decision_value(kernel.as_ref(), x)
     }
 }
 
@@ -364,29 +737,122 @@ impl<'a, TX: Number + RealNumber, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>
     for SVC<'a, TX, TY, X, Y>
 {
     fn eq(&self, other: &Self) -> bool {
-        if (self.b.unwrap().sub(other.b.unwrap())).abs() > TX::epsilon() * TX::two()
-            || self.w.as_ref().unwrap().len() != other.w.as_ref().unwrap().len()
-            || self.instances.as_ref().unwrap().len() != other.instances.as_ref().unwrap().len()
+        self.classes == other.classes
+            && self.classifiers.as_ref().unwrap().len() == other.classifiers.as_ref().unwrap().len()
+            && self
+                .classifiers
+                .as_ref()
+                .unwrap()
+                .iter()
+                .zip(other.classifiers.as_ref().unwrap().iter())
+                .all(|(a, b)| a.approximate_eq(b))
+    }
+}
+
+/// Evaluates the decision functions of `classifiers` for the rows in `x`, returns a _KxP_ matrix with one column per classifier.
+pub(crate) fn decision_values<TX: Number + RealNumber, X: Array2<TX>>(
+    classifiers: &[BinaryClassifier<TX>],
+    kernel: &dyn Kernel,
+    x: &X,
+) -> X {
+    let (n, _) = x.shape();
+    let mut decision_values = X::zeros(n, classifiers.len());
+
+    let mut row = Vec::with_capacity(n);
+    for i in 0..n {
+        row.clear();
+        row.extend(x.get_row(i).iterator(0).copied());
+        for (j, classifier) in classifiers.iter().enumerate() {
+            decision_values.set((i, j), classifier.decision_value(kernel, &row));
+        }
+    }
+
+    decision_values
+}
+
+/// Indices of the predicted classes, by one-vs-one voting or by the largest one-vs-rest decision value.
+pub(crate) fn predict_classes<TX: Number + RealNumber, X: Array2<TX>>(
+    classifiers: &[BinaryClassifier<TX>],
+    n_classes: usize,
+    decision_values: &X,
+) -> Vec<usize> {
+    let (n, _) = decision_values.shape();
+
+    let mut votes = vec![0usize; n_classes];
+    (0..n)
+        .map(|i| {
+            if classifiers[0].negative.is_some() {
+                // one-vs-one voting, ties go to the first class
+                votes.iter_mut().for_each(|v| *v = 0);
+                for (j, classifier) in classifiers.iter().enumerate() {
+                    if *decision_values.get((i, j)) > TX::zero() {
+                        votes[classifier.positive] += 1;
+                    } else {
+                        votes[classifier.negative.unwrap()] += 1;
+                    }
+                }
+                (0..n_classes).rev().max_by_key(|&c| votes[c]).unwrap()
+            } else {
+                // one-vs-rest, the class with the largest decision value
+                let mut winner = 0;
+                for j in 1..classifiers.len() {
+                    if *decision_values.get((i, j)) > *decision_values.get((i, winner)) {
+                        winner = j;
+                    }
+                }
+                classifiers[winner].positive
+            }
+        })
+        .collect()
+}
+
+impl<TX: Number + RealNumber> BinaryClassifier<TX> {
+    pub(crate) fn decision_value(&self, kernel: &dyn Kernel, x: &[TX]) -> TX {
+        let mut f = self.b;
+
+        let xi: Vec<_> = x.iter().map(|e| e.to_f64().unwrap()).collect();
+        for i in 0..self.instances.len() {
+            let xj: Vec<_> = self.instances[i]
+                .iter()
+                .map(|e| e.to_f64().unwrap())
+                .collect();
+            f += self.w[i] * TX::from(kernel.apply(&xi, &xj).unwrap()).unwrap();
+        }
+
+        f
+    }
+
+    /// Probability of the positive class given the decision value `f`.
+    fn probability(&self, f: TX) -> f64 {
+        let (a, b) = self.sigmoid.unwrap();
+        let f_apb = (f * a + b).to_f64().unwrap();
+        // evaluated without overflow for decision values of either sign
+        if f_apb >= 0. {
+            (-f_apb).exp() / (1. + (-f_apb).exp())
+        } else {
+            1. / (1. + f_apb.exp())
+        }
+    }
+
+    pub(crate) fn approximate_eq(&self, other: &Self) -> bool {
+        if self.positive != other.positive
+            || self.negative != other.negative
+            || (self.b.sub(other.b)).abs() > TX::epsilon() * TX::two()
+            || self.w.len() != other.w.len()
+            || self.instances.len() != other.instances.len()
         {
             false
         } else {
-            if !self
-                .w
-                .as_ref()
-                .unwrap()
-                .approximate_eq(other.w.as_ref().unwrap(), TX::epsilon())
-            {
+            if !self.w.approximate_eq(&other.w, TX::epsilon()) {
                 return false;
             }
-            for i in 0..self.w.as_ref().unwrap().len() {
-                if (self.w.as_ref().unwrap()[i].sub(other.w.as_ref().unwrap()[i])).abs()
-                    > TX::epsilon()
-                {
+            for i in 0..self.w.len() {
+                if (self.w[i].sub(other.w[i])).abs() > TX::epsilon() {
                     return false;
                 }
             }
-            for i in 0..self.instances.as_ref().unwrap().len() {
-                if !(self.instances.as_ref().unwrap()[i] == other.instances.as_ref().unwrap()[i]) {
+            for i in 0..self.instances.len() {
+                if !(self.instances[i] == other.instances[i]) {
                     return false;
                 }
             }
@@ -395,6 +861,137 @@ impl<'a, TX: Number + RealNumber, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>
     }
 }
 
+/// Fits the sigmoid \\(P(y = 1 \mid f) = 1 / (1 + e^{Af + B})\\) to decision values `f` and labels 1 and -1
+/// by maximum likelihood with regularized targets, using Newton's method with backtracking.
+/// Returns \\((A, B)\\).
+fn platt_scaling<TX: Number + RealNumber>(decision_values: &[f64], y: &[TX]) -> (f64, f64) {
+    const MAX_ITER: usize = 100;
+    const MIN_STEP: f64 = 1e-10;
+    const SIGMA: f64 = 1e-12;
+    const EPS: f64 = 1e-5;
+
+    let n_positive = y.iter().filter(|&&v| v > TX::zero()).count() as f64;
+    let n_negative = y.len() as f64 - n_positive;
+    let hi_target = (n_positive + 1.) / (n_positive + 2.);
+    let lo_target = 1. / (n_negative + 2.);
+    let targets: Vec<f64> = y
+        .iter()
+        .map(|&v| if v > TX::zero() { hi_target } else { lo_target })
+        .collect();
+
+    let objective = |a: f64, b: f64| -> f64 {
+        decision_values
+            .iter()
+            .zip(targets.iter())
+            .map(|(&f, &t)| {
+                let f_apb = f * a + b;
+                if f_apb >= 0. {
+                    t * f_apb + (1. + (-f_apb).exp()).ln()
+                } else {
+                    (t - 1.) * f_apb + (1. + f_apb.exp()).ln()
+                }
+            })
+            .sum()
+    };
+
+    let mut a = 0.;
+    let mut b = ((n_negative + 1.) / (n_positive + 1.)).ln();
+    let mut f_value = objective(a, b);
+
+    for _ in 0..MAX_ITER {
+        // gradient and Hessian of the negative log likelihood
+        let (mut h11, mut h22, mut h21, mut g1, mut g2) = (SIGMA, SIGMA, 0., 0., 0.);
+        for (&f, &t) in decision_values.iter().zip(targets.iter()) {
+            let f_apb = f * a + b;
+            let (p, q) = if f_apb >= 0. {
+                let e = (-f_apb).exp();
+                (e / (1. + e), 1. / (1. + e))
+            } else {
+                let e = f_apb.exp();
+                (1. / (1. + e), e / (1. + e))
+            };
+            let d2 = p * q;
+            h11 += f * f * d2;
+            h22 += d2;
+            h21 += f * d2;
+            let d1 = t - p;
+            g1 += f * d1;
+            g2 += d1;
+        }
+        if g1.abs() < EPS && g2.abs() < EPS {
+            break;
+        }
+
+        let det = h11 * h22 - h21 * h21;
+        let d_a = -(h22 * g1 - h21 * g2) / det;
+        let d_b = -(-h21 * g1 + h11 * g2) / det;
+        let gd = g1 * d_a + g2 * d_b;
+
+        let mut step = 1.;
+        while step >= MIN_STEP {
+            let new_a = a + step * d_a;
+            let new_b = b + step * d_b;
+            let new_f = objective(new_a, new_b);
+            if new_f < f_value + 1e-4 * step * gd {
+                a = new_a;
+                b = new_b;
+                f_value = new_f;
+                break;
+            }
+            step /= 2.;
+        }
+        if step < MIN_STEP {
+            break;
+        }
+    }
+
+    (a, b)
+}
+
+/// Class probabilities from pairwise probabilities `r[i][j]` \\(= P(y = i \mid y \in \{i, j\})\\)
+/// with the second method of Wu, Lin and Weng, a fixed point iteration for
+/// \\(\min_p \sum_i \sum_{j \neq i} (r_{ji} p_i - r_{ij} p_j)^2\\) subject to \\(\sum_i p_i = 1\\).
+fn pairwise_coupling(r: &[Vec<f64>]) -> Vec<f64> {
+    let k = r.len();
+    let max_iter = 100.max(k);
+    let eps = 0.005 / k as f64;
+
+    let mut q = vec![vec![0f64; k]; k];
+    for t in 0..k {
+        for j in 0..k {
+            if j != t {
+                q[t][t] += r[j][t] * r[j][t];
+                q[t][j] = -r[j][t] * r[t][j];
+            }
+        }
+    }
+
+    let mut p = vec![1. / k as f64; k];
+    let mut qp = vec![0f64; k];
+    for _ in 0..max_iter {
+        let mut p_qp = 0.;
+        for t in 0..k {
+            qp[t] = (0..k).map(|j| q[t][j] * p[j]).sum();
+            p_qp += p[t] * qp[t];
+        }
+        let max_error = qp.iter().map(|v| (v - p_qp).abs()).fold(0., f64::max);
+        if max_error < eps {
+            break;
+        }
+        for t in 0..k {
+            let diff = (-qp[t] + p_qp) / q[t][t];
+            p[t] += diff;
+            p_qp = (p_qp + diff * (diff * q[t][t] + 2. * qp[t])) / (1. + diff) / (1. + diff);
+            for j in 0..k {
+                qp[j] = (qp[j] + diff * q[t][j]) / (1. + diff);
+                p[j] /= 1. + diff;
+            }
+        }
+    }
+
+    p
+}
+
 impl<TX: Number + RealNumber> SupportVector<TX> {
     fn new(i: usize, x: Vec<TX>, y: TX, g: f64, c: f64, k_v: f64) -> SupportVector<TX> {
         let (cmin, cmax) = if y > TX::zero() {
@@ -444,7 +1041,8 @@ impl<'a, TX: Number + RealNumber, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>
 {
     fn new(
         x: &'a X,
-        y: &'a Y,
+        y: &'a [TX],
+        c: &'a [f64],
         parameters: &'a SVCParameters<TX, TY, X, Y>,
     ) -> Optimizer<'a, TX, TY, X, Y> {
         let (n, _) = x.shape();
@@ -452,6 +1050,7 @@ impl<'a, TX: Number + RealNumber, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>
         Optimizer {
             x,
             y,
+            c,
             parameters,
             svmin: 0,
             svmax: 0,
@@ -464,7 +1063,7 @@ impl<'a, TX: Number + RealNumber, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>
     }
 
     fn optimize(mut self) -> (Vec<Vec<TX>>, Vec<TX>, TX) {
-        let (n, _) = self.x.shape();
+        let (n, n_features) = self.x.shape();
 
         let mut cache: Cache<TX, TY, X, Y> = Cache::new();
 
@@ -473,12 +1072,12 @@ impl<'a, TX: Number + RealNumber, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>
         let tol = self.parameters.tol;
         let good_enough = TX::from_i32(1000).unwrap();
 
-        let mut x = Vec::with_capacity(n);
+        let mut x = Vec::with_capacity(n_features);
         for _ in 0..self.parameters.epoch {
             for i in self.permutate(n) {
                 x.clear();
-                x.extend(self.x.get_row(i).iterator(0).take(n).copied());
-                self.process(i, &x, *self.y.get(i), &mut cache);
+                x.extend(self.x.get_row(i).iterator(0).copied());
+                self.process(i, &x, self.y[i], &mut cache);
                 loop {
                     self.reprocess(tol, &mut cache);
                     self.find_min_max_gradient();
@@ -505,23 +1104,20 @@ impl<'a, TX: Number + RealNumber, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>
     }
 
     fn initialize(&mut self, cache: &mut Cache<TX, TY, X, Y>) {
-        let (n, _) = self.x.shape();
+        let (n, n_features) = self.x.shape();
         let few = 5;
         let mut cp = 0;
         let mut cn = 0;
 
-        let mut x = Vec::with_capacity(n);
+        let mut x = Vec::with_capacity(n_features);
         for i in self.permutate(n) {
             x.clear();
-            x.extend(self.x.get_row(i).iterator(0).take(n).copied());
-            if *self.y.get(i) == TY::one() && cp < few {
-                if self.process(i, &x, *self.y.get(i), cache) {
+            x.extend(self.x.get_row(i).iterator(0).copied());
+            if self.y[i] == TX::one() && cp < few {
+                if self.process(i, &x, self.y[i], cache) {
                     cp += 1;
                 }
-            } else if *self.y.get(i) == TY::from(-1).unwrap()
-                && cn < few
-                && self.process(i, &x, *self.y.get(i), cache)
-            {
+            } else if self.y[i] == -TX::one() && cn < few && self.process(i, &x, self.y[i], cache) {
                 cn += 1;
             }
 
@@ -531,7 +1127,7 @@ impl<'a, TX: Number + RealNumber, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>
         }
     }
 
-    fn process(&mut self, i: usize, x: &[TX], y: TY, cache: &mut Cache<TX, TY, X, Y>) -> bool {
+    fn process(&mut self, i: usize, x: &[TX], y: TX, cache: &mut Cache<TX, TY, X, Y>) -> bool {
         for j in 0..self.sv.len() {
             if self.sv[j].index == i {
                 return true;
@@ -559,8 +1155,8 @@ impl<'a, TX: Number + RealNumber, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>
         self.find_min_max_gradient();
 
         if self.gmin < self.gmax
-            && ((y > TY::zero() && g < self.gmin.to_f64().unwrap())
-                || (y < TY::zero() && g > self.gmax.to_f64().unwrap()))
+            && ((y > TX::zero() && g < self.gmin.to_f64().unwrap())
+                || (y < TX::zero() && g > self.gmax.to_f64().unwrap()))
         {
             return false;
         }
@@ -580,17 +1176,10 @@ impl<'a, TX: Number + RealNumber, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>
 
         self.sv.insert(
             0,
-            SupportVector::<TX>::new(
-                i,
-                x.to_vec(),
-                TX::from(y).unwrap(),
-                g,
-                self.parameters.c.to_f64().unwrap(),
-                k_v,
-            ),
+            SupportVector::<TX>::new(i, x.to_vec(), y, g, self.c[i], k_v),
         );
 
-        if y > TY::zero() {
+        if y > TX::zero() {
             self.smo(None, Some(0), TX::zero(), cache);
         } else {
             self.smo(Some(0), None, TX::zero(), cache);
@@ -926,9 +1515,11 @@ mod tests {
     use num::ToPrimitive;
 
     use super::*;
+    use crate::linalg::basic::arrays::{Array, ArrayView1};
     use crate::linalg::basic::matrix::DenseMatrix;
     use crate::metrics::accuracy;
     use crate::svm::Kernels;
+    use crate::test_datasets::{assert_weights_repeat_rows, iris};
 
     #[cfg_attr(
         all(target_arch = "wasm32", not(target_os = "wasi")),
@@ -978,6 +1569,241 @@ mod tests {
         assert!(acc >= 0.9, "accuracy ({acc}) is not larger or equal to 0.9");
     }
 
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn svc_fit_imbalanced() {
+        // 36 negative points on a grid and 4 positive points next to its corner
+        let mut values = Vec::new();
+        let mut y: Vec<i32> = Vec::new();
+        for i in 0..6 {
+            for j in 0..6 {
+                values.extend([0.2 * i as f64, 0.2 * j as f64]);
+                y.push(0);
+            }
+        }
+        values.extend([1.3, 1.3, 1.4, 1.2, 1.2, 1.4, 1.5, 1.5]);
+        y.extend([1, 1, 1, 1]);
+        let x = DenseMatrix::from_iterator(values.into_iter(), 40, 2, 0);
+
+        let recall = |y_hat: &Vec<f64>| y_hat[36..].iter().filter(|&&v| v == 1.).count();
+
+        let params = SVCParameters::default()
+            .with_c(0.05)
+            .with_solver(SVMSolverName::SMO)
+            .with_kernel(Kernels::linear());
+        let y_hat = SVC::fit(&x, &y, &params)
+            .and_then(|svc| svc.predict(&x))
+            .unwrap();
+        assert_eq!(recall(&y_hat), 0);
+
+        let balanced_params = SVCParameters::default()
+            .with_c(0.05)
+            .with_solver(SVMSolverName::SMO)
+            .with_kernel(Kernels::linear())
+            .with_class_weight(ClassWeight::Balanced);
+        let y_hat = SVC::fit(&x, &y, &balanced_params)
+            .and_then(|svc| svc.predict(&x))
+            .unwrap();
+        assert_eq!(recall(&y_hat), 4);
+
+        let custom_params = SVCParameters::default()
+            .with_c(0.05)
+            .with_solver(SVMSolverName::SMO)
+            .with_kernel(Kernels::linear())
+            .with_class_weight(ClassWeight::Custom(HashMap::from([(1, 5.)])));
+        let sample_weight: Vec<f64> = y
+            .iter()
+            .map(|&y_i| if y_i == 1 { 5. } else { 1. })
+            .collect();
+        let custom = SVC::fit(&x, &y, &custom_params).unwrap();
+        let weighted = SVC::fit_with_sample_weight(&x, &y, &sample_weight, &params).unwrap();
+        assert_eq!(custom, weighted);
+
+        let approximate_params = SVCParameters::default()
+            .with_c(0.05)
+            .with_kernel(Kernels::linear())
+            .with_class_weight(ClassWeight::Balanced)
+            .with_seed(Some(1));
+        let y_hat = SVC::fit(&x, &y, &approximate_params)
+            .and_then(|svc| svc.predict(&x))
+            .unwrap();
+        assert!(recall(&y_hat) >= 3);
+
+        assert!(SVC::fit_with_sample_weight(&x, &y, &[1.; 3], &params).is_err());
+        assert!(SVC::fit_with_sample_weight(&x, &y, &[-1.; 40], &params).is_err());
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn svc_fit_with_sample_weight() {
+        let (x, y) = iris();
+        let y: Vec<i32> = y.iter().map(|&c| if c == 2 { 1 } else { -1 }).collect();
+        let sample_weight: Vec<f64> = (0..18).map(|i| (i % 3 + 1) as f64).collect();
+
+        let params = SVCParameters::default()
+            .with_c(0.5)
+            .with_solver(SVMSolverName::SMO)
+            .with_tol(1e-6)
+            .with_kernel(Kernels::rbf().with_gamma(0.5));
+
+        assert_weights_repeat_rows(
+            &x,
+            &y,
+            &sample_weight,
+            |x_w, y_w, w| {
+                SVC::fit_with_sample_weight(x_w, y_w, w, &params)
+                    .and_then(|svc| svc.decision_function(&x))
+                    .unwrap()
+            },
+            |x_r, y_r| {
+                SVC::fit(x_r, y_r, &params)
+                    .and_then(|svc| svc.decision_function(&x))
+                    .unwrap()
+            },
+            1e-4,
+        );
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn svc_fit_precomputed() {
+        use crate::svm::PrecomputedKernel;
+
+        let (x, y) = iris();
+        let y: Vec<i32> = y.iter().map(|&c| if c == 2 { 1 } else { -1 }).collect();
+
+        let rows: Vec<Vec<f64>> = (0..18)
+            .map(|i| x.get_row(i).iterator(0).copied().collect())
+            .collect();
+        let gram = DenseMatrix::from_iterator(
+            rows.iter().flat_map(|x_i| {
+                rows.iter()
+                    .map(move |x_j| x_i.iter().zip(x_j).map(|(a, b)| a * b).sum())
+            }),
+            18,
+            18,
+            0,
+        );
+
+        let linear_params = SVCParameters::default()
+            .with_c(10.0)
+            .with_solver(SVMSolverName::SMO)
+            .with_kernel(Kernels::linear());
+        let precomputed_params = SVCParameters::default()
+            .with_c(10.0)
+            .with_solver(SVMSolverName::SMO)
+            .with_kernel(Kernels::precomputed());
+
+        let linear = SVC::fit(&x, &y, &linear_params).unwrap();
+        let precomputed_x = PrecomputedKernel::prepend_index(&gram);
+        let precomputed = SVC::fit(&precomputed_x, &y, &precomputed_params).unwrap();
+
+        let expected = linear.decision_function(&x).unwrap();
+        let result = precomputed.decision_function(&precomputed_x).unwrap();
+        for (e, r) in expected.iter().zip(result.iter()) {
+            assert!((e - r).abs() < 1e-6, "{e} != {r}");
+        }
+        assert_eq!(
+            linear.predict(&x).unwrap(),
+            precomputed.predict(&precomputed_x).unwrap()
+        );
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn svc_fit_smo() {
+        let (x, y) = iris();
+        let y: Vec<i32> = y.iter().map(|&c| if c == 2 { 1 } else { -1 }).collect();
+        let c = 0.5;
+        let tol = 1e-3;
+
+        let mut decisions = Vec::new();
+        for shrinking in [true, false] {
+            let params = SVCParameters::default()
+                .with_c(c)
+                .with_tol(tol)
+                .with_kernel(Kernels::rbf().with_gamma(0.1))
+                .with_solver(SVMSolverName::SMO)
+                .with_shrinking(shrinking)
+                .with_cache_size(0.);
+            let svc = SVC::fit(&x, &y, &params).unwrap();
+            let decision = svc.decision_function(&x).unwrap();
+            let classifier = &svc.classifiers.as_ref().unwrap()[0];
+
+            // the dual constraint y^T alpha = 0
+            let sum: f64 = classifier.w.iter().sum();
+            assert!(sum.abs() < 1e-8);
+
+            // KKT conditions of every observation
+            for (i, &label) in y.iter().enumerate() {
+                let row: Vec<f64> = x.get_row(i).iterator(0).copied().collect();
+                let alpha = classifier
+                    .instances
+                    .iter()
+                    .position(|instance| *instance == row)
+                    .map_or(0., |j| classifier.w[j].abs());
+                let margin = label as f64 * decision[i];
+                if alpha == 0. {
+                    assert!(margin >= 1. - 2. * tol, "{i}: {margin}");
+                } else if alpha >= c {
+                    assert!(margin <= 1. + 2. * tol, "{i}: {margin}");
+                } else {
+                    assert!((margin - 1.).abs() <= 2. * tol, "{i}: {margin}");
+                }
+            }
+            decisions.push(decision);
+        }
+
+        for (with_shrinking, without_shrinking) in decisions[0].iter().zip(decisions[1].iter()) {
+            assert!((with_shrinking - without_shrinking).abs() < 1e-2);
+        }
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn svc_fit_smo_libsvm_reference() {
+        // libsvm with a linear kernel and C = 1 keeps the observations 1 and 3 as support vectors
+        // with dual coefficients -0.25 and 0.25 and rho = 0
+        let x = DenseMatrix::from_2d_array(&[
+            &[-2.0, -1.0],
+            &[-1.0, -1.0],
+            &[-1.0, -2.0],
+            &[1.0, 1.0],
+            &[1.0, 2.0],
+            &[2.0, 1.0],
+        ])
+        .unwrap();
+        let y: Vec<i32> = vec![-1, -1, -1, 1, 1, 1];
+
+        let params = SVCParameters::default()
+            .with_c(1.0)
+            .with_kernel(Kernels::linear())
+            .with_solver(SVMSolverName::SMO);
+        let svc: SVC<f64, i32, DenseMatrix<f64>, Vec<i32>> = SVC::fit(&x, &y, &params).unwrap();
+        let classifier = &svc.classifiers.as_ref().unwrap()[0];
+
+        assert_eq!(classifier.instances, vec![vec![-1.0, -1.0], vec![1.0, 1.0]]);
+        assert!((classifier.w[0] + 0.25).abs() < 1e-6, "{:?}", classifier.w);
+        assert!((classifier.w[1] - 0.25).abs() < 1e-6, "{:?}", classifier.w);
+        assert!(classifier.b.abs() < 1e-6, "{}", classifier.b);
+        assert_eq!(svc.predict(&x).unwrap(), vec![-1., -1., -1., 1., 1., 1.]);
+    }
+
     #[cfg_attr(
         all(target_arch = "wasm32", not(target_os = "wasi")),
         wasm_bindgen_test::wasm_bindgen_test
@@ -1070,6 +1896,114 @@ mod tests {
         assert!(acc >= 0.9, "accuracy ({acc}) is not larger or equal to 0.9");
     }
 
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn svc_fit_predict_multiclass() {
+        let (x, y) = iris();
+        let y: Vec<u32> = y.iter().map(|&c| [3, 5, 8][c as usize]).collect();
+
+        for strategy in [MultiClassStrategy::OneVsOne, MultiClassStrategy::OneVsRest] {
+            let params = SVCParameters::default()
+                .with_c(10.0)
+                .with_kernel(Kernels::linear())
+                .with_multiclass_strategy(strategy.clone())
+                .with_seed(Some(100));
+            let svc = SVC::fit(&x, &y, &params).unwrap();
+
+            assert_eq!(svc.classes(), &vec![3, 5, 8]);
+            assert_eq!(
+                svc.decision_function_multiclass(&x).unwrap().shape(),
+                (18, 3)
+            );
+            assert!(svc.decision_function(&x).is_err());
+
+            let y_hat: Vec<u32> = svc
+                .predict(&x)
+                .unwrap()
+                .iter()
+                .map(|e| e.to_u32().unwrap())
+                .collect();
+            let acc = accuracy(&y, &y_hat);
+            assert!(
+                acc >= 0.9,
+                "{strategy:?} accuracy ({acc}) is not larger or equal to 0.9"
+            );
+        }
+
+        // two classes with arbitrary labels, the second class is the positive one
+        let y_binary: Vec<u32> = y.iter().map(|&c| if c == 3 { 0 } else { 7 }).collect();
+        let params = SVCParameters::default()
+            .with_c(10.0)
+            .with_kernel(Kernels::linear());
+        let svc = SVC::fit(&x, &y_binary, &params).unwrap();
+        let decision = svc.decision_function(&x).unwrap();
+        assert!(decision[..6].iter().all(|&d| d < 0.));
+        assert!(decision[6..].iter().all(|&d| d > 0.));
+        assert_eq!(svc.predict(&x).unwrap()[0], 0.);
+        assert_eq!(svc.predict(&x).unwrap()[17], 7.);
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn svc_predict_proba() {
+        let (x, y) = iris();
+
+        let params = SVCParameters::default()
+            .with_c(200.0)
+            .with_kernel(Kernels::linear());
+        let svc = SVC::fit(&x, &y, &params).unwrap();
+        assert!(svc.predict_proba(&x).is_err());
+
+        for strategy in [MultiClassStrategy::OneVsOne, MultiClassStrategy::OneVsRest] {
+            let params = SVCParameters::default()
+                .with_c(200.0)
+                .with_kernel(Kernels::linear())
+                .with_multiclass_strategy(strategy.clone())
+                .with_probability(true)
+                .with_seed(Some(100));
+            let svc = SVC::fit(&x, &y, &params).unwrap();
+            let proba = svc.predict_proba(&x).unwrap();
+            assert_eq!(proba.shape(), (18, 3));
+
+            let y_hat = svc.predict(&x).unwrap();
+            for (i, &label) in y_hat.iter().enumerate() {
+                let row: Vec<f64> = (0..3).map(|c| *proba.get((i, c))).collect();
+                assert!((row.iter().sum::<f64>() - 1.).abs() < 1e-6);
+                assert!(row.iter().all(|&p| (0. ..=1.).contains(&p)));
+                if strategy == MultiClassStrategy::OneVsOne {
+                    assert_eq!(row.argmax(), label as usize);
+                }
+            }
+        }
+
+        // binary probabilities increase with the decision value
+        let y_binary: Vec<u32> = y.iter().map(|&c| if c == 0 { 0 } else { 1 }).collect();
+        let params = SVCParameters::default()
+            .with_c(200.0)
+            .with_kernel(Kernels::linear())
+            .with_probability(true)
+            .with_seed(Some(100));
+        let svc = SVC::fit(&x, &y_binary, &params).unwrap();
+        let decision = svc.decision_function(&x).unwrap();
+        let proba = svc.predict_proba(&x).unwrap();
+        let mut order: Vec<usize> = (0..18).collect();
+        order.sort_by(|&i, &j| decision[i].partial_cmp(&decision[j]).unwrap());
+        for w in order.windows(2) {
+            assert!(*proba.get((w[0], 1)) <= *proba.get((w[1], 1)) + 1e-12);
+        }
+        for i in 0..18 {
+            assert!((*proba.get((i, 0)) + *proba.get((i, 1)) - 1.).abs() < 1e-12);
+        }
+        assert!(*proba.get((0, 0)) > 0.5);
+        assert!(*proba.get((17, 1)) > 0.5);
+    }
+
     #[cfg_attr(
         all(target_arch = "wasm32", not(target_os = "wasi")),
         wasm_bindgen_test::wasm_bindgen_test
@@ -18,6 +18,9 @@
 //!
 //! The parameter `C` > 0 determines the trade-off between the flatness of \\(f(x)\\) and the amount up to which deviations larger than \\(\epsilon\\) are tolerated
 //!
+//! By default the problem is solved by a simple SMO optimizer. Set `solver` to [`SVMSolverName::SMO`](../enum.SVMSolverName.html) to use
+//! the exact solver of libsvm, with shrinking and a kernel cache of `cache_size` megabytes, whose results match libsvm up to the tolerance `tol`.
+//!
 //! Example:
 //!
 //! ```
@@ -80,7 +83,8 @@ use crate::error::{Failed, FailedError};
 use crate::linalg::basic::arrays::{Array1, Array2, MutArray};
 use crate::numbers::basenum::Number;
 use crate::numbers::floatnum::FloatNumber;
-use crate::svm::Kernel;
+use crate::svm::smo::{KernelCache, Solver};
+use crate::svm::{Kernel, SVMSolverName};
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug)]
@@ -92,6 +96,12 @@ pub struct SVRParameters<T: Number + FloatNumber + PartialOrd> {
     pub c: T,
     /// Tolerance for stopping criterion.
     pub tol: T,
+    /// Solver of the optimization problem.
+    pub solver: SVMSolverName,
+    /// Whether the SMO solver uses the shrinking heuristic.
+    pub shrinking: bool,
+    /// Size of the kernel cache of the SMO solver, in megabytes.
+    pub cache_size: f64,
     /// The kernel function.
     #[cfg_attr(
         all(feature = "serde", target_arch = "wasm32"),
@@ -159,6 +169,21 @@ impl<T: Number + FloatNumber + PartialOrd> SVRParameters<T> {
         self.tol = tol;
         self
     }
+    /// Solver of the optimization problem.
+    pub fn with_solver(mut self, solver: SVMSolverName) -> Self {
+        self.solver = solver;
+        self
+    }
+    /// Whether the SMO solver uses the shrinking heuristic.
+    pub fn with_shrinking(mut self, shrinking: bool) -> Self {
+        self.shrinking = shrinking;
+        self
+    }
+    /// Size of the kernel cache of the SMO solver, in megabytes.
+    pub fn with_cache_size(mut self, cache_size: f64) -> Self {
+        self.cache_size = cache_size;
+        self
+    }
     /// The kernel function.
     pub fn with_kernel<K: Kernel + 'static>(mut self, kernel: K) -> Self {
         self.kernel = Some(Box::new(kernel));
@@ -172,6 +197,9 @@ impl<T: Number + FloatNumber + PartialOrd> Default for SVRParameters<T> {
             eps: T::from_f64(0.1).unwrap(),
             c: T::one(),
             tol: T::from_f64(1e-3).unwrap(),
+            solver: SVMSolverName::default(),
+            shrinking: true,
+            cache_size: 200.,
             kernel: Option::None,
         }
     }
@@ -228,9 +256,13 @@ impl<'a, T: Number + FloatNumber + PartialOrd, X: Array2<T>, Y: Array1<T>> SVR<'
             ));
         }
 
-        let optimizer: Optimizer<'a, T> = Optimizer::new(x, y, parameters);
-
-        let (support_vectors, weight, b) = optimizer.smo();
+        let (support_vectors, weight, b) = match parameters.solver {
+            SVMSolverName::Approximate => {
+                let optimizer: Optimizer<'a, T> = Optimizer::new(x, y, parameters);
+                optimizer.smo()
+            }
+            SVMSolverName::SMO => SVR::<T, X, Y>::fit_smo(x, y, parameters)?,
+        };
 
         Ok(SVR {
             instances: Some(support_vectors),
@@ -241,6 +273,60 @@ impl<'a, T: Number + FloatNumber + PartialOrd, X: Array2<T>, Y: Array1<T>> SVR<'
         })
     }
 
+    /// Solves the dual problem with the exact SMO solver, returns support vectors, their weights and the intercept.
+    /// Variable \\(i < n\\) is \\(\alpha_i\\) and variable \\(n + i\\) is \\(\alpha_i^*\\) of the same observation.
+    #[allow(clippy::type_complexity)]
+    fn fit_smo(
+        x: &X,
+        y: &Y,
+        parameters: &SVRParameters<T>,
+    ) -> Result<(Vec<Vec<f64>>, Vec<T>, T), Failed> {
+        let (n, _) = x.shape();
+        let rows: Vec<Vec<f64>> = (0..n)
+            .map(|i| {
+                x.get_row(i)
+                    .iterator(0)
+                    .map(|e| e.to_f64().unwrap())
+                    .collect()
+            })
+            .collect();
+        let eps = parameters.eps.to_f64().unwrap();
+        let targets: Vec<f64> = y.iterator(0).map(|e| e.to_f64().unwrap()).collect();
+
+        let mut cache = KernelCache::new(
+            &rows,
+            parameters.kernel.as_ref().unwrap().as_ref(),
+            parameters.cache_size,
+        );
+        let solution = Solver::new(
+            &mut cache,
+            (0..n).chain(0..n).collect(),
+            (0..2 * n).map(|i| if i < n { 1. } else { -1. }).collect(),
+            targets
+                .iter()
+                .map(|y_i| eps - y_i)
+                .chain(targets.iter().map(|y_i| eps + y_i))
+                .collect(),
+            vec![parameters.c.to_f64().unwrap(); 2 * n],
+            vec![0.; 2 * n],
+            parameters.tol.to_f64().unwrap(),
+            parameters.shrinking,
+        )
+        .solve()?;
+
+        let mut support_vectors = Vec::new();
+        let mut w = Vec::new();
+        for (i, row) in rows.into_iter().enumerate() {
+            let coefficient = solution.alpha[i] - solution.alpha[n + i];
+            if coefficient != 0. {
+                support_vectors.push(row);
+                w.push(T::from(coefficient).unwrap());
+            }
+        }
+
+        Ok((support_vectors, w, T::from(-solution.rho).unwrap()))
+    }
+
     /// Predict target values from `x`
     /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
     pub fn predict(&self, x: &'a X) -> Result<Vec<T>, Failed> {
@@ -595,9 +681,11 @@ impl<T: Clone> Cache<T> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::linalg::basic::arrays::Array;
     use crate::linalg::basic::matrix::DenseMatrix;
     use crate::metrics::mean_squared_error;
     use crate::svm::Kernels;
+    use crate::test_datasets::sine;
 
     // #[test]
     // fn search_parameters() {
@@ -665,6 +753,83 @@ mod tests {
         assert!(t < 2.5);
     }
 
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn svr_fit_smo() {
+        let (x, y) = sine();
+        let (c, eps, tol) = (1., 0.1, 1e-3);
+
+        let mut predictions = Vec::new();
+        for shrinking in [true, false] {
+            let params = SVRParameters::default()
+                .with_c(c)
+                .with_eps(eps)
+                .with_tol(tol)
+                .with_kernel(Kernels::rbf().with_gamma(0.5))
+                .with_solver(SVMSolverName::SMO)
+                .with_shrinking(shrinking);
+            let svr = SVR::fit(&x, &y, &params).unwrap();
+            let y_hat = svr.predict(&x).unwrap();
+
+            // the dual constraint sum(alpha - alpha*) = 0
+            let w = svr.w.as_ref().unwrap();
+            assert!(w.iter().sum::<f64>().abs() < 1e-8);
+
+            // KKT conditions of every observation
+            for i in 0..30 {
+                let coefficient = svr
+                    .instances
+                    .as_ref()
+                    .unwrap()
+                    .iter()
+                    .position(|instance| instance[0] == *x.get((i, 0)))
+                    .map_or(0., |j| w[j].abs());
+                let residual = (y[i] - y_hat[i]).abs();
+                if coefficient == 0. {
+                    assert!(residual <= eps + 2. * tol, "{i}: {residual}");
+                } else if coefficient >= c {
+                    assert!(residual >= eps - 2. * tol, "{i}: {residual}");
+                } else {
+                    assert!((residual - eps).abs() <= 2. * tol, "{i}: {residual}");
+                }
+            }
+            predictions.push(y_hat);
+        }
+
+        for (with_shrinking, without_shrinking) in predictions[0].iter().zip(predictions[1].iter())
+        {
+            assert!((with_shrinking - without_shrinking).abs() < 1e-2);
+        }
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn svr_fit_smo_reference() {
+        // the flattest line within eps = 0.1 of all points is f(x) = 0.9x + 0.1, its dual solution is unique,
+        // libsvm keeps the first and the last observation with dual coefficients -0.45 and 0.45 and rho = -0.1
+        let x = DenseMatrix::from_2d_array(&[&[0.0], &[1.0], &[2.0]]).unwrap();
+        let y: Vec<f64> = vec![0.0, 1.0, 2.0];
+
+        let params = SVRParameters::default()
+            .with_c(1.0)
+            .with_eps(0.1)
+            .with_kernel(Kernels::linear())
+            .with_solver(SVMSolverName::SMO);
+        let svr = SVR::fit(&x, &y, &params).unwrap();
+
+        assert_eq!(svr.instances.as_ref().unwrap(), &vec![vec![0.0], vec![2.0]]);
+        let w = svr.w.as_ref().unwrap();
+        assert!((w[0] + 0.45).abs() < 1e-6, "{w:?}");
+        assert!((w[1] - 0.45).abs() < 1e-6, "{w:?}");
+        assert!((svr.b - 0.1).abs() < 1e-6, "{}", svr.b);
+    }
+
     #[cfg_attr(
         all(target_arch = "wasm32", not(target_os = "wasi")),
         wasm_bindgen_test::wasm_bindgen_test
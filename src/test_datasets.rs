@@ -0,0 +1,96 @@
+//! Small datasets and checks shared by the unit tests.
+use crate::linalg::basic::arrays::Array2;
+use crate::linalg::basic::matrix::DenseMatrix;
+
+/// Longley's economic regression data, total employment by six macroeconomic indicators.
+pub(crate) fn longley() -> (DenseMatrix<f64>, Vec<f64>) {
+    let x = DenseMatrix::from_2d_array(&[
+        &[234.289, 235.6, 159.0, 107.608, 1947., 60.323],
+        &[259.426, 232.5, 145.6, 108.632, 1948., 61.122],
+        &[258.054, 368.2, 161.6, 109.773, 1949., 60.171],
+        &[284.599, 335.1, 165.0, 110.929, 1950., 61.187],
+        &[328.975, 209.9, 309.9, 112.075, 1951., 63.221],
+        &[346.999, 193.2, 359.4, 113.270, 1952., 63.639],
+        &[365.385, 187.0, 354.7, 115.094, 1953., 64.989],
+        &[363.112, 357.8, 335.0, 116.219, 1954., 63.761],
+        &[397.469, 290.4, 304.8, 117.388, 1955., 66.019],
+        &[419.180, 282.2, 285.7, 118.734, 1956., 67.857],
+        &[442.769, 293.6, 279.8, 120.445, 1957., 68.169],
+        &[444.546, 468.1, 263.7, 121.950, 1958., 66.513],
+        &[482.704, 381.3, 255.2, 123.366, 1959., 68.655],
+        &[502.601, 393.1, 251.4, 125.368, 1960., 69.564],
+        &[518.173, 480.6, 257.2, 127.852, 1961., 69.331],
+        &[554.894, 400.7, 282.7, 130.081, 1962., 70.551],
+    ])
+    .unwrap();
+
+    let y: Vec<f64> = vec![
+        83.0, 88.5, 88.2, 89.5, 96.2, 98.1, 99.0, 100.0, 101.2, 104.6, 108.4, 110.8, 112.6, 114.2,
+        115.7, 116.9,
+    ];
+
+    (x, y)
+}
+
+/// Checks that integer `sample_weight` are equivalent to repeating every observation as many times as its weight.
+/// `fit_weighted` and `fit` fit a model with and without weights and return outputs to compare, e.g. predictions for `x`.
+pub(crate) fn assert_weights_repeat_rows<T: Copy>(
+    x: &DenseMatrix<f64>,
+    y: &[T],
+    sample_weight: &[f64],
+    fit_weighted: impl Fn(&DenseMatrix<f64>, &Vec<T>, &[f64]) -> Vec<f64>,
+    fit: impl Fn(&DenseMatrix<f64>, &Vec<T>) -> Vec<f64>,
+    tol: f64,
+) {
+    let rows: Vec<usize> = sample_weight
+        .iter()
+        .enumerate()
+        .flat_map(|(i, &w)| vec![i; w as usize])
+        .collect();
+    let x_repeated = x.take(&rows, 0);
+    let y_repeated: Vec<T> = rows.iter().map(|&i| y[i]).collect();
+
+    let weighted = fit_weighted(x, &y.to_vec(), sample_weight);
+    let repeated = fit(&x_repeated, &y_repeated);
+    assert_eq!(weighted.len(), repeated.len());
+    for (w, r) in weighted.iter().zip(repeated.iter()) {
+        assert!((w - r).abs() < tol, "{w} != {r}");
+    }
+}
+
+/// The first six observations of every class of Fisher's iris data.
+pub(crate) fn iris() -> (DenseMatrix<f64>, Vec<u32>) {
+    let x = DenseMatrix::from_2d_array(&[
+        &[5.1, 3.5, 1.4, 0.2],
+        &[4.9, 3.0, 1.4, 0.2],
+        &[4.7, 3.2, 1.3, 0.2],
+        &[4.6, 3.1, 1.5, 0.2],
+        &[5.0, 3.6, 1.4, 0.2],
+        &[5.4, 3.9, 1.7, 0.4],
+        &[7.0, 3.2, 4.7, 1.4],
+        &[6.4, 3.2, 4.5, 1.5],
+        &[6.9, 3.1, 4.9, 1.5],
+        &[5.5, 2.3, 4.0, 1.3],
+        &[6.5, 2.8, 4.6, 1.5],
+        &[5.7, 2.8, 4.5, 1.3],
+        &[6.3, 3.3, 6.0, 2.5],
+        &[5.8, 2.7, 5.1, 1.9],
+        &[7.1, 3.0, 5.9, 2.1],
+        &[6.3, 2.9, 5.6, 1.8],
+        &[6.5, 3.0, 5.8, 2.2],
+        &[7.6, 3.0, 6.6, 2.1],
+    ])
+    .unwrap();
+
+    let y: Vec<u32> = vec![0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 1, 1, 2, 2, 2, 2, 2, 2];
+
+    (x, y)
+}
+
+/// The sine at thirty evenly spaced points between 0 and 10.
+pub(crate) fn sine() -> (DenseMatrix<f64>, Vec<f64>) {
+    let x = DenseMatrix::from_iterator((0..30).map(|i| i as f64 / 3.), 30, 1, 0);
+    let y: Vec<f64> = (0..30).map(|i| (i as f64 / 3.).sin()).collect();
+
+    (x, y)
+}
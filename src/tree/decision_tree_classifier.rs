@@ -18,6 +18,8 @@
 //!
 //! The Entropy, like Gini index will take on a small value if the *m*th node is pure.
 //!
+//! Missing feature values, encoded as `NaN`, follow a default direction learned at every split, see [missing values](../index.html#missing-values).
+//!
 //! Example:
 //!
 //! ```
@@ -55,6 +57,7 @@
 //! let tree = DecisionTreeClassifier::fit(&x, &y, Default::default()).unwrap();
 //!
 //! let y_hat = tree.predict(&x).unwrap(); // use the same data for prediction
+//! let y_proba = tree.predict_proba(&x).unwrap(); // class frequencies in the leaves
 //! ```
 //!
 //!
@@ -77,9 +80,12 @@ use serde::{Deserialize, Serialize};
 
 use crate::api::{Predictor, SupervisedEstimator};
 use crate::error::Failed;
-use crate::linalg::basic::arrays::{Array1, Array2, MutArrayView1};
+use crate::linalg::basic::arrays::{Array1, Array2};
 use crate::numbers::basenum::Number;
+use crate::numbers::floatnum::FloatNumber;
 use crate::rand_custom::get_rng_impl;
+use crate::tree::tree_structure::{TreeNode, TreeStructure};
+use crate::tree::{cost_complexity_prune, sort_columns, CostComplexityPruningPath, Splitter};
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
@@ -98,6 +104,12 @@ pub struct DecisionTreeClassifierParameters {
     /// The minimum number of samples required to split an internal node.
     pub min_samples_split: usize,
     #[cfg_attr(feature = "serde", serde(default))]
+    /// Complexity parameter used for [minimal cost-complexity pruning](../index.html#cost-complexity-pruning). No pruning is performed by default.
+    pub ccp_alpha: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Strategy used to choose the split of every candidate feature, see [split strategies](../index.html#split-strategies).
+    pub splitter: Splitter,
+    #[cfg_attr(feature = "serde", serde(default))]
     /// Controls the randomness of the estimator
     pub seed: Option<u64>,
 }
@@ -167,6 +179,16 @@ struct Node {
     true_child: Option<usize>,
     false_child: Option<usize>,
     impurity: Option<f64>,
+    /// whether observations with a missing value of the split feature go to the true child
+    #[cfg_attr(feature = "serde", serde(default))]
+    missing_to_true_child: bool,
+    /// whether the node separates observations with a missing value of the split feature, which go to the false child,
+    /// from all other observations, `split_value` is `None` for such nodes
+    #[cfg_attr(feature = "serde", serde(default))]
+    split_on_missing: bool,
+    /// number of training samples of every class at the node
+    #[cfg_attr(feature = "serde", serde(default))]
+    class_counts: Vec<usize>,
 }
 
 impl<TX: Number + PartialOrd, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>> PartialEq
@@ -196,6 +218,8 @@ impl PartialEq for Node {
     fn eq(&self, other: &Self) -> bool {
         self.output == other.output
             && self.split_feature == other.split_feature
+            && self.missing_to_true_child == other.missing_to_true_child
+            && self.split_on_missing == other.split_on_missing
             && match (self.split_value, other.split_value) {
                 (Some(a), Some(b)) => (a - b).abs() < std::f64::EPSILON,
                 (None, None) => true,
@@ -230,6 +254,16 @@ impl DecisionTreeClassifierParameters {
         self.min_samples_split = min_samples_split;
         self
     }
+    /// Complexity parameter used for minimal cost-complexity pruning.
+    pub fn with_ccp_alpha(mut self, ccp_alpha: f64) -> Self {
+        self.ccp_alpha = ccp_alpha;
+        self
+    }
+    /// Strategy used to choose the split of every candidate feature.
+    pub fn with_splitter(mut self, splitter: Splitter) -> Self {
+        self.splitter = splitter;
+        self
+    }
 }
 
 impl Default for DecisionTreeClassifierParameters {
@@ -239,6 +273,8 @@ impl Default for DecisionTreeClassifierParameters {
             max_depth: Option::None,
             min_samples_leaf: 1,
             min_samples_split: 2,
+            ccp_alpha: 0f64,
+            splitter: Splitter::default(),
             seed: Option::None,
         }
     }
@@ -261,6 +297,12 @@ pub struct DecisionTreeClassifierSearchParameters {
     /// The minimum number of samples required to split an internal node. See [Decision Tree Classifier](../../tree/decision_tree_classifier/index.html)
     pub min_samples_split: Vec<usize>,
     #[cfg_attr(feature = "serde", serde(default))]
+    /// Complexity parameter used for minimal cost-complexity pruning. See [Decision Tree Classifier](../../tree/decision_tree_classifier/index.html)
+    pub ccp_alpha: Vec<f64>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Strategy used to choose the split of every candidate feature. See [Decision Tree Classifier](../../tree/decision_tree_classifier/index.html)
+    pub splitter: Vec<Splitter>,
+    #[cfg_attr(feature = "serde", serde(default))]
     /// Controls the randomness of the estimator
     pub seed: Vec<Option<u64>>,
 }
@@ -272,6 +314,8 @@ pub struct DecisionTreeClassifierSearchParametersIterator {
     current_max_depth: usize,
     current_min_samples_leaf: usize,
     current_min_samples_split: usize,
+    current_ccp_alpha: usize,
+    current_splitter: usize,
     current_seed: usize,
 }
 
@@ -286,6 +330,8 @@ impl IntoIterator for DecisionTreeClassifierSearchParameters {
             current_max_depth: 0,
             current_min_samples_leaf: 0,
             current_min_samples_split: 0,
+            current_ccp_alpha: 0,
+            current_splitter: 0,
             current_seed: 0,
         }
     }
@@ -315,6 +361,16 @@ impl Iterator for DecisionTreeClassifierSearchParametersIterator {
                     .decision_tree_classifier_search_parameters
                     .min_samples_split
                     .len()
+            && self.current_ccp_alpha
+                == self
+                    .decision_tree_classifier_search_parameters
+                    .ccp_alpha
+                    .len()
+            && self.current_splitter
+                == self
+                    .decision_tree_classifier_search_parameters
+                    .splitter
+                    .len()
             && self.current_seed == self.decision_tree_classifier_search_parameters.seed.len()
         {
             return None;
@@ -332,6 +388,10 @@ impl Iterator for DecisionTreeClassifierSearchParametersIterator {
             min_samples_split: self
                 .decision_tree_classifier_search_parameters
                 .min_samples_split[self.current_min_samples_split],
+            ccp_alpha: self.decision_tree_classifier_search_parameters.ccp_alpha
+                [self.current_ccp_alpha],
+            splitter: self.decision_tree_classifier_search_parameters.splitter
+                [self.current_splitter],
             seed: self.decision_tree_classifier_search_parameters.seed[self.current_seed],
         };
 
@@ -369,18 +429,45 @@ impl Iterator for DecisionTreeClassifierSearchParametersIterator {
             self.current_max_depth = 0;
             self.current_min_samples_leaf = 0;
             self.current_min_samples_split += 1;
+        } else if self.current_ccp_alpha + 1
+            < self
+                .decision_tree_classifier_search_parameters
+                .ccp_alpha
+                .len()
+        {
+            self.current_criterion = 0;
+            self.current_max_depth = 0;
+            self.current_min_samples_leaf = 0;
+            self.current_min_samples_split = 0;
+            self.current_ccp_alpha += 1;
+        } else if self.current_splitter + 1
+            < self
+                .decision_tree_classifier_search_parameters
+                .splitter
+                .len()
+        {
+            self.current_criterion = 0;
+            self.current_max_depth = 0;
+            self.current_min_samples_leaf = 0;
+            self.current_min_samples_split = 0;
+            self.current_ccp_alpha = 0;
+            self.current_splitter += 1;
         } else if self.current_seed + 1 < self.decision_tree_classifier_search_parameters.seed.len()
         {
             self.current_criterion = 0;
             self.current_max_depth = 0;
             self.current_min_samples_leaf = 0;
             self.current_min_samples_split = 0;
+            self.current_ccp_alpha = 0;
+            self.current_splitter = 0;
             self.current_seed += 1;
         } else {
             self.current_criterion += 1;
             self.current_max_depth += 1;
             self.current_min_samples_leaf += 1;
             self.current_min_samples_split += 1;
+            self.current_ccp_alpha += 1;
+            self.current_splitter += 1;
             self.current_seed += 1;
         }
 
@@ -397,6 +484,8 @@ impl Default for DecisionTreeClassifierSearchParameters {
             max_depth: vec![default_params.max_depth],
             min_samples_leaf: vec![default_params.min_samples_leaf],
             min_samples_split: vec![default_params.min_samples_split],
+            ccp_alpha: vec![default_params.ccp_alpha],
+            splitter: vec![default_params.splitter],
             seed: vec![default_params.seed],
         }
     }
@@ -413,6 +502,47 @@ impl Node {
             true_child: Option::None,
             false_child: Option::None,
             impurity: Option::None,
+            missing_to_true_child: false,
+            split_on_missing: false,
+            class_counts: Vec::new(),
+        }
+    }
+
+    /// Fraction of training samples of every class at the node.
+    fn class_probabilities(&self, num_classes: usize) -> Vec<f64> {
+        let n: usize = self.class_counts.iter().sum();
+        if n == 0 {
+            // trees fitted before class counts were stored at the nodes
+            let mut probabilities = vec![0f64; num_classes];
+            probabilities[self.output] = 1f64;
+            probabilities
+        } else {
+            self.class_counts
+                .iter()
+                .map(|&count| count as f64 / n as f64)
+                .collect()
+        }
+    }
+
+    /// Turns the node into a leaf.
+    fn collapse(&mut self) {
+        self.split_feature = 0;
+        self.split_value = Option::None;
+        self.split_score = Option::None;
+        self.true_child = Option::None;
+        self.false_child = Option::None;
+        self.missing_to_true_child = false;
+        self.split_on_missing = false;
+    }
+
+    /// Whether an observation with `value` of the split feature goes to the true child.
+    fn goes_to_true_child(&self, value: f64) -> bool {
+        if value.is_nan() {
+            self.missing_to_true_child
+        } else if self.split_on_missing {
+            true
+        } else {
+            value <= self.split_value.unwrap_or(f64::NAN)
         }
     }
 }
@@ -423,6 +553,7 @@ struct NodeVisitor<'a, TX: Number + PartialOrd, X: Array2<TX>> {
     node: usize,
     samples: Vec<usize>,
     order: &'a [Vec<usize>],
+    missing: &'a [Vec<usize>],
     true_child_output: usize,
     false_child_output: usize,
     level: u16,
@@ -469,6 +600,7 @@ impl<'a, TX: Number + PartialOrd, X: Array2<TX>> NodeVisitor<'a, TX, X> {
         node_id: usize,
         samples: Vec<usize>,
         order: &'a [Vec<usize>],
+        missing: &'a [Vec<usize>],
         x: &'a X,
         y: &'a [usize],
         level: u16,
@@ -479,6 +611,7 @@ impl<'a, TX: Number + PartialOrd, X: Array2<TX>> NodeVisitor<'a, TX, X> {
             node: node_id,
             samples,
             order,
+            missing,
             true_child_output: 0,
             false_child_output: 0,
             level,
@@ -559,6 +692,13 @@ impl<TX: Number + PartialOrd, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>>
         mtry: usize,
         parameters: DecisionTreeClassifierParameters,
     ) -> Result<DecisionTreeClassifier<TX, TY, X, Y>, Failed> {
+        if parameters.ccp_alpha < 0f64 {
+            return Err(Failed::fit(&format!(
+                "ccp_alpha should be non-negative, got {}",
+                parameters.ccp_alpha
+            )));
+        }
+
         let y_ncols = y.shape();
         let (_, num_attributes) = x.shape();
         let classes = y.unique();
@@ -586,12 +726,7 @@ impl<TX: Number + PartialOrd, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>>
 
         let root = Node::new(which_max(&count), y_ncols);
         change_nodes.push(root);
-        let mut order: Vec<Vec<usize>> = Vec::new();
-
-        for i in 0..num_attributes {
-            let mut col_i: Vec<TX> = x.get_col(i).iterator(0).copied().collect();
-            order.push(col_i.argsort_mut());
-        }
+        let (order, missing) = sort_columns(x);
 
         let mut tree = DecisionTreeClassifier {
             nodes: change_nodes,
@@ -605,7 +740,7 @@ impl<TX: Number + PartialOrd, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>>
             _phantom_y: PhantomData,
         };
 
-        let mut visitor = NodeVisitor::<TX, X>::new(0, samples, &order, x, &yi, 1);
+        let mut visitor = NodeVisitor::<TX, X>::new(0, samples, &order, &missing, x, &yi, 1);
 
         let mut visitor_queue: LinkedList<NodeVisitor<'_, TX, X>> = LinkedList::new();
 
@@ -620,9 +755,104 @@ impl<TX: Number + PartialOrd, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>>
             };
         }
 
+        let ccp_alpha = tree.parameters().ccp_alpha;
+        if ccp_alpha > 0f64 {
+            tree.prune(ccp_alpha);
+        }
+
         Ok(tree)
     }
 
+    /// Compute the [minimal cost-complexity pruning](../index.html#cost-complexity-pruning) path of the fitted tree.
+    /// Fitting the tree again with `ccp_alpha` set to one of the returned alphas yields the corresponding subtree.
+    pub fn cost_complexity_pruning_path(&self) -> CostComplexityPruningPath {
+        let (children, risk) = self.pruning_inputs();
+        cost_complexity_prune(&children, &risk, f64::INFINITY).0
+    }
+
+    /// Structure of the fitted tree: split features, thresholds, children and predicted classes of all nodes.
+    pub fn structure(&self) -> TreeStructure {
+        let nodes = self
+            .nodes()
+            .iter()
+            .map(|node| {
+                let is_split = node.true_child.is_some();
+                TreeNode {
+                    feature: node.true_child.map(|_| node.split_feature),
+                    threshold: node.true_child.and(node.split_value),
+                    missing_to_true_child: is_split && node.missing_to_true_child,
+                    split_on_missing: is_split && node.split_on_missing,
+                    true_child: node.true_child,
+                    false_child: node.false_child,
+                    n_node_samples: node.n_node_samples,
+                    impurity: node.impurity,
+                    value: self.classes()[node.output].to_f64().unwrap(),
+                }
+            })
+            .collect();
+
+        TreeStructure {
+            nodes,
+            n_features: self.num_features,
+            classes: self
+                .classes()
+                .iter()
+                .map(|class| class.to_f64().unwrap())
+                .collect(),
+        }
+    }
+
+    /// Render the fitted tree as [Graphviz](https://graphviz.org/) DOT text, see [TreeStructure::export_graphviz].
+    pub fn export_graphviz(&self, feature_names: Option<&[&str]>) -> Result<String, Failed> {
+        self.structure().export_graphviz(feature_names)
+    }
+
+    /// Render the fitted tree as nested if/else rules, see [TreeStructure::export_text].
+    pub fn export_text(&self, feature_names: Option<&[&str]>) -> Result<String, Failed> {
+        self.structure().export_text(feature_names)
+    }
+
+    /// Children and impurity weighted by the fraction of training samples of every node.
+    fn pruning_inputs(&self) -> (Vec<Option<(usize, usize)>>, Vec<f64>) {
+        let n = self.nodes()[0].n_node_samples as f64;
+        self.nodes()
+            .iter()
+            .map(|node| {
+                (
+                    node.true_child.zip(node.false_child),
+                    node.n_node_samples as f64 * node.impurity.unwrap_or(0f64) / n,
+                )
+            })
+            .unzip()
+    }
+
+    fn prune(&mut self, ccp_alpha: f64) {
+        let (children, risk) = self.pruning_inputs();
+        let (_, is_leaf) = cost_complexity_prune(&children, &risk, ccp_alpha);
+
+        let mut nodes = vec![self.nodes[0].clone()];
+        let mut queue: LinkedList<(usize, usize, u16)> = LinkedList::new();
+        queue.push_back((0, 0, 1));
+        self.depth = 0;
+
+        while let Some((node_id, new_node_id, level)) = queue.pop_front() {
+            if is_leaf[node_id] {
+                nodes[new_node_id].collapse();
+                continue;
+            }
+            self.depth = u16::max(self.depth, level + 1);
+            let node = &self.nodes[node_id];
+            for child_id in [node.true_child.unwrap(), node.false_child.unwrap()] {
+                queue.push_back((child_id, nodes.len(), level + 1));
+                nodes.push(self.nodes[child_id].clone());
+            }
+            nodes[new_node_id].true_child = Some(nodes.len() - 2);
+            nodes[new_node_id].false_child = Some(nodes.len() - 1);
+        }
+
+        self.nodes = nodes;
+    }
+
     /// Predict class value for `x`.
     /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
     pub fn predict(&self, x: &X) -> Result<Y, Failed> {
@@ -637,7 +867,35 @@ impl<TX: Number + PartialOrd, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>>
         Ok(result)
     }
 
+    /// Predict class probabilities for `x`, estimated by the fraction of training samples of every class
+    /// in the leaf every observation falls into.
+    /// Returns a _KxC_ matrix, columns are ordered like the sorted class labels.
+    /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
+    pub fn predict_proba(&self, x: &X) -> Result<X, Failed>
+    where
+        TX: FloatNumber,
+    {
+        let (n, _) = x.shape();
+        let mut proba = X::zeros(n, self.num_classes);
+
+        for i in 0..n {
+            for (c, p) in self.predict_proba_for_row(x, i).into_iter().enumerate() {
+                proba.set((i, c), TX::from_f64(p).unwrap());
+            }
+        }
+
+        Ok(proba)
+    }
+
     pub(crate) fn predict_for_row(&self, x: &X, row: usize) -> usize {
+        self.nodes()[self.leaf_for_row(x, row)].output
+    }
+
+    pub(crate) fn predict_proba_for_row(&self, x: &X, row: usize) -> Vec<f64> {
+        self.nodes()[self.leaf_for_row(x, row)].class_probabilities(self.num_classes)
+    }
+
+    fn leaf_for_row(&self, x: &X, row: usize) -> usize {
         let mut result = 0;
         let mut queue: LinkedList<usize> = LinkedList::new();
 
@@ -648,9 +906,9 @@ impl<TX: Number + PartialOrd, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>>
                 Some(node_id) => {
                     let node = &self.nodes()[node_id];
                     if node.true_child.is_none() && node.false_child.is_none() {
-                        result = node.output;
-                    } else if x.get((row, node.split_feature)).to_f64().unwrap()
-                        <= node.split_value.unwrap_or(std::f64::NAN)
+                        result = node_id;
+                    } else if node
+                        .goes_to_true_child(x.get((row, node.split_feature)).to_f64().unwrap())
                     {
                         queue.push_back(node.true_child.unwrap());
                     } else {
@@ -695,6 +953,7 @@ impl<TX: Number + PartialOrd, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>>
         }
 
         self.nodes[visitor.node].impurity = Some(impurity(&self.parameters().criterion, &count, n));
+        self.nodes[visitor.node].class_counts = count.clone();
 
         if is_pure {
             return false;
@@ -710,8 +969,16 @@ impl<TX: Number + PartialOrd, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>>
             variables.shuffle(rng);
         }
 
+        let splitter = self.parameters().splitter;
         for variable in variables.iter().take(mtry) {
-            self.find_best_split(visitor, n, &count, &mut false_count, *variable);
+            match splitter {
+                Splitter::Best => {
+                    self.find_best_split(visitor, n, &count, &mut false_count, *variable)
+                }
+                Splitter::Random => {
+                    self.find_random_split(visitor, n, &count, &mut false_count, *variable, rng)
+                }
+            }
         }
 
         self.nodes()[visitor.node].split_score.is_some()
@@ -725,7 +992,14 @@ impl<TX: Number + PartialOrd, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>>
         false_count: &mut [usize],
         j: usize,
     ) {
+        let mut missing_count = vec![0; self.num_classes];
+        for i in visitor.missing[j].iter() {
+            missing_count[visitor.y[*i]] += visitor.samples[*i];
+        }
+        let has_missing = missing_count.iter().any(|&c| c > 0);
+
         let mut true_count = vec![0; self.num_classes];
+        let mut true_and_missing_count = missing_count.clone();
         let mut prevx = Option::None;
         let mut prevy = 0;
 
@@ -733,55 +1007,154 @@ impl<TX: Number + PartialOrd, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>>
             if visitor.samples[*i] > 0 {
                 let x_ij = *visitor.x.get((*i, j));
 
-                if prevx.is_none() || x_ij == prevx.unwrap() || visitor.y[*i] == prevy {
-                    prevx = Some(x_ij);
-                    prevy = visitor.y[*i];
-                    true_count[visitor.y[*i]] += visitor.samples[*i];
-                    continue;
+                if let Some(prev) = prevx.filter(|prev| x_ij != *prev && visitor.y[*i] != prevy) {
+                    let split_value = (x_ij + prev).to_f64().unwrap() / 2f64;
+                    self.try_split(
+                        visitor,
+                        n,
+                        count,
+                        &true_count,
+                        false_count,
+                        j,
+                        Some(split_value),
+                        false,
+                    );
+                    if has_missing {
+                        self.try_split(
+                            visitor,
+                            n,
+                            count,
+                            &true_and_missing_count,
+                            false_count,
+                            j,
+                            Some(split_value),
+                            true,
+                        );
+                    }
                 }
 
-                let tc = true_count.iter().sum();
-                let fc = n - tc;
+                prevx = Some(x_ij);
+                prevy = visitor.y[*i];
+                true_count[visitor.y[*i]] += visitor.samples[*i];
+                true_and_missing_count[visitor.y[*i]] += visitor.samples[*i];
+            }
+        }
 
-                if tc < self.parameters().min_samples_leaf
-                    || fc < self.parameters().min_samples_leaf
-                {
-                    prevx = Some(x_ij);
-                    prevy = visitor.y[*i];
-                    true_count[visitor.y[*i]] += visitor.samples[*i];
-                    continue;
-                }
+        // observations with a missing value against all other observations
+        if has_missing {
+            self.try_split(visitor, n, count, &true_count, false_count, j, None, false);
+        }
+    }
 
-                for l in 0..self.num_classes {
-                    false_count[l] = count[l] - true_count[l];
-                }
+    /// Splits feature `j` at a threshold drawn uniformly at random between its smallest and largest finite value at the node,
+    /// infinite values go to the side given by the comparison with the threshold.
+    fn find_random_split(
+        &mut self,
+        visitor: &mut NodeVisitor<'_, TX, X>,
+        n: usize,
+        count: &[usize],
+        false_count: &mut [usize],
+        j: usize,
+        rng: &mut impl Rng,
+    ) {
+        let mut values = visitor.order[j]
+            .iter()
+            .filter(|&&i| visitor.samples[i] > 0)
+            .map(|&i| visitor.x.get((i, j)).to_f64().unwrap())
+            .filter(|v| v.is_finite());
+        let (min, max) = match (values.next(), values.next_back()) {
+            (Some(min), Some(max)) if min < max => (min, max),
+            _ => return,
+        };
+        // a convex combination of the bounds does not overflow when max - min does
+        let u: f64 = rng.gen();
+        let split_value = (min * (1f64 - u) + max * u).clamp(min, max);
 
-                let true_label = which_max(&true_count);
-                let false_label = which_max(false_count);
-                let parent_impurity = self.nodes()[visitor.node].impurity.unwrap();
-                let gain = parent_impurity
-                    - tc as f64 / n as f64
-                        * impurity(&self.parameters().criterion, &true_count, tc)
-                    - fc as f64 / n as f64
-                        * impurity(&self.parameters().criterion, false_count, fc);
-
-                if self.nodes()[visitor.node].split_score.is_none()
-                    || gain > self.nodes()[visitor.node].split_score.unwrap()
-                {
-                    self.nodes[visitor.node].split_feature = j;
-                    self.nodes[visitor.node].split_value =
-                        Option::Some((x_ij + prevx.unwrap()).to_f64().unwrap() / 2f64);
-                    self.nodes[visitor.node].split_score = Option::Some(gain);
-
-                    visitor.true_child_output = true_label;
-                    visitor.false_child_output = false_label;
+        let mut true_count = vec![0; self.num_classes];
+        for i in visitor.order[j].iter() {
+            if visitor.samples[*i] > 0 {
+                if visitor.x.get((*i, j)).to_f64().unwrap() > split_value {
+                    break;
                 }
+                true_count[visitor.y[*i]] += visitor.samples[*i];
+            }
+        }
+        self.try_split(
+            visitor,
+            n,
+            count,
+            &true_count,
+            false_count,
+            j,
+            Some(split_value),
+            false,
+        );
 
-                prevx = Some(x_ij);
-                prevy = visitor.y[*i];
+        let mut has_missing = false;
+        for i in visitor.missing[j].iter() {
+            if visitor.samples[*i] > 0 {
+                has_missing = true;
                 true_count[visitor.y[*i]] += visitor.samples[*i];
             }
         }
+        if has_missing {
+            self.try_split(
+                visitor,
+                n,
+                count,
+                &true_count,
+                false_count,
+                j,
+                Some(split_value),
+                true,
+            );
+        }
+    }
+
+    /// Scores the split of the node into observations counted by `true_count` and all other observations,
+    /// and keeps it when it is the best split so far.
+    #[allow(clippy::too_many_arguments)]
+    fn try_split(
+        &mut self,
+        visitor: &mut NodeVisitor<'_, TX, X>,
+        n: usize,
+        count: &[usize],
+        true_count: &[usize],
+        false_count: &mut [usize],
+        j: usize,
+        split_value: Option<f64>,
+        missing_to_true_child: bool,
+    ) {
+        let tc = true_count.iter().sum();
+        let fc = n - tc;
+
+        if tc < self.parameters().min_samples_leaf || fc < self.parameters().min_samples_leaf {
+            return;
+        }
+
+        for l in 0..self.num_classes {
+            false_count[l] = count[l] - true_count[l];
+        }
+
+        let true_label = which_max(true_count);
+        let false_label = which_max(false_count);
+        let parent_impurity = self.nodes()[visitor.node].impurity.unwrap();
+        let gain = parent_impurity
+            - tc as f64 / n as f64 * impurity(&self.parameters().criterion, true_count, tc)
+            - fc as f64 / n as f64 * impurity(&self.parameters().criterion, false_count, fc);
+
+        if self.nodes()[visitor.node].split_score.is_none()
+            || gain > self.nodes()[visitor.node].split_score.unwrap()
+        {
+            self.nodes[visitor.node].split_feature = j;
+            self.nodes[visitor.node].split_value = split_value;
+            self.nodes[visitor.node].split_score = Option::Some(gain);
+            self.nodes[visitor.node].missing_to_true_child = missing_to_true_child;
+            self.nodes[visitor.node].split_on_missing = split_value.is_none();
+
+            visitor.true_child_output = true_label;
+            visitor.false_child_output = false_label;
+        }
     }
 
     fn split<'a>(
@@ -795,18 +1168,12 @@ impl<TX: Number + PartialOrd, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>>
         let mut tc = 0;
         let mut fc = 0;
         let mut true_samples: Vec<usize> = vec![0; n];
+        let split_feature = self.nodes()[visitor.node].split_feature;
 
         for (i, true_sample) in true_samples.iter_mut().enumerate().take(n) {
             if visitor.samples[i] > 0 {
-                if visitor
-                    .x
-                    .get((i, self.nodes()[visitor.node].split_feature))
-                    .to_f64()
-                    .unwrap()
-                    <= self.nodes()[visitor.node]
-                        .split_value
-                        .unwrap_or(std::f64::NAN)
-                {
+                let value = visitor.x.get((i, split_feature)).to_f64().unwrap();
+                if self.nodes()[visitor.node].goes_to_true_child(value) {
                     *true_sample = visitor.samples[i];
                     tc += *true_sample;
                     visitor.samples[i] = 0;
@@ -820,10 +1187,20 @@ impl<TX: Number + PartialOrd, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>>
             self.nodes[visitor.node].split_feature = 0;
             self.nodes[visitor.node].split_value = Option::None;
             self.nodes[visitor.node].split_score = Option::None;
+            self.nodes[visitor.node].missing_to_true_child = false;
+            self.nodes[visitor.node].split_on_missing = false;
 
             return false;
         }
 
+        // without missing values in training, missing values follow the larger child
+        if !visitor.missing[split_feature]
+            .iter()
+            .any(|&i| true_samples[i] > 0 || visitor.samples[i] > 0)
+        {
+            self.nodes[visitor.node].missing_to_true_child = tc >= fc;
+        }
+
         let true_child_idx = self.nodes().len();
 
         self.nodes.push(Node::new(visitor.true_child_output, tc));
@@ -838,6 +1215,7 @@ impl<TX: Number + PartialOrd, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>>
             true_child_idx,
             true_samples,
             visitor.order,
+            visitor.missing,
             visitor.x,
             visitor.y,
             visitor.level + 1,
@@ -851,6 +1229,7 @@ impl<TX: Number + PartialOrd, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>>
             false_child_idx,
             visitor.samples,
             visitor.order,
+            visitor.missing,
             visitor.x,
             visitor.y,
             visitor.level + 1,
@@ -894,6 +1273,7 @@ impl<TX: Number + PartialOrd, TY: Number + Ord, X: Array2<TX>, Y: Array1<TY>>
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::linalg::basic::arrays::Array;
     use crate::linalg::basic::matrix::DenseMatrix;
 
     #[test]
@@ -986,6 +1366,8 @@ mod tests {
                     max_depth: Some(3),
                     min_samples_leaf: 1,
                     min_samples_split: 2,
+                    ccp_alpha: 0f64,
+                    splitter: Splitter::Best,
                     seed: Option::None
                 }
             )
@@ -994,6 +1376,62 @@ mod tests {
         );
     }
 
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn random_splitter_with_infinite_values() {
+        // an infinite outlier does not keep the feature from being split
+        let x = DenseMatrix::from_2d_array(&[
+            &[f64::NEG_INFINITY],
+            &[1.],
+            &[2.],
+            &[3.],
+            &[4.],
+            &[f64::INFINITY],
+        ])
+        .unwrap();
+        let y: Vec<u32> = vec![0, 0, 0, 1, 1, 1];
+        let tree = DecisionTreeClassifier::fit(
+            &x,
+            &y,
+            DecisionTreeClassifierParameters {
+                seed: Some(1),
+                ..DecisionTreeClassifierParameters::default()
+                    .with_splitter(Splitter::Random)
+                    .with_min_samples_split(1)
+            },
+        )
+        .unwrap();
+        assert_eq!(tree.predict(&x).unwrap(), y);
+        assert!(tree.nodes.len() > 1);
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn predict_proba() {
+        let x = DenseMatrix::from_2d_array(&[&[1.], &[2.], &[3.], &[4.], &[5.], &[6.]]).unwrap();
+        let y: Vec<u32> = vec![0, 0, 0, 1, 0, 0];
+        let tree = DecisionTreeClassifier::fit(
+            &x,
+            &y,
+            DecisionTreeClassifierParameters::default().with_max_depth(1),
+        )
+        .unwrap();
+
+        let x_test = DenseMatrix::from_2d_array(&[&[2.], &[5.]]).unwrap();
+        let proba: DenseMatrix<f64> = tree.predict_proba(&x_test).unwrap();
+        assert_eq!(proba.shape(), (2, 2));
+        assert!((*proba.get((0, 0)) - 1.).abs() < 1e-8);
+        assert!(proba.get((0, 1)).abs() < 1e-8);
+        assert!((*proba.get((1, 0)) - 2. / 3.).abs() < 1e-8);
+        assert!((*proba.get((1, 1)) - 1. / 3.).abs() < 1e-8);
+    }
+
     #[test]
     fn test_random_matrix_with_wrong_rownum() {
         let x_rand: DenseMatrix<f64> = DenseMatrix::<f64>::rand(21, 200);
@@ -1081,6 +1519,98 @@ mod tests {
         );
     }
 
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn cost_complexity_pruning() {
+        let x = DenseMatrix::from_2d_array(&[&[1.], &[2.], &[3.], &[4.], &[5.], &[6.]]).unwrap();
+        let y: Vec<u32> = vec![0, 0, 0, 1, 0, 0];
+
+        // the root is the weakest link: (10/36 - 0) / (3 - 1) < (8/36 - 0) / (2 - 1)
+        let tree = DecisionTreeClassifier::fit(&x, &y, Default::default()).unwrap();
+        assert_eq!(tree.depth(), 3);
+        let path = tree.cost_complexity_pruning_path();
+        assert_eq!(path.ccp_alphas.len(), 2);
+        assert!((path.ccp_alphas[1] - 5. / 36.).abs() < 1e-8);
+        assert!(path.impurities[0].abs() < 1e-8);
+        assert!((path.impurities[1] - 10. / 36.).abs() < 1e-8);
+
+        let tree = DecisionTreeClassifier::fit(
+            &x,
+            &y,
+            DecisionTreeClassifierParameters::default().with_ccp_alpha(0.2),
+        )
+        .unwrap();
+        assert_eq!(tree.depth(), 0);
+        assert_eq!(tree.predict(&x).unwrap(), vec![0; 6]);
+        assert_eq!(tree.cost_complexity_pruning_path().ccp_alphas, vec![0.]);
+
+        assert!(DecisionTreeClassifier::fit(
+            &x,
+            &y,
+            DecisionTreeClassifierParameters::default().with_ccp_alpha(-1.)
+        )
+        .is_err());
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn fit_predict_missing_values() {
+        let nan = f64::NAN;
+
+        // a missing value is informative
+        let x = DenseMatrix::from_2d_array(&[
+            &[1.],
+            &[2.],
+            &[3.],
+            &[nan],
+            &[nan],
+            &[nan],
+            &[7.],
+            &[8.],
+        ])
+        .unwrap();
+        let y: Vec<u32> = vec![0, 0, 0, 1, 1, 1, 0, 0];
+        // a single split separates the classes
+        let tree = DecisionTreeClassifier::fit(
+            &x,
+            &y,
+            DecisionTreeClassifierParameters::default().with_max_depth(1),
+        )
+        .unwrap();
+        assert_eq!(tree.predict(&x).unwrap(), y);
+        // infinite values are present, not missing
+        let x_test = DenseMatrix::from_2d_array(&[&[f64::INFINITY], &[nan]]).unwrap();
+        assert_eq!(tree.predict(&x_test).unwrap(), vec![0, 1]);
+        let root = &tree.nodes()[0];
+        assert!(root.split_on_missing);
+        assert_eq!(root.split_value, None);
+
+        // missing values join the observations with small values
+        let x = DenseMatrix::from_2d_array(&[&[1.], &[2.], &[nan], &[8.], &[9.], &[nan]]).unwrap();
+        let y: Vec<u32> = vec![0, 0, 0, 1, 1, 0];
+        // a single split separates the classes
+        let tree = DecisionTreeClassifier::fit(
+            &x,
+            &y,
+            DecisionTreeClassifierParameters::default().with_max_depth(1),
+        )
+        .unwrap();
+        assert_eq!(tree.predict(&x).unwrap(), y);
+
+        // without missing values in training, missing values follow the larger child
+        let x = DenseMatrix::from_2d_array(&[&[1.], &[2.], &[3.], &[8.], &[9.]]).unwrap();
+        let y: Vec<u32> = vec![0, 0, 0, 1, 1];
+        let tree = DecisionTreeClassifier::fit(&x, &y, Default::default()).unwrap();
+        let x_test = DenseMatrix::from_2d_array(&[&[nan], &[8.5]]).unwrap();
+        assert_eq!(tree.predict(&x_test).unwrap(), vec![0, 1]);
+    }
+
     #[cfg_attr(
         all(target_arch = "wasm32", not(target_os = "wasi")),
         wasm_bindgen_test::wasm_bindgen_test
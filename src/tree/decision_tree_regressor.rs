@@ -15,6 +15,8 @@
 //! one predictor at a time. At each step of the tree-building process, the best split is made at that particular step, rather than looking ahead and picking a split that will lead to a better
 //! tree in some future step.
 //!
+//! Missing feature values, encoded as `NaN`, follow a default direction learned at every split, see [missing values](../index.html#missing-values).
+//!
 //! Example:
 //!
 //! ```
@@ -71,9 +73,11 @@ use serde::{Deserialize, Serialize};
 
 use crate::api::{Predictor, SupervisedEstimator};
 use crate::error::Failed;
-use crate::linalg::basic::arrays::{Array1, Array2, MutArrayView1};
+use crate::linalg::basic::arrays::{Array1, Array2};
 use crate::numbers::basenum::Number;
 use crate::rand_custom::get_rng_impl;
+use crate::tree::tree_structure::{TreeNode, TreeStructure};
+use crate::tree::{cost_complexity_prune, sort_columns, CostComplexityPruningPath, Splitter};
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
@@ -89,6 +93,12 @@ pub struct DecisionTreeRegressorParameters {
     /// The minimum number of samples required to split an internal node.
     pub min_samples_split: usize,
     #[cfg_attr(feature = "serde", serde(default))]
+    /// Complexity parameter used for [minimal cost-complexity pruning](../index.html#cost-complexity-pruning). No pruning is performed by default.
+    pub ccp_alpha: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Strategy used to choose the split of every candidate feature, see [split strategies](../index.html#split-strategies).
+    pub splitter: Splitter,
+    #[cfg_attr(feature = "serde", serde(default))]
     /// Controls the randomness of the estimator
     pub seed: Option<u64>,
 }
@@ -101,6 +111,8 @@ pub struct DecisionTreeRegressor<TX: Number + PartialOrd, TY: Number, X: Array2<
     nodes: Vec<Node>,
     parameters: Option<DecisionTreeRegressorParameters>,
     depth: u16,
+    #[cfg_attr(feature = "serde", serde(default))]
+    num_features: usize,
     _phantom_tx: PhantomData<TX>,
     _phantom_ty: PhantomData<TY>,
     _phantom_x: PhantomData<X>,
@@ -128,11 +140,22 @@ impl<TX: Number + PartialOrd, TY: Number, X: Array2<TX>, Y: Array1<TY>>
 #[derive(Debug, Clone)]
 struct Node {
     output: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    n_node_samples: usize,
     split_feature: usize,
     split_value: Option<f64>,
     split_score: Option<f64>,
     true_child: Option<usize>,
     false_child: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    impurity: Option<f64>,
+    /// whether observations with a missing value of the split feature go to the true child
+    #[cfg_attr(feature = "serde", serde(default))]
+    missing_to_true_child: bool,
+    /// whether the node separates observations with a missing value of the split feature, which go to the false child,
+    /// from all other observations, `split_value` is `None` for such nodes
+    #[cfg_attr(feature = "serde", serde(default))]
+    split_on_missing: bool,
 }
 
 impl DecisionTreeRegressorParameters {
@@ -151,6 +174,16 @@ impl DecisionTreeRegressorParameters {
         self.min_samples_split = min_samples_split;
         self
     }
+    /// Complexity parameter used for minimal cost-complexity pruning.
+    pub fn with_ccp_alpha(mut self, ccp_alpha: f64) -> Self {
+        self.ccp_alpha = ccp_alpha;
+        self
+    }
+    /// Strategy used to choose the split of every candidate feature.
+    pub fn with_splitter(mut self, splitter: Splitter) -> Self {
+        self.splitter = splitter;
+        self
+    }
 }
 
 impl Default for DecisionTreeRegressorParameters {
@@ -159,6 +192,8 @@ impl Default for DecisionTreeRegressorParameters {
             max_depth: Option::None,
             min_samples_leaf: 1,
             min_samples_split: 2,
+            ccp_alpha: 0f64,
+            splitter: Splitter::default(),
             seed: Option::None,
         }
     }
@@ -178,6 +213,12 @@ pub struct DecisionTreeRegressorSearchParameters {
     /// The minimum number of samples required to split an internal node. See [Decision Tree Regressor](../../tree/decision_tree_regressor/index.html)
     pub min_samples_split: Vec<usize>,
     #[cfg_attr(feature = "serde", serde(default))]
+    /// Complexity parameter used for minimal cost-complexity pruning. See [Decision Tree Regressor](../../tree/decision_tree_regressor/index.html)
+    pub ccp_alpha: Vec<f64>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    /// Strategy used to choose the split of every candidate feature. See [Decision Tree Regressor](../../tree/decision_tree_regressor/index.html)
+    pub splitter: Vec<Splitter>,
+    #[cfg_attr(feature = "serde", serde(default))]
     /// Controls the randomness of the estimator
     pub seed: Vec<Option<u64>>,
 }
@@ -188,6 +229,8 @@ pub struct DecisionTreeRegressorSearchParametersIterator {
     current_max_depth: usize,
     current_min_samples_leaf: usize,
     current_min_samples_split: usize,
+    current_ccp_alpha: usize,
+    current_splitter: usize,
     current_seed: usize,
 }
 
@@ -201,6 +244,8 @@ impl IntoIterator for DecisionTreeRegressorSearchParameters {
             current_max_depth: 0,
             current_min_samples_leaf: 0,
             current_min_samples_split: 0,
+            current_ccp_alpha: 0,
+            current_splitter: 0,
             current_seed: 0,
         }
     }
@@ -225,6 +270,16 @@ impl Iterator for DecisionTreeRegressorSearchParametersIterator {
                     .decision_tree_regressor_search_parameters
                     .min_samples_split
                     .len()
+            && self.current_ccp_alpha
+                == self
+                    .decision_tree_regressor_search_parameters
+                    .ccp_alpha
+                    .len()
+            && self.current_splitter
+                == self
+                    .decision_tree_regressor_search_parameters
+                    .splitter
+                    .len()
             && self.current_seed == self.decision_tree_regressor_search_parameters.seed.len()
         {
             return None;
@@ -239,6 +294,10 @@ impl Iterator for DecisionTreeRegressorSearchParametersIterator {
             min_samples_split: self
                 .decision_tree_regressor_search_parameters
                 .min_samples_split[self.current_min_samples_split],
+            ccp_alpha: self.decision_tree_regressor_search_parameters.ccp_alpha
+                [self.current_ccp_alpha],
+            splitter: self.decision_tree_regressor_search_parameters.splitter
+                [self.current_splitter],
             seed: self.decision_tree_regressor_search_parameters.seed[self.current_seed],
         };
 
@@ -266,16 +325,41 @@ impl Iterator for DecisionTreeRegressorSearchParametersIterator {
             self.current_max_depth = 0;
             self.current_min_samples_leaf = 0;
             self.current_min_samples_split += 1;
+        } else if self.current_ccp_alpha + 1
+            < self
+                .decision_tree_regressor_search_parameters
+                .ccp_alpha
+                .len()
+        {
+            self.current_max_depth = 0;
+            self.current_min_samples_leaf = 0;
+            self.current_min_samples_split = 0;
+            self.current_ccp_alpha += 1;
+        } else if self.current_splitter + 1
+            < self
+                .decision_tree_regressor_search_parameters
+                .splitter
+                .len()
+        {
+            self.current_max_depth = 0;
+            self.current_min_samples_leaf = 0;
+            self.current_min_samples_split = 0;
+            self.current_ccp_alpha = 0;
+            self.current_splitter += 1;
         } else if self.current_seed + 1 < self.decision_tree_regressor_search_parameters.seed.len()
         {
             self.current_max_depth = 0;
             self.current_min_samples_leaf = 0;
             self.current_min_samples_split = 0;
+            self.current_ccp_alpha = 0;
+            self.current_splitter = 0;
             self.current_seed += 1;
         } else {
             self.current_max_depth += 1;
             self.current_min_samples_leaf += 1;
             self.current_min_samples_split += 1;
+            self.current_ccp_alpha += 1;
+            self.current_splitter += 1;
             self.current_seed += 1;
         }
 
@@ -291,20 +375,48 @@ impl Default for DecisionTreeRegressorSearchParameters {
             max_depth: vec![default_params.max_depth],
             min_samples_leaf: vec![default_params.min_samples_leaf],
             min_samples_split: vec![default_params.min_samples_split],
+            ccp_alpha: vec![default_params.ccp_alpha],
+            splitter: vec![default_params.splitter],
             seed: vec![default_params.seed],
         }
     }
 }
 
 impl Node {
-    fn new(output: f64) -> Self {
+    fn new(output: f64, n_node_samples: usize) -> Self {
         Node {
             output,
+            n_node_samples,
             split_feature: 0,
             split_value: Option::None,
             split_score: Option::None,
             true_child: Option::None,
             false_child: Option::None,
+            impurity: Option::None,
+            missing_to_true_child: false,
+            split_on_missing: false,
+        }
+    }
+
+    /// Turns the node into a leaf.
+    fn collapse(&mut self) {
+        self.split_feature = 0;
+        self.split_value = Option::None;
+        self.split_score = Option::None;
+        self.true_child = Option::None;
+        self.false_child = Option::None;
+        self.missing_to_true_child = false;
+        self.split_on_missing = false;
+    }
+
+    /// Whether an observation with `value` of the split feature goes to the true child.
+    fn goes_to_true_child(&self, value: f64) -> bool {
+        if value.is_nan() {
+            self.missing_to_true_child
+        } else if self.split_on_missing {
+            true
+        } else {
+            value <= self.split_value.unwrap_or(f64::NAN)
         }
     }
 }
@@ -313,6 +425,8 @@ impl PartialEq for Node {
     fn eq(&self, other: &Self) -> bool {
         (self.output - other.output).abs() < std::f64::EPSILON
             && self.split_feature == other.split_feature
+            && self.missing_to_true_child == other.missing_to_true_child
+            && self.split_on_missing == other.split_on_missing
             && match (self.split_value, other.split_value) {
                 (Some(a), Some(b)) => (a - b).abs() < std::f64::EPSILON,
                 (None, None) => true,
@@ -347,6 +461,7 @@ struct NodeVisitor<'a, TX: Number + PartialOrd, TY: Number, X: Array2<TX>, Y: Ar
     node: usize,
     samples: Vec<usize>,
     order: &'a [Vec<usize>],
+    missing: &'a [Vec<usize>],
     true_child_output: f64,
     false_child_output: f64,
     level: u16,
@@ -361,6 +476,7 @@ impl<'a, TX: Number + PartialOrd, TY: Number, X: Array2<TX>, Y: Array1<TY>>
         node_id: usize,
         samples: Vec<usize>,
         order: &'a [Vec<usize>],
+        missing: &'a [Vec<usize>],
         x: &'a X,
         y: &'a Y,
         level: u16,
@@ -371,6 +487,7 @@ impl<'a, TX: Number + PartialOrd, TY: Number, X: Array2<TX>, Y: Array1<TY>>
             node: node_id,
             samples,
             order,
+            missing,
             true_child_output: 0f64,
             false_child_output: 0f64,
             level,
@@ -389,6 +506,7 @@ impl<TX: Number + PartialOrd, TY: Number, X: Array2<TX>, Y: Array1<TY>>
             nodes: vec![],
             parameters: Option::None,
             depth: 0u16,
+            num_features: 0usize,
             _phantom_tx: PhantomData,
             _phantom_ty: PhantomData,
             _phantom_x: PhantomData,
@@ -436,10 +554,16 @@ impl<TX: Number + PartialOrd, TY: Number, X: Array2<TX>, Y: Array1<TY>>
         mtry: usize,
         parameters: DecisionTreeRegressorParameters,
     ) -> Result<DecisionTreeRegressor<TX, TY, X, Y>, Failed> {
+        if parameters.ccp_alpha < 0f64 {
+            return Err(Failed::fit(&format!(
+                "ccp_alpha should be non-negative, got {}",
+                parameters.ccp_alpha
+            )));
+        }
+
         let y_m = y.clone();
 
         let y_ncols = y_m.shape();
-        let (_, num_attributes) = x.shape();
 
         let mut nodes: Vec<Node> = Vec::new();
         let mut rng = get_rng_impl(parameters.seed);
@@ -451,26 +575,23 @@ impl<TX: Number + PartialOrd, TY: Number, X: Array2<TX>, Y: Array1<TY>>
             sum += *sample_i as f64 * y_m.get(i).to_f64().unwrap();
         }
 
-        let root = Node::new(sum / (n as f64));
+        let root = Node::new(sum / (n as f64), n);
         nodes.push(root);
-        let mut order: Vec<Vec<usize>> = Vec::new();
-
-        for i in 0..num_attributes {
-            let mut col_i: Vec<TX> = x.get_col(i).iterator(0).copied().collect();
-            order.push(col_i.argsort_mut());
-        }
+        let (order, missing) = sort_columns(x);
 
         let mut tree = DecisionTreeRegressor {
             nodes,
             parameters: Some(parameters),
             depth: 0u16,
+            num_features: x.shape().1,
             _phantom_tx: PhantomData,
             _phantom_ty: PhantomData,
             _phantom_x: PhantomData,
             _phantom_y: PhantomData,
         };
 
-        let mut visitor = NodeVisitor::<TX, TY, X, Y>::new(0, samples, &order, x, &y_m, 1);
+        let mut visitor =
+            NodeVisitor::<TX, TY, X, Y>::new(0, samples, &order, &missing, x, &y_m, 1);
 
         let mut visitor_queue: LinkedList<NodeVisitor<'_, TX, TY, X, Y>> = LinkedList::new();
 
@@ -485,9 +606,100 @@ impl<TX: Number + PartialOrd, TY: Number, X: Array2<TX>, Y: Array1<TY>>
             };
         }
 
+        let ccp_alpha = tree.parameters().ccp_alpha;
+        if ccp_alpha > 0f64 {
+            tree.prune(ccp_alpha);
+        }
+
         Ok(tree)
     }
 
+    /// Compute the [minimal cost-complexity pruning](../index.html#cost-complexity-pruning) path of the fitted tree.
+    /// Fitting the tree again with `ccp_alpha` set to one of the returned alphas yields the corresponding subtree.
+    pub fn cost_complexity_pruning_path(&self) -> CostComplexityPruningPath {
+        let (children, risk) = self.pruning_inputs();
+        cost_complexity_prune(&children, &risk, f64::INFINITY).0
+    }
+
+    /// Structure of the fitted tree: split features, thresholds, children and predicted values of all nodes.
+    pub fn structure(&self) -> TreeStructure {
+        let nodes = self
+            .nodes()
+            .iter()
+            .map(|node| {
+                let is_split = node.true_child.is_some();
+                TreeNode {
+                    feature: node.true_child.map(|_| node.split_feature),
+                    threshold: node.true_child.and(node.split_value),
+                    missing_to_true_child: is_split && node.missing_to_true_child,
+                    split_on_missing: is_split && node.split_on_missing,
+                    true_child: node.true_child,
+                    false_child: node.false_child,
+                    n_node_samples: node.n_node_samples,
+                    impurity: node.impurity,
+                    value: node.output,
+                }
+            })
+            .collect();
+
+        TreeStructure {
+            nodes,
+            n_features: self.num_features,
+            classes: Vec::new(),
+        }
+    }
+
+    /// Render the fitted tree as [Graphviz](https://graphviz.org/) DOT text, see [TreeStructure::export_graphviz].
+    pub fn export_graphviz(&self, feature_names: Option<&[&str]>) -> Result<String, Failed> {
+        self.structure().export_graphviz(feature_names)
+    }
+
+    /// Render the fitted tree as nested if/else rules, see [TreeStructure::export_text].
+    pub fn export_text(&self, feature_names: Option<&[&str]>) -> Result<String, Failed> {
+        self.structure().export_text(feature_names)
+    }
+
+    /// Children and mean squared error weighted by the fraction of training samples of every node.
+    fn pruning_inputs(&self) -> (Vec<Option<(usize, usize)>>, Vec<f64>) {
+        let n = self.nodes()[0].n_node_samples as f64;
+        self.nodes()
+            .iter()
+            .map(|node| {
+                (
+                    node.true_child.zip(node.false_child),
+                    node.n_node_samples as f64 * node.impurity.unwrap_or(0f64) / n,
+                )
+            })
+            .unzip()
+    }
+
+    fn prune(&mut self, ccp_alpha: f64) {
+        let (children, risk) = self.pruning_inputs();
+        let (_, is_leaf) = cost_complexity_prune(&children, &risk, ccp_alpha);
+
+        let mut nodes = vec![self.nodes[0].clone()];
+        let mut queue: LinkedList<(usize, usize, u16)> = LinkedList::new();
+        queue.push_back((0, 0, 1));
+        self.depth = 0;
+
+        while let Some((node_id, new_node_id, level)) = queue.pop_front() {
+            if is_leaf[node_id] {
+                nodes[new_node_id].collapse();
+                continue;
+            }
+            self.depth = u16::max(self.depth, level + 1);
+            let node = &self.nodes[node_id];
+            for child_id in [node.true_child.unwrap(), node.false_child.unwrap()] {
+                queue.push_back((child_id, nodes.len(), level + 1));
+                nodes.push(self.nodes[child_id].clone());
+            }
+            nodes[new_node_id].true_child = Some(nodes.len() - 2);
+            nodes[new_node_id].false_child = Some(nodes.len() - 1);
+        }
+
+        self.nodes = nodes;
+    }
+
     /// Predict regression value for `x`.
     /// * `x` - _KxM_ data where _K_ is number of observations and _M_ is number of features.
     pub fn predict(&self, x: &X) -> Result<Y, Failed> {
@@ -503,7 +715,12 @@ impl<TX: Number + PartialOrd, TY: Number, X: Array2<TX>, Y: Array1<TY>>
     }
 
     pub(crate) fn predict_for_row(&self, x: &X, row: usize) -> TY {
-        let mut result = 0f64;
+        TY::from_f64(self.nodes()[self.leaf_for_row(x, row)].output).unwrap()
+    }
+
+    /// Index of the leaf the row `row` of `x` falls into.
+    pub(crate) fn leaf_for_row(&self, x: &X, row: usize) -> usize {
+        let mut result = 0;
         let mut queue: LinkedList<usize> = LinkedList::new();
 
         queue.push_back(0);
@@ -513,9 +730,9 @@ impl<TX: Number + PartialOrd, TY: Number, X: Array2<TX>, Y: Array1<TY>>
                 Some(node_id) => {
                     let node = &self.nodes()[node_id];
                     if node.true_child.is_none() && node.false_child.is_none() {
-                        result = node.output;
-                    } else if x.get((row, node.split_feature)).to_f64().unwrap()
-                        <= node.split_value.unwrap_or(std::f64::NAN)
+                        result = node_id;
+                    } else if node
+                        .goes_to_true_child(x.get((row, node.split_feature)).to_f64().unwrap())
                     {
                         queue.push_back(node.true_child.unwrap());
                     } else {
@@ -526,7 +743,17 @@ impl<TX: Number + PartialOrd, TY: Number, X: Array2<TX>, Y: Array1<TY>>
             };
         }
 
-        TY::from_f64(result).unwrap()
+        result
+    }
+
+    /// Value predicted at the node `node_id`.
+    pub(crate) fn node_output(&self, node_id: usize) -> f64 {
+        self.nodes()[node_id].output
+    }
+
+    /// Replace the value predicted at the node `node_id`, e.g. by a line search of a boosting loss.
+    pub(crate) fn set_node_output(&mut self, node_id: usize, output: f64) {
+        self.nodes[node_id].output = output;
     }
 
     fn find_best_cutoff(
@@ -539,6 +766,16 @@ impl<TX: Number + PartialOrd, TY: Number, X: Array2<TX>, Y: Array1<TY>>
 
         let n: usize = visitor.samples.iter().sum();
 
+        let mean = self.nodes()[visitor.node].output;
+        let squared_error: f64 = visitor
+            .samples
+            .iter()
+            .enumerate()
+            .filter(|(_, &s)| s > 0)
+            .map(|(i, &s)| s as f64 * (visitor.y.get(i).to_f64().unwrap() - mean).powi(2))
+            .sum();
+        self.nodes[visitor.node].impurity = Some(squared_error / n as f64);
+
         if n < self.parameters().min_samples_split {
             return false;
         }
@@ -554,8 +791,14 @@ impl<TX: Number + PartialOrd, TY: Number, X: Array2<TX>, Y: Array1<TY>>
         let parent_gain =
             n as f64 * self.nodes()[visitor.node].output * self.nodes()[visitor.node].output;
 
+        let splitter = self.parameters().splitter;
         for variable in variables.iter().take(mtry) {
-            self.find_best_split(visitor, n, sum, parent_gain, *variable);
+            match splitter {
+                Splitter::Best => self.find_best_split(visitor, n, sum, parent_gain, *variable),
+                Splitter::Random => {
+                    self.find_random_split(visitor, n, sum, parent_gain, *variable, rng)
+                }
+            }
         }
 
         self.nodes()[visitor.node].split_score.is_some()
@@ -569,6 +812,13 @@ impl<TX: Number + PartialOrd, TY: Number, X: Array2<TX>, Y: Array1<TY>>
         parent_gain: f64,
         j: usize,
     ) {
+        let mut missing_count = 0;
+        let mut missing_sum = 0f64;
+        for i in visitor.missing[j].iter() {
+            missing_count += visitor.samples[*i];
+            missing_sum += visitor.samples[*i] as f64 * visitor.y.get(*i).to_f64().unwrap();
+        }
+
         let mut true_sum = 0f64;
         let mut true_count = 0;
         let mut prevx = Option::None;
@@ -577,47 +827,165 @@ impl<TX: Number + PartialOrd, TY: Number, X: Array2<TX>, Y: Array1<TY>>
             if visitor.samples[*i] > 0 {
                 let x_ij = *visitor.x.get((*i, j));
 
-                if prevx.is_none() || x_ij == prevx.unwrap() {
-                    prevx = Some(x_ij);
-                    true_count += visitor.samples[*i];
-                    true_sum += visitor.samples[*i] as f64 * visitor.y.get(*i).to_f64().unwrap();
-                    continue;
+                if let Some(prev) = prevx.filter(|prev| x_ij != *prev) {
+                    let split_value = (x_ij + prev).to_f64().unwrap() / 2f64;
+                    self.try_split(
+                        visitor,
+                        n,
+                        sum,
+                        parent_gain,
+                        true_count,
+                        true_sum,
+                        j,
+                        Some(split_value),
+                        false,
+                    );
+                    if missing_count > 0 {
+                        self.try_split(
+                            visitor,
+                            n,
+                            sum,
+                            parent_gain,
+                            true_count + missing_count,
+                            true_sum + missing_sum,
+                            j,
+                            Some(split_value),
+                            true,
+                        );
+                    }
                 }
 
-                let false_count = n - true_count;
+                prevx = Some(x_ij);
+                true_count += visitor.samples[*i];
+                true_sum += visitor.samples[*i] as f64 * visitor.y.get(*i).to_f64().unwrap();
+            }
+        }
+
+        // observations with a missing value against all other observations
+        if missing_count > 0 {
+            self.try_split(
+                visitor,
+                n,
+                sum,
+                parent_gain,
+                true_count,
+                true_sum,
+                j,
+                None,
+                false,
+            );
+        }
+    }
 
-                if true_count < self.parameters().min_samples_leaf
-                    || false_count < self.parameters().min_samples_leaf
-                {
-                    prevx = Some(x_ij);
-                    true_count += visitor.samples[*i];
-                    true_sum += visitor.samples[*i] as f64 * visitor.y.get(*i).to_f64().unwrap();
-                    continue;
+    /// Splits feature `j` at a threshold drawn uniformly at random between its smallest and largest finite value at the node,
+    /// infinite values go to the side given by the comparison with the threshold.
+    fn find_random_split(
+        &mut self,
+        visitor: &mut NodeVisitor<'_, TX, TY, X, Y>,
+        n: usize,
+        sum: f64,
+        parent_gain: f64,
+        j: usize,
+        rng: &mut impl Rng,
+    ) {
+        let mut values = visitor.order[j]
+            .iter()
+            .filter(|&&i| visitor.samples[i] > 0)
+            .map(|&i| visitor.x.get((i, j)).to_f64().unwrap())
+            .filter(|v| v.is_finite());
+        let (min, max) = match (values.next(), values.next_back()) {
+            (Some(min), Some(max)) if min < max => (min, max),
+            _ => return,
+        };
+        // a convex combination of the bounds does not overflow when max - min does
+        let u: f64 = rng.gen();
+        let split_value = (min * (1f64 - u) + max * u).clamp(min, max);
+
+        let mut true_sum = 0f64;
+        let mut true_count = 0;
+        for i in visitor.order[j].iter() {
+            if visitor.samples[*i] > 0 {
+                if visitor.x.get((*i, j)).to_f64().unwrap() > split_value {
+                    break;
                 }
+                true_count += visitor.samples[*i];
+                true_sum += visitor.samples[*i] as f64 * visitor.y.get(*i).to_f64().unwrap();
+            }
+        }
+        self.try_split(
+            visitor,
+            n,
+            sum,
+            parent_gain,
+            true_count,
+            true_sum,
+            j,
+            Some(split_value),
+            false,
+        );
 
-                let true_mean = true_sum / true_count as f64;
-                let false_mean = (sum - true_sum) / false_count as f64;
+        let mut missing_count = 0;
+        let mut missing_sum = 0f64;
+        for i in visitor.missing[j].iter() {
+            missing_count += visitor.samples[*i];
+            missing_sum += visitor.samples[*i] as f64 * visitor.y.get(*i).to_f64().unwrap();
+        }
+        if missing_count > 0 {
+            self.try_split(
+                visitor,
+                n,
+                sum,
+                parent_gain,
+                true_count + missing_count,
+                true_sum + missing_sum,
+                j,
+                Some(split_value),
+                true,
+            );
+        }
+    }
 
-                let gain = (true_count as f64 * true_mean * true_mean
-                    + false_count as f64 * false_mean * false_mean)
-                    - parent_gain;
+    /// Scores the split of the node into `true_count` observations with responses summing to `true_sum`
+    /// and all other observations, and keeps it when it is the best split so far.
+    #[allow(clippy::too_many_arguments)]
+    fn try_split(
+        &mut self,
+        visitor: &mut NodeVisitor<'_, TX, TY, X, Y>,
+        n: usize,
+        sum: f64,
+        parent_gain: f64,
+        true_count: usize,
+        true_sum: f64,
+        j: usize,
+        split_value: Option<f64>,
+        missing_to_true_child: bool,
+    ) {
+        let false_count = n - true_count;
 
-                if self.nodes()[visitor.node].split_score.is_none()
-                    || gain > self.nodes()[visitor.node].split_score.unwrap()
-                {
-                    self.nodes[visitor.node].split_feature = j;
-                    self.nodes[visitor.node].split_value =
-                        Option::Some((x_ij + prevx.unwrap()).to_f64().unwrap() / 2f64);
-                    self.nodes[visitor.node].split_score = Option::Some(gain);
+        if true_count < self.parameters().min_samples_leaf
+            || false_count < self.parameters().min_samples_leaf
+        {
+            return;
+        }
 
-                    visitor.true_child_output = true_mean;
-                    visitor.false_child_output = false_mean;
-                }
+        let true_mean = true_sum / true_count as f64;
+        let false_mean = (sum - true_sum) / false_count as f64;
 
-                prevx = Some(x_ij);
-                true_sum += visitor.samples[*i] as f64 * visitor.y.get(*i).to_f64().unwrap();
-                true_count += visitor.samples[*i];
-            }
+        let gain = (true_count as f64 * true_mean * true_mean
+            + false_count as f64 * false_mean * false_mean)
+            - parent_gain;
+
+        if self.nodes()[visitor.node].split_score.is_none()
+            || gain > self.nodes()[visitor.node].split_score.unwrap()
+        {
+            self.nodes[visitor.node].split_feature = j;
+            self.nodes[visitor.node].split_value = split_value;
+            self.nodes[visitor.node].split_score = Option::Some(gain);
+            self.nodes[visitor.node].missing_to_true_child = missing_to_true_child;
+            self.nodes[visitor.node].split_on_missing = split_value.is_none();
+
+            visitor.true_child_output = true_mean;
+            visitor.false_child_output = false_mean;
         }
     }
 
@@ -632,18 +1000,12 @@ impl<TX: Number + PartialOrd, TY: Number, X: Array2<TX>, Y: Array1<TY>>
         let mut tc = 0;
         let mut fc = 0;
         let mut true_samples: Vec<usize> = vec![0; n];
+        let split_feature = self.nodes()[visitor.node].split_feature;
 
         for (i, true_sample) in true_samples.iter_mut().enumerate().take(n) {
             if visitor.samples[i] > 0 {
-                if visitor
-                    .x
-                    .get((i, self.nodes()[visitor.node].split_feature))
-                    .to_f64()
-                    .unwrap()
-                    <= self.nodes()[visitor.node]
-                        .split_value
-                        .unwrap_or(std::f64::NAN)
-                {
+                let value = visitor.x.get((i, split_feature)).to_f64().unwrap();
+                if self.nodes()[visitor.node].goes_to_true_child(value) {
                     *true_sample = visitor.samples[i];
                     tc += *true_sample;
                     visitor.samples[i] = 0;
@@ -657,15 +1019,25 @@ impl<TX: Number + PartialOrd, TY: Number, X: Array2<TX>, Y: Array1<TY>>
             self.nodes[visitor.node].split_feature = 0;
             self.nodes[visitor.node].split_value = Option::None;
             self.nodes[visitor.node].split_score = Option::None;
+            self.nodes[visitor.node].missing_to_true_child = false;
+            self.nodes[visitor.node].split_on_missing = false;
 
             return false;
         }
 
+        // without missing values in training, missing values follow the larger child
+        if !visitor.missing[split_feature]
+            .iter()
+            .any(|&i| true_samples[i] > 0 || visitor.samples[i] > 0)
+        {
+            self.nodes[visitor.node].missing_to_true_child = tc >= fc;
+        }
+
         let true_child_idx = self.nodes().len();
 
-        self.nodes.push(Node::new(visitor.true_child_output));
+        self.nodes.push(Node::new(visitor.true_child_output, tc));
         let false_child_idx = self.nodes().len();
-        self.nodes.push(Node::new(visitor.false_child_output));
+        self.nodes.push(Node::new(visitor.false_child_output, fc));
 
         self.nodes[visitor.node].true_child = Some(true_child_idx);
         self.nodes[visitor.node].false_child = Some(false_child_idx);
@@ -676,6 +1048,7 @@ impl<TX: Number + PartialOrd, TY: Number, X: Array2<TX>, Y: Array1<TY>>
             true_child_idx,
             true_samples,
             visitor.order,
+            visitor.missing,
             visitor.x,
             visitor.y,
             visitor.level + 1,
@@ -689,6 +1062,7 @@ impl<TX: Number + PartialOrd, TY: Number, X: Array2<TX>, Y: Array1<TY>>
             false_child_idx,
             visitor.samples,
             visitor.order,
+            visitor.missing,
             visitor.x,
             visitor.y,
             visitor.level + 1,
@@ -779,6 +1153,8 @@ mod tests {
                 max_depth: Option::None,
                 min_samples_leaf: 2,
                 min_samples_split: 6,
+                ccp_alpha: 0f64,
+                splitter: Splitter::Best,
                 seed: Option::None,
             },
         )
@@ -800,6 +1176,8 @@ mod tests {
                 max_depth: Option::None,
                 min_samples_leaf: 1,
                 min_samples_split: 3,
+                ccp_alpha: 0f64,
+                splitter: Splitter::Best,
                 seed: Option::None,
             },
         )
@@ -811,6 +1189,146 @@ mod tests {
         }
     }
 
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn cost_complexity_pruning() {
+        let x = DenseMatrix::from_2d_array(&[&[1.], &[2.], &[3.], &[4.]]).unwrap();
+        let y: Vec<f64> = vec![0., 0., 10., 12.];
+
+        let tree = DecisionTreeRegressor::fit(&x, &y, Default::default()).unwrap();
+        let path = tree.cost_complexity_pruning_path();
+        let expected_alphas = [0., 0., 0.5, 30.25];
+        let expected_impurities = [0., 0., 0.5, 30.75];
+        assert_eq!(path.ccp_alphas.len(), expected_alphas.len());
+        for i in 0..expected_alphas.len() {
+            assert!((path.ccp_alphas[i] - expected_alphas[i]).abs() < 1e-8);
+            assert!((path.impurities[i] - expected_impurities[i]).abs() < 1e-8);
+        }
+
+        let tree = DecisionTreeRegressor::fit(
+            &x,
+            &y,
+            DecisionTreeRegressorParameters::default().with_ccp_alpha(0.6),
+        )
+        .unwrap();
+        assert_eq!(tree.depth(), 2);
+        assert_eq!(tree.predict(&x).unwrap(), vec![0., 0., 11., 11.]);
+
+        let tree = DecisionTreeRegressor::fit(
+            &x,
+            &y,
+            DecisionTreeRegressorParameters::default().with_ccp_alpha(31.),
+        )
+        .unwrap();
+        assert_eq!(tree.depth(), 0);
+        assert_eq!(tree.predict(&x).unwrap(), vec![5.5; 4]);
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn fit_predict_missing_values() {
+        let nan = f64::NAN;
+
+        // missing values join the observations with large values
+        let x = DenseMatrix::from_2d_array(&[
+            &[1., 0.],
+            &[2., 1.],
+            &[nan, 0.],
+            &[8., 1.],
+            &[9., 0.],
+            &[nan, 1.],
+        ])
+        .unwrap();
+        let y: Vec<f64> = vec![1., 1., 5., 5., 5., 5.];
+        // a single split
+        let tree = DecisionTreeRegressor::fit(
+            &x,
+            &y,
+            DecisionTreeRegressorParameters::default().with_max_depth(1),
+        )
+        .unwrap();
+        assert_eq!(tree.predict(&x).unwrap(), y);
+
+        let x_test = DenseMatrix::from_2d_array(&[&[nan, nan], &[1.5, nan]]).unwrap();
+        assert_eq!(tree.predict(&x_test).unwrap(), vec![5., 1.]);
+
+        // a missing value is informative, infinite values are present
+        let x = DenseMatrix::from_2d_array(&[&[1.], &[nan], &[3.], &[nan], &[5.], &[6.]]).unwrap();
+        let y: Vec<f64> = vec![1., 9., 1., 9., 1., 1.];
+        let tree = DecisionTreeRegressor::fit(
+            &x,
+            &y,
+            DecisionTreeRegressorParameters::default().with_max_depth(1),
+        )
+        .unwrap();
+        assert!(tree.nodes()[0].split_on_missing);
+        let x_test = DenseMatrix::from_2d_array(&[&[f64::INFINITY], &[nan]]).unwrap();
+        assert_eq!(tree.predict(&x_test).unwrap(), vec![1., 9.]);
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn fit_random_splitter() {
+        let x = DenseMatrix::from_2d_array(&[&[1.], &[2.], &[3.], &[4.], &[5.], &[6.]]).unwrap();
+        let y: Vec<f64> = vec![1., 1., 4., 4., 9., 9.];
+        let parameters = DecisionTreeRegressorParameters::default().with_splitter(Splitter::Random);
+
+        // a fully grown tree fits the training set whatever its thresholds
+        let tree = DecisionTreeRegressor::fit(
+            &x,
+            &y,
+            DecisionTreeRegressorParameters {
+                seed: Some(1),
+                ..parameters.clone()
+            },
+        )
+        .unwrap();
+        assert_eq!(tree.predict(&x).unwrap(), y);
+
+        let other = DecisionTreeRegressor::fit(
+            &x,
+            &y,
+            DecisionTreeRegressorParameters {
+                seed: Some(2),
+                ..parameters.clone()
+            },
+        )
+        .unwrap();
+        assert!(tree != other);
+
+        // the range of extreme values overflows, but not the thresholds drawn in it
+        let x_extreme =
+            DenseMatrix::from_2d_array(&[&[-1e308], &[-1e307], &[1e307], &[1e308]]).unwrap();
+        let y_extreme: Vec<f64> = vec![1., 1., 9., 9.];
+        let tree = DecisionTreeRegressor::fit(&x_extreme, &y_extreme, parameters.clone()).unwrap();
+        assert_eq!(tree.predict(&x_extreme).unwrap(), y_extreme);
+
+        // thresholds are drawn between the finite values, infinite values follow the comparison
+        let x_infinite =
+            DenseMatrix::from_2d_array(&[&[f64::NEG_INFINITY], &[0.], &[1.], &[f64::INFINITY]])
+                .unwrap();
+        let y_infinite: Vec<f64> = vec![1., 1., 9., 9.];
+        let tree =
+            DecisionTreeRegressor::fit(&x_infinite, &y_infinite, parameters.clone()).unwrap();
+        assert_eq!(tree.predict(&x_infinite).unwrap(), y_infinite);
+        let threshold = tree.nodes()[0].split_value.unwrap();
+        assert!((0. ..1.).contains(&threshold));
+
+        // every threshold lies within the range of the observations at its node
+        let stump = DecisionTreeRegressor::fit(&x, &y, parameters.with_max_depth(1)).unwrap();
+        let threshold = stump.nodes()[0].split_value.unwrap();
+        assert!((1. ..6.).contains(&threshold));
+    }
+
     #[cfg_attr(
         all(target_arch = "wasm32", not(target_os = "wasi")),
         wasm_bindgen_test::wasm_bindgen_test
@@ -11,10 +11,40 @@
 //!
 //! `smartcore` uses [CART](https://en.wikipedia.org/wiki/Predictive_analytics#Classification_and_regression_trees_.28CART.29) learning technique to build both classification and regression trees.
 //!
+//! ## Missing values
+//!
+//! Trees handle missing feature values, encoded as `NaN`, without imputation. Every split learns a default direction for missing values:
+//! when searching for the best split, observations with a missing value of the split feature are sent to either child and the better
+//! alternative is kept. A split can also separate observations with a missing value from all other observations.
+//! When no observation at a node has a missing value of its split feature, missing values at prediction time follow the child with more training samples.
+//!
+//! ## Cost-complexity pruning
+//!
+//! A fully grown tree tends to overfit the training data. Minimal cost-complexity pruning finds the subtree \\(T\\) of the grown tree that minimizes
+//!
+//! \\[R_\alpha(T) = R(T) + \alpha|T|\\]
+//!
+//! where \\(R(T)\\) is the total impurity of the leaves of \\(T\\), weighted by the fraction of training samples that reach every leaf,
+//! and \\(|T|\\) is the number of leaves. The subtree is found by repeatedly collapsing the internal node \\(t\\) with the smallest effective alpha
+//!
+//! \\[\alpha_{eff}(t) = \frac{R(t) - R(T_t)}{|T_t| - 1}\\]
+//!
+//! where \\(T_t\\) is the branch rooted at \\(t\\), as long as \\(\alpha_{eff}(t)\\) does not exceed the complexity parameter `ccp_alpha`.
+//! Pruning is disabled with the default `ccp_alpha` of zero. Use `cost_complexity_pruning_path` of a fitted tree to find the alphas at which
+//! the subtrees of the tree change, and choose `ccp_alpha` among them, e.g. with cross-validation.
+//!
+//! ## Split strategies
+//!
+//! By default, every candidate feature of a node is split at the threshold that improves the split criterion the most ([`Splitter::Best`](enum.Splitter.html#variant.Best)).
+//! With [`Splitter::Random`](enum.Splitter.html#variant.Random), a single threshold is drawn uniformly at random between the smallest and the largest finite value of every candidate
+//! feature at the node, and the best of these random splits is kept. Random splits are much faster to find and make the trees more diverse,
+//! which is the idea behind [extremely randomized trees](../ensemble/index.html).
+//!
 //! ## References:
 //!
 //! * ["Classification and regression trees", Breiman, L, Friedman, J H, Olshen, R A, and Stone, C J, 1984](https://www.sciencebase.gov/catalog/item/545d07dfe4b0ba8303f728c1)
 //! * ["An Introduction to Statistical Learning", James G., Witten D., Hastie T., Tibshirani R., Chapter 8](http://faculty.marshall.usc.edu/gareth-james/ISL/)
+//! * ["Extremely randomized trees", Geurts P., Ernst D., Wehenkel L., Machine Learning 63, 2006](https://doi.org/10.1007/s10994-006-6226-1)
 //!
 //! <script src="https://polyfill.io/v3/polyfill.min.js?features=es6"></script>
 //! <script id="MathJax-script" async src="https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-mml-chtml.js"></script>
@@ -23,3 +53,141 @@
 pub mod decision_tree_classifier;
 /// Regression tree for for dependent variables that take continuous or ordered discrete values.
 pub mod decision_tree_regressor;
+/// Structure of a fitted tree, exported as Graphviz DOT text or if/else rules.
+pub mod tree_structure;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::linalg::basic::arrays::{Array2, MutArrayView1};
+use crate::numbers::basenum::Number;
+
+/// Strategy used to choose the split of every candidate feature at a node, see [split strategies](index.html#split-strategies).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Splitter {
+    /// Split at the threshold that improves the split criterion the most.
+    #[default]
+    Best,
+    /// Split at a threshold drawn uniformly at random between the smallest and the largest value of the feature.
+    Random,
+}
+
+/// Sequence of subtrees found by [minimal cost-complexity pruning](index.html#cost-complexity-pruning) of a fitted tree.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CostComplexityPruningPath {
+    /// Effective alphas of the subtrees, in increasing order. The first alpha is zero and corresponds to the tree itself.
+    pub ccp_alphas: Vec<f64>,
+    /// Total weighted impurity of the leaves of the subtree pruned with the corresponding alpha.
+    pub impurities: Vec<f64>,
+}
+
+/// Rows of every column of `x` sorted by value, without rows with missing values, and rows with missing values of every column.
+pub(crate) fn sort_columns<TX: Number + PartialOrd, X: Array2<TX>>(
+    x: &X,
+) -> (Vec<Vec<usize>>, Vec<Vec<usize>>) {
+    let (n, num_attributes) = x.shape();
+    let mut order = Vec::with_capacity(num_attributes);
+    let mut missing = Vec::with_capacity(num_attributes);
+
+    for j in 0..num_attributes {
+        let (present, missing_j): (Vec<usize>, Vec<usize>) =
+            (0..n).partition(|&i| !x.get((i, j)).to_f64().unwrap().is_nan());
+        let mut col_j: Vec<TX> = present.iter().map(|&i| *x.get((i, j))).collect();
+        let order_j = if col_j.is_empty() {
+            Vec::new()
+        } else {
+            col_j
+                .argsort_mut()
+                .into_iter()
+                .map(|i| present[i])
+                .collect()
+        };
+        order.push(order_j);
+        missing.push(missing_j);
+    }
+
+    (order, missing)
+}
+
+/// Minimal cost-complexity pruning of a tree, where `children` holds the (true, false) children of every node,
+/// every child has a larger index than its parent and `risk` is the weighted impurity of every node.
+/// Nodes are collapsed until the smallest effective alpha exceeds `ccp_alpha`.
+/// Returns the pruning path and whether every node is a leaf of the pruned tree.
+pub(crate) fn cost_complexity_prune(
+    children: &[Option<(usize, usize)>],
+    risk: &[f64],
+    ccp_alpha: f64,
+) -> (CostComplexityPruningPath, Vec<bool>) {
+    let n_nodes = children.len();
+    let mut parent = vec![None; n_nodes];
+    for (i, c) in children.iter().enumerate() {
+        if let Some((t, f)) = c {
+            parent[*t] = Some(i);
+            parent[*f] = Some(i);
+        }
+    }
+
+    // impurity and number of leaves of the branch rooted at every node
+    let mut is_leaf: Vec<bool> = children.iter().map(|c| c.is_none()).collect();
+    let mut branch_risk = risk.to_vec();
+    let mut n_leaves = vec![1usize; n_nodes];
+    for i in (0..n_nodes).rev() {
+        if let Some((t, f)) = children[i] {
+            branch_risk[i] = branch_risk[t] + branch_risk[f];
+            n_leaves[i] = n_leaves[t] + n_leaves[f];
+        }
+    }
+
+    // nodes of the pruned tree
+    let mut in_tree = vec![true; n_nodes];
+
+    let mut path = CostComplexityPruningPath {
+        ccp_alphas: vec![0f64],
+        impurities: vec![branch_risk.first().copied().unwrap_or(0f64)],
+    };
+
+    while n_nodes > 0 && !is_leaf[0] {
+        let mut weakest = 0;
+        let mut effective_alpha = f64::MAX;
+        for i in 0..n_nodes {
+            if !in_tree[i] || is_leaf[i] {
+                continue;
+            }
+            let alpha = (risk[i] - branch_risk[i]) / (n_leaves[i] - 1) as f64;
+            if alpha < effective_alpha {
+                effective_alpha = alpha;
+                weakest = i;
+            }
+        }
+        let effective_alpha = effective_alpha.max(0f64);
+
+        if effective_alpha > ccp_alpha {
+            break;
+        }
+
+        is_leaf[weakest] = true;
+        let risk_change = branch_risk[weakest] - risk[weakest];
+        let leaves_change = n_leaves[weakest] - 1;
+        let mut node = Some(weakest);
+        while let Some(i) = node {
+            branch_risk[i] -= risk_change;
+            n_leaves[i] -= leaves_change;
+            node = parent[i];
+        }
+
+        path.ccp_alphas.push(effective_alpha);
+        path.impurities.push(branch_risk[0]);
+
+        // drop the descendants of the collapsed node
+        for i in 0..n_nodes {
+            in_tree[i] = match parent[i] {
+                None => i == 0,
+                Some(p) => in_tree[p] && !is_leaf[p],
+            };
+        }
+    }
+
+    (path, is_leaf)
+}
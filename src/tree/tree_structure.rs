@@ -0,0 +1,341 @@
+//! # Structure of a fitted tree
+//!
+//! [`TreeStructure`](struct.TreeStructure.html) is a plain view of what a fitted [decision tree classifier](../decision_tree_classifier/index.html)
+//! or [decision tree regressor](../decision_tree_regressor/index.html) has learned: the split feature and threshold of every internal node,
+//! the children of every node and the value predicted at every node. Nodes are stored in a flat vector, the root is the first node and every child
+//! has a larger index than its parent.
+//!
+//! The structure can be rendered as [Graphviz](https://graphviz.org/) DOT text with [`TreeStructure::export_graphviz`](struct.TreeStructure.html#method.export_graphviz),
+//! as human-readable if/else rules with [`TreeStructure::export_text`](struct.TreeStructure.html#method.export_text) and, with the `serde` feature, serialized to JSON or any other format.
+//!
+//! Example:
+//!
+//! ```
+//! use smartcore::linalg::basic::matrix::DenseMatrix;
+//! use smartcore::tree::decision_tree_classifier::*;
+//!
+//! let x = DenseMatrix::from_2d_array(&[
+//!            &[1.0, 0.5],
+//!            &[2.0, 0.1],
+//!            &[3.0, 0.7],
+//!            &[4.0, 0.2],
+//!         ]).unwrap();
+//! let y = vec![0, 0, 1, 1];
+//!
+//! let tree = DecisionTreeClassifier::fit(&x, &y, Default::default()).unwrap();
+//!
+//! let structure = tree.structure();
+//! assert_eq!(structure.nodes[0].feature, Some(0));
+//! assert_eq!(structure.nodes[0].threshold, Some(2.5));
+//!
+//! let rules = structure.export_text(Some(&["length", "width"])).unwrap();
+//! assert_eq!(
+//!     rules,
+//!     "if length <= 2.5 or length is missing:\n    class: 0\nelse:\n    class: 1\n"
+//! );
+//!
+//! let dot = structure.export_graphviz(None).unwrap();
+//! assert!(dot.starts_with("digraph Tree {"));
+//! ```
+use std::fmt::Write;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::error::Failed;
+
+/// Node of a fitted tree.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TreeNode {
+    /// Index of the split feature, `None` for leaves.
+    pub feature: Option<usize>,
+    /// Observations with a value of the split feature less than or equal to the threshold go to the true child, `None` for leaves.
+    pub threshold: Option<f64>,
+    /// Whether observations with a missing value of the split feature go to the true child.
+    pub missing_to_true_child: bool,
+    /// Whether the node separates observations with a missing value of the split feature, which go to the false child,
+    /// from all other observations, which go to the true child. The threshold of such nodes is `None`.
+    pub split_on_missing: bool,
+    /// Index of the true child, `None` for leaves.
+    pub true_child: Option<usize>,
+    /// Index of the false child, `None` for leaves.
+    pub false_child: Option<usize>,
+    /// Number of training samples that reach the node.
+    pub n_node_samples: usize,
+    /// Impurity of the node: the split criterion for classification trees and the mean squared error for regression trees.
+    pub impurity: Option<f64>,
+    /// Value predicted at the node: a class label for classification trees and the mean target value for regression trees.
+    pub value: f64,
+}
+
+/// Structure of a fitted tree.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TreeStructure {
+    /// Nodes of the tree, the root is the first node.
+    pub nodes: Vec<TreeNode>,
+    /// Number of features the tree was fitted with.
+    pub n_features: usize,
+    /// Class labels of a classification tree, empty for regression trees.
+    pub classes: Vec<f64>,
+}
+
+impl TreeNode {
+    /// Whether the node has no children.
+    pub fn is_leaf(&self) -> bool {
+        self.true_child.is_none() || self.false_child.is_none()
+    }
+}
+
+impl TreeStructure {
+    /// Render the tree as [Graphviz](https://graphviz.org/) DOT text.
+    /// * `feature_names` - optional names of the features, `feature_<index>` is used by default.
+    pub fn export_graphviz(&self, feature_names: Option<&[&str]>) -> Result<String, Failed> {
+        let names: Vec<String> = self
+            .feature_names(feature_names)?
+            .iter()
+            .map(|name| name.replace('"', "\\\""))
+            .collect();
+        let mut dot = String::from("digraph Tree {\nnode [shape=box] ;\n");
+
+        for (id, node) in self.nodes.iter().enumerate() {
+            let mut label = String::new();
+            if !node.is_leaf() {
+                write!(label, "{}\\n", self.condition(node, &names)).unwrap();
+            }
+            if let Some(impurity) = node.impurity {
+                write!(label, "impurity = {}\\n", format_number(impurity)).unwrap();
+            }
+            write!(
+                label,
+                "samples = {}\\n{}",
+                node.n_node_samples,
+                self.value(node, " = ")
+            )
+            .unwrap();
+            writeln!(dot, "{id} [label=\"{label}\"] ;").unwrap();
+
+            if let (Some(true_child), Some(false_child)) = (node.true_child, node.false_child) {
+                let (true_label, false_label) = if id == 0 {
+                    (", headlabel=\"True\"", ", headlabel=\"False\"")
+                } else {
+                    ("", "")
+                };
+                writeln!(
+                    dot,
+                    "{id} -> {true_child} [labeldistance=2.5, labelangle=45{true_label}] ;"
+                )
+                .unwrap();
+                writeln!(
+                    dot,
+                    "{id} -> {false_child} [labeldistance=2.5, labelangle=-45{false_label}] ;"
+                )
+                .unwrap();
+            }
+        }
+
+        dot.push_str("}\n");
+        Ok(dot)
+    }
+
+    /// Render the tree as nested if/else rules.
+    /// * `feature_names` - optional names of the features, `feature_<index>` is used by default.
+    pub fn export_text(&self, feature_names: Option<&[&str]>) -> Result<String, Failed> {
+        let names = self.feature_names(feature_names)?;
+        let mut text = String::new();
+        if !self.nodes.is_empty() {
+            self.write_rules(0, 0, &names, &mut text);
+        }
+        Ok(text)
+    }
+
+    fn write_rules(&self, id: usize, level: usize, names: &[String], text: &mut String) {
+        let node = &self.nodes[id];
+        let indent = "    ".repeat(level);
+        match (node.true_child, node.false_child) {
+            (Some(true_child), Some(false_child)) => {
+                writeln!(text, "{indent}if {}:", self.condition(node, names)).unwrap();
+                self.write_rules(true_child, level + 1, names, text);
+                writeln!(text, "{indent}else:").unwrap();
+                self.write_rules(false_child, level + 1, names, text);
+            }
+            _ => writeln!(text, "{indent}{}", self.value(node, ": ")).unwrap(),
+        }
+    }
+
+    fn feature_names(&self, feature_names: Option<&[&str]>) -> Result<Vec<String>, Failed> {
+        match feature_names {
+            Some(names) if names.len() != self.n_features => Err(Failed::input(&format!(
+                "Expected {} feature names, got {}",
+                self.n_features,
+                names.len()
+            ))),
+            Some(names) => Ok(names.iter().map(|name| name.to_string()).collect()),
+            None => Ok((0..self.n_features)
+                .map(|j| format!("feature_{j}"))
+                .collect()),
+        }
+    }
+
+    fn condition(&self, node: &TreeNode, names: &[String]) -> String {
+        let name = &names[node.feature.unwrap_or(0)];
+        let threshold = node.threshold.unwrap_or(f64::NAN);
+        if node.split_on_missing {
+            format!("{name} is not missing")
+        } else if node.missing_to_true_child {
+            format!(
+                "{name} <= {} or {name} is missing",
+                format_number(threshold)
+            )
+        } else {
+            format!("{name} <= {}", format_number(threshold))
+        }
+    }
+
+    fn value(&self, node: &TreeNode, separator: &str) -> String {
+        if self.classes.is_empty() {
+            format!("value{separator}{}", format_number(node.value))
+        } else {
+            format!("class{separator}{}", format_number(node.value))
+        }
+    }
+}
+
+/// Formats `value` with at most six decimals.
+fn format_number(value: f64) -> String {
+    let formatted = format!("{value:.6}");
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+    if trimmed == "-0" {
+        "0".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::linalg::basic::matrix::DenseMatrix;
+    use crate::tree::decision_tree_classifier::DecisionTreeClassifier;
+    use crate::tree::decision_tree_regressor::{
+        DecisionTreeRegressor, DecisionTreeRegressorParameters,
+    };
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn classifier_structure() {
+        let x = DenseMatrix::from_2d_array(&[&[1.], &[2.], &[3.], &[4.], &[5.], &[6.]]).unwrap();
+        let y: Vec<u32> = vec![0, 0, 0, 1, 0, 0];
+        let tree = DecisionTreeClassifier::fit(&x, &y, Default::default()).unwrap();
+
+        let structure = tree.structure();
+        assert_eq!(structure.n_features, 1);
+        assert_eq!(structure.classes, vec![0., 1.]);
+        assert_eq!(structure.nodes.len(), 5);
+
+        let root = &structure.nodes[0];
+        assert_eq!(root.feature, Some(0));
+        assert_eq!(root.threshold, Some(3.5));
+        assert_eq!(root.n_node_samples, 6);
+        assert!(!root.is_leaf());
+        let leaf = &structure.nodes[root.true_child.unwrap()];
+        assert!(leaf.is_leaf());
+        assert_eq!(leaf.feature, None);
+        assert_eq!(leaf.threshold, None);
+        assert_eq!(leaf.n_node_samples, 3);
+        assert_eq!(leaf.value, 0.);
+
+        assert_eq!(
+            tree.export_text(Some(&["x"])).unwrap(),
+            "if x <= 3.5 or x is missing:\n    class: 0\nelse:\n    if x <= 4.5:\n        class: 1\n    else:\n        class: 0\n"
+        );
+
+        assert_eq!(
+            tree.export_graphviz(None).unwrap(),
+            "digraph Tree {\n\
+             node [shape=box] ;\n\
+             0 [label=\"feature_0 <= 3.5 or feature_0 is missing\\nimpurity = 0.277778\\nsamples = 6\\nclass = 0\"] ;\n\
+             0 -> 1 [labeldistance=2.5, labelangle=45, headlabel=\"True\"] ;\n\
+             0 -> 2 [labeldistance=2.5, labelangle=-45, headlabel=\"False\"] ;\n\
+             1 [label=\"impurity = 0\\nsamples = 3\\nclass = 0\"] ;\n\
+             2 [label=\"feature_0 <= 4.5\\nimpurity = 0.444444\\nsamples = 3\\nclass = 0\"] ;\n\
+             2 -> 3 [labeldistance=2.5, labelangle=45] ;\n\
+             2 -> 4 [labeldistance=2.5, labelangle=-45] ;\n\
+             3 [label=\"impurity = 0\\nsamples = 1\\nclass = 1\"] ;\n\
+             4 [label=\"impurity = 0\\nsamples = 2\\nclass = 0\"] ;\n\
+             }\n"
+        );
+
+        assert!(tree.export_text(Some(&["a", "b"])).is_err());
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn regressor_rules_with_missing_values() {
+        let nan = f64::NAN;
+        let x = DenseMatrix::from_2d_array(&[
+            &[0., 1.],
+            &[0., 2.],
+            &[0., nan],
+            &[0., 8.],
+            &[0., 9.],
+            &[0., nan],
+        ])
+        .unwrap();
+        let y: Vec<f64> = vec![1., 1., 5., 5., 5., 5.];
+        let tree = DecisionTreeRegressor::fit(
+            &x,
+            &y,
+            DecisionTreeRegressorParameters::default().with_max_depth(1),
+        )
+        .unwrap();
+
+        // missing values of b fail the condition and go to the false child
+        assert_eq!(
+            tree.export_text(Some(&["a", "b"])).unwrap(),
+            "if b <= 5:\n    value: 1\nelse:\n    value: 5\n"
+        );
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    fn classifier_rules_with_split_on_missing() {
+        let nan = f64::NAN;
+        let x = DenseMatrix::from_2d_array(&[&[1.], &[nan], &[3.], &[nan], &[5.], &[6.]]).unwrap();
+        let y: Vec<u32> = vec![0, 1, 0, 1, 0, 0];
+        let tree = DecisionTreeClassifier::fit(&x, &y, Default::default()).unwrap();
+
+        assert_eq!(
+            tree.export_text(Some(&["a"])).unwrap(),
+            "if a is not missing:\n    class: 0\nelse:\n    class: 1\n"
+        );
+    }
+
+    #[cfg_attr(
+        all(target_arch = "wasm32", not(target_os = "wasi")),
+        wasm_bindgen_test::wasm_bindgen_test
+    )]
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde() {
+        let x = DenseMatrix::from_2d_array(&[&[1.], &[2.], &[3.], &[4.]]).unwrap();
+        let y: Vec<f64> = vec![0., 0., 10., 12.];
+        let structure = DecisionTreeRegressor::fit(&x, &y, Default::default())
+            .unwrap()
+            .structure();
+
+        let deserialized: super::TreeStructure =
+            serde_json::from_str(&serde_json::to_string(&structure).unwrap()).unwrap();
+
+        assert_eq!(structure, deserialized);
+    }
+}